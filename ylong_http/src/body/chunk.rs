@@ -133,7 +133,8 @@ impl<'a> ChunkBody<FromBytes<'a>> {
         let (output_size, var) = self.encode_status.encode(src, dst);
 
         if let Some(v) = var {
-            self.chunk_data.chunk_count = v.cnt;
+            // Counts the chunks encoded, which locates the next one in `from`.
+            self.chunk_data.chunk_count += v.cnt;
             self.data_status = v.data_status;
         }
         output_size
@@ -325,18 +326,19 @@ impl<T: AsyncRead + Unpin + Send + Sync> async_impl::Body for ChunkBody<FromAsyn
 
 impl<'a> ChunkBody<FromBytes<'a>> {
     fn bytes_encode(&mut self, dst: &mut [u8]) -> usize {
+        let chunk_size = self.encode_status.chunk_size;
         if !self.encode_status.get_flag() {
             self.encode_status.set_flag(true);
             self.encode_status.set_chunk_idx(0);
-            let data_left = self.from.len() - self.chunk_data.chunk_count * CHUNK_SIZE;
-            self.chunk_data.chunk_last = if data_left < CHUNK_SIZE {
+            let data_left = self.from.len() - self.chunk_data.chunk_count * chunk_size;
+            self.chunk_data.chunk_last = if data_left < chunk_size {
                 data_left
             } else {
-                CHUNK_SIZE
+                chunk_size
             };
         }
-        let src = &self.from[self.chunk_data.chunk_count * CHUNK_SIZE
-            ..(self.chunk_data.chunk_count * CHUNK_SIZE + self.chunk_data.chunk_last)];
+        let src = &self.from[self.chunk_data.chunk_count * chunk_size
+            ..(self.chunk_data.chunk_count * chunk_size + self.chunk_data.chunk_last)];
         self.chunk_encode(src, dst)
     }
 }
@@ -367,6 +369,33 @@ impl<T> ChunkBody<T> {
         self.trailer_value = trailer_vec;
         self
     }
+
+    /// Sets the maximum size of the data of each chunk. The default size is
+    /// 1024 bytes. The size must be nonzero, and must be set before encoding
+    /// starts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http::body::ChunkBody;
+    ///
+    /// let task = ChunkBody::from_bytes("".as_bytes()).set_chunk_size(16);
+    /// assert!(task.is_ok());
+    /// let task = ChunkBody::from_bytes("".as_bytes()).set_chunk_size(0);
+    /// assert!(task.is_err());
+    /// ```
+    pub fn set_chunk_size(mut self, size: usize) -> Result<Self, HttpError> {
+        if size == 0 {
+            return Err(ErrorKind::InvalidInput.into());
+        }
+        self.encode_status.chunk_size = size;
+        self.encode_status.hex = EncodeHex::new(format!("{size:x}"));
+        // `FromBytes` encodes from the slice directly, without a buffer.
+        if !self.chunk_data.chunk_buf.is_empty() {
+            self.chunk_data.chunk_buf = vec![0; size];
+        }
+        Ok(self)
+    }
     fn chunk_encode_reader(&mut self, dst: &mut [u8]) -> usize {
         self.encode_status.chunk_last = self.chunk_data.chunk_last;
         let (output_size, var) = self.encode_status.encode(
@@ -447,11 +476,20 @@ impl EncodeStatus {
 
     fn encode(&mut self, src: &[u8], dst: &mut [u8]) -> (usize, Option<StatusVar>) {
         match self.chunk_status {
+            // No data is left, so the last chunk is encoded with the trailer.
+            ChunkState::MetaSize if self.chunk_last == 0 => {
+                self.read_flag = false;
+                let var = StatusVar {
+                    cnt: 0,
+                    data_status: DataState::Complete,
+                };
+                (0, Some(var))
+            }
             ChunkState::MetaSize => (self.meta_size_encode(dst), None),
             ChunkState::MetaExt => (0, None),
             ChunkState::MetaCrlf => (self.meta_crlf_encode(dst), None),
             ChunkState::Data => {
-                if self.chunk_last != CHUNK_SIZE {
+                if self.chunk_last != self.chunk_size {
                     self.tail_encode(src, dst)
                 } else {
                     self.data_encode(src, dst)
@@ -463,7 +501,7 @@ impl EncodeStatus {
     }
 
     fn meta_size_encode(&mut self, dst: &mut [u8]) -> usize {
-        if self.chunk_last == CHUNK_SIZE {
+        if self.chunk_last == self.chunk_size {
             match self.hex.encode(dst) {
                 TokenStatus::Complete(output_size) => {
                     self.chunk_status = ChunkState::MetaCrlf;
@@ -872,6 +910,7 @@ pub struct ChunkBodyDecoder {
     is_chunk_trailer: bool,
     is_trailer: bool,
     is_trailer_crlf: bool,
+    max_chunk_size: usize,
     stage: Stage,
 }
 
@@ -898,6 +937,7 @@ impl ChunkBodyDecoder {
             is_chunk_trailer: false,
             is_trailer: false,
             is_trailer_crlf: false,
+            max_chunk_size: usize::MAX,
             stage: Stage::Size,
         }
     }
@@ -908,6 +948,22 @@ impl ChunkBodyDecoder {
         self
     }
 
+    /// Sets the maximum size of a single chunk. A chunk whose declared size
+    /// exceeds it causes an error. There is no limit by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http::body::ChunkBodyDecoder;
+    ///
+    /// let mut decoder = ChunkBodyDecoder::new().max_chunk_size(4);
+    /// assert!(decoder.decode(b"10\r\nAAAAAAAAAAAAAAAA\r\n").is_err());
+    /// ```
+    pub fn max_chunk_size(mut self, size: usize) -> Self {
+        self.max_chunk_size = size;
+        self
+    }
+
     fn merge_trailer(&mut self, chunk: &Chunk) {
         if chunk.state() == &ChunkState::Finish || chunk.state() == &ChunkState::DataCrlf {
             self.trailer.extend_from_slice(chunk.trailer().unwrap());
@@ -1164,10 +1220,19 @@ impl ChunkBodyDecoder {
         &mut self,
         buf: &'a [u8],
     ) -> Result<(Chunk<'a>, &'a [u8]), HttpError> {
+        if !self.is_chunk_trailer && self.is_trailer && !self.num_flag && self.hex_count == 0 {
+            // The size of the last-chunk ended with the previous buffer, so
+            // the trailer-section follows it like an unsplit last-chunk.
+            self.is_chunk_trailer = true;
+            return self.skip_extension(buf);
+        }
         return if self.is_chunk_trailer {
             self.num_flag = false;
             self.skip_trailer_crlf(buf)
         } else {
+            if self.hex_count as u64 > self.max_chunk_size as u64 {
+                return Err(ErrorKind::InvalidInput.into());
+            }
             self.total_size = self.hex_count as usize;
             self.hex_count = 0;
             self.num_flag = false;
@@ -1529,6 +1594,57 @@ mod ut_chunk {
         assert_eq!(output_vec, res_message());
     }
 
+    /// UT test cases for `ChunkBody::set_chunk_size`.
+    ///
+    /// # Brief
+    /// 1. Creates `ChunkBody`s by calling `ChunkBody::set_chunk_size`.
+    /// 2. Encodes chunk body by calling `ChunkBody::data`
+    /// 3. Checks that the chunks are no larger than the chunk size, also when
+    ///    the data ends at a chunk boundary.
+    /// 4. Checks that a zero chunk size is rejected.
+    #[test]
+    fn ut_chunk_body_encode_chunk_size() {
+        let expected = "4\r\nhell\r\n4\r\no wo\r\n3\r\nrld\r\n0\r\n\r\n".as_bytes();
+
+        let mut task = ChunkBody::from_reader("hello world".as_bytes())
+            .set_chunk_size(4)
+            .unwrap();
+        let mut user_slice = [0_u8; 5];
+        let mut output_vec = vec![];
+        let mut size = user_slice.len();
+        while size == user_slice.len() {
+            size = task.data(user_slice.as_mut_slice()).unwrap();
+            output_vec.extend_from_slice(&user_slice[..size]);
+        }
+        assert_eq!(output_vec, expected);
+
+        let mut task = ChunkBody::from_bytes("hello world".as_bytes())
+            .set_chunk_size(4)
+            .unwrap();
+        let mut output_vec = vec![];
+        let mut size = user_slice.len();
+        while size == user_slice.len() {
+            size = task.data(user_slice.as_mut_slice()).unwrap();
+            output_vec.extend_from_slice(&user_slice[..size]);
+        }
+        assert_eq!(output_vec, expected);
+
+        let mut task = ChunkBody::from_reader("hello wo".as_bytes())
+            .set_chunk_size(4)
+            .unwrap();
+        let mut output_vec = vec![];
+        let mut size = user_slice.len();
+        while size == user_slice.len() {
+            size = task.data(user_slice.as_mut_slice()).unwrap();
+            output_vec.extend_from_slice(&user_slice[..size]);
+        }
+        assert_eq!(output_vec, b"4\r\nhell\r\n4\r\no wo\r\n0\r\n\r\n");
+
+        assert!(ChunkBody::from_reader("".as_bytes())
+            .set_chunk_size(0)
+            .is_err());
+    }
+
     /// UT test cases for `ChunkBody::data` in async condition.
     ///
     /// # Brief
@@ -2198,4 +2314,59 @@ mod ut_chunk {
         let value = trailer_headers.get("trailer");
         assert_eq!(value.unwrap().to_string().unwrap(), "value");
     }
+
+    /// UT test cases for `ChunkBodyDecoder::decode` with chunk extensions.
+    ///
+    /// # Brief
+    /// 1. Creates chunk bodies whose chunks and last-chunk carry extensions.
+    /// 2. Decodes each body split into two buffers at every position.
+    /// 3. Checks that the extensions are skipped and the data and trailers
+    ///    are correct.
+    #[test]
+    fn ut_chunk_body_decode_extension() {
+        let bodies: [&[u8]; 3] = [
+            b"1a;foo=bar\r\nabcdefghijklmnopqrstuvwxyz\r\n0;x=y\r\n\r\n",
+            b"1a ; a=\"b;c\" ;d\r\nabcdefghijklmnopqrstuvwxyz\r\n0;x\r\nt: v\r\n\r\n",
+            b"1a\r\nabcdefghijklmnopqrstuvwxyz\r\n0\r\nt: v\r\n\r\n",
+        ];
+        for body in bodies {
+            for i in 0..body.len() {
+                let mut decoder = ChunkBodyDecoder::new().contains_trailer(true);
+                let mut data = vec![];
+                for buf in [&body[..i], &body[i..]] {
+                    let (chunks, rest) = decoder.decode(buf).unwrap();
+                    assert!(rest.is_empty());
+                    for chunk in chunks {
+                        data.extend_from_slice(chunk.data());
+                    }
+                }
+                assert_eq!(data, b"abcdefghijklmnopqrstuvwxyz");
+                let trailer = decoder.get_trailer().unwrap();
+                if body.ends_with(b"t: v\r\n\r\n") {
+                    let value = trailer.unwrap().get("t").unwrap().to_string().unwrap();
+                    assert_eq!(value, "v");
+                }
+            }
+        }
+    }
+
+    /// UT test cases for `ChunkBodyDecoder::max_chunk_size`.
+    ///
+    /// # Brief
+    /// 1. Creates a `ChunkBodyDecoder` with a max chunk size.
+    /// 2. Decodes chunk bodies by calling `ChunkBodyDecoder::decode`.
+    /// 3. Checks if the chunk exceeding the max size causes an error.
+    #[test]
+    fn ut_chunk_body_decode_max_chunk_size() {
+        let mut decoder = ChunkBodyDecoder::new().max_chunk_size(16);
+        let buf = b"10\r\nAAAAAAAAAAAAAAAA\r\n0\r\n\r\n";
+        assert!(decoder.decode(buf).is_ok());
+
+        let mut decoder = ChunkBodyDecoder::new().max_chunk_size(15);
+        assert_eq!(decoder.decode(buf), Err(ErrorKind::InvalidInput.into()));
+
+        let mut decoder = ChunkBodyDecoder::new().max_chunk_size(15);
+        assert!(decoder.decode(b"FFFFFFFF").is_ok());
+        assert!(decoder.decode(b"\r\n").is_err());
+    }
 }
@@ -20,6 +20,7 @@ use std::task::{Context, Poll};
 use std::vec::IntoIter;
 
 use crate::body::async_impl::{Body, ReusableReader};
+use crate::error::{ErrorKind, HttpError};
 use crate::{AsyncRead, ReadBuf};
 
 /// A structure that helps you build a `multipart/form-data` message.
@@ -71,6 +72,34 @@ impl MultiPart {
         self
     }
 
+    /// Sets the boundary of this `Multipart` instead of the random one.
+    ///
+    /// The boundary must be 1 to 70 characters of letters, digits, spaces and
+    /// `'()+_,-./:=?`, and must not end with a space. It also must not appear
+    /// in the bodies of the parts, otherwise reading this `Multipart` fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ylong_http::body::{MultiPart, Part};
+    ///
+    /// let multipart = MultiPart::new()
+    ///     .part(Part::new().name("name").body("xiaoming"))
+    ///     .set_boundary("test-boundary")
+    ///     .unwrap();
+    /// assert_eq!(multipart.boundary(), "test-boundary");
+    /// ```
+    pub fn set_boundary(mut self, boundary: &str) -> Result<Self, HttpError> {
+        if !is_valid_boundary(boundary.as_bytes()) {
+            return Err(ErrorKind::InvalidInput.into());
+        }
+        self.boundary = String::from(boundary);
+        if self.contains_boundary() {
+            return Err(ErrorKind::InvalidInput.into());
+        }
+        Ok(self)
+    }
+
     /// Gets the boundary of this `Multipart`.
     ///
     /// # Examples
@@ -134,6 +163,18 @@ impl MultiPart {
         Some(size)
     }
 
+    // Checks whether the delimiter appears in the bytes bodies of the parts.
+    fn contains_boundary(&self) -> bool {
+        let delimiter = format!("--{}", self.boundary).into_bytes();
+        self.parts.iter().any(|part| match part.body {
+            Some(MultiPartState::Bytes(ref bytes)) => bytes
+                .get_ref()
+                .windows(delimiter.len())
+                .any(|window| window == delimiter.as_slice()),
+            _ => false,
+        })
+    }
+
     pub(crate) fn build_status(&mut self) {
         let mut states = Vec::new();
         for part in self.parts.iter_mut() {
@@ -208,7 +249,15 @@ impl AsyncRead for MultiPart {
         buf: &mut ReadBuf<'_>,
     ) -> Poll<std::io::Result<()>> {
         match self.status {
-            ReadStatus::Never => self.build_status(),
+            ReadStatus::Never => {
+                if self.contains_boundary() {
+                    return Poll::Ready(Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "Boundary appears in the body of multipart",
+                    )));
+                }
+                self.build_status()
+            }
             ReadStatus::Reading(_) => {}
             ReadStatus::Finish(_) => return Poll::Ready(Ok(())),
         }
@@ -536,6 +585,17 @@ impl core::fmt::Debug for MultiPartState {
     }
 }
 
+// bchars := bcharsnospace / " "
+// bcharsnospace := DIGIT / ALPHA / "'" / "(" / ")" / "+" / "_" / "," / "-" /
+//                  "." / "/" / ":" / "=" / "?"
+fn is_valid_boundary(boundary: &[u8]) -> bool {
+    (1..=70).contains(&boundary.len())
+        && !boundary.ends_with(b" ")
+        && boundary
+            .iter()
+            .all(|b| b.is_ascii_alphanumeric() || b"'()+_,-./:=? ".contains(b))
+}
+
 fn gen_boundary() -> String {
     format!(
         "{:016x}-{:016x}-{:016x}-{:016x}",
@@ -673,6 +733,30 @@ mod ut_mime {
         assert!(!mp.boundary().is_empty());
     }
 
+    /// UT test cases for `MultiPart::set_boundary`.
+    ///
+    /// # Brief
+    /// 1. Creates `MultiPart`s and sets boundaries.
+    /// 2. Checks whether the result is correct.
+    #[test]
+    fn ut_multipart_set_boundary() {
+        let mp = MultiPart::new()
+            .part(Part::new().name("name").body("xiaoming"))
+            .set_boundary("abc'()+_,-./:=? 123")
+            .unwrap();
+        assert_eq!(mp.boundary(), "abc'()+_,-./:=? 123");
+
+        assert!(MultiPart::new().set_boundary("").is_err());
+        assert!(MultiPart::new().set_boundary("boundary ").is_err());
+        assert!(MultiPart::new().set_boundary("bound\"ary").is_err());
+        assert!(MultiPart::new().set_boundary(&"a".repeat(71)).is_err());
+
+        let mp = MultiPart::new()
+            .part(Part::new().name("name").body("a--boundary-b"))
+            .set_boundary("boundary");
+        assert!(mp.is_err());
+    }
+
     /// UT test cases for `MultiPart::part` and `MultiPart::total_bytes`.
     ///
     /// # Brief
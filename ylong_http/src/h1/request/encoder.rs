@@ -60,10 +60,12 @@
 //! assert_eq!(message.as_slice(), result.as_bytes());
 //! ```
 
+use std::collections::HashMap;
 use std::io::Read;
+use std::vec::IntoIter;
 
 use crate::error::{ErrorKind, HttpError};
-use crate::headers::{HeaderName, Headers, HeadersIntoIter};
+use crate::headers::{HeaderName, HeaderValue, Headers, HeadersIntoIter};
 use crate::request::method::Method;
 use crate::request::uri::Uri;
 use crate::request::RequestPart;
@@ -307,6 +309,54 @@ impl RequestEncoder {
         self.is_absolute_uri = is_absolute;
     }
 
+    /// Sets the names in the exact case to write the headers with.
+    ///
+    /// `HeaderName` is stored in lowercase, so the headers are written in
+    /// lowercase by default. A header whose name matches one of `names`
+    /// case-insensitively is written with that name instead. If several
+    /// names match the same header, the last one is used. Names that match
+    /// no header, or that are not valid header names, are ignored.
+    ///
+    /// You should use this method before the headers being encoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http::h1::RequestEncoder;
+    /// use ylong_http::request::Request;
+    ///
+    /// let request = Request::builder()
+    ///     .method("GET")
+    ///     .url("www.example.com")
+    ///     .version("HTTP/1.1")
+    ///     .header("X-MyHeader", "value")
+    ///     .body(())
+    ///     .unwrap();
+    ///
+    /// let (part, _) = request.into_parts();
+    /// let mut encoder = RequestEncoder::new(part);
+    /// encoder.header_case(["X-MyHeader"]);
+    ///
+    /// let mut buf = [0u8; 1024];
+    /// let size = encoder.encode(&mut buf).unwrap();
+    /// assert_eq!(
+    ///     &buf[..size],
+    ///     b"GET / HTTP/1.1\r\nX-MyHeader:value\r\n\r\n".as_slice()
+    /// );
+    /// ```
+    pub fn header_case<I, T>(&mut self, names: I)
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        for name in names {
+            let raw = name.as_ref();
+            if let Ok(key) = HeaderName::from_bytes(raw) {
+                self.headers_part.cases.insert(key, raw.to_vec());
+            }
+        }
+    }
+
     fn method_encode(&mut self, dst: &mut [u8]) -> Result<usize, HttpError> {
         match self.method_part.encode(dst)? {
             TokenStatus::Complete(output_size) => {
@@ -491,11 +541,27 @@ struct EncodeHeader {
     status: Option<HeaderStatus>,
     name: HeaderName,
     value: Vec<u8>,
+    // The names to write instead of the lowercase `HeaderName`s.
+    cases: HashMap<HeaderName, Vec<u8>>,
+    // The remaining values of `name`, each of which is encoded as a separate
+    // header line, except for `Cookie`.
+    values: IntoIter<Vec<u8>>,
     name_idx: usize,
     colon_idx: usize,
     value_idx: usize,
 }
 
+// Gets the values of a header, each of which is encoded as a separate header
+// line. The values of `Cookie` are joined by "; " into one line instead, as
+// RFC6265 doesn't allow several `Cookie` headers.
+fn header_values(name: &HeaderName, value: &HeaderValue) -> IntoIter<Vec<u8>> {
+    let values = value.iter().cloned().collect::<Vec<_>>();
+    if name.as_bytes() == b"cookie" && values.len() > 1 {
+        return vec![values.join(&b"; "[..])].into_iter();
+    }
+    values.into_iter()
+}
+
 enum HeaderStatus {
     Name,
     Colon,
@@ -508,11 +574,14 @@ impl EncodeHeader {
     fn new(header: Headers) -> Self {
         let mut header_iter = header.into_iter();
         if let Some((header_name, header_value)) = header_iter.next() {
+            let mut values = header_values(&header_name, &header_value);
             Self {
                 inner: header_iter,
                 status: Some(HeaderStatus::Name),
                 name: header_name,
-                value: header_value.to_string().unwrap().into_bytes(),
+                value: values.next().unwrap_or_default(),
+                cases: HashMap::new(),
+                values,
                 name_idx: 0,
                 colon_idx: 0,
                 value_idx: 0,
@@ -523,6 +592,8 @@ impl EncodeHeader {
                 status: Some(HeaderStatus::EmptyHeader),
                 name: HeaderName::from_bytes(" ".as_bytes()).unwrap(),
                 value: vec![],
+                cases: HashMap::new(),
+                values: Vec::new().into_iter(),
                 name_idx: 0,
                 colon_idx: 0,
                 value_idx: 0,
@@ -541,7 +612,10 @@ impl EncodeHeader {
     }
 
     fn encode_name(&mut self, buf: &mut [u8]) -> TokenResult<usize> {
-        let name = self.name.as_bytes();
+        let name = match self.cases.get(&self.name) {
+            Some(name) => name.as_slice(),
+            None => self.name.as_bytes(),
+        };
         let mut task = WriteData::new(name, &mut self.name_idx, buf);
         match task.write()? {
             TokenStatus::Complete(size) => {
@@ -589,11 +663,20 @@ impl EncodeHeader {
     fn encode_crlf(&mut self, buf: &mut [u8], mut crlf: EncodeCrlf) -> TokenResult<usize> {
         match crlf.encode(buf)? {
             TokenStatus::Complete(size) => {
-                if let Some(iter) = self.inner.next() {
+                if let Some(value) = self.values.next() {
+                    self.status = Some(HeaderStatus::Name);
+                    self.value = value;
+                    self.name_idx = 0;
+                    self.colon_idx = 0;
+                    self.value_idx = 0;
+                    Ok(TokenStatus::Partial(size))
+                } else if let Some(iter) = self.inner.next() {
                     let (header_name, header_value) = iter;
+                    let mut values = header_values(&header_name, &header_value);
                     self.status = Some(HeaderStatus::Name);
                     self.name = header_name;
-                    self.value = header_value.to_string().unwrap().into_bytes();
+                    self.value = values.next().unwrap_or_default();
+                    self.values = values;
                     self.name_idx = 0;
                     self.colon_idx = 0;
                     self.value_idx = 0;
@@ -772,6 +855,110 @@ mod ut_request_encoder {
         }
     }
 
+    /// UT test cases for `RequestEncoder::encode` with a header of several
+    /// values.
+    ///
+    /// # Brief
+    /// 1. Creates a `Request` with two values appended to the same header.
+    /// 2. Calls `RequestEncoder::encode` method in a loop and collects the
+    ///    results.
+    /// 3. Checks that each value is encoded as a separate header line.
+    #[test]
+    fn ut_request_encoder_encode_appended_header() {
+        let request = Request::builder()
+            .method("GET")
+            .url("www.example.com")
+            .append_header("X-Forwarded-For", "10.0.0.1")
+            .append_header("X-Forwarded-For", "10.0.0.2")
+            .header("Accept", "text/html")
+            .body(())
+            .unwrap();
+        let (part, _) = request.into_parts();
+        let mut encoder = RequestEncoder::new(part);
+        let mut buf = [0u8; 5];
+        let mut res = Vec::new();
+        loop {
+            let size = encoder.encode(&mut buf).unwrap();
+            res.extend_from_slice(&buf[..size]);
+            if size < buf.len() {
+                break;
+            }
+        }
+        let str = std::str::from_utf8(res.as_slice()).unwrap();
+        assert!(str.contains("x-forwarded-for:10.0.0.1\r\nx-forwarded-for:10.0.0.2\r\n"));
+        assert!(str.contains("accept:text/html\r\n"));
+        assert!(str.ends_with("\r\n\r\n"));
+    }
+
+    /// UT test cases for `RequestEncoder::encode` with several `Cookie`
+    /// values.
+    ///
+    /// # Brief
+    /// 1. Creates a `Request` with two values appended to `Cookie`.
+    /// 2. Calls `RequestEncoder::encode` method in a loop and collects the
+    ///    results.
+    /// 3. Checks that the values are joined into one header line.
+    #[test]
+    fn ut_request_encoder_encode_cookies() {
+        let request = Request::builder()
+            .method("GET")
+            .url("www.example.com")
+            .append_header("Cookie", "a=1")
+            .append_header("Cookie", "b=2")
+            .body(())
+            .unwrap();
+        let (part, _) = request.into_parts();
+        let mut encoder = RequestEncoder::new(part);
+        let mut buf = [0u8; 5];
+        let mut res = Vec::new();
+        loop {
+            let size = encoder.encode(&mut buf).unwrap();
+            res.extend_from_slice(&buf[..size]);
+            if size < buf.len() {
+                break;
+            }
+        }
+        let str = std::str::from_utf8(res.as_slice()).unwrap();
+        assert!(str.contains("cookie:a=1; b=2\r\n"));
+        assert_eq!(str.matches("cookie:").count(), 1);
+    }
+
+    /// UT test cases for `RequestEncoder::header_case`.
+    ///
+    /// # Brief
+    /// 1. Creates a `Request` with headers in mixed case.
+    /// 2. Calls `RequestEncoder::header_case` with some of the names.
+    /// 3. Calls `RequestEncoder::encode` method in a loop and collects the
+    ///    results.
+    /// 4. Checks that only the given names keep their case.
+    #[test]
+    fn ut_request_encoder_header_case() {
+        let request = Request::builder()
+            .method("GET")
+            .url("www.example.com")
+            .append_header("X-MyHeader", "a")
+            .append_header("X-MyHeader", "b")
+            .header("Accept", "text/html")
+            .body(())
+            .unwrap();
+        let (part, _) = request.into_parts();
+        let mut encoder = RequestEncoder::new(part);
+        encoder.header_case(["x-myheader", "X-MyHeader", "X-Unused", "\0"]);
+        let mut buf = [0u8; 5];
+        let mut res = Vec::new();
+        loop {
+            let size = encoder.encode(&mut buf).unwrap();
+            res.extend_from_slice(&buf[..size]);
+            if size < buf.len() {
+                break;
+            }
+        }
+        let str = std::str::from_utf8(res.as_slice()).unwrap();
+        assert!(str.contains("X-MyHeader:a\r\nX-MyHeader:b\r\n"));
+        assert!(str.contains("accept:text/html\r\n"));
+        assert!(!str.contains("X-Unused"));
+    }
+
     /// UT test cases for `RequestEncoder::absolute_uri`.
     ///
     /// # Brief
@@ -63,6 +63,7 @@ pub struct ResponseDecoder {
     stage: ParseStage,
     version: Option<Version>,
     status_code: Option<StatusCode>,
+    reason: Option<Vec<u8>>,
     headers: Option<Headers>,
     // Cache the parsed header key.
     head_key: Vec<u8>,
@@ -167,6 +168,7 @@ impl ResponseDecoder {
             stage: ParseStage::Initial,
             version: None,
             status_code: None,
+            reason: None,
             headers: None,
             head_key: vec![],
             rest: vec![],
@@ -278,8 +280,14 @@ impl ResponseDecoder {
     ) -> Result<Option<(ResponsePart, &'a [u8])>, HttpError> {
         self.stage = ParseStage::Reason;
         match decode_reason(buffer)? {
-            Some(unparsed) => self.status_crlf_phase(unparsed),
-            None => Ok(None),
+            TokenStatus::Complete((reason, unparsed)) => {
+                self.reason = Some(self.take_value(reason));
+                self.status_crlf_phase(unparsed)
+            }
+            TokenStatus::Partial(rest) => {
+                self.rest.extend_from_slice(rest);
+                Ok(None)
+            }
         }
     }
 
@@ -334,6 +342,7 @@ impl ResponseDecoder {
                 let response_part = ResponsePart {
                     version: self.version.take().unwrap(),
                     status: self.status_code.take().unwrap(),
+                    reason: self.reason.take(),
                     headers: self.headers.take().unwrap(),
                 };
                 Ok(Some((response_part, unparsed)))
@@ -544,15 +553,15 @@ fn status_token(buffer: &[u8]) -> TokenResult {
     Ok(TokenStatus::Partial(buffer))
 }
 
-fn decode_reason(buffer: &[u8]) -> Result<Option<&[u8]>, HttpError> {
+fn decode_reason(buffer: &[u8]) -> TokenResult<'_> {
     for (i, b) in buffer.iter().enumerate() {
         if *b == b'\r' || *b == b'\n' {
-            return Ok(Some(&buffer[i..]));
+            return Ok(TokenStatus::Complete((&buffer[..i], &buffer[i..])));
         } else if !is_legal_reason_byte(*b) {
             return Err(ErrorKind::H1(H1Error::InvalidResponse).into());
         }
     }
-    Ok(None)
+    Ok(TokenStatus::Partial(buffer))
 }
 
 fn consume_crlf(buffer: &[u8], cr_meet: bool) -> Result<TokenStatus<&[u8], usize>, HttpError> {
@@ -918,4 +927,28 @@ mod ut_decoder {
         test_unit_invalid!("HTTP/1.1 304 OK\r\nAge:270646\r\n\rDate:Mon, 19 Dec 2022 01:46:59 GMT\r\nEtag:\"3147526947+gzip\"\r\n\r\nbody part".as_bytes(), Some(HttpError::from(ErrorKind::H1(H1Error::InvalidResponse))));
         test_unit_invalid!("HTTP/1.1 304 OK\r\nAge:270646\r\n\rDate:Mon, 19 Dec 2022 01:46:59 GMT\r\nEtag:\"3147526947+gzip\"\r\n\r\r\nbody part".as_bytes(), Some(HttpError::from(ErrorKind::H1(H1Error::InvalidResponse))));
     }
+
+    /// UT test cases for `ResponseDecoder::decode`.
+    ///
+    /// # Brief
+    /// Decode the reason-phrase of status-line.
+    /// 1. Creates a `ResponseDecoder` by calling `ResponseDecoder::new`.
+    /// 2. Decodes response bytes in segments by calling
+    ///    `ResponseDecoder::decode`.
+    /// 3. Checks if the origin reason-phrase is kept.
+    #[test]
+    fn ut_response_decoder_decode_reason() {
+        let mut decoder = ResponseDecoder::new();
+        assert!(decoder.decode(b"HTTP/1.0 404 Not ").unwrap().is_none());
+        assert!(decoder.decode(b"Fo").unwrap().is_none());
+        let (part, body) = decoder.decode(b"und\xe9\r\n\r\nbody").unwrap().unwrap();
+        assert_eq!(part.version.as_str(), "HTTP/1.0");
+        assert_eq!(part.status.as_u16(), 404);
+        assert_eq!(part.reason, Some(b"Not Found\xe9".to_vec()));
+        assert_eq!(body, b"body");
+
+        let mut decoder = ResponseDecoder::new();
+        let (part, _) = decoder.decode(b"HTTP/1.1 200 \r\n\r\n").unwrap().unwrap();
+        assert_eq!(part.reason, Some(vec![]));
+    }
 }
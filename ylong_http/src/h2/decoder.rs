@@ -150,6 +150,7 @@ impl core::iter::IntoIterator for Frames {
 
 /// When Headers Frames or Continuation Frames are not End Headers, they are
 /// represented as `FrameKind::Partial`.
+#[allow(clippy::large_enum_variant)]
 pub enum FrameKind {
     /// PUSH_PROMISE or HEADERS frame parsing completed.
     Complete(Frame),
@@ -880,6 +881,16 @@ pub fn get_setting(id: u16, value: u32) -> Result<Option<Setting>, H2Error> {
             Ok(Some(Setting::MaxFrameSize(value)))
         }
         6 => Ok(Some(Setting::MaxHeaderListSize(value))),
+        8 => {
+            let enable_connect_protocol = match value {
+                0 => false,
+                1 => true,
+                _ => return Err(H2Error::ConnectionError(ErrorCode::ProtocolError)),
+            };
+            Ok(Some(Setting::EnableConnectProtocol(
+                enable_connect_protocol,
+            )))
+        }
         _ => Ok(None),
     }
 }
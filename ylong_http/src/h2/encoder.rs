@@ -977,14 +977,7 @@ impl FrameEncoder {
             let setting_byte_index = payload_byte_index % 6;
 
             if let Some(setting) = settings.get_settings().get(setting_index) {
-                let (id, value) = match setting {
-                    Setting::HeaderTableSize(v) => (0x1, *v),
-                    Setting::EnablePush(v) => (0x2, *v as u32),
-                    Setting::MaxConcurrentStreams(v) => (0x3, *v),
-                    Setting::InitialWindowSize(v) => (0x4, *v),
-                    Setting::MaxFrameSize(v) => (0x5, *v),
-                    Setting::MaxHeaderListSize(v) => (0x6, *v),
-                };
+                let (id, value) = (setting.setting_identifier(), setting.setting_value());
                 match setting_byte_index {
                     0..=1 => {
                         *buf_item = ((id >> (8 * (1 - setting_byte_index))) & 0xFF) as u8;
@@ -1456,6 +1449,8 @@ mod ut_frame_encoder {
                 Setting::InitialWindowSize(v) => (0x4, *v),
                 Setting::MaxFrameSize(v) => (0x5, *v),
                 Setting::MaxHeaderListSize(v) => (0x6, *v),
+                Setting::EnableConnectProtocol(v) => (0x8, *v as u32),
+                Setting::Unknown(id, v) => (*id, *v),
             };
             expected_encoded_settings[offset] = (id >> 8) as u8;
             expected_encoded_settings[offset + 1] = (id & 0xFF) as u8;
@@ -14,6 +14,7 @@
 use std::convert::TryFrom;
 
 use crate::error::HttpError;
+use crate::h2::decoder::get_setting;
 use crate::h2::{ErrorCode, H2Error, Parts, PseudoHeaders};
 use crate::headers;
 
@@ -103,6 +104,7 @@ pub enum Payload {
 /// frame. Each setting has a different role in the HTTP/2 communication
 /// process.
 #[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
 pub enum Setting {
     /// SETTINGS_HEADER_TABLE_SIZE
     HeaderTableSize(u32),
@@ -116,6 +118,11 @@ pub enum Setting {
     MaxFrameSize(u32),
     /// SETTINGS_MAX_HEADER_LIST_SIZE
     MaxHeaderListSize(u32),
+    /// SETTINGS_ENABLE_CONNECT_PROTOCOL
+    EnableConnectProtocol(bool),
+    /// A setting whose identifier is not interpreted by this crate, such as an
+    /// experimental or extension setting.
+    Unknown(u16, u32),
 }
 
 /// HTTP/2 frame flags.
@@ -400,6 +407,26 @@ impl Settings {
 }
 
 impl Setting {
+    /// Creates a `Setting` from its identifier and value, checking that the
+    /// value is allowed for the identifier. Identifiers that are not known
+    /// are kept as `Setting::Unknown`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http::h2::Setting;
+    ///
+    /// assert_eq!(
+    ///     Setting::from_raw(0x5, 16384),
+    ///     Ok(Setting::MaxFrameSize(16384))
+    /// );
+    /// assert_eq!(Setting::from_raw(0xf0, 1), Ok(Setting::Unknown(0xf0, 1)));
+    /// assert!(Setting::from_raw(0x2, 2).is_err());
+    /// ```
+    pub fn from_raw(id: u16, value: u32) -> Result<Self, H2Error> {
+        Ok(get_setting(id, value)?.unwrap_or(Setting::Unknown(id, value)))
+    }
+
     /// Returns the identifier associated with the setting.
     pub fn setting_identifier(&self) -> u16 {
         match self {
@@ -409,6 +436,21 @@ impl Setting {
             Setting::InitialWindowSize(_) => 0x04,
             Setting::MaxFrameSize(_) => 0x05,
             Setting::MaxHeaderListSize(_) => 0x06,
+            Setting::EnableConnectProtocol(_) => 0x08,
+            Setting::Unknown(id, _) => *id,
+        }
+    }
+
+    /// Returns the value of the setting as it is encoded on the wire.
+    pub fn setting_value(&self) -> u32 {
+        match self {
+            Setting::HeaderTableSize(v)
+            | Setting::MaxConcurrentStreams(v)
+            | Setting::InitialWindowSize(v)
+            | Setting::MaxFrameSize(v)
+            | Setting::MaxHeaderListSize(v)
+            | Setting::Unknown(_, v) => *v,
+            Setting::EnablePush(v) | Setting::EnableConnectProtocol(v) => *v as u32,
         }
     }
 }
@@ -603,6 +645,11 @@ impl PushPromise {
             parts,
         }
     }
+
+    /// Returns the identifier of the stream reserved by the `PushPromise`.
+    pub fn promised_stream_id(&self) -> StreamId {
+        self.promised_stream_id
+    }
 }
 
 #[cfg(test)]
@@ -679,6 +726,36 @@ mod ut_frame {
 
         let setting_max_header_list_size = Setting::MaxHeaderListSize(8192);
         assert_eq!(setting_max_header_list_size.setting_identifier(), 0x06);
+
+        let setting_enable_connect_protocol = Setting::EnableConnectProtocol(true);
+        assert_eq!(setting_enable_connect_protocol.setting_identifier(), 0x08);
+        assert_eq!(setting_enable_connect_protocol.setting_value(), 1);
+
+        let setting_unknown = Setting::Unknown(0xf0, 7);
+        assert_eq!(setting_unknown.setting_identifier(), 0xf0);
+        assert_eq!(setting_unknown.setting_value(), 7);
+    }
+
+    /// UT test cases for `Setting::from_raw`.
+    ///
+    /// # Brief
+    /// 1. Creates `Setting`s from raw identifiers and values.
+    /// 2. Checks that known identifiers are mapped to their variants.
+    /// 3. Checks that unknown identifiers are kept as `Setting::Unknown`.
+    /// 4. Checks that invalid values are rejected.
+    #[test]
+    fn ut_setting_from_raw() {
+        assert_eq!(
+            Setting::from_raw(0x1, 4096),
+            Ok(Setting::HeaderTableSize(4096))
+        );
+        assert_eq!(
+            Setting::from_raw(0x8, 1),
+            Ok(Setting::EnableConnectProtocol(true))
+        );
+        assert_eq!(Setting::from_raw(0x9, 3), Ok(Setting::Unknown(0x9, 3)));
+        assert!(Setting::from_raw(0x5, 100).is_err());
+        assert!(Setting::from_raw(0x8, 2).is_err());
     }
 
     /// UT test cases for `Settings`.
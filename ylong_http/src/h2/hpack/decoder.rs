@@ -189,6 +189,7 @@ impl<'a> Updater<'a> {
                     .search_header_name(index)
                     .ok_or(H2Error::ConnectionError(ErrorCode::CompressionError))?
             }
+            Name::Literal(octets) if octets.as_slice() == b":protocol" => Header::Protocol,
             Name::Literal(octets) => Header::Other(unsafe { String::from_utf8_unchecked(octets) }),
         };
         let v = unsafe { String::from_utf8_unchecked(value) };
@@ -607,4 +608,35 @@ mod ut_hpack_decoder {
         let res = decoder.decode(&buf);
         assert!(res.is_ok());
     }
+
+    /// UT test cases for decoding the `:protocol` pseudo-header.
+    ///
+    /// # Brief
+    /// 1. Encodes a `Parts` that contains `:method` and `:protocol` by
+    ///    `HpackEncoder`.
+    /// 2. Calls `HpackDecoder::decode()` and `HpackDecoder::finish()`.
+    /// 3. Checks if `:protocol` is decoded as a pseudo-header.
+    #[test]
+    fn ut_decode_protocol_pseudo_header() {
+        use crate::h2::hpack::HpackEncoder;
+        use crate::h2::{Parts, PseudoHeaders};
+
+        let mut pseudo = PseudoHeaders::new();
+        pseudo.set_method(Some(String::from("CONNECT")));
+        pseudo.set_protocol(Some(String::from("websocket")));
+        let mut parts = Parts::new();
+        parts.set_pseudo(pseudo);
+
+        let mut encoder = HpackEncoder::new(4096, false);
+        encoder.set_parts(parts);
+        let mut buf = [0u8; 128];
+        let size = encoder.encode(&mut buf);
+
+        let mut decoder = HpackDecoder::with_max_size(4096, 16 << 20);
+        assert!(decoder.decode(&buf[..size]).is_ok());
+        let (pseudo, map) = decoder.finish().unwrap().into_parts();
+        assert_eq!(pseudo.method(), Some("CONNECT"));
+        assert_eq!(pseudo.protocol(), Some("websocket"));
+        assert!(map.is_empty());
+    }
 }
@@ -163,6 +163,7 @@ enum PartsIterDirection {
     Path,
     Scheme,
     Status,
+    Protocol,
     Other,
 }
 
@@ -177,7 +178,7 @@ impl PartsIter {
     }
 
     /// Gets headers in the order of `Method`, `Status`, `Scheme`, `Path`,
-    /// `Authority` and `Other`.
+    /// `Authority`, `Protocol` and `Other`.
     fn next(&mut self) -> Option<(Header, String)> {
         loop {
             match self.next_type {
@@ -199,6 +200,10 @@ impl PartsIter {
                 },
                 PartsIterDirection::Authority => match self.pseudo.take_authority() {
                     Some(value) => return Some((Header::Authority, value)),
+                    None => self.next_type = PartsIterDirection::Protocol,
+                },
+                PartsIterDirection::Protocol => match self.pseudo.take_protocol() {
+                    Some(value) => return Some((Header::Protocol, value)),
                     None => self.next_type = PartsIterDirection::Other,
                 },
                 PartsIterDirection::Other => {
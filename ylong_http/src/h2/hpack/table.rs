@@ -342,6 +342,8 @@ impl StaticTable {
             (Header::Status, "404") => Some(TableIndex::Header(13)),
             (Header::Status, "500") => Some(TableIndex::Header(14)),
             (Header::Status, _) => Some(TableIndex::HeaderName(8)),
+            // `:protocol` is not in the static table.
+            (Header::Protocol, _) => None,
             (Header::Other(s), v) => Self::index_headers(s.as_str(), v),
         }
     }
@@ -409,6 +411,7 @@ pub(crate) enum Header {
     Path,
     Scheme,
     Status,
+    Protocol,
     Other(String),
 }
 
@@ -425,6 +428,8 @@ impl Header {
             Header::Scheme => 7,
             // 7 is the length of "status".
             Header::Status => 7,
+            // 9 is the length of ":protocol".
+            Header::Protocol => 9,
             Header::Other(s) => s.len(),
         }
     }
@@ -436,6 +441,7 @@ impl Header {
             Header::Path => String::from(":path"),
             Header::Scheme => String::from(":scheme"),
             Header::Status => String::from(":status"),
+            Header::Protocol => String::from(":protocol"),
             Header::Other(s) => s,
         }
     }
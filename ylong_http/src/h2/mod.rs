@@ -62,8 +62,8 @@ pub use decoder::{FrameDecoder, FrameKind, Frames, FramesIntoIter};
 pub use encoder::FrameEncoder;
 pub use error::{ErrorCode, H2Error};
 pub use frame::{
-    Data, Frame, FrameFlags, Goaway, Headers, Payload, Ping, RstStream, Setting, Settings,
-    SettingsBuilder, StreamId, WindowUpdate,
+    Data, Frame, FrameFlags, Goaway, Headers, Payload, Ping, Priority, RstStream, Setting,
+    Settings, SettingsBuilder, StreamId, WindowUpdate,
 };
 pub(crate) use hpack::{HpackDecoder, HpackEncoder};
 pub use parts::Parts;
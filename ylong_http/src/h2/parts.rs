@@ -52,6 +52,7 @@ impl Parts {
             Header::Path => self.pseudo.set_path(Some(value)),
             Header::Scheme => self.pseudo.set_scheme(Some(value)),
             Header::Status => self.pseudo.set_status(Some(value)),
+            Header::Protocol => self.pseudo.set_protocol(Some(value)),
             Header::Other(header) => self.map.append(header.as_str(), value.as_str()).unwrap(),
         }
     }
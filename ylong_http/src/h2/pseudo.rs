@@ -25,6 +25,7 @@ pub struct PseudoHeaders {
     path: Option<String>,
     scheme: Option<String>,
     status: Option<String>,
+    protocol: Option<String>,
 }
 
 // TODO: 去掉冗余的方法。
@@ -37,6 +38,7 @@ impl PseudoHeaders {
             path: None,
             scheme: None,
             status: None,
+            protocol: None,
         }
     }
 
@@ -46,6 +48,7 @@ impl PseudoHeaders {
             && self.path.is_none()
             && self.scheme.is_none()
             && self.status.is_none()
+            && self.protocol.is_none()
     }
 
     /// Check if it contains `Authority`.
@@ -147,6 +150,31 @@ impl PseudoHeaders {
     pub(crate) fn take_status(&mut self) -> Option<String> {
         self.status.take()
     }
+
+    /// Check if it contains `Protocol`.
+    pub(crate) fn contains_protocol(&self) -> bool {
+        self.protocol.is_some()
+    }
+
+    /// Get the `&str` value of `Protocol`.
+    ///
+    /// `:protocol` is only used by the extended CONNECT method defined in
+    /// [RFC8441].
+    ///
+    /// [RFC8441]: https://www.rfc-editor.org/rfc/rfc8441.html#section-4
+    pub fn protocol(&self) -> Option<&str> {
+        self.protocol.as_deref()
+    }
+
+    /// Set the value of `Protocol`.
+    pub fn set_protocol(&mut self, protocol: Option<String>) {
+        self.protocol = protocol;
+    }
+
+    /// Take the `String` value of `Protocol`.
+    pub(crate) fn take_protocol(&mut self) -> Option<String> {
+        self.protocol.take()
+    }
 }
 
 impl Default for PseudoHeaders {
@@ -172,6 +200,7 @@ mod ut_pseudo_headers {
         assert!(pseudo.path.is_none());
         assert!(pseudo.scheme.is_none());
         assert!(pseudo.status.is_none());
+        assert!(pseudo.protocol.is_none());
     }
 
     /// UT test cases for `PseudoHeaders::contains_authority`.
@@ -509,4 +538,28 @@ mod ut_pseudo_headers {
         pseudo.status = Some(String::from("status"));
         assert_eq!(pseudo.take_status(), Some(String::from("status")));
     }
+
+    /// UT test cases for `PseudoHeaders::protocol`.
+    ///
+    /// # Brief
+    /// 1. Creates a `PseudoHeaders`.
+    /// 2. Calls `PseudoHeaders::protocol` of it.
+    /// 3. Calls `PseudoHeaders::set_protocol` and `PseudoHeaders::protocol` of
+    ///    it.
+    /// 4. Calls `PseudoHeaders::take_protocol` of it.
+    /// 5. Checks the results.
+    #[test]
+    fn ut_pseudo_headers_protocol() {
+        let mut pseudo = PseudoHeaders::new();
+        assert!(!pseudo.contains_protocol());
+        assert!(pseudo.protocol().is_none());
+
+        pseudo.set_protocol(Some(String::from("websocket")));
+        assert!(pseudo.contains_protocol());
+        assert!(!pseudo.is_empty());
+        assert_eq!(pseudo.protocol(), Some("websocket"));
+
+        assert_eq!(pseudo.take_protocol(), Some(String::from("websocket")));
+        assert!(pseudo.is_empty());
+    }
 }
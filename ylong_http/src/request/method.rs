@@ -101,6 +101,13 @@ impl Method {
     /// [`RFC9110 9.3.8`]: https://httpwg.org/specs/rfc9110.html#TRACE
     pub const TRACE: Self = Self(Inner::Trace);
 
+    /// Apply partial modifications to the target resource.
+    ///
+    /// See [`RFC5789 2`] for more details.
+    ///
+    /// [`RFC5789 2`]: https://www.rfc-editor.org/rfc/rfc5789#section-2
+    pub const PATCH: Self = Self(Inner::Patch);
+
     /// Tries converting &[u8] to `Method`. Only uppercase letters are
     /// supported.
     ///
@@ -121,6 +128,7 @@ impl Method {
             b'P' => match bytes[1] {
                 b'U' if b"T" == &bytes[2..] => Ok(Method::PUT),
                 b'O' if b"ST" == &bytes[2..] => Ok(Method::POST),
+                b'A' if b"TCH" == &bytes[2..] => Ok(Method::PATCH),
                 _ => Err(ErrorKind::InvalidInput.into()),
             },
             b'H' if b"EAD" == &bytes[1..] => Ok(Method::HEAD),
@@ -150,6 +158,7 @@ impl Method {
             Inner::Options => "OPTIONS",
             Inner::Trace => "TRACE",
             Inner::Connect => "CONNECT",
+            Inner::Patch => "PATCH",
         }
     }
 }
@@ -164,6 +173,7 @@ enum Inner {
     Connect,
     Options,
     Trace,
+    Patch,
 }
 
 impl<'a> TryFrom<&'a [u8]> for Method {
@@ -202,6 +212,7 @@ mod ut_method {
         assert_eq!(Method::OPTIONS.as_str(), "OPTIONS");
         assert_eq!(Method::TRACE.as_str(), "TRACE");
         assert_eq!(Method::CONNECT.as_str(), "CONNECT");
+        assert_eq!(Method::PATCH.as_str(), "PATCH");
     }
 
     /// UT test cases for `Method::from_bytes`.
@@ -220,6 +231,7 @@ mod ut_method {
         assert_eq!(Method::from_bytes(b"OPTIONS").unwrap(), Method::OPTIONS);
         assert_eq!(Method::from_bytes(b"TRACE").unwrap(), Method::TRACE);
         assert_eq!(Method::from_bytes(b"CONNECT").unwrap(), Method::CONNECT);
+        assert_eq!(Method::from_bytes(b"PATCH").unwrap(), Method::PATCH);
 
         // Exception Test Cases:
         // 1. Empty bytes slice.
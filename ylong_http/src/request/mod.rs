@@ -236,6 +236,24 @@ impl Request<()> {
     {
         RequestBuilder::new().method(Method::TRACE).url(uri)
     }
+
+    /// Creates a `RequestBuilder` for the given `Uri` with method set to
+    /// `PATCH`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http::request::Request;
+    ///
+    /// let request = Request::patch("www.example.com").body(()).unwrap();
+    /// ```
+    pub fn patch<T>(uri: T) -> RequestBuilder
+    where
+        Uri: TryFrom<T>,
+        <Uri as TryFrom<T>>::Error: Into<HttpError>,
+    {
+        RequestBuilder::new().method(Method::PATCH).url(uri)
+    }
 }
 
 impl<T> Request<T> {
@@ -602,6 +620,9 @@ impl RequestBuilder {
     /// Adds a `Header` to `Request`. Appends `HeaderValue` to the end of
     /// previous `HeaderValue` if the `HeaderName` already exists.
     ///
+    /// Unlike `header`, the previous values are kept. In `HTTP/1`, each value
+    /// is sent as a separate header line.
+    ///
     /// # Examples
     ///
     /// ```
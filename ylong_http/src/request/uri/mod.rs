@@ -1658,6 +1658,22 @@ mod ut_uri {
                 None,
             )),
         );
+
+        uri_test_case!(
+            b"http://[fe80::1%eth0]",
+            Ok((Some("http"), Some("[fe80::1%eth0]"), None, None, None)),
+        );
+
+        uri_test_case!(
+            b"http://[fe80::1%25eth0]:8080/",
+            Ok((
+                Some("http"),
+                Some("[fe80::1%25eth0]"),
+                Some("8080"),
+                Some("/"),
+                None,
+            )),
+        );
     }
 
     /// UT test cases for `Uri::authority`.
@@ -28,9 +28,10 @@ use crate::version::Version;
 ///
 /// The body can be saved in the user-defined type.
 ///
-/// According to the [`RFC9112`], the origin reason-phrase of status-line is not
-/// saved, the unified reason in `StatusCode` is used to indicate the status
-/// code reason.
+/// According to the [`RFC9112`], the reason-phrase of status-line is only
+/// informational, the unified reason in `StatusCode` is used to indicate the
+/// status code reason. The origin reason-phrase received in a HTTP/1.x
+/// status-line is kept as-is and can be got by [`Response::reason`].
 ///
 /// [`RFC9112`]: https://httpwg.org/specs/rfc9112.html
 /// [`Response`]: https://httpwg.org/specs/rfc9112.html#status.line
@@ -50,11 +51,25 @@ impl<T> Response<T> {
         self.part.status
     }
 
+    /// Gets the origin reason-phrase bytes of the status-line.
+    ///
+    /// Returns `None` if the response does not carry a reason-phrase, such as
+    /// a HTTP/2 or HTTP/3 response. An empty slice means that the status-line
+    /// has an empty reason-phrase.
+    pub fn reason(&self) -> Option<&[u8]> {
+        self.part.reason.as_deref()
+    }
+
     /// Gets an immutable reference to the `Headers`.
     pub fn headers(&self) -> &Headers {
         &self.part.headers
     }
 
+    /// Gets a mutable reference to the `Headers`.
+    pub fn headers_mut(&mut self) -> &mut Headers {
+        &mut self.part.headers
+    }
+
     /// Gets an immutable reference to the `Body`.
     pub fn body(&self) -> &T {
         &self.body
@@ -90,16 +105,47 @@ impl<T: Clone> Clone for Response<T> {
 ///
 /// [`RFC9112`]: https://httpwg.org/specs/rfc9112.html
 /// [`Status Line`]: https://httpwg.org/specs/rfc9112.html#status.line
+///
+/// `ResponsePart` is `#[non_exhaustive]`, so that fields can be added to it.
+/// Use `ResponsePart::new` to create one outside of this crate.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
 pub struct ResponsePart {
     /// HTTP Version implementation.
     pub version: Version,
     /// HTTP Status Codes implementation.
     pub status: StatusCode,
+    /// The origin reason-phrase of a HTTP/1.x status-line.
+    pub reason: Option<Vec<u8>>,
     /// HTTP Headers, which is called Fields in RFC9110.
     pub headers: Headers,
 }
 
+impl ResponsePart {
+    /// Creates a `ResponsePart` without a reason-phrase.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http::headers::Headers;
+    /// use ylong_http::response::status::StatusCode;
+    /// use ylong_http::response::ResponsePart;
+    /// use ylong_http::version::Version;
+    ///
+    /// let part = ResponsePart::new(Version::HTTP2, StatusCode::OK, Headers::new());
+    /// assert_eq!(part.status, StatusCode::OK);
+    /// assert_eq!(part.reason, None);
+    /// ```
+    pub fn new(version: Version, status: StatusCode, headers: Headers) -> Self {
+        Self {
+            version,
+            status,
+            reason: None,
+            headers,
+        }
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "http1_1")]
 mod ut_response {
@@ -172,6 +218,32 @@ mod ut_response {
         assert_eq!(response.headers(), &headers)
     }
 
+    /// UT test cases for `Response::headers_mut`.
+    ///
+    /// # Brief
+    /// 1. Creates a `ResponsePart` by calling `ResponseDecoder::decode`.
+    /// 2. Modifies the `Headers` by calling `Response::headers_mut`.
+    /// 3. Checks if the test result is correct.
+    #[test]
+    fn ut_response_headers_mut() {
+        let response_str =
+            "HTTP/1.1 200 \r\nContent-Encoding: gzip\r\nContent-Length: 10\r\n\r\n".as_bytes();
+        let mut decoder = ResponseDecoder::new();
+        let result = decoder.decode(response_str).unwrap().unwrap();
+        let mut response = Response {
+            part: result.0,
+            body: result.1,
+        };
+        response.headers_mut().remove("content-encoding");
+        response
+            .headers_mut()
+            .insert("Content-Length", "20")
+            .expect(ERROR_HEADER);
+        let mut headers = Headers::new();
+        headers.insert("Content-Length", "20").expect(ERROR_HEADER);
+        assert_eq!(response.headers(), &headers)
+    }
+
     /// UT test cases for `Response::body`.
     ///
     /// # Brief
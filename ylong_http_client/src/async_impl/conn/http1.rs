@@ -11,28 +11,35 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::future::poll_fn;
 use std::mem::take;
+use std::ops::Range;
 use std::pin::Pin;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::task::{Context, Poll};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use ylong_http::body::async_impl::Body;
 use ylong_http::body::{ChunkBody, TextBody};
 use ylong_http::h1::{RequestEncoder, ResponseDecoder};
+use ylong_http::request::method::Method;
 use ylong_http::request::uri::Scheme;
+use ylong_http::request::{Request as Req, RequestPart};
+use ylong_http::response::status::StatusCode;
 use ylong_http::response::ResponsePart;
 use ylong_http::version::Version;
 
 use super::StreamData;
 use crate::async_impl::request::Message;
 use crate::async_impl::{HttpBody, Request, Response};
-use crate::error::HttpClientError;
-use crate::runtime::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
-use crate::util::dispatcher::http1::Http1Conn;
+use crate::error::{Closed, ErrorKind, HttpClientError};
+use crate::runtime::{sleep, timeout, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
+use crate::util::dispatcher::http1::{Http1Conn, Inner};
 use crate::util::information::ConnInfo;
 use crate::util::interceptor::Interceptors;
-use crate::util::normalizer::BodyLengthParser;
+use crate::util::normalizer::{BodyLength, BodyLengthParser};
+use crate::util::pacer::RatePacer;
 
 const TEMP_BUF_SIZE: usize = 16 * 1024;
 
@@ -53,62 +60,362 @@ where
         .ref_mut()
         .time_group_mut()
         .set_transfer_start(Instant::now());
+    let empty = message.request.ref_mut().body().is_empty();
+    set_body_framing(message.request.ref_mut(), empty);
+    conn.set_pipelinable(false);
+    let guard = CancelGuard::new(&conn);
     encode_request_part(
         message.request.ref_mut(),
         &message.interceptor,
+        message.preserve_header_case,
         &mut conn,
         &mut buf,
     )
     .await?;
-    encode_various_body(message.request.ref_mut(), &mut conn, &mut buf).await?;
-    // Decodes response part.
-    let (part, pre) = {
-        let mut decoder = ResponseDecoder::new();
-        loop {
-            let size = match conn.raw_mut().read(buf.as_mut_slice()).await {
-                Ok(0) => {
-                    conn.shutdown();
-                    return err_from_msg!(Request, "Tcp closed");
+
+    // Waits for the server to ask for the body before sending it.
+    let wait = message
+        .expect_continue_timeout
+        .filter(|_| expects_continue(message.request.ref_mut()));
+    let mut early = None;
+    if let Some(dur) = wait {
+        wait_turn(&mut conn).await?;
+        early = read_response_head(&mut message, &mut conn, &mut buf, Some(dur)).await?;
+    }
+    let (part, pre, head_size) = match early {
+        // The server answers without reading the body, so the connection is
+        // left in an unknown state.
+        Some(head) => {
+            conn.shutdown();
+            head
+        }
+        None => {
+            encode_various_body(
+                message.request.ref_mut(),
+                &message.interceptor,
+                message.chunk_size,
+                message.upload_rate_limit,
+                &mut conn,
+                &mut buf,
+            )
+            .await?;
+            conn.set_pipelinable(is_pipelinable(message.request.ref_mut()));
+            wait_turn(&mut conn).await?;
+            // Decodes response part.
+            decode_response_part(&mut message, &mut conn, &mut buf).await?
+        }
+    };
+    guard.disarm();
+    let mut response = decode_response(message, part, conn, &buf[pre])?;
+    response.set_header_bytes(head_size);
+    Ok(response)
+}
+
+// Shuts the connection down if the request is dropped or fails before its
+// response head is read, such as a cancelled hedged request, since the
+// connection is then left in an unknown state.
+struct CancelGuard<S> {
+    inner: Option<Arc<Inner<S>>>,
+}
+
+impl<S> CancelGuard<S> {
+    fn new(conn: &Http1Conn<S>) -> Self {
+        Self {
+            inner: Some(conn.inner.clone()),
+        }
+    }
+
+    fn disarm(mut self) {
+        self.inner = None;
+    }
+}
+
+impl<S> Drop for CancelGuard<S> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.take() {
+            inner.shutdown.store(true, Ordering::Release);
+        }
+    }
+}
+
+/// Sends an HTTP/1.1 upgrade request without a body. Once the server switches
+/// protocols, the connection is handed over to the caller together with the
+/// bytes received after the response head.
+pub(crate) async fn upgrade<S>(
+    mut conn: Http1Conn<S>,
+    mut message: Message,
+) -> Result<(ResponsePart, Http1Conn<S>, Vec<u8>), HttpClientError>
+where
+    S: AsyncRead + AsyncWrite + ConnInfo + Sync + Send + Unpin + 'static,
+{
+    message
+        .interceptor
+        .intercept_request(message.request.ref_mut())?;
+    let mut buf = vec![0u8; TEMP_BUF_SIZE];
+    encode_request_part(
+        message.request.ref_mut(),
+        &message.interceptor,
+        message.preserve_header_case,
+        &mut conn,
+        &mut buf,
+    )
+    .await?;
+    let (part, pre, _) = decode_response_part(&mut message, &mut conn, &mut buf).await?;
+    let pre = buf[pre].to_vec();
+    // The connection no longer speaks HTTP, so it must not go back to the pool.
+    conn.shutdown();
+    if part.status != StatusCode::SWITCHING_PROTOCOLS {
+        return err_from_msg!(ConnectionUpgrade, "The server refused to switch protocols");
+    }
+    Ok((part, conn, pre))
+}
+
+// Waits for the responses before the pipelined request to be read.
+async fn wait_turn<S>(conn: &mut Http1Conn<S>) -> Result<(), HttpClientError> {
+    if poll_fn(|cx| conn.poll_turn(cx)).await {
+        Ok(())
+    } else {
+        Err(HttpClientError::from_closed(
+            ErrorKind::Request,
+            Closed::BeforeResponse,
+        ))
+    }
+}
+
+// Checks if the request asks the server for `100 Continue` before its body is
+// sent.
+fn expects_continue(request: &Request) -> bool {
+    *request.version() == Version::HTTP1_1
+        && !request.body().is_empty()
+        && request
+            .part()
+            .headers
+            .get("Expect")
+            .and_then(|value| value.to_string().ok())
+            .map(|value| value.trim().eq_ignore_ascii_case("100-continue"))
+            .unwrap_or(false)
+}
+
+// Returns the response part, the range of `buf` that holds the bytes received
+// after it, and the size of the response head.
+async fn decode_response_part<S>(
+    message: &mut Message,
+    conn: &mut Http1Conn<S>,
+    buf: &mut [u8],
+) -> Result<(ResponsePart, Range<usize>, u64), HttpClientError>
+where
+    S: AsyncRead + AsyncWrite + Sync + Send + Unpin + 'static,
+{
+    match read_response_head(message, conn, buf, None).await? {
+        Some(head) => Ok(head),
+        None => err_from_msg!(Request, "Response head is missing"),
+    }
+}
+
+// Reads the head of the final response, skipping the interim ones. If `wait`
+// is set, returns `None` once the server sends `100 Continue`, or if it sends
+// nothing for that long.
+async fn read_response_head<S>(
+    message: &mut Message,
+    conn: &mut Http1Conn<S>,
+    buf: &mut [u8],
+    wait: Option<Duration>,
+) -> Result<Option<(ResponsePart, Range<usize>, u64)>, HttpClientError>
+where
+    S: AsyncRead + AsyncWrite + Sync + Send + Unpin + 'static,
+{
+    // On `&mut Http1Conn`, `conn.shutdown()` resolves to the unawaited
+    // `AsyncWriteExt::shutdown`, so the inherent method is called explicitly.
+    let mut decoder = ResponseDecoder::new();
+    // The head bytes of earlier reads, if the head spans several reads.
+    let mut head = Vec::new();
+    let mut first_byte = true;
+    loop {
+        let read = conn.read(buf);
+        let ttfb = message.ttfb_timeout.filter(|_| first_byte);
+        // Stops waiting once a response head starts.
+        let waiting = wait.filter(|_| head.is_empty());
+        let result = match ttfb
+            .into_iter()
+            .chain(message.read_timeout)
+            .chain(waiting)
+            .min()
+        {
+            Some(dur) => match timeout(dur, read).await {
+                Ok(result) => result,
+                Err(_) => {
+                    if waiting == Some(dur) {
+                        return Ok(None);
+                    }
+                    Http1Conn::shutdown(conn);
+                    if ttfb == Some(dur) {
+                        return err_from_msg!(Timeout, "Time to first byte timed out");
+                    }
+                    return err_from_msg!(Timeout, "Read timed out");
                 }
-                Ok(size) => {
-                    if message
+            },
+            None => read.await,
+        };
+        first_byte = false;
+        let size = match result {
+            Ok(0) => {
+                Http1Conn::shutdown(conn);
+                return Err(HttpClientError::from_closed(
+                    ErrorKind::Request,
+                    Closed::BeforeResponse,
+                ));
+            }
+            Ok(size) => {
+                if message
+                    .request
+                    .ref_mut()
+                    .time_group_mut()
+                    .transfer_end_time()
+                    .is_none()
+                {
+                    message
                         .request
                         .ref_mut()
                         .time_group_mut()
-                        .transfer_end_time()
-                        .is_none()
-                    {
-                        message
-                            .request
-                            .ref_mut()
-                            .time_group_mut()
-                            .set_transfer_end(Instant::now())
+                        .set_transfer_end(Instant::now())
+                }
+                size
+            }
+            Err(e) => {
+                Http1Conn::shutdown(conn);
+                return err_from_io!(Request, e);
+            }
+        };
+
+        message.interceptor.intercept_output(&buf[..size])?;
+        let mut start = 0;
+        while start < size {
+            match decoder.decode(&buf[start..size]) {
+                Ok(None) => {
+                    head.extend_from_slice(&buf[start..size]);
+                    if exceeds(message.max_header_size, head.len()) {
+                        Http1Conn::shutdown(conn);
+                        return err_from_msg!(Request, "Response head is too large");
                     }
-                    size
+                    break;
                 }
-                Err(e) => {
-                    conn.shutdown();
-                    return err_from_io!(Request, e);
+                Ok(Some((part, rem))) => {
+                    let end = size - rem.len();
+                    let head_size = head.len() + end - start;
+                    if exceeds(message.max_header_size, head_size) {
+                        Http1Conn::shutdown(conn);
+                        return err_from_msg!(Request, "Response head is too large");
+                    }
+                    let head_size = head_size as u64;
+                    intercept_response_head(&message.interceptor, &mut head, &buf[start..end])?;
+                    if !is_interim(&part) {
+                        return Ok(Some((part, end..size, head_size)));
+                    }
+                    if wait.is_some() && part.status == StatusCode::CONTINUE && end == size {
+                        return Ok(None);
+                    }
+                    decoder = ResponseDecoder::new();
+                    head.clear();
+                    start = end;
                 }
-            };
-
-            message.interceptor.intercept_output(&buf[..size])?;
-            match decoder.decode(&buf[..size]) {
-                Ok(None) => {}
-                Ok(Some((part, rem))) => break (part, rem),
                 Err(e) => {
-                    conn.shutdown();
+                    Http1Conn::shutdown(conn);
+                    intercept_response_head(&message.interceptor, &mut head, &buf[start..size])?;
                     return err_from_other!(Request, e);
                 }
             }
         }
-    };
+    }
+}
 
-    decode_response(message, part, conn, pre)
+// Checks if the size of a response head exceeds the limit.
+fn exceeds(limit: Option<usize>, size: usize) -> bool {
+    match limit {
+        Some(limit) => size > limit,
+        None => false,
+    }
+}
+
+// Checks if the response is an interim one which precedes the final response.
+// `101 Switching Protocols` ends the HTTP exchange, so it is final.
+fn is_interim(part: &ResponsePart) -> bool {
+    part.status.is_informational() && part.status != StatusCode::SWITCHING_PROTOCOLS
+}
+
+// Intercepts the response head, whose last bytes are `tail`.
+fn intercept_response_head(
+    interceptor: &Arc<Interceptors>,
+    head: &mut Vec<u8>,
+    tail: &[u8],
+) -> Result<(), HttpClientError> {
+    if head.is_empty() {
+        interceptor.intercept_response_head(tail)
+    } else {
+        head.extend_from_slice(tail);
+        interceptor.intercept_response_head(head)
+    }
+}
+
+// Checks if requests can be pipelined behind `request`, which must be
+// idempotent and fully sent once its head is written.
+pub(crate) fn is_pipelinable(request: &Request) -> bool {
+    matches!(*request.method(), Method::GET | Method::HEAD)
+        && *request.version() == Version::HTTP1_1
+        && request.body().is_empty()
+}
+
+// Sends a body of unknown length chunked, whatever the method is, so that the
+// body is neither dropped nor read by the server as the next request. An empty
+// body of a method which customarily carries one is sent with
+// `Content-Length: 0`, since some servers reject such requests without it.
+fn set_body_framing<T>(request: &mut Req<T>, empty: bool) {
+    let headers = &request.part().headers;
+    if headers.get("Content-Length").is_some() || headers.get("Transfer-Encoding").is_some() {
+        return;
+    }
+    if empty {
+        if matches!(
+            *request.method(),
+            Method::POST | Method::PUT | Method::PATCH
+        ) {
+            let _ = request.headers_mut().insert("Content-Length", "0");
+        }
+    } else if *request.version() == Version::HTTP1_1 {
+        let _ = request.headers_mut().insert("Transfer-Encoding", "chunked");
+    }
+}
+
+// Serializes the head of `part` as it is written on a connection, with the body
+// framing of a body which is empty or not. The target is in the absolute form
+// if the connection goes through a proxy.
+pub(crate) fn render_request_head(
+    part: RequestPart,
+    empty: bool,
+    is_proxy: bool,
+    header_case: &[String],
+) -> Result<Vec<u8>, HttpClientError> {
+    let mut request = Req::from_raw_parts(part, ());
+    set_body_framing(&mut request, empty);
+    let absolute = is_proxy && request.uri().scheme() == Some(&Scheme::HTTP);
+    let mut part_encoder = RequestEncoder::new(request.part().clone());
+    part_encoder.absolute_uri(absolute);
+    part_encoder.header_case(header_case);
+    let mut buf = [0u8; 1024];
+    let mut head = Vec::new();
+    loop {
+        match part_encoder.encode(&mut buf[..]) {
+            Ok(0) => return Ok(head),
+            Ok(written) => head.extend_from_slice(&buf[..written]),
+            Err(e) => return err_from_other!(Request, e),
+        }
+    }
 }
 
 async fn encode_various_body<S>(
     request: &mut Request,
+    interceptor: &Arc<Interceptors>,
+    chunk_size: Option<usize>,
+    rate_limit: Option<u64>,
     conn: &mut Http1Conn<S>,
     buf: &mut [u8],
 ) -> Result<(), HttpClientError>
@@ -131,20 +438,29 @@ where
         .map(|v| v.contains("chunked"))
         .unwrap_or(false);
 
-    let body = request.body_mut();
+    let body = InterceptedBody {
+        body: request.body_mut(),
+        interceptor,
+    };
+    let mut pacer = rate_limit.map(RatePacer::new);
 
     match (content_length, transfer_encoding) {
         (_, true) => {
-            let body = ChunkBody::from_async_reader(body);
-            encode_body(conn, body, buf).await?;
+            let mut body = ChunkBody::from_async_reader(body);
+            if let Some(size) = chunk_size {
+                body = body
+                    .set_chunk_size(size)
+                    .map_err(|e| HttpClientError::from_error(ErrorKind::Request, e))?;
+            }
+            encode_body(conn, body, interceptor, pacer.as_mut(), buf).await?;
         }
         (true, false) => {
             let body = TextBody::from_async_reader(body);
-            encode_body(conn, body, buf).await?;
+            encode_body(conn, body, interceptor, pacer.as_mut(), buf).await?;
         }
         (false, false) => {
             let body = TextBody::from_async_reader(body);
-            encode_body(conn, body, buf).await?;
+            encode_body(conn, body, interceptor, pacer.as_mut(), buf).await?;
         }
     };
     Ok(())
@@ -153,6 +469,7 @@ where
 async fn encode_request_part<S>(
     request: &Request,
     interceptor: &Arc<Interceptors>,
+    preserve_case: bool,
     conn: &mut Http1Conn<S>,
     buf: &mut [u8],
 ) -> Result<(), HttpClientError>
@@ -164,19 +481,22 @@ where
     if conn.raw_mut().is_proxy() && request.uri().scheme() == Some(&Scheme::HTTP) {
         part_encoder.absolute_uri(true);
     }
+    if preserve_case {
+        part_encoder.header_case(&request.header_case);
+    }
     loop {
         match part_encoder.encode(&mut buf[..]) {
             Ok(0) => break,
             Ok(written) => {
                 interceptor.intercept_input(&buf[..written])?;
                 // RequestEncoder writes `buf` as much as possible.
-                if let Err(e) = conn.raw_mut().write_all(&buf[..written]).await {
-                    conn.shutdown();
+                if let Err(e) = conn.write_all(&buf[..written]).await {
+                    Http1Conn::shutdown(conn);
                     return err_from_io!(Request, e);
                 }
             }
             Err(e) => {
-                conn.shutdown();
+                Http1Conn::shutdown(conn);
                 return err_from_other!(Request, e);
             }
         }
@@ -193,6 +513,13 @@ fn decode_response<S>(
 where
     S: AsyncRead + AsyncWrite + ConnInfo + Sync + Send + Unpin + 'static,
 {
+    // The request didn't ask to upgrade, so the bytes after a protocol switch
+    // can't be read as HTTP, and the connection can never be reused.
+    if part.status == StatusCode::SWITCHING_PROTOCOLS {
+        conn.shutdown();
+        return err_from_msg!(Request, "Unexpected 101 Switching Protocols response");
+    }
+
     // The shutdown function only sets the current connection to the closed state
     // and does not release the connection immediately.
     // Instead, the connection will be completely closed
@@ -224,16 +551,29 @@ where
         }
     }
 
-    let length = match BodyLengthParser::new(message.request.ref_mut().method(), &part).parse() {
+    let parser = BodyLengthParser::new(message.request.ref_mut().method(), &part);
+    let length = match parser.parse() {
         Ok(length) => length,
         Err(e) => {
             conn.shutdown();
             return Err(e);
         }
     };
+    // The body ends with the connection, which can never be reused.
+    if length == BodyLength::UntilClose {
+        conn.shutdown();
+        if message.reject_until_close {
+            return err_from_msg!(Request, "Response body is delimited by connection close");
+        }
+    }
+    // The connection with ambiguous framing must not be reused.
+    if parser.is_framing_ambiguous() {
+        conn.shutdown();
+    }
 
     let time_group = take(message.request.ref_mut().time_group_mut());
-    let body = HttpBody::new(message.interceptor, length, Box::new(conn), pre)?;
+    let mut body = HttpBody::new(message.interceptor, length, Box::new(conn), pre)?;
+    body.set_max_until_close_size(message.max_until_close_size);
     let mut response = Response::new(ylong_http::response::Response::from_raw_parts(part, body));
     response.set_time_group(time_group);
     Ok(response)
@@ -242,6 +582,8 @@ where
 async fn encode_body<S, T>(
     conn: &mut Http1Conn<S>,
     mut body: T,
+    interceptor: &Arc<Interceptors>,
+    mut pacer: Option<&mut RatePacer>,
     buf: &mut [u8],
 ) -> Result<(), HttpClientError>
 where
@@ -259,10 +601,14 @@ where
             end_body = end;
         }
         if written == buf.len() || end_body {
-            if let Err(e) = conn.raw_mut().write_all(&buf[..written]).await {
-                conn.shutdown();
+            interceptor.intercept_input(&buf[..written])?;
+            if let Err(e) = conn.write_all(&buf[..written]).await {
+                Http1Conn::shutdown(conn);
                 return err_from_io!(BodyTransfer, e);
             }
+            if let Some(delay) = pacer.as_mut().and_then(|pacer| pacer.delay(written)) {
+                sleep(delay).await;
+            }
             written = 0;
         }
     }
@@ -296,17 +642,72 @@ where
     Ok((curr, end_body))
 }
 
-impl<S: AsyncRead + Unpin> AsyncRead for Http1Conn<S> {
+// Passes the request body to the interceptor before it is encoded.
+struct InterceptedBody<'a> {
+    body: &'a mut crate::async_impl::Body,
+    interceptor: &'a Arc<Interceptors>,
+}
+
+impl AsyncRead for InterceptedBody<'_> {
     fn poll_read(
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
         buf: &mut ReadBuf<'_>,
     ) -> Poll<std::io::Result<()>> {
+        let filled = buf.filled().len();
+        match Pin::new(&mut *self.body).poll_read(cx, buf) {
+            Poll::Ready(Ok(())) => {
+                if let Err(e) = self
+                    .interceptor
+                    .intercept_request_body(&buf.filled()[filled..])
+                {
+                    return Poll::Ready(Err(std::io::Error::other(e)));
+                }
+                Poll::Ready(Ok(()))
+            }
+            x => x,
+        }
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> AsyncRead for Http1Conn<S> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        // The pipelined requests are written while the responses before them
+        // are read.
+        if let Poll::Ready(Err(e)) = self.poll_write_pipelined(cx) {
+            return Poll::Ready(Err(e));
+        }
         Pin::new(self.raw_mut()).poll_read(cx, buf)
     }
 }
 
-impl<S: AsyncRead + Unpin> StreamData for Http1Conn<S> {
+impl<S: AsyncWrite + Unpin> AsyncWrite for Http1Conn<S> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        if self.is_pipelined() {
+            self.buffer_pipelined(buf);
+            return Poll::Ready(Ok(buf.len()));
+        }
+        Pin::new(self.raw_mut()).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(self.raw_mut()).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(self.raw_mut()).poll_shutdown(cx)
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> StreamData for Http1Conn<S> {
     fn shutdown(&self) {
         Self::shutdown(self)
     }
@@ -316,3 +717,814 @@ impl<S: AsyncRead + Unpin> StreamData for Http1Conn<S> {
         true
     }
 }
+
+#[cfg(test)]
+mod ut_http1 {
+    use std::collections::VecDeque;
+    use std::future::Future;
+    use std::io;
+    use std::mem::take;
+    use std::pin::Pin;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll};
+    #[cfg(feature = "ylong_base")]
+    use std::time::Duration;
+
+    use ylong_http::response::status::StatusCode;
+
+    use crate::async_impl::conn::http1::request;
+    use crate::async_impl::http_body::noop_waker;
+    use crate::async_impl::request::Message;
+    use crate::async_impl::{Body, Request};
+    use crate::runtime::{AsyncRead, AsyncWrite, ReadBuf};
+    use crate::util::dispatcher::http1::Http1Dispatcher;
+    use crate::util::dispatcher::Dispatcher;
+    use crate::util::interceptor::{ByteCounter, ConnProtocol, IdleInterceptor, Interceptor};
+    use crate::util::request::RequestArc;
+    use crate::util::ConnInfo;
+    use crate::{ConnData, ConnDetail, ErrorKind, HttpClientError};
+
+    // Returns one scripted response for each read and records what is written.
+    // An empty response makes the read wait once.
+    struct ScriptIo {
+        responses: VecDeque<&'static [u8]>,
+        written: Arc<Mutex<Vec<u8>>>,
+    }
+
+    impl ConnInfo for ScriptIo {
+        fn is_proxy(&self) -> bool {
+            false
+        }
+
+        fn conn_data(&self) -> ConnData {
+            let detail = ConnDetail {
+                protocol: ConnProtocol::Tcp,
+                local: "127.0.0.1:0".parse().unwrap(),
+                peer: "127.0.0.1:0".parse().unwrap(),
+                addr: String::new(),
+                proxy: None,
+            };
+            ConnData::builder().build(detail)
+        }
+
+        #[cfg(feature = "http3")]
+        fn quic_conn(&mut self) -> Option<crate::async_impl::quic::QuicConn> {
+            None
+        }
+    }
+
+    impl AsyncRead for ScriptIo {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            match self.responses.pop_front() {
+                Some([]) => return Poll::Pending,
+                Some(response) => buf.put_slice(response),
+                None => {}
+            }
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    impl AsyncWrite for ScriptIo {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            self.written.lock().unwrap().extend_from_slice(buf);
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    // `ScriptIo` never blocks, so the future completes on the first poll.
+    fn ready<F: Future>(fut: F) -> F::Output {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        match Box::pin(fut).as_mut().poll(&mut cx) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("The future is not ready"),
+        }
+    }
+
+    // Records every intercepted response head.
+    #[derive(Clone, Default)]
+    struct HeadInterceptor {
+        heads: Arc<Mutex<Vec<Vec<u8>>>>,
+    }
+
+    impl Interceptor for HeadInterceptor {
+        fn intercept_response_head(&self, bytes: &[u8]) -> Result<(), HttpClientError> {
+            self.heads.lock().unwrap().push(bytes.to_vec());
+            Ok(())
+        }
+    }
+
+    fn message(method: &str, body: Body) -> Message {
+        let request = Request::builder()
+            .method(method)
+            .url("http://127.0.0.1:80/")
+            .body(body)
+            .unwrap();
+        Message {
+            request: RequestArc::new(request),
+            interceptor: Arc::new(IdleInterceptor),
+            ttfb_timeout: None,
+            read_timeout: None,
+            chunk_size: None,
+            upload_rate_limit: None,
+            reject_until_close: false,
+            max_until_close_size: None,
+            max_header_size: None,
+            expect_continue_timeout: None,
+            preserve_header_case: false,
+            #[cfg(feature = "http2")]
+            stream_idle_timeout: None,
+        }
+    }
+
+    /// UT test cases for reusing a connection after a `HEAD` response.
+    ///
+    /// # Brief
+    /// 1. Sends a `HEAD` request whose response carries framing headers.
+    /// 2. Checks that the connection is released and not shut down.
+    /// 3. Sends a `GET` request on the same connection.
+    /// 4. Checks if the results are correct.
+    #[test]
+    fn ut_http1_head_then_get() {
+        let io = ScriptIo {
+            responses: VecDeque::from([
+                "HTTP/1.1 200 OK\r\nContent-Length: 5\r\nTransfer-Encoding: chunked\r\n\r\n"
+                    .as_bytes(),
+                "HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello".as_bytes(),
+            ]),
+            written: Arc::new(Mutex::new(Vec::new())),
+        };
+        let dispatcher = Http1Dispatcher::new(io);
+
+        let conn = dispatcher.dispatch().unwrap();
+        let response = ready(request(conn, message("HEAD", Body::empty()))).unwrap();
+        drop(response);
+        assert!(!dispatcher.is_shutdown());
+
+        let conn = dispatcher.dispatch().unwrap();
+        let response = ready(request(conn, message("GET", Body::empty()))).unwrap();
+        assert_eq!(ready(response.text()).unwrap(), "hello");
+        assert!(!dispatcher.is_shutdown());
+        assert!(dispatcher.dispatch().is_some());
+    }
+
+    /// UT test cases for sending a body of unknown length on a `GET` request.
+    ///
+    /// # Brief
+    /// 1. Sends a `GET` request with a stream body.
+    /// 2. Checks that the request is chunked and the body is sent.
+    #[test]
+    fn ut_http1_get_with_stream_body() {
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let io = ScriptIo {
+            responses: VecDeque::from(["HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n".as_bytes()]),
+            written: written.clone(),
+        };
+        let dispatcher = Http1Dispatcher::new(io);
+
+        let conn = dispatcher.dispatch().unwrap();
+        let body = Body::stream("hello".as_bytes());
+        let response = ready(request(conn, message("GET", body))).unwrap();
+        drop(response);
+
+        let written = String::from_utf8(written.lock().unwrap().clone()).unwrap();
+        assert!(written.starts_with("GET / HTTP/1.1\r\n"));
+        assert!(written.contains("transfer-encoding:chunked\r\n"));
+        assert!(written.ends_with("\r\n\r\n5\r\nhello\r\n0\r\n\r\n"));
+    }
+
+    /// UT test cases for intercepting the raw response head.
+    ///
+    /// # Brief
+    /// 1. Receives a response whose head spans two reads.
+    /// 2. Checks that the whole head is intercepted without the body.
+    /// 3. Receives a malformed response head.
+    /// 4. Checks that the bytes received are intercepted before the error.
+    #[test]
+    fn ut_http1_intercept_response_head() {
+        let io = ScriptIo {
+            responses: VecDeque::from([
+                "HTTP/1.1 200 OK\r\nContent-".as_bytes(),
+                "Length: 5\r\n\r\nhello".as_bytes(),
+                "HTTP/1.1 2OO OK\r\n\r\n".as_bytes(),
+            ]),
+            written: Arc::new(Mutex::new(Vec::new())),
+        };
+        let dispatcher = Http1Dispatcher::new(io);
+        let interceptor = HeadInterceptor::default();
+
+        let conn = dispatcher.dispatch().unwrap();
+        let mut msg = message("GET", Body::empty());
+        msg.interceptor = Arc::new(interceptor.clone());
+        let response = ready(request(conn, msg)).unwrap();
+        assert_eq!(ready(response.text()).unwrap(), "hello");
+
+        let conn = dispatcher.dispatch().unwrap();
+        let mut msg = message("GET", Body::empty());
+        msg.interceptor = Arc::new(interceptor.clone());
+        assert!(ready(request(conn, msg)).is_err());
+
+        let heads = interceptor.heads.lock().unwrap();
+        assert_eq!(
+            *heads,
+            vec![
+                b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\n".to_vec(),
+                b"HTTP/1.1 2OO OK\r\n\r\n".to_vec(),
+            ]
+        );
+    }
+
+    /// UT test cases for the connection closed by the peer.
+    ///
+    /// # Brief
+    /// 1. Sends a request and closes the connection before the response.
+    /// 2. Checks that the error tells the response was not received.
+    /// 3. Sends a request and closes the connection in the response body.
+    /// 4. Checks that the error tells the body is incomplete.
+    #[test]
+    fn ut_http1_connection_closed() {
+        let io = ScriptIo {
+            responses: VecDeque::new(),
+            written: Arc::new(Mutex::new(Vec::new())),
+        };
+        let dispatcher = Http1Dispatcher::new(io);
+        let conn = dispatcher.dispatch().unwrap();
+        let error = match ready(request(conn, message("GET", Body::empty()))) {
+            Err(e) => e,
+            Ok(_) => panic!("The response is received"),
+        };
+        assert!(error.is_closed_before_response());
+        assert!(!error.is_body_incomplete());
+
+        let io = ScriptIo {
+            responses: VecDeque::from([
+                "HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\nhello".as_bytes()
+            ]),
+            written: Arc::new(Mutex::new(Vec::new())),
+        };
+        let dispatcher = Http1Dispatcher::new(io);
+        let conn = dispatcher.dispatch().unwrap();
+        let response = ready(request(conn, message("GET", Body::empty()))).unwrap();
+        let error = ready(response.text()).unwrap_err();
+        assert!(error.is_body_incomplete());
+        assert!(!error.is_closed_before_response());
+    }
+
+    /// UT test cases for dropping a request before its response.
+    ///
+    /// # Brief
+    /// 1. Sends a request whose response never comes and drops it.
+    /// 2. Checks that the connection is shut down and not reused.
+    #[test]
+    fn ut_http1_request_cancelled() {
+        let io = ScriptIo {
+            responses: VecDeque::from(["".as_bytes()]),
+            written: Arc::new(Mutex::new(Vec::new())),
+        };
+        let dispatcher = Http1Dispatcher::new(io);
+        let conn = dispatcher.dispatch().unwrap();
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut fut = Box::pin(request(conn, message("GET", Body::empty())));
+        assert!(fut.as_mut().poll(&mut cx).is_pending());
+        assert!(!dispatcher.is_shutdown());
+
+        drop(fut);
+        assert!(dispatcher.is_shutdown());
+    }
+
+    /// UT test cases for sending a `POST` request with an empty body.
+    ///
+    /// # Brief
+    /// 1. Sends a `POST` request and a `GET` request with empty bodies.
+    /// 2. Checks that only the `POST` request carries `Content-Length: 0`.
+    #[test]
+    fn ut_http1_empty_post_content_length() {
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let io = ScriptIo {
+            responses: VecDeque::from([
+                "HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n".as_bytes(),
+                "HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n".as_bytes(),
+            ]),
+            written: written.clone(),
+        };
+        let dispatcher = Http1Dispatcher::new(io);
+
+        let conn = dispatcher.dispatch().unwrap();
+        let response = ready(request(conn, message("POST", Body::empty()))).unwrap();
+        drop(response);
+        let post = String::from_utf8(take(&mut *written.lock().unwrap())).unwrap();
+        assert!(post.starts_with("POST / HTTP/1.1\r\n"));
+        assert!(post.contains("content-length:0\r\n"));
+
+        let conn = dispatcher.dispatch().unwrap();
+        let response = ready(request(conn, message("GET", Body::empty()))).unwrap();
+        drop(response);
+        let get = String::from_utf8(take(&mut *written.lock().unwrap())).unwrap();
+        assert!(get.starts_with("GET / HTTP/1.1\r\n"));
+        assert!(!get.contains("content-length"));
+    }
+
+    /// UT test cases for counting the bytes sent and received.
+    ///
+    /// # Brief
+    /// 1. Sends a chunked request with a `ByteCounter` interceptor.
+    /// 2. Reads the whole response body.
+    /// 3. Checks that the counts equal the bytes on the wire.
+    #[test]
+    fn ut_http1_byte_counter() {
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let raw = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n0\r\n\r\n";
+        let io = ScriptIo {
+            responses: VecDeque::from([raw.as_bytes()]),
+            written: written.clone(),
+        };
+        let dispatcher = Http1Dispatcher::new(io);
+        let counter = Arc::new(ByteCounter::default());
+
+        let conn = dispatcher.dispatch().unwrap();
+        let mut msg = message("POST", Body::stream("hello".as_bytes()));
+        msg.interceptor = counter.clone();
+        let response = ready(request(conn, msg)).unwrap();
+        assert_eq!(ready(response.text()).unwrap(), "hello");
+
+        assert_eq!(counter.sent(), written.lock().unwrap().len() as u64);
+        assert_eq!(counter.received(), raw.len() as u64);
+    }
+
+    /// UT test cases for `Response::header_bytes`.
+    ///
+    /// # Brief
+    /// 1. Receives a response whose head spans two reads.
+    /// 2. Checks that the size of the whole head is recorded.
+    #[test]
+    fn ut_http1_header_bytes() {
+        let head = "HTTP/1.1 200 OK\r\nServer: test\r\nContent-Length: 2\r\n\r\n";
+        let io = ScriptIo {
+            responses: VecDeque::from([
+                &head.as_bytes()[..29],
+                "\r\nContent-Length: 2\r\n\r\nhi".as_bytes(),
+            ]),
+            written: Arc::new(Mutex::new(Vec::new())),
+        };
+        let dispatcher = Http1Dispatcher::new(io);
+
+        let conn = dispatcher.dispatch().unwrap();
+        let response = ready(request(conn, message("GET", Body::empty()))).unwrap();
+        assert_eq!(response.header_bytes(), Some(head.len() as u64));
+        assert_eq!(ready(response.text()).unwrap(), "hi");
+    }
+
+    /// UT test cases for a response head larger than the read buffer.
+    ///
+    /// # Brief
+    /// 1. Receives a response with a 20 KiB header split across three reads
+    ///    under a generous head size limit.
+    /// 2. Checks that the header and the body are decoded correctly.
+    /// 3. Receives the same response under a smaller head size limit.
+    /// 4. Checks that the request fails and the connection is shut down.
+    #[test]
+    fn ut_http1_large_response_head() {
+        let value = "a".repeat(20 * 1024);
+        let raw = format!("HTTP/1.1 200 OK\r\nX-Large: {value}\r\nContent-Length: 2\r\n\r\nhi");
+        let raw: &'static [u8] = Box::leak(raw.into_bytes().into_boxed_slice());
+        let reads = || VecDeque::from([&raw[..10000], &raw[10000..20000], &raw[20000..]]);
+
+        let io = ScriptIo {
+            responses: reads(),
+            written: Arc::new(Mutex::new(Vec::new())),
+        };
+        let dispatcher = Http1Dispatcher::new(io);
+        let conn = dispatcher.dispatch().unwrap();
+        let mut msg = message("GET", Body::empty());
+        msg.max_header_size = Some(64 * 1024);
+        let response = ready(request(conn, msg)).unwrap();
+        assert_eq!(
+            response
+                .headers()
+                .get("x-large")
+                .unwrap()
+                .to_string()
+                .unwrap(),
+            value
+        );
+        assert_eq!(response.header_bytes(), Some(raw.len() as u64 - 2));
+        assert_eq!(ready(response.text()).unwrap(), "hi");
+
+        let io = ScriptIo {
+            responses: reads(),
+            written: Arc::new(Mutex::new(Vec::new())),
+        };
+        let dispatcher = Http1Dispatcher::new(io);
+        let conn = dispatcher.dispatch().unwrap();
+        let mut msg = message("GET", Body::empty());
+        msg.max_header_size = Some(16 * 1024);
+        assert!(ready(request(conn, msg)).is_err());
+        assert!(dispatcher.is_shutdown());
+    }
+
+    /// UT test cases for sending a chunked request with a chunk size.
+    ///
+    /// # Brief
+    /// 1. Sends a request with a stream body and a small chunk size.
+    /// 2. Checks that the body is split into chunks of that size.
+    #[test]
+    fn ut_http1_request_chunk_size() {
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let io = ScriptIo {
+            responses: VecDeque::from(["HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n".as_bytes()]),
+            written: written.clone(),
+        };
+        let dispatcher = Http1Dispatcher::new(io);
+
+        let conn = dispatcher.dispatch().unwrap();
+        let mut msg = message("POST", Body::stream("hello".as_bytes()));
+        msg.chunk_size = Some(2);
+        let response = ready(request(conn, msg)).unwrap();
+        drop(response);
+
+        let written = String::from_utf8(written.lock().unwrap().clone()).unwrap();
+        assert!(written.ends_with("\r\n\r\n2\r\nhe\r\n2\r\nll\r\n1\r\no\r\n0\r\n\r\n"));
+    }
+
+    /// UT test cases for the framing of an in-memory body.
+    ///
+    /// # Brief
+    /// 1. Sends a request with a slice body.
+    /// 2. Checks that the body is sent with `Content-Length`.
+    /// 3. Sends a request with a slice body and `chunked(true)`.
+    /// 4. Checks that the body is sent chunked.
+    #[test]
+    fn ut_http1_slice_body_framing() {
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let io = ScriptIo {
+            responses: VecDeque::from([
+                "HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n".as_bytes(),
+                "HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n".as_bytes(),
+            ]),
+            written: written.clone(),
+        };
+        let dispatcher = Http1Dispatcher::new(io);
+
+        let conn = dispatcher.dispatch().unwrap();
+        let response = ready(request(conn, message("POST", Body::slice("hello")))).unwrap();
+        drop(response);
+        let sized = String::from_utf8(take(&mut *written.lock().unwrap())).unwrap();
+        assert!(sized.contains("content-length:5\r\n"));
+        assert!(!sized.contains("transfer-encoding"));
+        assert!(sized.ends_with("\r\n\r\nhello"));
+
+        let conn = dispatcher.dispatch().unwrap();
+        let mut msg = message("POST", Body::empty());
+        msg.request = RequestArc::new(
+            Request::builder()
+                .method("POST")
+                .url("http://127.0.0.1:80/")
+                .chunked(true)
+                .body(Body::slice("hello"))
+                .unwrap(),
+        );
+        let response = ready(request(conn, msg)).unwrap();
+        drop(response);
+        let chunked = String::from_utf8(take(&mut *written.lock().unwrap())).unwrap();
+        assert!(chunked.contains("transfer-encoding:chunked\r\n"));
+        assert!(!chunked.contains("content-length"));
+        assert!(chunked.ends_with("\r\n\r\n5\r\nhello\r\n0\r\n\r\n"));
+    }
+
+    /// UT test cases for pipelining requests on a connection.
+    ///
+    /// # Brief
+    /// 1. Sends a `GET` request and pipelines another `GET` request behind it.
+    /// 2. Checks that no more request can be pipelined than the depth.
+    /// 3. Reads the responses and checks that they match the requests in
+    ///    order.
+    /// 4. Sends a `POST` request and checks that no request can be pipelined
+    ///    behind it.
+    #[test]
+    fn ut_http1_pipelining() {
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let io = ScriptIo {
+            responses: VecDeque::from([
+                "HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nfirst".as_bytes(),
+                "HTTP/1.1 200 OK\r\nContent-Length: 6\r\n\r\nsecond".as_bytes(),
+                "HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n".as_bytes(),
+            ]),
+            written: written.clone(),
+        };
+        let dispatcher = Http1Dispatcher::with_pipeline(io, 2);
+
+        let conn = dispatcher.dispatch().unwrap();
+        let first = ready(request(conn, message("GET", Body::empty()))).unwrap();
+        let conn = dispatcher.pipeline().unwrap();
+        assert!(dispatcher.pipeline().is_none());
+        let mut second = Box::pin(request(conn, message("GET", Body::empty())));
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        assert!(second.as_mut().poll(&mut cx).is_pending());
+
+        assert_eq!(ready(first.text()).unwrap(), "first");
+        let second = match second.as_mut().poll(&mut cx) {
+            Poll::Ready(result) => result.unwrap(),
+            Poll::Pending => panic!("The future is not ready"),
+        };
+        assert_eq!(ready(second.text()).unwrap(), "second");
+        let written = String::from_utf8(take(&mut *written.lock().unwrap())).unwrap();
+        assert_eq!(written.matches("GET / HTTP/1.1\r\n").count(), 2);
+
+        let conn = dispatcher.dispatch().unwrap();
+        let _response = ready(request(conn, message("POST", Body::empty()))).unwrap();
+        assert!(dispatcher.pipeline().is_none());
+    }
+
+    /// UT test cases for the connection closed before a pipelined response.
+    ///
+    /// # Brief
+    /// 1. Sends a `GET` request and pipelines another `GET` request behind it.
+    /// 2. Drops the first response before its body is read.
+    /// 3. Checks that the pipelined request fails as closed before its
+    ///    response.
+    #[test]
+    fn ut_http1_pipelining_closed() {
+        let io = ScriptIo {
+            responses: VecDeque::from([
+                "HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\n".as_bytes(),
+                "first".as_bytes(),
+            ]),
+            written: Arc::new(Mutex::new(Vec::new())),
+        };
+        let dispatcher = Http1Dispatcher::with_pipeline(io, 2);
+
+        let conn = dispatcher.dispatch().unwrap();
+        let first = ready(request(conn, message("GET", Body::empty()))).unwrap();
+        let conn = dispatcher.pipeline().unwrap();
+        let mut second = Box::pin(request(conn, message("GET", Body::empty())));
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        assert!(second.as_mut().poll(&mut cx).is_pending());
+
+        drop(first);
+        let error = match second.as_mut().poll(&mut cx) {
+            Poll::Ready(Err(e)) => e,
+            _ => panic!("The pipelined request does not fail"),
+        };
+        assert!(error.is_closed_before_response());
+        assert!(dispatcher.is_shutdown());
+    }
+
+    /// UT test cases for a response body delimited by connection close.
+    ///
+    /// # Brief
+    /// 1. Receives a response without `Content-Length` or `Transfer-Encoding`.
+    /// 2. Checks that the body is read and the connection is shut down.
+    /// 3. Receives such a response with `reject_until_close` set.
+    /// 4. Checks that an error is returned.
+    /// 5. Receives such a response with a `max_until_close_size` smaller than
+    ///    the body.
+    /// 6. Checks that reading the body fails.
+    #[test]
+    fn ut_http1_until_close_body() {
+        let script = || ScriptIo {
+            responses: VecDeque::from(["HTTP/1.1 200 OK\r\n\r\nhello".as_bytes()]),
+            written: Arc::new(Mutex::new(Vec::new())),
+        };
+
+        let dispatcher = Http1Dispatcher::new(script());
+        let conn = dispatcher.dispatch().unwrap();
+        let response = ready(request(conn, message("GET", Body::empty()))).unwrap();
+        assert!(dispatcher.is_shutdown());
+        assert_eq!(ready(response.text()).unwrap(), "hello");
+
+        let dispatcher = Http1Dispatcher::new(script());
+        let conn = dispatcher.dispatch().unwrap();
+        let mut strict = message("GET", Body::empty());
+        strict.reject_until_close = true;
+        assert!(ready(request(conn, strict)).is_err());
+
+        let dispatcher = Http1Dispatcher::new(script());
+        let conn = dispatcher.dispatch().unwrap();
+        let mut limited = message("GET", Body::empty());
+        limited.max_until_close_size = Some(3);
+        let response = ready(request(conn, limited)).unwrap();
+        assert!(ready(response.text()).is_err());
+    }
+
+    /// UT test cases for receiving an unsolicited protocol switch.
+    ///
+    /// # Brief
+    /// 1. Receives a `101 Switching Protocols` response to a normal request.
+    /// 2. Checks that an error is returned and the connection is shut down.
+    #[test]
+    fn ut_http1_unexpected_switching_protocols() {
+        let io = ScriptIo {
+            responses: VecDeque::from([
+                &b"HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\n\r\n\x81\x00"[..],
+            ]),
+            written: Arc::new(Mutex::new(Vec::new())),
+        };
+        let dispatcher = Http1Dispatcher::new(io);
+        let conn = dispatcher.dispatch().unwrap();
+        match ready(request(conn, message("GET", Body::empty()))) {
+            Err(e) => assert_eq!(e.error_kind(), ErrorKind::Request),
+            Ok(_) => panic!("The protocol switch is accepted"),
+        }
+        assert!(dispatcher.is_shutdown());
+    }
+
+    /// UT test cases for skipping interim responses.
+    ///
+    /// # Brief
+    /// 1. Receives a `103 Early Hints` response before the final one.
+    /// 2. Checks that the final response is returned.
+    #[test]
+    fn ut_http1_interim_response() {
+        let io = ScriptIo {
+            responses: VecDeque::from([
+                "HTTP/1.1 103 Early Hints\r\nLink: </style.css>\r\n\r\nHTTP/1.1 200 OK\r\n"
+                    .as_bytes(),
+                "Content-Length: 2\r\n\r\nhi".as_bytes(),
+            ]),
+            written: Arc::new(Mutex::new(Vec::new())),
+        };
+        let dispatcher = Http1Dispatcher::new(io);
+        let conn = dispatcher.dispatch().unwrap();
+        let response = ready(request(conn, message("GET", Body::empty()))).unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.header_bytes(), Some(38));
+        assert_eq!(ready(response.text()).unwrap(), "hi");
+    }
+
+    #[cfg(feature = "ylong_base")]
+    fn expect_continue_message(timeout: Duration) -> Message {
+        let request = Request::builder()
+            .method("POST")
+            .url("http://127.0.0.1:80/")
+            .header("Expect", "100-continue")
+            .body(Body::slice("hello"))
+            .unwrap();
+        let mut message = message("POST", Body::empty());
+        message.request = RequestArc::new(request);
+        message.expect_continue_timeout = Some(timeout);
+        message
+    }
+
+    /// UT test cases for sending a request which expects `100 Continue`.
+    ///
+    /// # Brief
+    /// 1. Sends a request with `Expect: 100-continue`, and receives `100
+    ///    Continue` before the final response.
+    /// 2. Checks that the body is sent and the final response is returned.
+    /// 3. Sends the request once again, and receives a final response at once.
+    /// 4. Checks that the body is not sent and the connection is shut down.
+    #[cfg(feature = "ylong_base")]
+    #[test]
+    fn ut_http1_expect_continue() {
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let io = ScriptIo {
+            responses: VecDeque::from([
+                "HTTP/1.1 100 Continue\r\n\r\n".as_bytes(),
+                "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok".as_bytes(),
+            ]),
+            written: written.clone(),
+        };
+        let dispatcher = Http1Dispatcher::new(io);
+        let conn = dispatcher.dispatch().unwrap();
+        let message = expect_continue_message(Duration::from_secs(10));
+        let response = ylong_runtime::block_on(request(conn, message)).unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(ylong_runtime::block_on(response.text()).unwrap(), "ok");
+        assert!(written.lock().unwrap().ends_with(b"\r\n\r\nhello"));
+        assert!(!dispatcher.is_shutdown());
+
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let io = ScriptIo {
+            responses: VecDeque::from([
+                "HTTP/1.1 417 Expectation Failed\r\nContent-Length: 0\r\n\r\n".as_bytes(),
+            ]),
+            written: written.clone(),
+        };
+        let dispatcher = Http1Dispatcher::new(io);
+        let conn = dispatcher.dispatch().unwrap();
+        let message = expect_continue_message(Duration::from_secs(10));
+        let response = ylong_runtime::block_on(request(conn, message)).unwrap();
+        assert_eq!(response.status(), StatusCode::EXPECTATION_FAILED);
+        assert!(!written.lock().unwrap().ends_with(b"hello"));
+        assert!(dispatcher.is_shutdown());
+    }
+
+    /// UT test cases for the timeout of waiting for `100 Continue`.
+    ///
+    /// # Brief
+    /// 1. Sends a request with `Expect: 100-continue` to a server which sends
+    ///    nothing until the body arrives.
+    /// 2. Checks that the body is sent after the timeout and the final response
+    ///    is returned.
+    #[cfg(feature = "ylong_base")]
+    #[test]
+    fn ut_http1_expect_continue_timeout() {
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let io = ScriptIo {
+            responses: VecDeque::from([
+                "".as_bytes(),
+                "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok".as_bytes(),
+            ]),
+            written: written.clone(),
+        };
+        let dispatcher = Http1Dispatcher::new(io);
+        let conn = dispatcher.dispatch().unwrap();
+        let message = expect_continue_message(Duration::from_millis(10));
+        let response = ylong_runtime::block_on(request(conn, message)).unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(written.lock().unwrap().ends_with(b"\r\n\r\nhello"));
+        assert_eq!(ylong_runtime::block_on(response.text()).unwrap(), "ok");
+    }
+
+    // Records every intercepted byte sent on the connection.
+    #[derive(Clone, Default)]
+    struct InputInterceptor {
+        input: Arc<Mutex<Vec<u8>>>,
+    }
+
+    impl Interceptor for InputInterceptor {
+        fn intercept_input(&self, bytes: &[u8]) -> Result<(), HttpClientError> {
+            self.input.lock().unwrap().extend_from_slice(bytes);
+            Ok(())
+        }
+    }
+
+    /// UT test cases for sending a header with several values.
+    ///
+    /// # Brief
+    /// 1. Sends a request with two values appended to the same header.
+    /// 2. Checks that the interceptor sees each value as a separate header
+    ///    line.
+    #[test]
+    fn ut_http1_append_header() {
+        let io = ScriptIo {
+            responses: VecDeque::from(["HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n".as_bytes()]),
+            written: Arc::new(Mutex::new(Vec::new())),
+        };
+        let dispatcher = Http1Dispatcher::new(io);
+        let forwarded = Request::builder()
+            .url("http://127.0.0.1:80/")
+            .append_header("X-Forwarded-For", "10.0.0.1")
+            .append_header("X-Forwarded-For", "10.0.0.2")
+            .body(Body::empty())
+            .unwrap();
+        let interceptor = InputInterceptor::default();
+        let mut message = message("GET", Body::empty());
+        message.request = RequestArc::new(forwarded);
+        message.interceptor = Arc::new(interceptor.clone());
+
+        let conn = dispatcher.dispatch().unwrap();
+        ready(request(conn, message)).unwrap();
+        let input = String::from_utf8(take(&mut *interceptor.input.lock().unwrap())).unwrap();
+        assert!(input.contains("x-forwarded-for:10.0.0.1\r\nx-forwarded-for:10.0.0.2\r\n"));
+    }
+
+    /// UT test cases for sending connection-specific headers in `HTTP/1.1`.
+    ///
+    /// # Brief
+    /// 1. Sends a request with `Connection` and `Keep-Alive` headers.
+    /// 2. Checks that the headers are sent as they are.
+    #[test]
+    fn ut_http1_connection_specific_headers() {
+        let io = ScriptIo {
+            responses: VecDeque::from(["HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n".as_bytes()]),
+            written: Arc::new(Mutex::new(Vec::new())),
+        };
+        let dispatcher = Http1Dispatcher::new(io);
+        let keep_alive = Request::builder()
+            .url("http://127.0.0.1:80/")
+            .header("Connection", "keep-alive")
+            .header("Keep-Alive", "timeout=5")
+            .body(Body::empty())
+            .unwrap();
+        let interceptor = InputInterceptor::default();
+        let mut message = message("GET", Body::empty());
+        message.request = RequestArc::new(keep_alive);
+        message.interceptor = Arc::new(interceptor.clone());
+
+        let conn = dispatcher.dispatch().unwrap();
+        ready(request(conn, message)).unwrap();
+        let input = String::from_utf8(take(&mut *interceptor.input.lock().unwrap())).unwrap();
+        assert!(input.contains("connection:keep-alive\r\n"));
+        assert!(input.contains("keep-alive:timeout=5\r\n"));
+    }
+}
@@ -22,7 +22,6 @@ use std::time::Instant;
 use ylong_http::error::HttpError;
 use ylong_http::h2;
 use ylong_http::h2::{ErrorCode, Frame, FrameFlags, H2Error, Payload, PseudoHeaders};
-use ylong_http::headers::Headers;
 use ylong_http::request::uri::Scheme;
 use ylong_http::request::RequestPart;
 use ylong_http::response::status::StatusCode;
@@ -32,11 +31,11 @@ use crate::async_impl::conn::StreamData;
 use crate::async_impl::request::Message;
 use crate::async_impl::{HttpBody, Response};
 use crate::error::{ErrorKind, HttpClientError};
-use crate::runtime::{AsyncRead, ReadBuf};
+use crate::runtime::{timeout, AsyncRead, ReadBuf};
 use crate::util::data_ref::BodyDataRef;
 use crate::util::dispatcher::http2::Http2Conn;
 use crate::util::h2::RequestWrapper;
-use crate::util::normalizer::BodyLengthParser;
+use crate::util::normalizer::{remove_connection_specific_headers, BodyLength, BodyLengthParser};
 
 const UNUSED_FLAG: u8 = 0x0;
 
@@ -56,10 +55,12 @@ where
     let is_end_stream = message.request.ref_mut().body().is_empty();
     let (flag, payload) = build_headers_payload(part, is_end_stream)
         .map_err(|e| HttpClientError::from_error(ErrorKind::Request, e))?;
-    let data = BodyDataRef::new(message.request.clone());
+    let priority = message.request.ref_mut().priority.clone();
+    let data = BodyDataRef::new(message.request.clone(), message.interceptor.clone());
     let stream = RequestWrapper {
         flag,
         payload,
+        priority,
         data,
     };
     message
@@ -68,7 +69,16 @@ where
         .time_group_mut()
         .set_transfer_start(Instant::now());
     conn.send_frame_to_controller(stream)?;
-    let frame = conn.receiver.recv().await?;
+    let frame = match message.stream_idle_timeout {
+        Some(dur) => match timeout(dur, conn.receiver.recv()).await {
+            Ok(frame) => frame?,
+            Err(_) => {
+                conn.reset_stream();
+                return Err(HttpClientError::from_stream_idle());
+            }
+        },
+        None => conn.receiver.recv().await?,
+    };
     message
         .request
         .ref_mut()
@@ -77,6 +87,52 @@ where
     frame_2_response(conn, frame, message)
 }
 
+/// Sends an extended CONNECT request with the given `:protocol` and returns a
+/// `Response` whose body reads the DATA frames of the tunnel. The request body
+/// stays open and is used as the sending half of the tunnel.
+pub(crate) async fn extended_connect<S>(
+    mut conn: Http2Conn<S>,
+    mut message: Message,
+    protocol: &str,
+) -> Result<Response, HttpClientError>
+where
+    S: Sync + Send + Unpin + 'static,
+{
+    message
+        .interceptor
+        .intercept_request(message.request.ref_mut())?;
+    let part = message.request.ref_mut().part().clone();
+    let (flag, payload) = build_frame_payload(part, false, Some(protocol))
+        .map_err(|e| HttpClientError::from_error(ErrorKind::Request, e))?;
+    let data = BodyDataRef::new(message.request.clone(), message.interceptor.clone());
+    let stream = RequestWrapper {
+        flag,
+        payload,
+        priority: None,
+        data,
+    };
+    conn.send_frame_to_controller(stream)?;
+    let frame = conn.receiver.recv().await?;
+    let part = frame_2_part(&frame)?;
+    if !part.status.is_successful() {
+        return err_from_msg!(
+            ConnectionUpgrade,
+            "Extended CONNECT is rejected by the server"
+        );
+    }
+
+    let text_io = TextIo::new(conn);
+    let body = HttpBody::new(
+        message.interceptor,
+        BodyLength::UntilClose,
+        Box::new(text_io),
+        &[0u8; 0],
+    )?;
+    Ok(Response::new(
+        ylong_http::response::Response::from_raw_parts(part, body),
+    ))
+}
+
 fn frame_2_response<S>(
     conn: Http2Conn<S>,
     headers_frame: Frame,
@@ -85,6 +141,25 @@ fn frame_2_response<S>(
 where
     S: Sync + Send + Unpin + 'static,
 {
+    let part = frame_2_part(&headers_frame)?;
+
+    let text_io = TextIo::new(conn);
+    let length = match BodyLengthParser::new(message.request.ref_mut().method(), &part).parse() {
+        Ok(length) => length,
+        Err(e) => {
+            return Err(e);
+        }
+    };
+    let time_group = take(message.request.ref_mut().time_group_mut());
+    let mut body = HttpBody::new(message.interceptor, length, Box::new(text_io), &[0u8; 0])?;
+    body.set_stream_idle_timeout(message.stream_idle_timeout);
+
+    let mut response = Response::new(ylong_http::response::Response::from_raw_parts(part, body));
+    response.set_time_group(time_group);
+    Ok(response)
+}
+
+fn frame_2_part(headers_frame: &Frame) -> Result<ResponsePart, HttpClientError> {
     let part = match headers_frame.payload() {
         Payload::Headers(headers) => {
             let (pseudo, fields) = headers.parts();
@@ -101,11 +176,11 @@ where
                     ));
                 }
             };
-            ResponsePart {
-                version: ylong_http::version::Version::HTTP2,
-                status: status_code,
-                headers: fields.clone(),
-            }
+            ResponsePart::new(
+                ylong_http::version::Version::HTTP2,
+                status_code,
+                fields.clone(),
+            )
         }
         Payload::RstStream(reset) => {
             return Err(HttpClientError::from_error(
@@ -126,28 +201,24 @@ where
             ));
         }
     };
-
-    let text_io = TextIo::new(conn);
-    let length = match BodyLengthParser::new(message.request.ref_mut().method(), &part).parse() {
-        Ok(length) => length,
-        Err(e) => {
-            return Err(e);
-        }
-    };
-    let time_group = take(message.request.ref_mut().time_group_mut());
-    let body = HttpBody::new(message.interceptor, length, Box::new(text_io), &[0u8; 0])?;
-
-    let mut response = Response::new(ylong_http::response::Response::from_raw_parts(part, body));
-    response.set_time_group(time_group);
-    Ok(response)
+    Ok(part)
 }
 
 pub(crate) fn build_headers_payload(
+    part: RequestPart,
+    is_end_stream: bool,
+) -> Result<(FrameFlags, Payload), HttpError> {
+    build_frame_payload(part, is_end_stream, None)
+}
+
+fn build_frame_payload(
     mut part: RequestPart,
     is_end_stream: bool,
+    protocol: Option<&str>,
 ) -> Result<(FrameFlags, Payload), HttpError> {
     remove_connection_specific_headers(&mut part.headers)?;
-    let pseudo = build_pseudo_headers(&mut part)?;
+    let mut pseudo = build_pseudo_headers(&mut part)?;
+    pseudo.set_protocol(protocol.map(String::from));
     let mut header_part = h2::Parts::new();
     header_part.set_header_lines(part.headers);
     header_part.set_pseudo(pseudo);
@@ -161,31 +232,6 @@ pub(crate) fn build_headers_payload(
     Ok((flag, Payload::Headers(headers_payload)))
 }
 
-// Illegal headers validation in http2.
-// [`Connection-Specific Headers`] implementation.
-//
-// [`Connection-Specific Headers`]: https://www.rfc-editor.org/rfc/rfc9113.html#name-connection-specific-header-
-fn remove_connection_specific_headers(headers: &mut Headers) -> Result<(), HttpError> {
-    const CONNECTION_SPECIFIC_HEADERS: &[&str; 5] = &[
-        "connection",
-        "keep-alive",
-        "proxy-connection",
-        "upgrade",
-        "transfer-encoding",
-    ];
-    for specific_header in CONNECTION_SPECIFIC_HEADERS.iter() {
-        headers.remove(*specific_header);
-    }
-
-    if let Some(te_ref) = headers.get("te") {
-        let te = te_ref.to_string()?;
-        if te.as_str() != "trailers" {
-            headers.remove("te");
-        }
-    }
-    Ok(())
-}
-
 fn build_pseudo_headers(request_part: &mut RequestPart) -> Result<PseudoHeaders, HttpError> {
     let mut pseudo = PseudoHeaders::default();
     match request_part.uri.scheme() {
@@ -357,6 +403,10 @@ impl<S: Sync + Send + Unpin + 'static> StreamData for TextIo<S> {
     fn is_stream_closable(&self) -> bool {
         self.is_closed
     }
+
+    fn reset(self: Box<Self>) {
+        self.handle.reset_stream();
+    }
 }
 
 impl<S: Sync + Send + Unpin + 'static> AsyncRead for TextIo<S> {
@@ -397,7 +447,7 @@ mod ut_http2 {
     use ylong_http::h2::Payload;
     use ylong_http::request::RequestBuilder;
 
-    use crate::async_impl::conn::http2::build_headers_payload;
+    use crate::async_impl::conn::http2::{build_frame_payload, build_headers_payload};
 
     macro_rules! build_request {
         (
@@ -449,6 +499,75 @@ mod ut_http2 {
         }
     }
 
+    /// UT test cases for `build_headers_payload` with connection-specific
+    /// headers.
+    ///
+    /// # Brief
+    /// 1. Creates a request with the headers forbidden in `HTTP/2`.
+    /// 2. Calls `build_headers_payload`.
+    /// 3. Checks that only the allowed headers are in the frame.
+    #[test]
+    fn ut_http2_build_headers_payload_connection_specific() {
+        let request = build_request!(
+            Request: {
+            Method: "GET",
+            Uri: "http://127.0.0.1:0/data",
+            Version: "HTTP/2.0",
+            Header: "connection", "keep-alive, x-hop",
+            Header: "keep-alive", "timeout=5",
+            Header: "proxy-connection", "keep-alive",
+            Header: "transfer-encoding", "chunked",
+            Header: "upgrade", "h2c",
+            Header: "te", "gzip",
+            Header: "x-hop", "1",
+            Header: "accept", "*/*",
+            Header: "host", "127.0.0.1:0",
+            Body: "",
+        }
+        );
+        let (_, payload) = build_headers_payload(request.part().clone(), true).unwrap();
+        if let Payload::Headers(headers) = payload {
+            let (_, headers) = headers.parts();
+            assert_eq!(headers.len(), 1);
+            assert!(headers.get("accept").is_some());
+        } else {
+            panic!("Unexpected frame type")
+        }
+    }
+
+    /// UT test cases for `build_frame_payload` with the `:protocol`
+    /// pseudo-header.
+    ///
+    /// # Brief
+    /// 1. Creates a CONNECT request.
+    /// 2. Calls `build_frame_payload` with `websocket` protocol.
+    /// 3. Checks that the stream stays open and `:protocol` is set along with
+    ///    `:scheme` and `:path`.
+    #[test]
+    fn ut_http2_build_extended_connect_payload() {
+        let request = build_request!(
+            Request: {
+            Method: "CONNECT",
+            Uri: "https://127.0.0.1:0/chat",
+            Version: "HTTP/2.0",
+            Header: "host", "127.0.0.1:0",
+            Body: "",
+        }
+        );
+        let (flag, payload) =
+            build_frame_payload(request.part().clone(), false, Some("websocket")).unwrap();
+        assert!(!flag.is_end_stream());
+        if let Payload::Headers(headers) = payload {
+            let (pseudo, _headers) = headers.parts();
+            assert_eq!(pseudo.method().unwrap(), "CONNECT");
+            assert_eq!(pseudo.protocol().unwrap(), "websocket");
+            assert_eq!(pseudo.scheme().unwrap(), "https");
+            assert_eq!(pseudo.path().unwrap(), "/chat");
+        } else {
+            panic!("Unexpected frame type")
+        }
+    }
+
     /// UT for ensure that the response body(data frame) can read ends normally.
     ///
     /// # Brief
@@ -475,14 +594,16 @@ mod ut_http2 {
 
         let (resp_tx, resp_rx) = ylong_runtime::sync::mpsc::bounded_channel(20);
         let (req_tx, _req_rx) = crate::runtime::unbounded_channel();
+        let (reset_tx, _reset_rx) = crate::runtime::unbounded_channel();
         let shutdown = Arc::new(AtomicBool::new(false));
         let detail = ConnDetail {
             protocol: ConnProtocol::Tcp,
             local: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
             peer: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 443),
             addr: "localhost".to_string(),
+            proxy: None,
         };
-        let mut conn: Http2Conn<()> = Http2Conn::new(20, shutdown, req_tx, detail);
+        let mut conn: Http2Conn<()> = Http2Conn::new(20, shutdown, (req_tx, reset_tx), detail);
         conn.receiver.set_receiver(resp_rx);
         let mut text_io = TextIo::new(conn);
         let data_1 = Frame::new(
@@ -33,7 +33,7 @@ use crate::async_impl::{HttpBody, Response};
 use crate::runtime::AsyncRead;
 use crate::util::data_ref::BodyDataRef;
 use crate::util::dispatcher::http3::{DispatchErrorKind, Http3Conn, RequestWrapper, RespMessage};
-use crate::util::normalizer::BodyLengthParser;
+use crate::util::normalizer::{remove_connection_specific_headers, BodyLengthParser};
 use crate::{ErrorKind, HttpClientError};
 
 pub(crate) async fn request<S>(
@@ -51,7 +51,7 @@ where
     // TODO Implement trailer.
     let headers = build_headers_frame(part)
         .map_err(|e| HttpClientError::from_error(ErrorKind::Request, e))?;
-    let data = BodyDataRef::new(message.request.clone());
+    let data = BodyDataRef::new(message.request.clone(), message.interceptor.clone());
     let stream = RequestWrapper {
         header: headers,
         data,
@@ -62,7 +62,7 @@ where
 }
 
 pub(crate) fn build_headers_frame(mut part: RequestPart) -> Result<Frame, HttpError> {
-    // todo: check rfc to see if any headers should be removed
+    remove_connection_specific_headers(&mut part.headers)?;
     let pseudo = build_pseudo_headers(&mut part)?;
     let mut header_part = Parts::new();
     header_part.set_header_lines(part.headers);
@@ -121,11 +121,11 @@ where
                     ));
                 }
             };
-            ResponsePart {
-                version: ylong_http::version::Version::HTTP3,
-                status: status_code,
-                headers: fields.clone(),
-            }
+            ResponsePart::new(
+                ylong_http::version::Version::HTTP3,
+                status_code,
+                fields.clone(),
+            )
         }
         Payload::PushPromise(_) => {
             todo!();
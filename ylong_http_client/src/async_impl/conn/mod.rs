@@ -20,6 +20,11 @@ mod http2;
 #[cfg(feature = "http3")]
 mod http3;
 
+#[cfg(feature = "http1_1")]
+pub(crate) use http1::{is_pipelinable, render_request_head, upgrade};
+#[cfg(feature = "http2")]
+pub(crate) use http2::extended_connect;
+
 use crate::async_impl::request::Message;
 use crate::async_impl::Response;
 use crate::error::HttpClientError;
@@ -31,6 +36,12 @@ pub(crate) trait StreamData: AsyncRead {
     fn shutdown(&self);
 
     fn is_stream_closable(&self) -> bool;
+
+    // Aborts the response of this io. Without streams, the whole connection
+    // is closed.
+    fn reset(self: Box<Self>) {
+        self.shutdown();
+    }
 }
 
 // TODO: Use structures instead of a function to reuse the io buf.
@@ -17,19 +17,20 @@ mod stream;
 
 use core::future::Future;
 use std::io::{Error, ErrorKind};
-use std::net::SocketAddr;
+use std::net::{Ipv6Addr, SocketAddr, SocketAddrV6};
 use std::sync::Arc;
+use std::time::Duration;
 
 use ylong_http::request::uri::Uri;
 #[cfg(feature = "http3")]
 use ylong_runtime::net::{ConnectedUdpSocket, UdpSocket};
 
 use crate::async_impl::dns::{DefaultDnsResolver, EyeBallConfig, HappyEyeballs, Resolver};
-use crate::runtime::{AsyncRead, AsyncWrite, TcpStream};
-use crate::util::config::{ConnectorConfig, HttpVersion};
+use crate::runtime::{timeout, AsyncRead, AsyncWrite, TcpStream};
+use crate::util::config::{ConnectorConfig, HttpVersion, SocketConfig};
 /// Information of an IO.
 use crate::util::ConnInfo;
-use crate::{HttpClientError, Timeout};
+use crate::{HttpClientError, Proxy, Timeout};
 
 /// `Connector` trait used by `async_impl::Client`. `Connector` provides
 /// asynchronous connection establishment interfaces.
@@ -46,6 +47,75 @@ pub trait Connector {
 
     /// Attempts to establish a connection.
     fn connect(&self, uri: &Uri, http_version: HttpVersion) -> Self::Future;
+
+    /// Attempts to establish a connection through the given proxy instead of
+    /// the configured ones. `None` means a direct connection.
+    ///
+    /// This is used by requests which set their own proxy. The default
+    /// implementation ignores `proxy` and calls `connect`.
+    fn connect_with_proxy(
+        &self,
+        uri: &Uri,
+        http_version: HttpVersion,
+        proxy: Option<&Proxy>,
+    ) -> Self::Future {
+        let _ = proxy;
+        self.connect(uri, http_version)
+    }
+
+    /// Attempts to establish a connection which verifies neither the certs
+    /// nor the hostname of the server. `proxy` is `Some` if the request sets
+    /// its own proxy, see `connect_with_proxy`.
+    ///
+    /// This is used by requests which call `danger_accept_invalid_certs`. The
+    /// default implementation verifies the server as usual.
+    fn connect_unverified(
+        &self,
+        uri: &Uri,
+        http_version: HttpVersion,
+        proxy: Option<Option<&Proxy>>,
+    ) -> Self::Future {
+        match proxy {
+            Some(proxy) => self.connect_with_proxy(uri, http_version, proxy),
+            None => self.connect(uri, http_version),
+        }
+    }
+
+    /// Attempts to establish a connection to `addr` instead of the resolved
+    /// addresses of the host of `uri`, which is still used for SNI and the
+    /// verification of the server. `proxy` is `Some` if the request sets its
+    /// own proxy, see `connect_with_proxy`, and `unverified` is whether the
+    /// server is not verified, see `connect_unverified`.
+    ///
+    /// This is used by requests which call `resolve_to`. The default
+    /// implementation ignores `addr`.
+    fn connect_pinned(
+        &self,
+        uri: &Uri,
+        http_version: HttpVersion,
+        proxy: Option<Option<&Proxy>>,
+        unverified: bool,
+        addr: SocketAddr,
+    ) -> Self::Future {
+        let _ = addr;
+        if unverified {
+            return self.connect_unverified(uri, http_version, proxy);
+        }
+        match proxy {
+            Some(proxy) => self.connect_with_proxy(uri, http_version, proxy),
+            None => self.connect(uri, http_version),
+        }
+    }
+
+    /// Checks if a connection to `uri` goes through a proxy. `proxy` is
+    /// `Some` if the request sets its own proxy, see `connect_with_proxy`.
+    ///
+    /// This is used to render the requests without connecting. The default
+    /// implementation returns `false`.
+    fn is_proxied(&self, uri: &Uri, proxy: Option<Option<&Proxy>>) -> bool {
+        let _ = (uri, proxy);
+        false
+    }
 }
 
 /// Connector for creating HTTP or HTTPS connections asynchronously.
@@ -57,6 +127,16 @@ pub struct HttpConnector {
 }
 
 impl HttpConnector {
+    // Checks if `uri` is connected through the proxy set by the request, or
+    // through the proxies of the connector otherwise.
+    fn matches_proxy(&self, uri: &Uri, proxy: Option<Option<&Proxy>>) -> bool {
+        match proxy {
+            Some(Some(proxy)) => proxy.inner_ref().is_intercepted(uri),
+            Some(None) => false,
+            None => self.config.proxies.match_proxy(uri).is_some(),
+        }
+    }
+
     /// Creates a new `HttpConnector` with a `ConnectorConfig`.
     pub(crate) fn new(config: ConnectorConfig, resolver: Arc<dyn Resolver>) -> Self {
         Self { config, resolver }
@@ -107,25 +187,106 @@ async fn tcp_stream(eyeballs: HappyEyeballs) -> Result<TcpStream, HttpClientErro
         })
 }
 
+// Resolves `addr`, unless the connection is pinned to an address.
 async fn dns_query(
     resolver: Arc<dyn Resolver>,
     addr: &str,
+    dns_timeout: Option<Duration>,
+    pinned: Option<SocketAddr>,
 ) -> Result<Vec<SocketAddr>, HttpClientError> {
+    if let Some(socket_addr) = pinned {
+        return Ok(vec![socket_addr]);
+    }
+    if let Some(socket_addr) = ipv6_literal(addr)? {
+        return Ok(vec![socket_addr]);
+    }
     let addr_fut = resolver.resolve(addr);
-    let socket_addr = addr_fut.await.map_err(|e| {
-        HttpClientError::from_dns_error(
+    let result = match dns_timeout {
+        Some(dur) => timeout(dur, addr_fut)
+            .await
+            .map_err(|_| HttpClientError::from_dns_timeout(authority_host(addr)))?,
+        None => addr_fut.await,
+    };
+    let socket_addr = result.map_err(|e| {
+        HttpClientError::from_dns_host_error(
             crate::ErrorKind::Connect,
+            authority_host(addr),
             Error::new(ErrorKind::Interrupted, e),
         )
     })?;
     Ok(socket_addr.collect::<Vec<_>>())
 }
 
+// Gets the host part of an authority like `host:port`.
+fn authority_host(addr: &str) -> &str {
+    match addr.rsplit_once(':') {
+        Some((host, port)) if port.parse::<u16>().is_ok() => host,
+        _ => addr,
+    }
+}
+
+// Parses an authority like `[fe80::1%eth0]:8080` into a socket address, using
+// the zone id as the scope id. Returns `None` if the host is not a bracketed
+// IPv6 literal.
+fn ipv6_literal(addr: &str) -> Result<Option<SocketAddr>, HttpClientError> {
+    let Some((host, port)) = addr.rsplit_once(':') else {
+        return Ok(None);
+    };
+    let Some(host) = host.strip_prefix('[').and_then(|h| h.strip_suffix(']')) else {
+        return Ok(None);
+    };
+    let Ok(port) = port.parse::<u16>() else {
+        return Ok(None);
+    };
+    let (ip, zone) = match host.split_once('%') {
+        // RFC 6874 encodes the zone delimiter as `%25`.
+        Some((ip, zone)) => (
+            ip,
+            Some(
+                zone.strip_prefix("25")
+                    .filter(|z| !z.is_empty())
+                    .unwrap_or(zone),
+            ),
+        ),
+        None => (host, None),
+    };
+    let Ok(ip) = ip.parse::<Ipv6Addr>() else {
+        return Ok(None);
+    };
+    let scope_id = match zone {
+        Some(zone) => scope_id(zone)?,
+        None => 0,
+    };
+    Ok(Some(SocketAddr::V6(SocketAddrV6::new(
+        ip, port, 0, scope_id,
+    ))))
+}
+
+// Converts a zone id, either an interface index or an interface name, into a
+// scope id.
+fn scope_id(zone: &str) -> Result<u32, HttpClientError> {
+    if let Ok(index) = zone.parse::<u32>() {
+        return Ok(index);
+    }
+    #[cfg(all(unix, feature = "libc"))]
+    {
+        if let Ok(name) = std::ffi::CString::new(zone) {
+            // SAFETY: `name` is a valid nul-terminated string.
+            let index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+            if index != 0 {
+                return Ok(index);
+            }
+        }
+    }
+    err_from_msg!(Connect, "Unknown IPv6 zone id")
+}
+
 async fn eyeballs_connect(
     addrs: Vec<SocketAddr>,
     timeout: Timeout,
+    socket: SocketConfig,
 ) -> Result<TcpStream, HttpClientError> {
-    let eyeball_config = EyeBallConfig::new(timeout.inner(), None);
+    let eyeball_config = EyeBallConfig::new(timeout.inner(), None).socket(socket);
     let happy_eyeballs = HappyEyeballs::new(addrs, eyeball_config);
     tcp_stream(happy_eyeballs).await
 }
@@ -150,6 +311,7 @@ pub(crate) async fn udp_stream(
 mod no_tls {
     use core::future::Future;
     use core::pin::Pin;
+    use std::net::SocketAddr;
     use std::time::Instant;
 
     use ylong_http::request::uri::Uri;
@@ -160,31 +322,88 @@ mod no_tls {
     use crate::runtime::TcpStream;
     use crate::util::config::HttpVersion;
     use crate::util::interceptor::ConnProtocol;
-    use crate::{ConnData, ConnDetail, HttpClientError, TimeGroup};
+    use crate::util::proxy;
+    use crate::{ConnData, ConnDetail, HttpClientError, Proxy, TimeGroup};
 
     impl Connector for HttpConnector {
         type Stream = HttpStream<TcpStream>;
         type Future =
             Pin<Box<dyn Future<Output = Result<Self::Stream, HttpClientError>> + Sync + Send>>;
 
-        fn connect(&self, uri: &Uri, _http_version: HttpVersion) -> Self::Future {
+        fn connect(&self, uri: &Uri, http_version: HttpVersion) -> Self::Future {
+            self.connect_through(
+                uri,
+                http_version,
+                self.config.proxies.match_proxy(uri),
+                None,
+            )
+        }
+
+        fn connect_with_proxy(
+            &self,
+            uri: &Uri,
+            http_version: HttpVersion,
+            proxy: Option<&Proxy>,
+        ) -> Self::Future {
+            let proxy = proxy
+                .map(Proxy::inner_ref)
+                .filter(|proxy| proxy.is_intercepted(uri));
+            self.connect_through(uri, http_version, proxy, None)
+        }
+
+        fn connect_pinned(
+            &self,
+            uri: &Uri,
+            http_version: HttpVersion,
+            proxy: Option<Option<&Proxy>>,
+            _unverified: bool,
+            addr: SocketAddr,
+        ) -> Self::Future {
+            let proxy = match proxy {
+                Some(proxy) => proxy
+                    .map(Proxy::inner_ref)
+                    .filter(|proxy| proxy.is_intercepted(uri)),
+                None => self.config.proxies.match_proxy(uri),
+            };
+            self.connect_through(uri, http_version, proxy, Some(addr))
+        }
+
+        fn is_proxied(&self, uri: &Uri, proxy: Option<Option<&Proxy>>) -> bool {
+            self.matches_proxy(uri, proxy)
+        }
+    }
+
+    impl HttpConnector {
+        fn connect_through(
+            &self,
+            uri: &Uri,
+            _http_version: HttpVersion,
+            proxy: Option<&proxy::Proxy>,
+            pinned: Option<SocketAddr>,
+        ) -> <Self as Connector>::Future {
+            // A proxied connection resolves the host by the proxy.
+            let pinned = pinned.filter(|_| proxy.is_none());
             // Checks if this uri need be proxied.
             let mut is_proxy = false;
+            let mut via_proxy = None;
             let mut addr = uri.authority().unwrap().to_string();
-            if let Some(proxy) = self.config.proxies.match_proxy(uri) {
+            if let Some(proxy) = proxy {
                 addr = proxy.via_proxy(uri).authority().unwrap().to_string();
+                via_proxy = Some(Box::new(proxy.intercept.proxy_info().clone()));
                 is_proxy = true;
             }
 
             let resolver = self.resolver.clone();
             let timeout = self.config.timeout.clone();
+            let dns_timeout = self.config.dns_timeout;
+            let socket = self.config.socket;
             Box::pin(async move {
                 let mut time_group = TimeGroup::default();
                 time_group.set_dns_start(Instant::now());
-                let socket_addrs = dns_query(resolver, addr.as_str()).await?;
+                let socket_addrs = dns_query(resolver, addr.as_str(), dns_timeout, pinned).await?;
                 time_group.set_dns_end(Instant::now());
                 time_group.set_tcp_start(Instant::now());
-                let stream = eyeballs_connect(socket_addrs, timeout).await?;
+                let stream = eyeballs_connect(socket_addrs, timeout, socket).await?;
                 time_group.set_tcp_end(Instant::now());
                 let local = stream
                     .local_addr()
@@ -197,6 +416,7 @@ mod no_tls {
                     local,
                     peer,
                     addr,
+                    proxy: via_proxy,
                 };
 
                 let data = ConnData::builder()
@@ -216,9 +436,15 @@ mod tls {
     use std::error;
     use std::fmt::{Debug, Display, Formatter};
     use std::io::{Error, ErrorKind, Write};
+    use std::net::SocketAddr;
     use std::time::Instant;
 
+    use ylong_http::h1::ResponseDecoder;
+    use ylong_http::headers::Headers;
     use ylong_http::request::uri::{Scheme, Uri};
+    use ylong_http::response::status::StatusCode;
+    use ylong_http::response::ResponsePart;
+    use ylong_http::version::Version;
 
     use super::{eyeballs_connect, Connector, HttpConnector};
     use crate::async_impl::connector::dns_query;
@@ -229,25 +455,105 @@ mod tls {
     use crate::async_impl::ssl_stream::AsyncSslStream;
     #[cfg(all(target_os = "linux", feature = "ylong_base", feature = "__tls"))]
     use crate::config::FchownConfig;
-    use crate::runtime::{AsyncReadExt, AsyncWriteExt, TcpStream};
+    use crate::runtime::{timeout, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, TcpStream};
     use crate::util::config::HttpVersion;
     #[cfg(feature = "http2")]
     use crate::util::information::NegotiateInfo;
     use crate::util::interceptor::ConnProtocol;
-    use crate::{ConnData, ConnDetail, HttpClientError, TimeGroup, TlsConfig};
+    use crate::util::{digest_auth, proxy};
+    use crate::{
+        ConnData, ConnDetail, HttpClientError, Proxy, ProxyInfo, TimeGroup, Timeout, TlsConfig,
+    };
+
+    // The basic authorization, digest credentials, `CONNECT` headers, host and
+    // port of a tunnel.
+    type TunnelParams = (
+        Option<String>,
+        Option<(String, String)>,
+        Headers,
+        String,
+        u16,
+    );
 
     impl Connector for HttpConnector {
         type Stream = HttpStream<MixStream>;
         type Future =
             Pin<Box<dyn Future<Output = Result<Self::Stream, HttpClientError>> + Sync + Send>>;
 
-        fn connect(&self, uri: &Uri, _http_version: HttpVersion) -> Self::Future {
+        fn connect(&self, uri: &Uri, http_version: HttpVersion) -> Self::Future {
+            let proxy = self.config.proxies.match_proxy(uri);
+            self.connect_through(uri, http_version, proxy, false, None)
+        }
+
+        fn connect_with_proxy(
+            &self,
+            uri: &Uri,
+            http_version: HttpVersion,
+            proxy: Option<&Proxy>,
+        ) -> Self::Future {
+            let proxy = proxy
+                .map(Proxy::inner_ref)
+                .filter(|proxy| proxy.is_intercepted(uri));
+            self.connect_through(uri, http_version, proxy, false, None)
+        }
+
+        fn connect_unverified(
+            &self,
+            uri: &Uri,
+            http_version: HttpVersion,
+            proxy: Option<Option<&Proxy>>,
+        ) -> Self::Future {
+            let proxy = match proxy {
+                Some(proxy) => proxy
+                    .map(Proxy::inner_ref)
+                    .filter(|proxy| proxy.is_intercepted(uri)),
+                None => self.config.proxies.match_proxy(uri),
+            };
+            self.connect_through(uri, http_version, proxy, true, None)
+        }
+
+        fn connect_pinned(
+            &self,
+            uri: &Uri,
+            http_version: HttpVersion,
+            proxy: Option<Option<&Proxy>>,
+            unverified: bool,
+            addr: SocketAddr,
+        ) -> Self::Future {
+            let proxy = match proxy {
+                Some(proxy) => proxy
+                    .map(Proxy::inner_ref)
+                    .filter(|proxy| proxy.is_intercepted(uri)),
+                None => self.config.proxies.match_proxy(uri),
+            };
+            self.connect_through(uri, http_version, proxy, unverified, Some(addr))
+        }
+
+        fn is_proxied(&self, uri: &Uri, proxy: Option<Option<&Proxy>>) -> bool {
+            self.matches_proxy(uri, proxy)
+        }
+    }
+
+    impl HttpConnector {
+        fn connect_through(
+            &self,
+            uri: &Uri,
+            http_version: HttpVersion,
+            proxy: Option<&proxy::Proxy>,
+            unverified: bool,
+            pinned: Option<SocketAddr>,
+        ) -> <Self as Connector>::Future {
+            // A proxied connection resolves the host by the proxy.
+            let pinned = pinned.filter(|_| proxy.is_none());
             // Make sure all parts of uri is accurate.
             let mut addr = uri.authority().unwrap().to_string();
             let mut auth = None;
+            let mut credentials = None;
+            let mut headers = Headers::new();
             let mut is_proxy = false;
+            let mut via_proxy = None;
 
-            if let Some(proxy) = self.config.proxies.match_proxy(uri) {
+            if let Some(proxy) = proxy {
                 addr = proxy.via_proxy(uri).authority().unwrap().to_string();
                 auth = proxy
                     .intercept
@@ -255,20 +561,26 @@ mod tls {
                     .basic_auth
                     .as_ref()
                     .and_then(|v| v.to_string().ok());
+                credentials = proxy.intercept.proxy_info().credentials.clone();
+                headers = proxy.intercept.proxy_info().connect_headers.clone();
+                via_proxy = Some(Box::new(proxy.intercept.proxy_info().clone()));
                 is_proxy = true;
             }
             #[cfg(all(target_os = "linux", feature = "ylong_base", feature = "__tls"))]
             let fchown = self.config.fchown.clone();
             let resolver = self.resolver.clone();
             let timeout = self.config.timeout.clone();
+            let dns_timeout = self.config.dns_timeout;
+            let socket = self.config.socket;
             match *uri.scheme().unwrap() {
                 Scheme::HTTP => Box::pin(async move {
                     let mut time_group = TimeGroup::default();
                     time_group.set_dns_start(Instant::now());
-                    let socket_addrs = dns_query(resolver, addr.as_str()).await?;
+                    let socket_addrs =
+                        dns_query(resolver, addr.as_str(), dns_timeout, pinned).await?;
                     time_group.set_dns_end(Instant::now());
                     time_group.set_tcp_start(Instant::now());
-                    let stream = eyeballs_connect(socket_addrs, timeout).await?;
+                    let stream = eyeballs_connect(socket_addrs, timeout, socket).await?;
                     time_group.set_tcp_end(Instant::now());
                     #[cfg(all(target_os = "linux", feature = "ylong_base", feature = "__tls"))]
                     if let Some(fchown) = fchown {
@@ -286,6 +598,7 @@ mod tls {
                         local,
                         peer,
                         addr,
+                        proxy: via_proxy,
                     };
                     let data = ConnData::builder()
                         .time_group(time_group)
@@ -297,19 +610,22 @@ mod tls {
                 Scheme::HTTPS => {
                     let host = uri.host().unwrap().to_string();
                     let port = uri.port().unwrap().as_u16().unwrap();
-                    let config = self.config.tls.clone();
+                    let mut config = self.config.tls_config(&host).clone();
+                    if unverified {
+                        config = config.unverified();
+                    }
+                    // An `HTTP/1.1` connection mustn't be negotiated as another
+                    // protocol by the ALPN protocols of the client.
+                    if http_version == HttpVersion::Http1 {
+                        config = config.http1_only();
+                    }
+                    let handshake_timeout = self.config.tls_handshake_timeout.clone();
                     #[cfg(feature = "http3")]
-                    if _http_version == HttpVersion::Http3 {
+                    if http_version == HttpVersion::Http3 {
                         return Box::pin(async move {
                             let mut time_group = TimeGroup::default();
                             time_group.set_dns_start(Instant::now());
-                            let addr_fut = resolver.resolve(&addr);
-                            let addrs = addr_fut.await.map_err(|e| {
-                                HttpClientError::from_dns_error(
-                                    crate::ErrorKind::Connect,
-                                    Error::new(ErrorKind::Interrupted, e),
-                                )
-                            })?;
+                            let addrs = dns_query(resolver, &addr, dns_timeout, pinned).await?;
                             time_group.set_dns_end(Instant::now());
                             time_group.set_quic_start(Instant::now());
                             let mut last_e = None;
@@ -332,6 +648,7 @@ mod tls {
                                     local,
                                     peer,
                                     addr: addr.clone(),
+                                    proxy: via_proxy.clone(),
                                 };
 
                                 let mut data = ConnData::builder()
@@ -361,19 +678,20 @@ mod tls {
                     Box::pin(async move {
                         let mut time_group = TimeGroup::default();
                         time_group.set_dns_start(Instant::now());
-                        let socket_addrs = dns_query(resolver, addr.as_str()).await?;
+                        let socket_addrs =
+                            dns_query(resolver, addr.as_str(), dns_timeout, pinned).await?;
                         time_group.set_dns_end(Instant::now());
                         time_group.set_tcp_start(Instant::now());
-                        let stream = eyeballs_connect(socket_addrs, timeout).await?;
+                        let stream = eyeballs_connect(socket_addrs, timeout, socket).await?;
                         time_group.set_tcp_end(Instant::now());
                         #[cfg(all(target_os = "linux", feature = "ylong_base", feature = "__tls"))]
                         {
                             https_connect(
-                                config,
+                                (config, handshake_timeout),
                                 addr,
                                 stream,
-                                is_proxy,
-                                (auth, host, port),
+                                via_proxy,
+                                (auth, credentials, headers, host, port),
                                 fchown,
                                 time_group,
                             )
@@ -386,11 +704,11 @@ mod tls {
                         )))]
                         {
                             https_connect(
-                                config,
+                                (config, handshake_timeout),
                                 addr,
                                 stream,
-                                is_proxy,
-                                (auth, host, port),
+                                via_proxy,
+                                (auth, credentials, headers, host, port),
                                 time_group,
                             )
                             .await
@@ -402,11 +720,11 @@ mod tls {
     }
 
     async fn https_connect(
-        config: TlsConfig,
+        (config, handshake_timeout): (TlsConfig, Timeout),
         addr: String,
         tcp_stream: TcpStream,
-        is_proxy: bool,
-        (auth, host, port): (Option<String>, String, u16),
+        via_proxy: Option<Box<ProxyInfo>>,
+        (auth, credentials, headers, host, port): TunnelParams,
         #[cfg(all(target_os = "linux", feature = "ylong_base", feature = "__tls"))] fchown: Option<
             FchownConfig,
         >,
@@ -423,8 +741,9 @@ mod tls {
         let peer = tcp
             .peer_addr()
             .map_err(|e| HttpClientError::from_io_error(crate::ErrorKind::Connect, e))?;
+        let is_proxy = via_proxy.is_some();
         if is_proxy {
-            tcp = tunnel(tcp, &host, port, auth)
+            tcp = tunnel(tcp, &host, port, auth, credentials.as_ref(), &headers)
                 .await
                 .map_err(|e| HttpClientError::from_io_error(crate::ErrorKind::Connect, e))?;
         };
@@ -441,7 +760,14 @@ mod tls {
             })?;
 
         time_group.set_tls_start(Instant::now());
-        Pin::new(&mut stream).connect().await.map_err(|e| {
+        let handshake = Pin::new(&mut stream).connect();
+        let result = match handshake_timeout.inner() {
+            Some(dur) => timeout(dur, handshake)
+                .await
+                .map_err(|_| HttpClientError::from_tls_handshake_timeout())?,
+            None => handshake.await,
+        };
+        result.map_err(|e| {
             HttpClientError::from_tls_error(
                 crate::ErrorKind::Connect,
                 Error::new(ErrorKind::Other, e),
@@ -449,6 +775,7 @@ mod tls {
         })?;
         time_group.set_tls_end(Instant::now());
 
+        let tls = stream.tls_info();
         #[cfg(feature = "http2")]
         let alpn = stream.negotiated_alpn_protocol().map(Vec::from);
         let detail = ConnDetail {
@@ -456,6 +783,7 @@ mod tls {
             local,
             peer,
             addr,
+            proxy: via_proxy,
         };
 
         #[cfg(feature = "http2")]
@@ -463,62 +791,150 @@ mod tls {
             .time_group(time_group)
             .proxy(is_proxy)
             .negotiate(NegotiateInfo::from_alpn(alpn))
+            .tls(tls)
             .build(detail);
 
         #[cfg(not(feature = "http2"))]
         let data = ConnData::builder()
             .time_group(time_group)
             .proxy(is_proxy)
+            .tls(tls)
             .build(detail);
 
         Ok(HttpStream::new(MixStream::Https(stream), data))
     }
 
-    async fn tunnel(
-        mut conn: TcpStream,
+    async fn tunnel<S>(
+        mut conn: S,
         host: &str,
         port: u16,
         auth: Option<String>,
-    ) -> Result<TcpStream, Error> {
-        let mut req = Vec::new();
+        credentials: Option<&(String, String)>,
+        headers: &Headers,
+    ) -> Result<S, Error>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let mut authorization = auth.map(|value| format!("Basic {value}"));
+        let mut challenged = false;
 
-        write!(
-            &mut req,
-            "CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n"
-        )?;
+        loop {
+            let mut req = Vec::new();
 
-        if let Some(value) = auth {
-            write!(&mut req, "Proxy-Authorization: Basic {value}\r\n")?;
-        }
+            write!(
+                &mut req,
+                "CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n"
+            )?;
 
-        write!(&mut req, "\r\n")?;
+            if let Some(value) = authorization.as_ref() {
+                write!(&mut req, "Proxy-Authorization: {value}\r\n")?;
+            }
 
-        conn.write_all(&req).await?;
+            for (name, value) in headers {
+                for sub in value.iter() {
+                    req.extend_from_slice(name.as_bytes());
+                    req.extend_from_slice(b": ");
+                    req.extend_from_slice(sub);
+                    req.extend_from_slice(b"\r\n");
+                }
+            }
+
+            write!(&mut req, "\r\n")?;
+
+            conn.write_all(&req).await?;
+
+            let (part, body, reusable) = read_connect_response(&mut conn).await?;
+            if part.status.is_successful() {
+                return Ok(conn);
+            }
+
+            // Answers a `Digest` challenge once, on the same connection.
+            let challenge = part
+                .headers
+                .get("Proxy-Authenticate")
+                .and_then(|value| digest_auth::find_challenge(value.iter().map(Vec::as_slice)));
+            match challenge {
+                Some(challenge)
+                    if part.status == StatusCode::PROXY_AUTHENTICATION_REQUIRED && !challenged =>
+                {
+                    let (username, password) = credentials.ok_or(other_io_error(
+                        CreateTunnelErr::DigestAuth("No credentials for the digest challenge"),
+                    ))?;
+                    let value = digest_auth::authorization(
+                        challenge,
+                        (username, password),
+                        ("CONNECT", &format!("{host}:{port}")),
+                        1,
+                    )
+                    .map_err(|e| other_io_error(CreateTunnelErr::DigestAuth(e)))?;
+                    if !reusable {
+                        return Err(other_io_error(CreateTunnelErr::DigestAuth(
+                            "Proxy closes the connection of the digest challenge",
+                        )));
+                    }
+                    authorization = Some(value);
+                    challenged = true;
+                }
+                _ => {
+                    return Err(other_io_error(CreateTunnelErr::Rejected(
+                        part.status,
+                        String::from_utf8_lossy(&body).into_owned(),
+                    )))
+                }
+            }
+        }
+    }
 
+    // Reads the response of the proxy to `CONNECT`, with the body of a non-2xx
+    // response. Also returns whether the connection can be used for another
+    // `CONNECT` request.
+    async fn read_connect_response<S>(conn: &mut S) -> Result<(ResponsePart, Vec<u8>, bool), Error>
+    where
+        S: AsyncRead + Unpin,
+    {
         let mut buf = [0; 8192];
         let mut pos = 0;
 
         loop {
             let n = conn.read(&mut buf[pos..]).await?;
-
-            if n == 0 {
-                return Err(other_io_error(CreateTunnelErr::Unsuccessful));
-            }
-
             pos += n;
-            let resp = &buf[..pos];
-            if resp.starts_with(b"HTTP/1.1 200") || resp.starts_with(b"HTTP/1.0 200") {
-                if resp.ends_with(b"\r\n\r\n") {
-                    return Ok(conn);
-                }
-                if pos == buf.len() {
-                    return Err(other_io_error(CreateTunnelErr::ProxyHeadersTooLong));
+            // No more bytes of the response can be received.
+            let end = n == 0 || pos == buf.len();
+
+            let (part, body) = match ResponseDecoder::new().decode(&buf[..pos]) {
+                Ok(Some(decoded)) => decoded,
+                Ok(None) if n == 0 => return Err(other_io_error(CreateTunnelErr::Unsuccessful)),
+                Ok(None) if end => {
+                    return Err(other_io_error(CreateTunnelErr::ProxyHeadersTooLong))
                 }
-            } else if resp.starts_with(b"HTTP/1.1 407") {
-                return Err(other_io_error(CreateTunnelErr::ProxyAuthenticationRequired));
-            } else {
-                return Err(other_io_error(CreateTunnelErr::Unsuccessful));
+                Ok(None) => continue,
+                Err(_) => return Err(other_io_error(CreateTunnelErr::Unsuccessful)),
+            };
+            if part.status.is_successful() {
+                return Ok((part, Vec::new(), false));
             }
+
+            // Keeps the body of the refusal, such as an authentication
+            // challenge, for the error. A body without `Content-Length` is
+            // taken as it is, instead of waiting for the close of the proxy.
+            let length = part
+                .headers
+                .get("Content-Length")
+                .and_then(|v| v.to_string().ok())
+                .and_then(|v| v.trim().parse::<usize>().ok());
+            let (body, complete) = match length {
+                Some(len) if body.len() < len && !end => continue,
+                Some(len) => (body[..len.min(body.len())].to_vec(), body.len() == len),
+                None => (body.to_vec(), false),
+            };
+            let close = part
+                .headers
+                .get("Connection")
+                .and_then(|v| v.to_string().ok())
+                .map(|v| v.eq_ignore_ascii_case("close"))
+                .unwrap_or(false);
+            let reusable = complete && !close && part.version == Version::HTTP1_1;
+            return Ok((part, body, reusable));
         }
     }
 
@@ -527,16 +943,25 @@ mod tls {
     }
 
     enum CreateTunnelErr {
+        // The `Digest` challenge of the proxy can not be answered.
+        DigestAuth(&'static str),
         ProxyHeadersTooLong,
-        ProxyAuthenticationRequired,
+        // The proxy responds to `CONNECT` with a non-2xx status and a body.
+        Rejected(StatusCode, String),
         Unsuccessful,
     }
 
     impl Debug for CreateTunnelErr {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
             match self {
+                Self::DigestAuth(msg) => write!(f, "Proxy digest authentication failed: {msg}"),
                 Self::ProxyHeadersTooLong => f.write_str("Proxy headers too long for tunnel"),
-                Self::ProxyAuthenticationRequired => f.write_str("Proxy authentication required"),
+                Self::Rejected(status, body) if body.is_empty() => {
+                    write!(f, "Proxy rejected tunnel with status {status}")
+                }
+                Self::Rejected(status, body) => {
+                    write!(f, "Proxy rejected tunnel with status {status}: {body}")
+                }
                 Self::Unsuccessful => f.write_str("Unsuccessful tunnel"),
             }
         }
@@ -552,7 +977,194 @@ mod tls {
 
     #[cfg(all(test, feature = "__tls"))]
     mod ut_tunnel_error_debug {
-        use crate::async_impl::connector::tls::CreateTunnelErr;
+        use std::collections::VecDeque;
+        use std::future::Future;
+        use std::io;
+        use std::pin::Pin;
+        use std::task::{Context, Poll};
+
+        use ylong_http::headers::Headers;
+        use ylong_http::response::status::StatusCode;
+
+        use crate::async_impl::connector::tls::{tunnel, CreateTunnelErr};
+        use crate::async_impl::http_body::noop_waker;
+        use crate::runtime::{AsyncRead, AsyncWrite, ReadBuf};
+
+        // A proxy which responds to each `CONNECT` with the given bytes.
+        struct ProxyIo {
+            responses: VecDeque<&'static [u8]>,
+            written: Vec<u8>,
+        }
+
+        impl AsyncRead for ProxyIo {
+            fn poll_read(
+                mut self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+                buf: &mut ReadBuf<'_>,
+            ) -> Poll<io::Result<()>> {
+                if let Some(response) = self.responses.pop_front() {
+                    let size = response.len().min(buf.remaining());
+                    buf.put_slice(&response[..size]);
+                    if size < response.len() {
+                        self.responses.push_front(&response[size..]);
+                    }
+                }
+                Poll::Ready(Ok(()))
+            }
+        }
+
+        impl AsyncWrite for ProxyIo {
+            fn poll_write(
+                mut self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+                buf: &[u8],
+            ) -> Poll<io::Result<usize>> {
+                self.written.extend_from_slice(buf);
+                Poll::Ready(Ok(buf.len()))
+            }
+
+            fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+                Poll::Ready(Ok(()))
+            }
+
+            fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+                Poll::Ready(Ok(()))
+            }
+        }
+
+        fn tunnel_through(response: &'static [u8]) -> Result<ProxyIo, io::Error> {
+            tunnel_with(vec![response], None)
+        }
+
+        fn tunnel_with(
+            responses: Vec<&'static [u8]>,
+            credentials: Option<&(String, String)>,
+        ) -> Result<ProxyIo, io::Error> {
+            let io = ProxyIo {
+                responses: VecDeque::from(responses),
+                written: Vec::new(),
+            };
+            let waker = noop_waker();
+            let mut cx = Context::from_waker(&waker);
+            let headers = Headers::new();
+            let mut fut = Box::pin(tunnel(
+                io,
+                "ylong_http.com",
+                443,
+                None,
+                credentials,
+                &headers,
+            ));
+            match fut.as_mut().poll(&mut cx) {
+                Poll::Ready(result) => result,
+                Poll::Pending => panic!("The tunnel is not ready"),
+            }
+        }
+
+        /// UT test cases for `tunnel` with a proxy which rejects `CONNECT`.
+        ///
+        /// # Brief
+        /// 1. Creates a tunnel through a proxy which responds `407` with an
+        ///    authentication challenge.
+        /// 2. Checks that the error carries the status and the body.
+        /// 3. Creates tunnels through proxies which respond `200`, `204` and
+        ///    an incomplete response.
+        /// 4. Checks if the results are as expected.
+        #[test]
+        fn ut_tunnel_proxy_rejected() {
+            let err = tunnel_through(
+                b"HTTP/1.1 407 Proxy Authentication Required\r\n\
+                Proxy-Authenticate: Basic realm=\"proxy\"\r\n\
+                Content-Length: 19\r\n\r\nAccess denied here.",
+            )
+            .err()
+            .unwrap();
+            assert_eq!(
+                err.to_string(),
+                "Proxy rejected tunnel with status 407 Proxy Authentication Required: Access \
+                 denied here."
+            );
+
+            let err = tunnel_through(b"HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\n\r\n")
+                .err()
+                .unwrap();
+            assert_eq!(
+                err.to_string(),
+                "Proxy rejected tunnel with status 403 Forbidden"
+            );
+
+            let io = tunnel_through(b"HTTP/1.1 200 Connection Established\r\n\r\n").unwrap();
+            assert!(io
+                .written
+                .starts_with(b"CONNECT ylong_http.com:443 HTTP/1.1\r\n"));
+            assert!(tunnel_through(b"HTTP/1.0 204 No Content\r\n\r\n").is_ok());
+
+            let err = tunnel_through(b"HTTP/1.1 200 Connection").err().unwrap();
+            assert_eq!(err.to_string(), "Unsuccessful tunnel");
+        }
+
+        /// UT test cases for `tunnel` with a proxy which sends a `Digest`
+        /// challenge.
+        ///
+        /// # Brief
+        /// 1. Creates a tunnel through a proxy which responds `407` with a
+        ///    `Digest` challenge, then `200`.
+        /// 2. Checks that the second `CONNECT` carries the digest response.
+        /// 3. Creates tunnels without credentials, with an unsupported
+        ///    algorithm, and through a proxy which challenges twice.
+        /// 4. Checks if the errors are as expected.
+        #[test]
+        fn ut_tunnel_proxy_digest_auth() {
+            const CHALLENGE: &[u8] = b"HTTP/1.1 407 Proxy Authentication Required\r\n\
+                Proxy-Authenticate: Basic realm=\"proxy\"\r\n\
+                Proxy-Authenticate: Digest realm=\"proxy\", nonce=\"abc\", qop=\"auth\", \
+                algorithm=SHA-256\r\nContent-Length: 6\r\n\r\ndenied";
+            let credentials = (String::from("user"), String::from("pass"));
+
+            let io = tunnel_with(
+                vec![CHALLENGE, b"HTTP/1.1 200 Connection Established\r\n\r\n"],
+                Some(&credentials),
+            )
+            .unwrap();
+            let written = String::from_utf8(io.written).unwrap();
+            let (first, second) = written.split_once("\r\n\r\n").unwrap();
+            assert!(!first.contains("Proxy-Authorization"));
+            assert!(second.starts_with("CONNECT ylong_http.com:443 HTTP/1.1\r\n"));
+            assert!(second.contains(
+                "Proxy-Authorization: Digest username=\"user\", realm=\"proxy\", \
+                 nonce=\"abc\", uri=\"ylong_http.com:443\", algorithm=SHA-256, response=\""
+            ));
+            assert!(second.contains(", qop=auth, nc=00000001, cnonce=\""));
+
+            let err = tunnel_with(vec![CHALLENGE], None).err().unwrap();
+            assert_eq!(
+                err.to_string(),
+                "Proxy digest authentication failed: No credentials for the digest challenge"
+            );
+
+            let err = tunnel_with(
+                vec![
+                    b"HTTP/1.1 407 Proxy Authentication Required\r\n\
+                    Proxy-Authenticate: Digest realm=\"proxy\", nonce=\"abc\", \
+                    algorithm=SHA-512\r\nContent-Length: 0\r\n\r\n",
+                ],
+                Some(&credentials),
+            )
+            .err()
+            .unwrap();
+            assert_eq!(
+                err.to_string(),
+                "Proxy digest authentication failed: Unsupported digest algorithm"
+            );
+
+            let err = tunnel_with(vec![CHALLENGE, CHALLENGE], Some(&credentials))
+                .err()
+                .unwrap();
+            assert_eq!(
+                err.to_string(),
+                "Proxy rejected tunnel with status 407 Proxy Authentication Required: denied"
+            );
+        }
 
         /// UT test cases for debug of`CreateTunnelErr`.
         ///
@@ -566,8 +1178,11 @@ mod tls {
                 "Proxy headers too long for tunnel"
             );
             assert_eq!(
-                format!("{:?}", CreateTunnelErr::ProxyAuthenticationRequired),
-                "Proxy authentication required"
+                format!(
+                    "{:?}",
+                    CreateTunnelErr::Rejected(StatusCode::BAD_GATEWAY, String::new())
+                ),
+                "Proxy rejected tunnel with status 502 Bad Gateway"
             );
             assert_eq!(
                 format!("{:?}", CreateTunnelErr::Unsuccessful),
@@ -578,8 +1193,14 @@ mod tls {
                 "Proxy headers too long for tunnel"
             );
             assert_eq!(
-                format!("{}", CreateTunnelErr::ProxyAuthenticationRequired),
-                "Proxy authentication required"
+                format!(
+                    "{}",
+                    CreateTunnelErr::Rejected(
+                        StatusCode::PROXY_AUTHENTICATION_REQUIRED,
+                        String::from("denied")
+                    )
+                ),
+                "Proxy rejected tunnel with status 407 Proxy Authentication Required: denied"
             );
             assert_eq!(
                 format!("{}", CreateTunnelErr::Unsuccessful),
@@ -593,6 +1214,8 @@ mod tls {
         use std::net::SocketAddr;
         use std::str::FromStr;
 
+        use ylong_http::headers::Headers;
+        use ylong_http::response::status::StatusCode;
         use ylong_runtime::io::AsyncWriteExt;
 
         use crate::async_impl::connector::tcp_stream;
@@ -629,6 +1252,8 @@ mod tls {
                     "ylong_http.com",
                     443,
                     Some(String::from("base64 bytes")),
+                    None,
+                    &Headers::new(),
                 )
                 .await;
                 assert_eq!(
@@ -666,13 +1291,18 @@ mod tls {
                     "ylong_http.com",
                     443,
                     Some(String::from("base64 bytes")),
+                    None,
+                    &Headers::new(),
                 )
                 .await;
                 assert_eq!(
                     format!("{:?}", res.err()),
                     format!(
                         "{:?}",
-                        Some(other_io_error(CreateTunnelErr::ProxyAuthenticationRequired))
+                        Some(other_io_error(CreateTunnelErr::Rejected(
+                            StatusCode::PROXY_AUTHENTICATION_REQUIRED,
+                            String::from("METHOD GET!")
+                        )))
                     )
                 );
                 handle
@@ -706,11 +1336,19 @@ mod tls {
                     "ylong_http.com",
                     443,
                     Some(String::from("base64 bytes")),
+                    None,
+                    &Headers::new(),
                 )
                 .await;
                 assert_eq!(
                     format!("{:?}", res.err()),
-                    format!("{:?}", Some(other_io_error(CreateTunnelErr::Unsuccessful)))
+                    format!(
+                        "{:?}",
+                        Some(other_io_error(CreateTunnelErr::Rejected(
+                            StatusCode::PAYMENT_REQUIRED,
+                            String::from("METHOD GET!")
+                        )))
+                    )
                 );
                 handle
                     .server_shutdown
@@ -753,6 +1391,8 @@ mod tls {
                     "ylong_http.com",
                     443,
                     Some(String::from("base64 bytes")),
+                    None,
+                    &Headers::new(),
                 )
                 .await;
                 assert!(res.is_ok());
@@ -800,6 +1440,8 @@ mod tls {
                     "ylong_http.com",
                     443,
                     Some(String::from("base64 bytes")),
+                    None,
+                    &Headers::new(),
                 )
                 .await;
                 assert_eq!(
@@ -818,3 +1460,68 @@ mod tls {
         }
     }
 }
+
+#[cfg(test)]
+mod ut_connector {
+    use std::net::{Ipv6Addr, SocketAddr, SocketAddrV6};
+
+    use super::ipv6_literal;
+
+    /// UT test cases for the dns timeout of `dns_query`.
+    ///
+    /// # Brief
+    /// 1. Resolves an authority with a dns timeout by a `Resolver` which never
+    ///    completes.
+    /// 2. Checks that a dns timeout error with the host is returned.
+    #[cfg(feature = "ylong_base")]
+    #[test]
+    fn ut_connector_dns_timeout() {
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        use super::dns_query;
+        use crate::async_impl::{Resolver, SocketFuture};
+
+        struct PendingResolver;
+
+        impl Resolver for PendingResolver {
+            fn resolve(&self, _authority: &str) -> SocketFuture {
+                Box::pin(std::future::pending())
+            }
+        }
+
+        let resolver = Arc::new(PendingResolver) as Arc<dyn Resolver>;
+        let dns_timeout = Some(Duration::from_millis(10));
+        let res = ylong_runtime::block_on(dns_query(resolver, "example.com:80", dns_timeout, None));
+        let err = res.err().unwrap();
+        assert!(err.is_dns_timeout());
+        assert_eq!(err.dns_host(), Some("example.com"));
+    }
+
+    /// UT test cases for `ipv6_literal`.
+    ///
+    /// # Brief
+    /// 1. Parses authorities with and without bracketed IPv6 hosts and zones.
+    /// 2. Checks that the zone id is used as the scope id.
+    /// 3. Checks that non-literal hosts and unknown zones are handled.
+    #[test]
+    fn ut_connector_ipv6_literal() {
+        let ip = "fe80::1".parse::<Ipv6Addr>().unwrap();
+        assert_eq!(
+            ipv6_literal("[fe80::1]:8080").unwrap(),
+            Some(SocketAddr::V6(SocketAddrV6::new(ip, 8080, 0, 0)))
+        );
+        assert_eq!(
+            ipv6_literal("[fe80::1%2]:8080").unwrap(),
+            Some(SocketAddr::V6(SocketAddrV6::new(ip, 8080, 0, 2)))
+        );
+        assert_eq!(
+            ipv6_literal("[fe80::1%253]:80").unwrap(),
+            Some(SocketAddr::V6(SocketAddrV6::new(ip, 80, 0, 3)))
+        );
+        assert_eq!(ipv6_literal("www.example.com:80").unwrap(), None);
+        assert_eq!(ipv6_literal("127.0.0.1:80").unwrap(), None);
+        assert_eq!(ipv6_literal("[fe80::1]").unwrap(), None);
+        assert!(ipv6_literal("[fe80::1%no-such-interface]:80").is_err());
+    }
+}
@@ -0,0 +1,154 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Client level dns cache in front of any `Resolver`.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::async_impl::dns::{Addrs, Resolver, SocketFuture};
+
+const MAX_ENTRIES_LEN: usize = 30000;
+
+/// Resolved addresses of each authority, which expire after a TTL.
+pub(crate) struct DnsCache {
+    ttl: Duration,
+    map: Mutex<HashMap<String, (Vec<SocketAddr>, Instant)>>,
+}
+
+impl DnsCache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            map: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // Gets the addresses of `authority` if they have not expired.
+    fn get(&self, authority: &str) -> Option<Vec<SocketAddr>> {
+        let map = self.map.lock().unwrap();
+        match map.get(authority) {
+            Some((addrs, expiration)) if *expiration > Instant::now() => Some(addrs.clone()),
+            _ => None,
+        }
+    }
+
+    fn insert(&self, authority: String, addrs: Vec<SocketAddr>) {
+        let mut map = self.map.lock().unwrap();
+        if map.len() > MAX_ENTRIES_LEN {
+            let now = Instant::now();
+            map.retain(|_, (_, expiration)| *expiration > now);
+        }
+        map.insert(authority, (addrs, Instant::now() + self.ttl));
+    }
+
+    /// Removes all cached addresses.
+    pub(crate) fn flush(&self) {
+        self.map.lock().unwrap().clear();
+    }
+}
+
+/// A `Resolver` that consults a `DnsCache` before invoking the inner
+/// `Resolver`, and caches the addresses it resolves.
+pub(crate) struct CachedResolver {
+    inner: Arc<dyn Resolver>,
+    cache: Arc<DnsCache>,
+}
+
+impl CachedResolver {
+    pub(crate) fn new(inner: Arc<dyn Resolver>, cache: Arc<DnsCache>) -> Self {
+        Self { inner, cache }
+    }
+}
+
+impl Resolver for CachedResolver {
+    fn resolve(&self, authority: &str) -> SocketFuture<'_> {
+        if let Some(addrs) = self.cache.get(authority) {
+            return Box::pin(async move { Ok(Box::new(addrs.into_iter()) as Addrs) });
+        }
+        let authority = authority.to_string();
+        Box::pin(async move {
+            let addrs = self.inner.resolve(&authority).await?.collect::<Vec<_>>();
+            self.cache.insert(authority, addrs.clone());
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod ut_cached_resolver {
+    use std::net::SocketAddr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::{Context, Poll};
+    use std::time::Duration;
+
+    use crate::async_impl::dns::cache::{CachedResolver, DnsCache};
+    use crate::async_impl::dns::{Addrs, Resolver, SocketFuture};
+    use crate::async_impl::http_body::noop_waker;
+
+    // Resolves every authority to one address and counts the resolutions.
+    #[derive(Clone, Default)]
+    struct CountResolver {
+        count: Arc<AtomicUsize>,
+    }
+
+    impl Resolver for CountResolver {
+        fn resolve(&self, _authority: &str) -> SocketFuture<'_> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            let addr = "127.0.0.1:80".parse::<SocketAddr>().unwrap();
+            Box::pin(async move { Ok(Box::new(vec![addr].into_iter()) as Addrs) })
+        }
+    }
+
+    fn resolve(resolver: &CachedResolver, authority: &str) -> Vec<SocketAddr> {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        match resolver.resolve(authority).as_mut().poll(&mut cx) {
+            Poll::Ready(Ok(addrs)) => addrs.collect(),
+            _ => panic!("The resolution is not ready"),
+        }
+    }
+
+    /// UT test cases for `CachedResolver::resolve`.
+    ///
+    /// # Brief
+    /// 1. Creates a `CachedResolver` in front of a counting `Resolver`.
+    /// 2. Resolves the same authority several times.
+    /// 3. Checks that the inner `Resolver` is only invoked again after the
+    ///    TTL expires, or after the cache is flushed.
+    #[test]
+    fn ut_cached_resolver_resolve() {
+        let inner = CountResolver::default();
+        let cache = Arc::new(DnsCache::new(Duration::from_millis(100)));
+        let resolver = CachedResolver::new(Arc::new(inner.clone()), cache.clone());
+        let addr = "127.0.0.1:80".parse::<SocketAddr>().unwrap();
+
+        assert_eq!(resolve(&resolver, "example.com:80"), vec![addr]);
+        assert_eq!(resolve(&resolver, "example.com:80"), vec![addr]);
+        assert_eq!(inner.count.load(Ordering::SeqCst), 1);
+
+        resolve(&resolver, "example.org:80");
+        assert_eq!(inner.count.load(Ordering::SeqCst), 2);
+
+        cache.flush();
+        resolve(&resolver, "example.com:80");
+        assert_eq!(inner.count.load(Ordering::SeqCst), 3);
+
+        std::thread::sleep(Duration::from_millis(150));
+        resolve(&resolver, "example.com:80");
+        assert_eq!(inner.count.load(Ordering::SeqCst), 4);
+    }
+}
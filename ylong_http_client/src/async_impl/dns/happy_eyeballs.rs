@@ -22,6 +22,7 @@ use std::ops::{Deref, DerefMut};
 
 use crate::async_impl::dns::resolver::ResolvedAddrs;
 use crate::runtime::{Sleep, TcpStream};
+use crate::util::config::SocketConfig;
 
 const HAPPY_EYEBALLS_PREFERRED_TIMEOUT_MS: u64 = 300;
 
@@ -34,6 +35,7 @@ struct RemoteAddrs {
     addrs: DomainAddrs,
     // timeout of each socket address.
     timeout: Option<Duration>,
+    socket: SocketConfig,
 }
 
 struct DelayedAddrs {
@@ -46,6 +48,8 @@ pub(crate) struct EyeBallConfig {
     timeout: Option<Duration>,
     // Delay to start other address family when the preferred address family is not complete
     delay: Option<Duration>,
+    // Options set on each socket before it connects.
+    socket: SocketConfig,
 }
 
 struct DomainAddrs {
@@ -98,34 +102,43 @@ impl DelayedAddrs {
 
 impl EyeBallConfig {
     pub(crate) fn new(timeout: Option<Duration>, delay: Option<Duration>) -> Self {
-        Self { timeout, delay }
+        Self {
+            timeout,
+            delay,
+            socket: SocketConfig::default(),
+        }
+    }
+
+    pub(crate) fn socket(mut self, socket: SocketConfig) -> Self {
+        self.socket = socket;
+        self
     }
 }
 
 impl RemoteAddrs {
-    fn new(addrs: Vec<SocketAddr>, timeout: Option<Duration>) -> Self {
+    fn new(addrs: Vec<SocketAddr>, timeout: Option<Duration>, socket: SocketConfig) -> Self {
         Self {
             addrs: DomainAddrs::new(addrs),
             timeout,
+            socket,
         }
     }
 
-    async fn connect(&mut self) -> Result<TcpStream, io::Error> {
-        let mut unexpected = None;
+    // Tries each address in order, returns all the failed attempts if none of
+    // them can be connected.
+    async fn connect(&mut self) -> Result<TcpStream, Vec<(SocketAddr, io::Error)>> {
+        let mut attempts = vec![];
         for addr in self.addrs.iter() {
-            match connect(addr, self.timeout).await {
+            match connect(addr, self.timeout, self.socket).await {
                 Ok(stream) => {
                     return Ok(stream);
                 }
                 Err(e) => {
-                    unexpected = Some(e);
+                    attempts.push((*addr, e));
                 }
             }
         }
-        match unexpected {
-            None => Err(Error::new(ErrorKind::NotConnected, "Invalid domain")),
-            Some(e) => Err(e),
-        }
+        Err(attempts)
     }
 }
 
@@ -143,6 +156,7 @@ impl HappyEyeballs {
                     config
                         .timeout
                         .and_then(|time| time.checked_div(preferred_size as u32)),
+                    config.socket,
                 ),
                 delay_addr: None,
             }
@@ -158,6 +172,7 @@ impl HappyEyeballs {
                     config
                         .timeout
                         .and_then(|time| time.checked_div(preferred_size as u32)),
+                    config.socket,
                 ),
                 // TODO Is it necessary to subtract the delay time
                 delay_addr: Some(DelayedAddrs::new(
@@ -166,6 +181,7 @@ impl HappyEyeballs {
                         config
                             .timeout
                             .and_then(|time| time.checked_div(second_size as u32)),
+                        config.socket,
                     ),
                     crate::runtime::sleep(delay),
                 )),
@@ -175,7 +191,7 @@ impl HappyEyeballs {
 
     pub(crate) async fn connect(mut self) -> io::Result<TcpStream> {
         match self.delay_addr {
-            None => self.preferred_addr.connect().await,
+            None => self.preferred_addr.connect().await.map_err(attempts_error),
             Some(mut second_addrs) => {
                 let preferred_fut = self.preferred_addr.connect();
                 let second_fut = second_addrs.addrs.connect();
@@ -221,21 +237,45 @@ impl HappyEyeballs {
                     },
                 };
 
-                if stream.is_err() {
-                    stream_fut.await
-                } else {
-                    stream
+                match stream {
+                    Ok(stream) => Ok(stream),
+                    Err(mut attempts) => stream_fut.await.map_err(|others| {
+                        attempts.extend(others);
+                        attempts_error(attempts)
+                    }),
                 }
             }
         }
     }
 }
 
+// Converts the failed attempts into an error. The only error is returned as it
+// is, otherwise an error listing all the attempts is returned.
+fn attempts_error(mut attempts: Vec<(SocketAddr, io::Error)>) -> io::Error {
+    match attempts.len() {
+        0 => Error::new(ErrorKind::NotConnected, "Invalid domain"),
+        1 => attempts.pop().map(|(_, e)| e).unwrap(),
+        _ => {
+            let kind = attempts[attempts.len() - 1].1.kind();
+            let detail = attempts
+                .iter()
+                .map(|(addr, e)| format!("{addr}: {e}"))
+                .collect::<Vec<_>>()
+                .join("; ");
+            Error::new(
+                kind,
+                format!("Failed to connect to all addresses [{detail}]"),
+            )
+        }
+    }
+}
+
 fn connect(
     addr: &SocketAddr,
     timeout: Option<Duration>,
+    socket: SocketConfig,
 ) -> impl Future<Output = io::Result<TcpStream>> {
-    let stream_fut = TcpStream::connect(*addr);
+    let stream_fut = connect_socket(*addr, socket);
     async move {
         match timeout {
             None => stream_fut.await,
@@ -247,3 +287,92 @@ fn connect(
         }
     }
 }
+
+#[cfg(feature = "tokio_base")]
+async fn connect_socket(addr: SocketAddr, socket: SocketConfig) -> io::Result<TcpStream> {
+    if socket.is_empty() {
+        return TcpStream::connect(addr).await;
+    }
+    let tcp = if addr.is_ipv4() {
+        tokio::net::TcpSocket::new_v4()?
+    } else {
+        tokio::net::TcpSocket::new_v6()?
+    };
+    tcp.set_reuseaddr(socket.reuse_addr)?;
+    #[cfg(all(unix, not(target_os = "solaris"), not(target_os = "illumos")))]
+    tcp.set_reuseport(socket.reuse_port)?;
+    if let Some(local) = socket.local_addr {
+        if local.is_ipv4() != addr.is_ipv4() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Local address {local} does not match the family of {addr}"),
+            ));
+        }
+        tcp.bind(local).map_err(|e| match e.kind() {
+            ErrorKind::AddrInUse => Error::new(
+                ErrorKind::AddrInUse,
+                format!("Local address {local} is already in use"),
+            ),
+            _ => e,
+        })?;
+    }
+    tcp.connect(addr).await
+}
+
+#[cfg(feature = "ylong_base")]
+async fn connect_socket(addr: SocketAddr, socket: SocketConfig) -> io::Result<TcpStream> {
+    if !socket.is_empty() {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "Socket options are not supported by the runtime",
+        ));
+    }
+    TcpStream::connect(addr).await
+}
+
+#[cfg(all(test, feature = "ylong_base"))]
+mod ut_happy_eyeballs {
+    use std::net::{SocketAddr, TcpListener};
+
+    use crate::async_impl::dns::{EyeBallConfig, HappyEyeballs};
+
+    fn refused_addr() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap()
+    }
+
+    /// UT test cases for `HappyEyeballs::connect`.
+    ///
+    /// # Brief
+    /// 1. Creates a `HappyEyeballs` whose first address refuses connection.
+    /// 2. Calls `HappyEyeballs::connect`.
+    /// 3. Checks if the next address is connected.
+    #[test]
+    fn ut_happy_eyeballs_connect_next_addr() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let eyeballs =
+            HappyEyeballs::new(vec![refused_addr(), addr], EyeBallConfig::new(None, None));
+        let handle = ylong_runtime::spawn(async move { eyeballs.connect().await });
+        let stream = ylong_runtime::block_on(handle).unwrap().unwrap();
+        assert_eq!(stream.peer_addr().unwrap(), addr);
+    }
+
+    /// UT test cases for `HappyEyeballs::connect`.
+    ///
+    /// # Brief
+    /// 1. Creates a `HappyEyeballs` whose addresses all refuse connection.
+    /// 2. Calls `HappyEyeballs::connect`.
+    /// 3. Checks if the error lists all the attempts.
+    #[test]
+    fn ut_happy_eyeballs_connect_all_failed() {
+        let first = refused_addr();
+        let second = refused_addr();
+        let eyeballs = HappyEyeballs::new(vec![first, second], EyeBallConfig::new(None, None));
+        let handle = ylong_runtime::spawn(async move { eyeballs.connect().await });
+        let err = ylong_runtime::block_on(handle).unwrap().unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains(&first.to_string()));
+        assert!(msg.contains(&second.to_string()));
+    }
+}
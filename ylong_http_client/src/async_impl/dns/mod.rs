@@ -21,8 +21,10 @@
 //!
 //! - [`DefaultDnsResolver`]: Default dns resolver.
 
+mod cache;
 mod happy_eyeballs;
 mod resolver;
 
+pub(crate) use cache::{CachedResolver, DnsCache};
 pub(crate) use happy_eyeballs::{EyeBallConfig, HappyEyeballs};
 pub use resolver::{Addrs, DefaultDnsResolver, Resolver, SocketFuture, StdError};
@@ -0,0 +1,164 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// A map of typed values attached to a `Request`, which holds at most one
+/// value of each type.
+///
+/// Extensions pass context from the call site to the `Interceptor`s of the
+/// `Request`, they are never sent to the peer.
+///
+/// # Examples
+///
+/// ```
+/// use ylong_http_client::async_impl::Extensions;
+///
+/// struct RequestId(u64);
+///
+/// let mut extensions = Extensions::new();
+/// extensions.insert(RequestId(1));
+/// assert_eq!(extensions.get::<RequestId>().map(|id| id.0), Some(1));
+/// ```
+#[derive(Default)]
+pub struct Extensions {
+    // The values are shared with the hedged copies of the `Request`.
+    map: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl Extensions {
+    /// Creates an empty `Extensions`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts a value into the `Extensions`, returns the previous value of
+    /// the same type if any.
+    pub fn insert<T: Send + Sync + 'static>(&mut self, value: T) -> Option<T> {
+        self.map
+            .insert(TypeId::of::<T>(), Arc::new(value))
+            .and_then(unwrap_value)
+    }
+
+    /// Gets a reference to the value of type `T`.
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.map
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    /// Gets a mutable reference to the value of type `T`.
+    pub fn get_mut<T: Send + Sync + 'static>(&mut self) -> Option<&mut T> {
+        self.map
+            .get_mut(&TypeId::of::<T>())
+            .and_then(Arc::get_mut)
+            .and_then(|value| value.downcast_mut())
+    }
+
+    /// Removes the value of type `T` from the `Extensions` and returns it.
+    pub fn remove<T: Send + Sync + 'static>(&mut self) -> Option<T> {
+        self.map.remove(&TypeId::of::<T>()).and_then(unwrap_value)
+    }
+
+    /// Checks whether the `Extensions` holds no value.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Removes all the values from the `Extensions`.
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    // Creates an `Extensions` that shares the values of this one.
+    pub(crate) fn share(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+        }
+    }
+}
+
+// Takes the value out of the `Arc`, unless it is still shared.
+fn unwrap_value<T: Send + Sync + 'static>(value: Arc<dyn Any + Send + Sync>) -> Option<T> {
+    value
+        .downcast::<T>()
+        .ok()
+        .and_then(|value| Arc::try_unwrap(value).ok())
+}
+
+impl Debug for Extensions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Extensions").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod ut_extensions {
+    use crate::async_impl::Extensions;
+
+    #[derive(Debug, PartialEq)]
+    struct RequestId(u64);
+
+    /// UT test cases for `Extensions`.
+    ///
+    /// # Brief
+    /// 1. Inserts values of different types into an `Extensions`.
+    /// 2. Gets, replaces and removes them.
+    /// 3. Checks if the results are as expected.
+    #[test]
+    fn ut_extensions() {
+        let mut extensions = Extensions::new();
+        assert!(extensions.is_empty());
+        assert_eq!(extensions.insert(RequestId(1)), None);
+        assert_eq!(extensions.insert("scope"), None);
+        assert_eq!(extensions.get::<RequestId>(), Some(&RequestId(1)));
+        assert_eq!(extensions.get::<&str>(), Some(&"scope"));
+        assert_eq!(extensions.get::<u64>(), None);
+
+        extensions.get_mut::<RequestId>().unwrap().0 = 2;
+        assert_eq!(extensions.insert(RequestId(3)), Some(RequestId(2)));
+        assert_eq!(extensions.remove::<RequestId>(), Some(RequestId(3)));
+        assert_eq!(extensions.get::<RequestId>(), None);
+
+        extensions.clear();
+        assert!(extensions.is_empty());
+    }
+
+    /// UT test cases for `Extensions::share`.
+    ///
+    /// # Brief
+    /// 1. Creates an `Extensions` that shares the values of another one.
+    /// 2. Checks that both hold the values.
+    /// 3. Checks that a shared value can't be taken out or mutated, until the
+    ///    other `Extensions` is dropped.
+    #[test]
+    fn ut_extensions_share() {
+        let mut extensions = Extensions::new();
+        extensions.insert(RequestId(1));
+        let shared = extensions.share();
+        assert_eq!(shared.get::<RequestId>(), Some(&RequestId(1)));
+        assert_eq!(extensions.get::<RequestId>(), Some(&RequestId(1)));
+        assert_eq!(extensions.get_mut::<RequestId>(), None);
+        assert_eq!(extensions.insert(RequestId(2)), None);
+        assert_eq!(shared.get::<RequestId>(), Some(&RequestId(1)));
+
+        let shared = extensions.share();
+        assert_eq!(extensions.get_mut::<RequestId>(), None);
+        drop(shared);
+        assert_eq!(extensions.get_mut::<RequestId>(), Some(&mut RequestId(2)));
+        assert_eq!(extensions.remove::<RequestId>(), Some(RequestId(2)));
+    }
+}
@@ -0,0 +1,224 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! gRPC-Web response framing.
+//!
+//! A gRPC-Web response body is a sequence of frames, each of which starts with
+//! a flag byte and a 4-byte big-endian length. The frame whose flag has the
+//! most significant bit set carries the trailer fields, encoded like the
+//! header fields of `HTTP/1.1`, and ends the body ([gRPC-Web]).
+//!
+//! [gRPC-Web]: https://github.com/grpc/grpc/blob/master/doc/PROTOCOL-WEB.md
+
+use ylong_http::headers::Headers;
+
+use crate::error::{ErrorKind, HttpClientError};
+
+const FRAME_HEAD_LEN: usize = 5;
+const TRAILER_FLAG: u8 = 0x80;
+
+/// A gRPC-Web response, which consists of the messages and the trailer
+/// fields decoded from the response body.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ylong_http_client::async_impl::{Body, Client, Request};
+/// use ylong_http_client::HttpClientError;
+///
+/// async fn grpc_web() -> Result<(), HttpClientError> {
+///     let client = Client::builder().request_trailers(true).build()?;
+///     let request = Request::builder()
+///         .method("POST")
+///         .url("http://127.0.0.1:8080/helloworld.Greeter/SayHello")
+///         .header("Content-Type", "application/grpc-web+proto")
+///         .body(Body::slice(vec![0u8, 0, 0, 0, 0]))?;
+///     let response = client.request(request).await?.grpc_web().await?;
+///     let status = response.grpc_status();
+///     Ok(())
+/// }
+/// ```
+pub struct GrpcWeb {
+    messages: Vec<Vec<u8>>,
+    trailer: Headers,
+    headers: Headers,
+}
+
+impl GrpcWeb {
+    // Decodes the frames of `body`. The trailer fields are taken from the
+    // trailer frame, or from the `HTTP` trailer if there is no such frame.
+    pub(crate) fn decode(
+        headers: Headers,
+        body: &[u8],
+        http_trailer: Option<Headers>,
+    ) -> Result<Self, HttpClientError> {
+        let mut messages = Vec::new();
+        let mut trailer = None;
+        let mut rest = body;
+        while !rest.is_empty() {
+            if trailer.is_some() {
+                return err_from_msg!(BodyDecode, "Data after gRPC-Web trailer frame");
+            }
+            if rest.len() < FRAME_HEAD_LEN {
+                return err_from_msg!(BodyDecode, "Incomplete gRPC-Web frame");
+            }
+            let len = u32::from_be_bytes([rest[1], rest[2], rest[3], rest[4]]) as usize;
+            let payload = match rest[FRAME_HEAD_LEN..].get(..len) {
+                Some(payload) => payload,
+                None => return err_from_msg!(BodyDecode, "Incomplete gRPC-Web frame"),
+            };
+            if rest[0] & TRAILER_FLAG != 0 {
+                trailer = Some(decode_trailer(payload)?);
+            } else {
+                messages.push(payload.to_vec());
+            }
+            rest = &rest[FRAME_HEAD_LEN + len..];
+        }
+        Ok(Self {
+            messages,
+            trailer: trailer.or(http_trailer).unwrap_or_default(),
+            headers,
+        })
+    }
+
+    /// Gets the messages of the response, in the order they are received.
+    /// The messages are not decompressed.
+    pub fn messages(&self) -> &[Vec<u8>] {
+        &self.messages
+    }
+
+    /// Gets the trailer fields of the response.
+    pub fn trailer(&self) -> &Headers {
+        &self.trailer
+    }
+
+    /// Gets the `grpc-status` of the response. The trailer fields are looked
+    /// up first, and then the header fields, where a trailers-only response
+    /// puts the status.
+    ///
+    /// Returns `None` if there is no `grpc-status` or it is not a number.
+    pub fn grpc_status(&self) -> Option<u32> {
+        self.field("grpc-status")?.parse().ok()
+    }
+
+    /// Gets the `grpc-message` of the response, which is looked up like the
+    /// `grpc-status`. The message is returned as it is received, without
+    /// percent-decoding.
+    pub fn grpc_message(&self) -> Option<String> {
+        self.field("grpc-message")
+    }
+
+    fn field(&self, name: &str) -> Option<String> {
+        self.trailer
+            .get(name)
+            .or_else(|| self.headers.get(name))
+            .and_then(|value| value.to_string().ok())
+    }
+}
+
+fn decode_trailer(payload: &[u8]) -> Result<Headers, HttpClientError> {
+    let mut headers = Headers::new();
+    for line in payload.split(|b| *b == b'\n') {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        if line.is_empty() {
+            continue;
+        }
+        let pos = match line.iter().position(|b| *b == b':') {
+            Some(pos) => pos,
+            None => return err_from_msg!(BodyDecode, "Invalid gRPC-Web trailer field"),
+        };
+        let name = std::str::from_utf8(&line[..pos])
+            .map_err(|e| HttpClientError::from_error(ErrorKind::BodyDecode, e))?;
+        let value = std::str::from_utf8(&line[pos + 1..])
+            .map_err(|e| HttpClientError::from_error(ErrorKind::BodyDecode, e))?;
+        headers
+            .append(name.trim().to_ascii_lowercase().as_str(), value.trim())
+            .map_err(|e| HttpClientError::from_error(ErrorKind::BodyDecode, e))?;
+    }
+    Ok(headers)
+}
+
+#[cfg(test)]
+mod ut_grpc_web {
+    use ylong_http::headers::Headers;
+
+    use crate::async_impl::grpc_web::GrpcWeb;
+
+    fn frame(flag: u8, payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![flag];
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    /// UT test cases for `GrpcWeb::decode`.
+    ///
+    /// # Brief
+    /// 1. Decodes a body of two messages followed by a trailer frame.
+    /// 2. Checks that the messages and the trailer fields are correct.
+    /// 3. Decodes an empty body with the status in the header fields.
+    /// 4. Checks that the status is taken from the header fields.
+    #[test]
+    fn ut_grpc_web_decode() {
+        let mut body = frame(0x00, b"hello");
+        body.extend(frame(0x00, b""));
+        body.extend(frame(
+            0x80,
+            b"Grpc-Status: 5\r\ngrpc-message: not%20found\r\n",
+        ));
+        let response = GrpcWeb::decode(Headers::new(), &body, None).unwrap();
+        assert_eq!(response.messages(), &[b"hello".to_vec(), Vec::new()]);
+        assert_eq!(response.grpc_status(), Some(5));
+        assert_eq!(response.grpc_message(), Some(String::from("not%20found")));
+
+        let mut headers = Headers::new();
+        headers.insert("grpc-status", "0").unwrap();
+        let response = GrpcWeb::decode(headers, b"", None).unwrap();
+        assert!(response.messages().is_empty());
+        assert_eq!(response.grpc_status(), Some(0));
+        assert_eq!(response.grpc_message(), None);
+    }
+
+    /// UT test cases for `GrpcWeb::decode` with the `HTTP` trailer.
+    ///
+    /// # Brief
+    /// 1. Decodes a body without a trailer frame, but with an `HTTP` trailer.
+    /// 2. Checks that the status is taken from the `HTTP` trailer.
+    #[test]
+    fn ut_grpc_web_decode_http_trailer() {
+        let mut trailer = Headers::new();
+        trailer.insert("grpc-status", "2").unwrap();
+        let body = frame(0x00, b"hello");
+        let response = GrpcWeb::decode(Headers::new(), &body, Some(trailer)).unwrap();
+        assert_eq!(response.grpc_status(), Some(2));
+    }
+
+    /// UT test cases for `GrpcWeb::decode` with malformed bodies.
+    ///
+    /// # Brief
+    /// 1. Decodes truncated frames, data after the trailer frame and an
+    ///    invalid trailer field.
+    /// 2. Checks that errors are returned.
+    #[test]
+    fn ut_grpc_web_decode_invalid() {
+        assert!(GrpcWeb::decode(Headers::new(), &[0, 0, 0], None).is_err());
+        assert!(GrpcWeb::decode(Headers::new(), &[0, 0, 0, 0, 2, 1], None).is_err());
+
+        let mut body = frame(0x80, b"grpc-status: 0\r\n");
+        body.extend(frame(0x00, b"hello"));
+        assert!(GrpcWeb::decode(Headers::new(), &body, None).is_err());
+
+        let body = frame(0x80, b"grpc-status 0\r\n");
+        assert!(GrpcWeb::decode(Headers::new(), &body, None).is_err());
+    }
+}
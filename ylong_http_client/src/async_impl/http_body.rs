@@ -12,10 +12,14 @@
 // limitations under the License.
 
 use core::pin::Pin;
-use core::task::{Context, Poll};
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
 use std::future::Future;
 use std::io::{Cursor, Read};
+#[cfg(feature = "http1_1")]
+use std::mem::take;
+use std::ptr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use ylong_http::body::async_impl::Body;
 use ylong_http::body::TextBodyDecoder;
@@ -24,12 +28,15 @@ use ylong_http::body::{ChunkBodyDecoder, ChunkState};
 use ylong_http::headers::Headers;
 
 use super::conn::StreamData;
-use crate::error::{ErrorKind, HttpClientError};
-use crate::runtime::{AsyncRead, ReadBuf, Sleep};
+use crate::error::{Closed, ErrorKind, HttpClientError};
+use crate::runtime::{sleep, AsyncRead, ReadBuf, Sleep};
+use crate::util::dispatcher::WrappedSemPermit;
 use crate::util::interceptor::Interceptors;
 use crate::util::normalizer::BodyLength;
+use crate::util::pacer::RatePacer;
 
 const TRAILER_SIZE: usize = 1024;
+const DRAIN_BUF_SIZE: usize = 1024;
 
 /// `HttpBody` is the body part of the `Response` returned by `Client::request`.
 /// `HttpBody` implements `Body` trait, so users can call related methods to get
@@ -65,6 +72,23 @@ const TRAILER_SIZE: usize = 1024;
 pub struct HttpBody {
     kind: Kind,
     sleep: Option<Pin<Box<Sleep>>>,
+    // Timeout for each read, and the timer of the pending read.
+    read_timeout: Option<Duration>,
+    read_sleep: Option<Pin<Box<Sleep>>>,
+    // Timeout for the stream to receive anything, after which only the
+    // stream is reset, and the timer of the pending read.
+    idle_timeout: Option<Duration>,
+    idle_sleep: Option<Pin<Box<Sleep>>>,
+    // Paces the reads under the download rate limit, and the timer of the
+    // delay before the next read.
+    pacer: Option<RatePacer>,
+    pace_sleep: Option<Pin<Box<Sleep>>>,
+    drain_limit: usize,
+    // The length declared by the response, `None` if it is unknown.
+    length: Option<u64>,
+    // The slot of the request in the limit of requests in flight, which is
+    // released once the body ends.
+    permit: Option<WrappedSemPermit>,
 }
 
 type BoxStreamData = Box<dyn StreamData + Sync + Send + Unpin>;
@@ -76,6 +100,11 @@ impl HttpBody {
         io: BoxStreamData,
         pre: &[u8],
     ) -> Result<Self, HttpClientError> {
+        let length = match body_length {
+            BodyLength::Empty => Some(0),
+            BodyLength::Length(len) => Some(len),
+            _ => None,
+        };
         let kind = match body_length {
             BodyLength::Empty => {
                 if !pre.is_empty() {
@@ -91,12 +120,99 @@ impl HttpBody {
             #[cfg(feature = "http1_1")]
             BodyLength::Chunk => Kind::Chunk(Chunk::new(pre, io, interceptors)),
         };
-        Ok(Self { kind, sleep: None })
+        Ok(Self {
+            kind,
+            sleep: None,
+            read_timeout: None,
+            read_sleep: None,
+            idle_timeout: None,
+            idle_sleep: None,
+            pacer: None,
+            pace_sleep: None,
+            drain_limit: 0,
+            length,
+            permit: None,
+        })
+    }
+
+    pub(crate) fn content_length(&self) -> Option<u64> {
+        self.length
     }
 
     pub(crate) fn set_sleep(&mut self, sleep: Option<Pin<Box<Sleep>>>) {
         self.sleep = sleep;
     }
+
+    pub(crate) fn set_read_timeout(&mut self, timeout: Option<Duration>) {
+        self.read_timeout = timeout;
+    }
+
+    #[cfg(feature = "http2")]
+    pub(crate) fn set_stream_idle_timeout(&mut self, timeout: Option<Duration>) {
+        self.idle_timeout = timeout;
+    }
+
+    pub(crate) fn set_rate_limit(&mut self, bytes_per_sec: Option<u64>) {
+        self.pacer = bytes_per_sec.map(RatePacer::new);
+    }
+
+    pub(crate) fn set_request_permit(&mut self, permit: WrappedSemPermit) {
+        // An empty body needs no reading.
+        if !matches!(self.kind, Kind::Empty) {
+            self.permit = Some(permit);
+        }
+    }
+
+    pub(crate) fn set_drain_limit(&mut self, limit: usize) {
+        self.drain_limit = limit;
+    }
+
+    #[cfg(feature = "http1_1")]
+    pub(crate) fn set_max_until_close_size(&mut self, size: Option<u64>) {
+        if let Kind::UntilClose(ref mut until_close) = self.kind {
+            until_close.max_size = size;
+        }
+    }
+
+    #[cfg(feature = "http1_1")]
+    pub(crate) fn set_max_chunk_size(&mut self, size: usize) {
+        if let Kind::Chunk(ref mut chunk) = self.kind {
+            chunk.decoder = take(&mut chunk.decoder).max_chunk_size(size);
+        }
+    }
+
+    // Reads and discards the remaining body which is already available, at
+    // most `drain_limit` bytes. If the body is finished, the io is released
+    // without being shutdown, so the connection can be reused.
+    fn drain(&mut self) {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut buf = [0u8; DRAIN_BUF_SIZE];
+        let mut remaining = self.drain_limit;
+        while remaining > 0 {
+            let size = remaining.min(DRAIN_BUF_SIZE);
+            let result = match self.kind {
+                Kind::Text(ref mut text) => text.data(&mut cx, &mut buf[..size]),
+                #[cfg(feature = "http1_1")]
+                Kind::Chunk(ref mut chunk) => chunk.data(&mut cx, &mut buf[..size]),
+                // The end of `UntilClose` is the close of the connection.
+                _ => return,
+            };
+            match result {
+                Poll::Ready(Ok(read)) if read != 0 => remaining -= read,
+                _ => return,
+            }
+        }
+    }
+}
+
+// Creates a `Waker` which does nothing when woken, used to poll the io without
+// waiting for it.
+pub(crate) fn noop_waker() -> Waker {
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(|_| RAW, |_| {}, |_| {}, |_| {});
+    const RAW: RawWaker = RawWaker::new(ptr::null(), &VTABLE);
+    // The vtable functions never access the data pointer.
+    unsafe { Waker::from_raw(RAW) }
 }
 
 impl Body for HttpBody {
@@ -117,13 +233,55 @@ impl Body for HttpBody {
             }
         }
 
-        match self.kind {
+        if let Some(delay) = self.pace_sleep.as_mut() {
+            if Pin::new(delay).poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            self.pace_sleep = None;
+        }
+
+        let result = match self.kind {
             Kind::Empty => Poll::Ready(Ok(0)),
             Kind::Text(ref mut text) => text.data(cx, buf),
             Kind::UntilClose(ref mut until_close) => until_close.data(cx, buf),
             #[cfg(feature = "http1_1")]
             Kind::Chunk(ref mut chunk) => chunk.data(cx, buf),
+        };
+        if result.is_pending() {
+            if let Some(dur) = self.read_timeout {
+                let delay = self.read_sleep.get_or_insert_with(|| Box::pin(sleep(dur)));
+                if let Poll::Ready(()) = Pin::new(delay).poll(cx) {
+                    self.read_sleep = None;
+                    return Poll::Ready(err_from_msg!(Timeout, "Read timed out"));
+                }
+            }
+            if let Some(dur) = self.idle_timeout {
+                let delay = self.idle_sleep.get_or_insert_with(|| Box::pin(sleep(dur)));
+                if let Poll::Ready(()) = Pin::new(delay).poll(cx) {
+                    self.idle_sleep = None;
+                    self.permit = None;
+                    if let Some(io) = self.kind.take_io() {
+                        io.reset();
+                    }
+                    return Poll::Ready(Err(HttpClientError::from_stream_idle()));
+                }
+            }
+            return Poll::Pending;
+        }
+        self.read_sleep = None;
+        self.idle_sleep = None;
+        if matches!(result, Poll::Ready(Ok(0)) | Poll::Ready(Err(_))) {
+            self.permit = None;
         }
+        if let Poll::Ready(Ok(size)) = result {
+            if let Some(interceptors) = self.kind.interceptors().filter(|_| size != 0) {
+                interceptors.intercept_response_body(&buf[..size])?;
+            }
+            if let Some(delay) = self.pacer.as_mut().and_then(|pacer| pacer.delay(size)) {
+                self.pace_sleep = Some(Box::pin(sleep(delay)));
+            }
+        }
+        result
     }
 
     fn poll_trailer(
@@ -162,6 +320,9 @@ impl Body for HttpBody {
 
 impl Drop for HttpBody {
     fn drop(&mut self) {
+        if self.drain_limit != 0 {
+            self.drain();
+        }
         let io = match self.kind {
             Kind::Text(ref mut text) => text.io.as_mut(),
             #[cfg(feature = "http1_1")]
@@ -185,10 +346,36 @@ enum Kind {
     UntilClose(UntilClose),
 }
 
+impl Kind {
+    fn interceptors(&self) -> Option<&Arc<Interceptors>> {
+        match self {
+            Kind::Empty => None,
+            Kind::Text(text) => Some(&text.interceptors),
+            Kind::UntilClose(until_close) => Some(&until_close.interceptors),
+            #[cfg(feature = "http1_1")]
+            Kind::Chunk(chunk) => Some(&chunk.interceptors),
+        }
+    }
+
+    // Takes the io out, so the body ends without reading it further.
+    fn take_io(&mut self) -> Option<BoxStreamData> {
+        match self {
+            Kind::Empty => None,
+            Kind::Text(text) => text.io.take(),
+            Kind::UntilClose(until_close) => until_close.io.take(),
+            #[cfg(feature = "http1_1")]
+            Kind::Chunk(chunk) => chunk.io.take(),
+        }
+    }
+}
+
 struct UntilClose {
     interceptors: Arc<Interceptors>,
     pre: Option<Cursor<Vec<u8>>>,
     io: Option<BoxStreamData>,
+    // The maximum size of the body, and the size received so far.
+    max_size: Option<u64>,
+    received: u64,
 }
 
 impl UntilClose {
@@ -197,6 +384,8 @@ impl UntilClose {
             interceptors,
             pre: (!pre.is_empty()).then_some(Cursor::new(pre.to_vec())),
             io: Some(io),
+            max_size: None,
+            received: 0,
         }
     }
 
@@ -204,6 +393,27 @@ impl UntilClose {
         &mut self,
         cx: &mut Context<'_>,
         buf: &mut [u8],
+    ) -> Poll<Result<usize, HttpClientError>> {
+        let result = self.read_data(cx, buf);
+        if let (Poll::Ready(Ok(size)), Some(max_size)) = (&result, self.max_size) {
+            self.received += *size as u64;
+            if self.received > max_size {
+                if let Some(io) = self.io.take() {
+                    io.shutdown();
+                }
+                return Poll::Ready(err_from_msg!(
+                    BodyDecode,
+                    "Response body exceeds the maximum size"
+                ));
+            }
+        }
+        result
+    }
+
+    fn read_data(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
     ) -> Poll<Result<usize, HttpClientError>> {
         if buf.is_empty() {
             return Poll::Ready(Ok(0));
@@ -242,7 +452,7 @@ impl UntilClose {
                 if filled == 0 {
                     // Stream closed, and get the fin.
                     if io.is_stream_closable() {
-                        return Poll::Ready(Ok(0));
+                        return Poll::Ready(Ok(read));
                     }
                     // Disconnected for http1.
                     io.shutdown();
@@ -374,7 +584,10 @@ impl Text {
                         return Poll::Ready(Ok(0));
                     }
                     io.shutdown();
-                    return Poll::Ready(err_from_msg!(BodyDecode, "Response body incomplete"));
+                    return Poll::Ready(Err(HttpClientError::from_closed(
+                        ErrorKind::BodyDecode,
+                        Closed::InBody,
+                    )));
                 }
                 let (text, rem) = self.decoder.decode(read_buf.filled());
                 self.interceptors.intercept_output(read_buf.filled())?;
@@ -454,7 +667,17 @@ impl Chunk {
                 self.pre = None;
             }
 
-            let (size, flag) = self.merge_chunks(&mut buf[read..read + size])?;
+            let (size, flag) = match self.merge_chunks(&mut buf[read..read + size]) {
+                Ok(res) => res,
+                Err(e) => {
+                    // A malformed chunk makes the framing of the connection
+                    // unreliable, so it must not be reused.
+                    if let Some(io) = self.io.take() {
+                        io.shutdown();
+                    }
+                    return Poll::Ready(Err(e));
+                }
+            };
             read += size;
 
             if flag {
@@ -475,10 +698,22 @@ impl Chunk {
                     let filled = read_buf.filled().len();
                     if filled == 0 {
                         io.shutdown();
-                        return Poll::Ready(err_from_msg!(BodyDecode, "Response body incomplete"));
+                        return Poll::Ready(Err(HttpClientError::from_closed(
+                            ErrorKind::BodyDecode,
+                            Closed::InBody,
+                        )));
+                    }
+                    let (size, flag) = match self.merge_chunks(read_buf.filled_mut()) {
+                        Ok(res) => res,
+                        Err(e) => {
+                            io.shutdown();
+                            return Poll::Ready(Err(e));
+                        }
+                    };
+                    if let Err(e) = self.interceptors.intercept_output(read_buf.filled_mut()) {
+                        io.shutdown();
+                        return Poll::Ready(Err(e));
                     }
-                    let (size, flag) = self.merge_chunks(read_buf.filled_mut())?;
-                    self.interceptors.intercept_output(read_buf.filled_mut())?;
                     read += size;
                     if flag {
                         // Return if we find a 0-sized chunk.
@@ -564,15 +799,76 @@ impl Chunk {
 #[cfg(feature = "ylong_base")]
 #[cfg(test)]
 mod ut_async_http_body {
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicBool, Ordering};
     use std::sync::Arc;
+    use std::task::{Context, Poll};
 
     use ylong_http::body::async_impl;
 
+    use crate::async_impl::conn::StreamData;
     use crate::async_impl::HttpBody;
+    use crate::runtime::{AsyncRead, ReadBuf};
     use crate::util::interceptor::IdleInterceptor;
     use crate::util::normalizer::BodyLength;
     use crate::ErrorKind;
 
+    struct DrainIo {
+        data: &'static [u8],
+        shutdown: Arc<AtomicBool>,
+    }
+
+    impl AsyncRead for DrainIo {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<std::io::Result<()>> {
+            Pin::new(&mut self.data).poll_read(cx, buf)
+        }
+    }
+
+    impl StreamData for DrainIo {
+        fn shutdown(&self) {
+            self.shutdown.store(true, Ordering::SeqCst);
+        }
+
+        fn is_stream_closable(&self) -> bool {
+            true
+        }
+    }
+
+    fn drop_text_body(data: &'static [u8], drain_limit: usize) -> bool {
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let io = DrainIo {
+            data,
+            shutdown: shutdown.clone(),
+        };
+        let mut body = HttpBody::new(
+            Arc::new(IdleInterceptor),
+            BodyLength::Length(data.len() as u64),
+            Box::new(io),
+            b"",
+        )
+        .unwrap();
+        body.set_drain_limit(drain_limit);
+        drop(body);
+        shutdown.load(Ordering::SeqCst)
+    }
+
+    /// UT test cases for dropping a `HttpBody` with a drain limit.
+    ///
+    /// # Brief
+    /// 1. Creates a text `HttpBody` and sets a drain limit.
+    /// 2. Drops the `HttpBody` without reading it.
+    /// 3. Checks if the io is shutdown only when the body exceeds the limit.
+    #[test]
+    fn ut_http_body_drain_on_drop() {
+        assert!(!drop_text_body(b"hello world!", 1024));
+        assert!(drop_text_body(b"hello world!", 4));
+        assert!(drop_text_body(b"hello world!", 0));
+    }
+
     /// UT test cases for `HttpBody::trailer`.
     ///
     /// # Brief
@@ -823,6 +1119,130 @@ mod ut_async_http_body {
         assert_eq!(read, 0);
     }
 
+    /// UT test cases for `HttpBody::content_length`.
+    ///
+    /// # Brief
+    /// 1. Creates `HttpBody`s of each body length.
+    /// 2. Checks that the declared length is kept, an empty body has length
+    ///    `0`, and the length of a chunked or until-close body is unknown.
+    #[test]
+    fn ut_http_body_content_length() {
+        let body = |length| {
+            HttpBody::new(
+                Arc::new(IdleInterceptor),
+                length,
+                Box::new("".as_bytes()),
+                &[],
+            )
+            .unwrap()
+        };
+        assert_eq!(body(BodyLength::Length(11)).content_length(), Some(11));
+        assert_eq!(body(BodyLength::Empty).content_length(), Some(0));
+        assert_eq!(body(BodyLength::UntilClose).content_length(), None);
+        #[cfg(feature = "http1_1")]
+        assert_eq!(body(BodyLength::Chunk).content_length(), None);
+    }
+
+    // Never receives anything, like a stalled `HTTP/2` stream.
+    struct IdleIo {
+        reset: Arc<AtomicBool>,
+        shutdown: Arc<AtomicBool>,
+    }
+
+    impl AsyncRead for IdleIo {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            _buf: &mut ReadBuf<'_>,
+        ) -> Poll<std::io::Result<()>> {
+            Poll::Pending
+        }
+    }
+
+    impl StreamData for IdleIo {
+        fn shutdown(&self) {
+            self.shutdown.store(true, Ordering::SeqCst);
+        }
+
+        fn is_stream_closable(&self) -> bool {
+            false
+        }
+
+        fn reset(self: Box<Self>) {
+            self.reset.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// UT test cases for `HttpBody::set_stream_idle_timeout`.
+    ///
+    /// # Brief
+    /// 1. Creates a text `HttpBody` whose io never receives anything, with a
+    ///    stream idle timeout.
+    /// 2. Reads the body.
+    /// 3. Checks that the read fails with a stream idle error, and that the
+    ///    io is reset instead of shutdown.
+    #[cfg(feature = "ylong_base")]
+    #[test]
+    fn ut_http_body_stream_idle_timeout() {
+        let reset = Arc::new(AtomicBool::new(false));
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let io = IdleIo {
+            reset: reset.clone(),
+            shutdown: shutdown.clone(),
+        };
+        let mut text = HttpBody::new(
+            Arc::new(IdleInterceptor),
+            BodyLength::Length(10),
+            Box::new(io),
+            b"",
+        )
+        .unwrap();
+        text.set_stream_idle_timeout(Some(std::time::Duration::from_millis(10)));
+
+        let mut buf = [0u8; 10];
+        let error =
+            ylong_runtime::block_on(async_impl::Body::data(&mut text, &mut buf)).unwrap_err();
+        assert!(error.is_stream_idle_timeout());
+        drop(text);
+        assert!(reset.load(Ordering::SeqCst));
+        assert!(!shutdown.load(Ordering::SeqCst));
+    }
+
+    /// UT test cases for `HttpBody::set_rate_limit`.
+    ///
+    /// # Brief
+    /// 1. Creates a text `HttpBody` with a download rate limit.
+    /// 2. Reads the whole body.
+    /// 3. Checks that reading takes at least the time the limit requires.
+    #[cfg(feature = "ylong_base")]
+    #[test]
+    fn ut_http_body_rate_limit() {
+        let handle = ylong_runtime::spawn(async move {
+            let mut text = HttpBody::new(
+                Arc::new(IdleInterceptor),
+                BodyLength::Length(300),
+                Box::new(&[0u8; 300][..]),
+                b"",
+            )
+            .unwrap();
+            text.set_rate_limit(Some(1000));
+
+            let start = std::time::Instant::now();
+            let mut buf = [0u8; 100];
+            let mut total = 0;
+            loop {
+                let read = async_impl::Body::data(&mut text, &mut buf).await.unwrap();
+                if read == 0 {
+                    break;
+                }
+                total += read;
+            }
+            assert_eq!(total, 300);
+            assert!(start.elapsed() >= std::time::Duration::from_millis(200));
+        });
+        ylong_runtime::block_on(handle).unwrap();
+    }
+
     /// UT test cases for until_close `HttpBody::new`.
     ///
     /// # Brief
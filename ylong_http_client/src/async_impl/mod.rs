@@ -28,11 +28,18 @@ mod connector;
 
 mod dns;
 mod downloader;
+mod extensions;
+#[cfg(feature = "grpc_web")]
+mod grpc_web;
 mod http_body;
 mod request;
 mod response;
+#[cfg(feature = "sse")]
+mod sse;
 mod timeout;
+mod token;
 mod uploader;
+mod websocket;
 
 #[cfg(feature = "__tls")]
 mod ssl_stream;
@@ -48,12 +55,22 @@ mod quic;
 pub use client::ClientBuilder;
 pub use connector::{Connector, HttpConnector};
 pub use downloader::{DownloadOperator, Downloader, DownloaderBuilder};
+pub use extensions::Extensions;
+#[cfg(feature = "grpc_web")]
+pub use grpc_web::GrpcWeb;
 pub use http_body::HttpBody;
+#[cfg(test)]
+pub(crate) use http_body::noop_waker;
+pub use pool::SharedPool;
 #[cfg(feature = "http3")]
 pub use quic::QuicConn;
-pub use request::{Body, PercentEncoder, Request, RequestBuilder};
-pub use response::Response;
+pub use request::{Body, ClientRequestBuilder, PercentEncoder, Request, RequestBuilder};
+pub use response::{Lines, Response};
+#[cfg(feature = "sse")]
+pub use sse::{Event, EventSource, Events};
+pub use token::{TokenFuture, TokenProvider};
 pub use uploader::{UploadOperator, Uploader, UploaderBuilder};
+pub use websocket::WebSocket;
 pub use ylong_http::body::{MultiPart, Part};
 
 // TODO: Remove these later.
@@ -12,8 +12,9 @@
 // limitations under the License.
 
 use std::mem::take;
+use std::net::SocketAddr;
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 #[cfg(feature = "http3")]
 use ylong_http::request::uri::Authority;
@@ -21,11 +22,12 @@ use ylong_http::request::uri::Authority;
 use ylong_http::request::uri::Scheme;
 use ylong_http::request::uri::Uri;
 
+use crate::async_impl::dns::DnsCache;
 #[cfg(feature = "http3")]
 use crate::async_impl::quic::QuicConn;
-use crate::async_impl::Connector;
 #[cfg(feature = "http3")]
 use crate::async_impl::Response;
+use crate::async_impl::{Connector, HttpConnector};
 use crate::error::HttpClientError;
 use crate::runtime::{AsyncRead, AsyncWrite};
 #[cfg(feature = "http3")]
@@ -34,22 +36,25 @@ use crate::util::alt_svc::{AltService, AltServiceMap};
 use crate::util::config::H2Config;
 #[cfg(feature = "http3")]
 use crate::util::config::H3Config;
-use crate::util::config::{HttpConfig, HttpVersion};
-use crate::util::dispatcher::http1::{WrappedSemPermit, WrappedSemaphore};
-use crate::util::dispatcher::{Conn, ConnDispatcher, Dispatcher, TimeInfoConn};
-use crate::util::pool::{Pool, PoolKey};
+use crate::util::config::{HttpConfig, HttpVersion, SocketConfig};
+use crate::util::dispatcher::{
+    Conn, ConnDispatcher, Dispatcher, TimeInfoConn, WrappedSemPermit, WrappedSemaphore,
+};
+use crate::util::pool::{Pool, PoolKey, Route};
 #[cfg(feature = "http3")]
 use crate::util::request::RequestArc;
 use crate::util::ConnInfo;
 #[cfg(feature = "http2")]
 use crate::ConnDetail;
-use crate::TimeGroup;
+use crate::{Proxy, TimeGroup, Timeout};
 
 pub(crate) struct ConnPool<C, S> {
     pool: Pool<PoolKey, Conns<S>>,
     #[cfg(feature = "http3")]
     alt_svcs: AltServiceMap,
     connector: Arc<C>,
+    // Limits the number of connect attempts in flight.
+    connects: Option<WrappedSemaphore>,
     config: HttpConfig,
 }
 
@@ -60,39 +65,245 @@ impl<C: Connector> ConnPool<C, C::Stream> {
             #[cfg(feature = "http3")]
             alt_svcs: AltServiceMap::new(),
             connector: Arc::new(connector),
+            connects: config.max_concurrent_connects.map(WrappedSemaphore::new),
             config,
         }
     }
 
+    pub(crate) fn connector(&self) -> &C {
+        &self.connector
+    }
+
     pub(crate) async fn connect_to(
         &self,
         uri: &Uri,
+        options: ConnectOptions<'_>,
+        pipelined: bool,
     ) -> Result<TimeInfoConn<C::Stream>, HttpClientError> {
+        let mut config = self.config.clone();
+        if let Some(version) = options.version {
+            config.version = version.clone();
+        }
         let key = PoolKey::new(
             uri.scheme().unwrap().clone(),
             uri.authority().unwrap().clone(),
-        );
+        )
+        .route(route(uri, options.proxy))
+        .unverified(options.unverified)
+        .resolve_to(options.resolve_to)
+        .version(config.version.clone());
 
         #[cfg(feature = "http3")]
         let alt_svc = self.alt_svcs.get_alt_svcs(&key);
+        #[cfg(feature = "http1_1")]
+        let depth = self.config.http1_config.pipelining();
         self.pool
-            .get(key, Conns::new, self.config.http1_config.max_conn_num())
+            .get(
+                key,
+                |num| {
+                    Conns::new(
+                        num,
+                        #[cfg(feature = "http1_1")]
+                        depth,
+                    )
+                },
+                self.config.http1_config.max_conn_num(),
+            )
             .conn(
-                self.config.clone(),
+                config,
                 self.connector.clone(),
                 uri,
+                options,
+                self.connects.as_ref(),
+                pipelined,
                 #[cfg(feature = "http3")]
                 alt_svc,
             )
             .await
     }
 
+    // Opens a new connection to the server of `uri` that bypasses the pool,
+    // which is closed when dropped.
+    pub(crate) async fn connect_new(
+        &self,
+        uri: &Uri,
+        options: ConnectOptions<'_>,
+    ) -> Result<C::Stream, HttpClientError> {
+        let version = options.version.unwrap_or(&self.config.version).clone();
+        connect(
+            &*self.connector,
+            uri,
+            version,
+            options,
+            self.connects.as_ref(),
+        )
+        .await
+    }
+
     #[cfg(feature = "http3")]
     pub(crate) fn set_alt_svcs(&self, request: RequestArc, response: &Response) {
         self.alt_svcs.set_alt_svcs(request, response);
     }
 }
 
+/// A handle of a connection pool which is shared by the `Client`s built with
+/// it, so that a connection is reused by all of them regardless of which
+/// `Client` opened it. See `ClientBuilder::with_shared_pool`.
+///
+/// The pool, together with the settings used to connect, is created by the
+/// first `Client` built with the handle, and later `Client`s reuse it. Only
+/// `Client`s which connect in the same way can share the pool, see
+/// `ClientBuilder::with_shared_pool`.
+/// Clones of a `SharedPool` refer to the same pool.
+///
+/// # Examples
+///
+/// ```
+/// use ylong_http_client::async_impl::{ClientBuilder, SharedPool};
+///
+/// let pool = SharedPool::new();
+/// let first = ClientBuilder::new().with_shared_pool(pool.clone()).build();
+/// let second = ClientBuilder::new().with_shared_pool(pool).build();
+/// ```
+#[derive(Clone, Default)]
+pub struct SharedPool {
+    inner: Arc<Mutex<Option<SharedConnPool>>>,
+}
+
+type HttpConnPool = ConnPool<HttpConnector, <HttpConnector as Connector>::Stream>;
+
+struct SharedConnPool {
+    settings: PoolSettings,
+    pool: Arc<HttpConnPool>,
+    dns_cache: Option<Arc<DnsCache>>,
+}
+
+impl SharedPool {
+    /// Creates a new `SharedPool`, whose pool is created by the first
+    /// `Client` built with it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::async_impl::SharedPool;
+    ///
+    /// let pool = SharedPool::new();
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Gets the shared pool and dns cache, which are the ones of the first
+    // client. Fails if `settings` differ from those of the first client, or
+    // if either client has a custom connector, which can't be compared.
+    pub(crate) fn get_or_insert(
+        &self,
+        settings: PoolSettings,
+        pool: HttpConnPool,
+        dns_cache: Option<Arc<DnsCache>>,
+    ) -> Result<(Arc<HttpConnPool>, Option<Arc<DnsCache>>), HttpClientError> {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(shared) = inner.as_ref() {
+            if shared.settings != settings || settings.custom_connector {
+                return err_from_msg!(Build, "Client config is incompatible with the shared pool");
+            }
+            return Ok((shared.pool.clone(), shared.dns_cache.clone()));
+        }
+        let pool = Arc::new(pool);
+        *inner = Some(SharedConnPool {
+            settings,
+            pool: pool.clone(),
+            dns_cache: dns_cache.clone(),
+        });
+        Ok((pool, dns_cache))
+    }
+}
+
+// The settings of a client which decide how its connections are opened and
+// pooled, the clients sharing a pool must have the same ones.
+#[derive(Clone, PartialEq)]
+pub(crate) struct PoolSettings {
+    pub(crate) version: HttpVersion,
+    #[cfg(feature = "http1_1")]
+    pub(crate) max_conn_num: usize,
+    #[cfg(feature = "http1_1")]
+    pub(crate) pipelining: usize,
+    pub(crate) max_concurrent_connects: Option<usize>,
+    pub(crate) connect_timeout: Timeout,
+    pub(crate) dns_timeout: Option<Duration>,
+    pub(crate) dns_cache_ttl: Option<Duration>,
+    pub(crate) socket: SocketConfig,
+    #[cfg(feature = "__tls")]
+    pub(crate) tls_handshake_timeout: Timeout,
+    // Whether the TLS, proxy, DNS resolver or `HTTP/2` options are set.
+    pub(crate) custom_connector: bool,
+}
+
+// How a request connects to the server, which decides the connections it can
+// share with other requests.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct ConnectOptions<'a> {
+    // The proxy of the request, which overrides the proxies of the client.
+    pub(crate) proxy: Option<&'a Option<Proxy>>,
+    // Whether the certs and the hostname of the server are not verified.
+    pub(crate) unverified: bool,
+    // The address connected to instead of resolving the host of the server.
+    pub(crate) resolve_to: Option<SocketAddr>,
+    // The version of the request, which overrides the version of the client.
+    pub(crate) version: Option<&'a HttpVersion>,
+}
+
+// Gets the route to the server of `uri`, a request with its own proxy doesn't
+// use the proxies of the client.
+fn route(uri: &Uri, proxy: Option<&Option<Proxy>>) -> Route {
+    let proxy = match proxy {
+        Some(proxy) => proxy.as_ref().map(Proxy::inner_ref),
+        None => return Route::Default,
+    };
+    match proxy.filter(|proxy| proxy.is_intercepted(uri)) {
+        Some(proxy) => {
+            let info = proxy.intercept.proxy_info();
+            Route::Proxy(info.scheme().clone(), info.authority().clone())
+        }
+        None => Route::Direct,
+    }
+}
+
+// Connects through the proxy of the request if it has one, without verifying
+// the server if the request asks so, to the address the request is pinned to
+// if any. If the number of connect attempts is limited, waits for a free slot
+// first.
+async fn connect<C: Connector>(
+    connector: &C,
+    url: &Uri,
+    http_version: HttpVersion,
+    options: ConnectOptions<'_>,
+    limit: Option<&WrappedSemaphore>,
+) -> Result<C::Stream, HttpClientError> {
+    let _permit = match limit {
+        Some(limit) => Some(limit.acquire().await),
+        None => None,
+    };
+    if let Some(addr) = options.resolve_to {
+        let proxy = options.proxy.map(Option::as_ref);
+        return connector
+            .connect_pinned(url, http_version, proxy, options.unverified, addr)
+            .await;
+    }
+    if options.unverified {
+        let proxy = options.proxy.map(Option::as_ref);
+        return connector.connect_unverified(url, http_version, proxy).await;
+    }
+    match options.proxy {
+        Some(proxy) => {
+            connector
+                .connect_with_proxy(url, http_version, proxy.as_ref())
+                .await
+        }
+        None => connector.connect(url, http_version).await,
+    }
+}
+
 pub(crate) enum H1ConnOption<T> {
     Some(T),
     None(WrappedSemPermit),
@@ -101,6 +312,9 @@ pub(crate) enum H1ConnOption<T> {
 pub(crate) struct Conns<S> {
     usable: WrappedSemaphore,
     list: Arc<Mutex<Vec<ConnDispatcher<S>>>>,
+    // The maximum number of requests in flight on a http1 connection.
+    #[cfg(feature = "http1_1")]
+    pipelining: usize,
     #[cfg(feature = "http2")]
     h2_conn: Arc<crate::runtime::AsyncMutex<Vec<ConnDispatcher<S>>>>,
     #[cfg(feature = "http3")]
@@ -108,12 +322,15 @@ pub(crate) struct Conns<S> {
 }
 
 impl<S> Conns<S> {
-    fn new(max_conn_num: usize) -> Self {
+    fn new(max_conn_num: usize, #[cfg(feature = "http1_1")] pipelining: usize) -> Self {
         Self {
             usable: WrappedSemaphore::new(max_conn_num),
 
             list: Arc::new(Mutex::new(Vec::new())),
 
+            #[cfg(feature = "http1_1")]
+            pipelining,
+
             #[cfg(feature = "http2")]
             h2_conn: Arc::new(crate::runtime::AsyncMutex::new(Vec::with_capacity(1))),
 
@@ -130,6 +347,8 @@ impl<S> Clone for Conns<S> {
         Self {
             usable: self.usable.clone(),
             list: self.list.clone(),
+            #[cfg(feature = "http1_1")]
+            pipelining: self.pipelining,
 
             #[cfg(feature = "http2")]
             h2_conn: self.h2_conn.clone(),
@@ -146,6 +365,9 @@ impl<S: AsyncRead + AsyncWrite + ConnInfo + Unpin + Send + Sync + 'static> Conns
         config: HttpConfig,
         connector: Arc<C>,
         url: &Uri,
+        options: ConnectOptions<'_>,
+        limit: Option<&WrappedSemaphore>,
+        pipelined: bool,
         #[cfg(feature = "http3")] alt_svc: Option<Vec<AltService>>,
     ) -> Result<TimeInfoConn<S>, HttpClientError>
     where
@@ -154,26 +376,45 @@ impl<S: AsyncRead + AsyncWrite + ConnInfo + Unpin + Send + Sync + 'static> Conns
         let conn_start = Instant::now();
         let mut conn = match config.version {
             #[cfg(feature = "http3")]
-            HttpVersion::Http3 => self.conn_h3(connector, url, config.http3_config).await,
+            HttpVersion::Http3 => {
+                self.conn_h3(connector, url, options, limit, config.http3_config)
+                    .await
+            }
             #[cfg(feature = "http2")]
-            HttpVersion::Http2 => self.conn_h2(connector, url, config.http2_config).await,
+            HttpVersion::Http2 => {
+                self.conn_h2(connector, url, options, limit, config.http2_config)
+                    .await
+            }
             #[cfg(feature = "http1_1")]
-            HttpVersion::Http1 => self.conn_h1(connector, url).await,
+            HttpVersion::Http1 => {
+                self.conn_h1(connector, url, options, limit, pipelined)
+                    .await
+            }
             #[cfg(all(feature = "http1_1", not(feature = "http2")))]
-            HttpVersion::Negotiate => self.conn_h1(connector, url).await,
+            HttpVersion::Negotiate => {
+                self.conn_h1(connector, url, options, limit, pipelined)
+                    .await
+            }
             #[cfg(all(feature = "http1_1", feature = "http2"))]
             HttpVersion::Negotiate => {
                 #[cfg(feature = "http3")]
                 if let Some(mut conn) = self
-                    .conn_alt_svc(&connector, url, alt_svc, config.http3_config)
+                    .conn_alt_svc(&connector, url, limit, alt_svc, config.http3_config)
                     .await
                 {
                     conn.time_group_mut().set_connect_start(conn_start);
                     conn.time_group_mut().set_connect_end(Instant::now());
                     return Ok(conn);
                 }
-                self.conn_negotiate(connector, url, config.http2_config)
-                    .await
+                self.conn_negotiate(
+                    connector,
+                    url,
+                    options,
+                    limit,
+                    pipelined,
+                    config.http2_config,
+                )
+                .await
             }
         }?;
         conn.time_group_mut().set_connect_start(conn_start);
@@ -181,22 +422,29 @@ impl<S: AsyncRead + AsyncWrite + ConnInfo + Unpin + Send + Sync + 'static> Conns
         Ok(conn)
     }
 
+    #[cfg(feature = "http1_1")]
     async fn conn_h1<C>(
         &self,
         connector: Arc<C>,
         url: &Uri,
+        options: ConnectOptions<'_>,
+        limit: Option<&WrappedSemaphore>,
+        pipelined: bool,
     ) -> Result<TimeInfoConn<S>, HttpClientError>
     where
         C: Connector<Stream = S>,
     {
+        if let Some(conn) = self.pipeline_h1_conn(pipelined) {
+            return Ok(TimeInfoConn::new(conn, TimeGroup::default()));
+        }
         let semaphore = self.usable.acquire().await;
         match self.exist_h1_conn(semaphore) {
             H1ConnOption::Some(conn) => Ok(TimeInfoConn::new(conn, TimeGroup::default())),
             H1ConnOption::None(permit) => {
-                let stream = connector.connect(url, HttpVersion::Http1).await?;
+                let stream = connect(&*connector, url, HttpVersion::Http1, options, limit).await?;
                 let time_group = take(stream.conn_data().time_group_mut());
 
-                let dispatcher = ConnDispatcher::http1(stream);
+                let dispatcher = ConnDispatcher::http1_with_pipeline(stream, self.pipelining);
                 let conn = self.dispatch_h1_conn(dispatcher, permit);
                 Ok(TimeInfoConn::new(conn, time_group))
             }
@@ -208,6 +456,8 @@ impl<S: AsyncRead + AsyncWrite + ConnInfo + Unpin + Send + Sync + 'static> Conns
         &self,
         connector: Arc<C>,
         url: &Uri,
+        options: ConnectOptions<'_>,
+        limit: Option<&WrappedSemaphore>,
         config: H2Config,
     ) -> Result<TimeInfoConn<S>, HttpClientError>
     where
@@ -221,7 +471,7 @@ impl<S: AsyncRead + AsyncWrite + ConnInfo + Unpin + Send + Sync + 'static> Conns
         if let Some(conn) = Self::exist_h2_conn(&mut lock) {
             return Ok(TimeInfoConn::new(conn, TimeGroup::default()));
         }
-        let stream = connector.connect(url, HttpVersion::Http2).await?;
+        let stream = connect(&*connector, url, HttpVersion::Http2, options, limit).await?;
         let mut data = stream.conn_data();
         let tls = if let Some(scheme) = url.scheme() {
             *scheme == Scheme::HTTPS
@@ -245,6 +495,8 @@ impl<S: AsyncRead + AsyncWrite + ConnInfo + Unpin + Send + Sync + 'static> Conns
         &self,
         connector: Arc<C>,
         url: &Uri,
+        options: ConnectOptions<'_>,
+        limit: Option<&WrappedSemaphore>,
         config: H3Config,
     ) -> Result<TimeInfoConn<S>, HttpClientError>
     where
@@ -255,7 +507,7 @@ impl<S: AsyncRead + AsyncWrite + ConnInfo + Unpin + Send + Sync + 'static> Conns
         if let Some(conn) = Self::exist_h3_conn(&mut lock) {
             return Ok(TimeInfoConn::new(conn, TimeGroup::default()));
         }
-        let mut stream = connector.connect(url, HttpVersion::Http3).await?;
+        let mut stream = connect(&*connector, url, HttpVersion::Http3, options, limit).await?;
 
         let quic_conn = stream.quic_conn().ok_or(HttpClientError::from_str(
             crate::ErrorKind::Connect,
@@ -275,6 +527,9 @@ impl<S: AsyncRead + AsyncWrite + ConnInfo + Unpin + Send + Sync + 'static> Conns
         &self,
         connector: Arc<C>,
         url: &Uri,
+        options: ConnectOptions<'_>,
+        limit: Option<&WrappedSemaphore>,
+        pipelined: bool,
         h2_config: H2Config,
     ) -> Result<TimeInfoConn<S>, HttpClientError>
     where
@@ -286,6 +541,9 @@ impl<S: AsyncRead + AsyncWrite + ConnInfo + Unpin + Send + Sync + 'static> Conns
                 if let Some(conn) = Self::exist_h2_conn(&mut lock) {
                     return Ok(TimeInfoConn::new(conn, TimeGroup::default()));
                 }
+                if let Some(conn) = self.pipeline_h1_conn(pipelined) {
+                    return Ok(TimeInfoConn::new(conn, TimeGroup::default()));
+                }
                 let permit = self.usable.acquire().await;
                 let permit = match self.exist_h1_conn(permit) {
                     H1ConnOption::Some(conn) => {
@@ -293,14 +551,15 @@ impl<S: AsyncRead + AsyncWrite + ConnInfo + Unpin + Send + Sync + 'static> Conns
                     }
                     H1ConnOption::None(permit) => permit,
                 };
-                let stream = connector.connect(url, HttpVersion::Negotiate).await?;
+                let stream =
+                    connect(&*connector, url, HttpVersion::Negotiate, options, limit).await?;
                 let mut data = stream.conn_data();
                 let time_group = take(data.time_group_mut());
 
                 let protocol = if let Some(bytes) = data.negotiate().alpn() {
                     bytes
                 } else {
-                    let dispatcher = ConnDispatcher::http1(stream);
+                    let dispatcher = ConnDispatcher::http1_with_pipeline(stream, self.pipelining);
                     return Ok(TimeInfoConn::new(
                         self.dispatch_h1_conn(dispatcher, permit),
                         time_group,
@@ -308,7 +567,7 @@ impl<S: AsyncRead + AsyncWrite + ConnInfo + Unpin + Send + Sync + 'static> Conns
                 };
 
                 if protocol == b"http/1.1" {
-                    let dispatcher = ConnDispatcher::http1(stream);
+                    let dispatcher = ConnDispatcher::http1_with_pipeline(stream, self.pipelining);
                     Ok(TimeInfoConn::new(
                         self.dispatch_h1_conn(dispatcher, permit),
                         time_group,
@@ -322,7 +581,10 @@ impl<S: AsyncRead + AsyncWrite + ConnInfo + Unpin + Send + Sync + 'static> Conns
                     err_from_msg!(Connect, "Alpn negotiate a wrong protocol version.")
                 }
             }
-            Scheme::HTTP => self.conn_h1(connector, url).await,
+            Scheme::HTTP => {
+                self.conn_h1(connector, url, options, limit, pipelined)
+                    .await
+            }
         }
     }
 
@@ -331,6 +593,7 @@ impl<S: AsyncRead + AsyncWrite + ConnInfo + Unpin + Send + Sync + 'static> Conns
         &self,
         connector: &Arc<C>,
         url: &Uri,
+        limit: Option<&WrappedSemaphore>,
         alt_svcs: Option<Vec<AltService>>,
         h3_config: H3Config,
     ) -> Option<TimeInfoConn<S>>
@@ -359,7 +622,15 @@ impl<S: AsyncRead + AsyncWrite + ConnInfo + Unpin + Send + Sync + 'static> Conns
                 let path = url.path().cloned();
                 let query = url.query().cloned();
                 let alt_url = Uri::from_raw_parts(Some(scheme), Some(authority), path, query);
-                let mut stream = connector.connect(&alt_url, HttpVersion::Http3).await.ok()?;
+                let mut stream = connect(
+                    &**connector,
+                    &alt_url,
+                    HttpVersion::Http3,
+                    Default::default(),
+                    limit,
+                )
+                .await
+                .ok()?;
                 let quic_conn = stream.quic_conn().unwrap();
                 let mut data = stream.conn_data();
                 let time_group = take(data.time_group_mut());
@@ -422,6 +693,19 @@ impl<S: AsyncRead + AsyncWrite + ConnInfo + Unpin + Send + Sync + 'static> Conns
         conn
     }
 
+    // Pipelines the request on a busy connection if no connection is idle.
+    #[cfg(feature = "http1_1")]
+    fn pipeline_h1_conn(&self, pipelined: bool) -> Option<Conn<S>> {
+        if !pipelined || self.pipelining <= 1 {
+            return None;
+        }
+        let list = self.list.lock().unwrap();
+        if list.iter().any(ConnDispatcher::is_idle) {
+            return None;
+        }
+        list.iter().find_map(ConnDispatcher::pipeline)
+    }
+
     fn exist_h1_conn(&self, permit: WrappedSemPermit) -> H1ConnOption<Conn<S>> {
         let mut list = self.list.lock().unwrap();
         let mut conn = None;
@@ -446,23 +730,18 @@ impl<S: AsyncRead + AsyncWrite + ConnInfo + Unpin + Send + Sync + 'static> Conns
         }
     }
 
+    // Opens a stream on the latest h2 connection which is not draining.
+    // Closed connections are discarded, while draining ones are kept until
+    // they are closed, so that their streams in flight can finish.
     #[cfg(feature = "http2")]
     fn exist_h2_conn(
         lock: &mut crate::runtime::MutexGuard<Vec<ConnDispatcher<S>>>,
     ) -> Option<Conn<S>> {
-        if let Some(dispatcher) = lock.pop() {
-            if dispatcher.is_shutdown() {
-                return None;
-            }
-            if !dispatcher.is_goaway() {
-                if let Some(conn) = dispatcher.dispatch() {
-                    lock.push(dispatcher);
-                    return Some(conn);
-                }
-            }
-            lock.push(dispatcher);
-        }
-        None
+        lock.retain(|dispatcher| !dispatcher.is_shutdown());
+        lock.iter()
+            .rev()
+            .filter(|dispatcher| !dispatcher.is_goaway())
+            .find_map(ConnDispatcher::dispatch)
     }
 
     #[cfg(feature = "http3")]
@@ -485,3 +764,465 @@ impl<S: AsyncRead + AsyncWrite + ConnInfo + Unpin + Send + Sync + 'static> Conns
         None
     }
 }
+
+#[cfg(all(test, feature = "http1_1"))]
+mod ut_pool {
+    use std::future::Future;
+    use std::io;
+    use std::net::SocketAddr;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll};
+
+    use ylong_http::request::uri::Uri;
+
+    use crate::async_impl::http_body::noop_waker;
+    use crate::async_impl::pool::{route, ConnPool, ConnectOptions};
+    use crate::async_impl::Connector;
+    use crate::runtime::{AsyncRead, AsyncWrite, ReadBuf};
+    use crate::util::config::{HttpConfig, HttpVersion};
+    use crate::util::interceptor::ConnProtocol;
+    use crate::util::pool::Route;
+    use crate::util::ConnInfo;
+    use crate::{ConnData, ConnDetail, HttpClientError, Proxy};
+
+    #[derive(Default)]
+    struct Counter {
+        opened: AtomicUsize,
+        closed: AtomicUsize,
+        pinned: Mutex<Vec<SocketAddr>>,
+    }
+
+    struct MockIo {
+        counter: Arc<Counter>,
+    }
+
+    impl MockIo {
+        fn open(counter: Arc<Counter>) -> Self {
+            counter.opened.fetch_add(1, Ordering::SeqCst);
+            Self { counter }
+        }
+    }
+
+    impl Drop for MockIo {
+        fn drop(&mut self) {
+            self.counter.closed.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    impl ConnInfo for MockIo {
+        fn is_proxy(&self) -> bool {
+            false
+        }
+
+        fn conn_data(&self) -> ConnData {
+            let detail = ConnDetail {
+                protocol: ConnProtocol::Tcp,
+                local: "127.0.0.1:0".parse().unwrap(),
+                peer: "127.0.0.1:0".parse().unwrap(),
+                addr: String::new(),
+                proxy: None,
+            };
+            ConnData::builder().build(detail)
+        }
+
+        #[cfg(feature = "http3")]
+        fn quic_conn(&mut self) -> Option<crate::async_impl::quic::QuicConn> {
+            None
+        }
+    }
+
+    impl AsyncRead for MockIo {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            _buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            Poll::Pending
+        }
+    }
+
+    impl AsyncWrite for MockIo {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            _buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            Poll::Pending
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    // Opens a socket and then never finishes the handshake on it.
+    struct StuckConnector {
+        counter: Arc<Counter>,
+    }
+
+    impl Connector for StuckConnector {
+        type Stream = MockIo;
+        type Future =
+            Pin<Box<dyn Future<Output = Result<Self::Stream, HttpClientError>> + Sync + Send>>;
+
+        fn connect(&self, _uri: &Uri, _http_version: HttpVersion) -> Self::Future {
+            let counter = self.counter.clone();
+            Box::pin(async move {
+                let io = MockIo::open(counter);
+                std::future::pending::<()>().await;
+                Ok(io)
+            })
+        }
+
+        fn connect_pinned(
+            &self,
+            uri: &Uri,
+            http_version: HttpVersion,
+            _proxy: Option<Option<&Proxy>>,
+            _unverified: bool,
+            addr: SocketAddr,
+        ) -> Self::Future {
+            self.counter.pinned.lock().unwrap().push(addr);
+            self.connect(uri, http_version)
+        }
+    }
+
+    /// UT test cases for dropping `ConnPool::connect_to` mid-connect.
+    ///
+    /// # Brief
+    /// 1. Creates a `ConnPool` with a connector that never completes.
+    /// 2. Polls `ConnPool::connect_to` once and drops the future.
+    /// 3. Checks that the opened socket is closed and the pool is usable.
+    #[test]
+    fn ut_conn_pool_connect_cancel() {
+        let counter = Arc::new(Counter::default());
+        let mut config = HttpConfig {
+            version: HttpVersion::Http1,
+            ..Default::default()
+        };
+        config.http1_config.set_max_conn_num(1);
+        let pool = ConnPool::new(
+            config,
+            StuckConnector {
+                counter: counter.clone(),
+            },
+        );
+        let uri = Uri::from_bytes(b"http://127.0.0.1:80").unwrap();
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        for round in 1..=2 {
+            let mut fut = Box::pin(pool.connect_to(&uri, Default::default(), false));
+            assert!(fut.as_mut().poll(&mut cx).is_pending());
+            assert_eq!(counter.opened.load(Ordering::SeqCst), round);
+            assert_eq!(counter.closed.load(Ordering::SeqCst), round - 1);
+            drop(fut);
+            assert_eq!(counter.closed.load(Ordering::SeqCst), round);
+        }
+    }
+
+    /// UT test cases for limiting the connect attempts of `ConnPool`.
+    ///
+    /// # Brief
+    /// 1. Creates a `ConnPool` which allows one connect attempt at a time.
+    /// 2. Connects to two servers with a connector that never completes.
+    /// 3. Checks that the second connect waits until the first is dropped.
+    #[test]
+    fn ut_conn_pool_max_concurrent_connects() {
+        let counter = Arc::new(Counter::default());
+        let config = HttpConfig {
+            version: HttpVersion::Http1,
+            max_concurrent_connects: Some(1),
+            ..Default::default()
+        };
+        let pool = ConnPool::new(
+            config,
+            StuckConnector {
+                counter: counter.clone(),
+            },
+        );
+        let uri1 = Uri::from_bytes(b"http://127.0.0.1:80").unwrap();
+        let uri2 = Uri::from_bytes(b"http://127.0.0.2:80").unwrap();
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let mut fut1 = Box::pin(pool.connect_to(&uri1, Default::default(), false));
+        assert!(fut1.as_mut().poll(&mut cx).is_pending());
+        let mut fut2 = Box::pin(pool.connect_to(&uri2, Default::default(), false));
+        assert!(fut2.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(counter.opened.load(Ordering::SeqCst), 1);
+
+        drop(fut1);
+        assert!(fut2.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(counter.opened.load(Ordering::SeqCst), 2);
+    }
+
+    /// UT test cases for `ConnPool::connect_to` with different versions.
+    ///
+    /// # Brief
+    /// 1. Creates a `ConnPool` which allows one `HTTP/1.1` connection.
+    /// 2. Connects to a server by `HTTP/1.1` twice and by `HTTP/2` once.
+    /// 3. Checks that the `HTTP/2` connect doesn't wait for the `HTTP/1.1`
+    ///    connections.
+    #[cfg(feature = "http2")]
+    #[test]
+    fn ut_conn_pool_connect_version() {
+        let counter = Arc::new(Counter::default());
+        let mut config = HttpConfig {
+            version: HttpVersion::Http1,
+            ..Default::default()
+        };
+        config.http1_config.set_max_conn_num(1);
+        let pool = ConnPool::new(
+            config,
+            StuckConnector {
+                counter: counter.clone(),
+            },
+        );
+        let uri = Uri::from_bytes(b"http://127.0.0.1:80").unwrap();
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let mut fut1 = Box::pin(pool.connect_to(&uri, Default::default(), false));
+        assert!(fut1.as_mut().poll(&mut cx).is_pending());
+        let options = ConnectOptions {
+            version: Some(&HttpVersion::Http1),
+            ..Default::default()
+        };
+        let mut fut2 = Box::pin(pool.connect_to(&uri, options, false));
+        assert!(fut2.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(counter.opened.load(Ordering::SeqCst), 1);
+
+        let options = ConnectOptions {
+            version: Some(&HttpVersion::Http2),
+            ..Default::default()
+        };
+        let mut fut3 = Box::pin(pool.connect_to(&uri, options, false));
+        assert!(fut3.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(counter.opened.load(Ordering::SeqCst), 2);
+    }
+
+    /// UT test cases for `ConnPool::connect_to` with a pinned address.
+    ///
+    /// # Brief
+    /// 1. Creates a `ConnPool` which allows one `HTTP/1.1` connection.
+    /// 2. Connects to a server as usual, then pinned to an address twice.
+    /// 3. Checks that the pinned connect doesn't wait for the usual one and
+    ///    is made by `Connector::connect_pinned` with the address.
+    /// 4. Checks that the pinned connects share their connections.
+    #[test]
+    fn ut_conn_pool_connect_resolve_to() {
+        let counter = Arc::new(Counter::default());
+        let mut config = HttpConfig {
+            version: HttpVersion::Http1,
+            ..Default::default()
+        };
+        config.http1_config.set_max_conn_num(1);
+        let pool = ConnPool::new(
+            config,
+            StuckConnector {
+                counter: counter.clone(),
+            },
+        );
+        let uri = Uri::from_bytes(b"http://www.example.com:80").unwrap();
+        let addr: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let mut fut1 = Box::pin(pool.connect_to(&uri, Default::default(), false));
+        assert!(fut1.as_mut().poll(&mut cx).is_pending());
+        let options = ConnectOptions {
+            resolve_to: Some(addr),
+            ..Default::default()
+        };
+        let mut fut2 = Box::pin(pool.connect_to(&uri, options, false));
+        assert!(fut2.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(counter.opened.load(Ordering::SeqCst), 2);
+        assert_eq!(*counter.pinned.lock().unwrap(), vec![addr]);
+
+        let mut fut3 = Box::pin(pool.connect_to(&uri, options, false));
+        assert!(fut3.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(counter.opened.load(Ordering::SeqCst), 2);
+    }
+
+    // Opens a socket right away.
+    struct ReadyConnector {
+        counter: Arc<Counter>,
+    }
+
+    impl Connector for ReadyConnector {
+        type Stream = MockIo;
+        type Future =
+            Pin<Box<dyn Future<Output = Result<Self::Stream, HttpClientError>> + Sync + Send>>;
+
+        fn connect(&self, _uri: &Uri, _http_version: HttpVersion) -> Self::Future {
+            let counter = self.counter.clone();
+            Box::pin(async move { Ok(MockIo::open(counter)) })
+        }
+    }
+
+    /// UT test cases for `ConnPool::connect_new`.
+    ///
+    /// # Brief
+    /// 1. Creates a `ConnPool` which allows one `HTTP/1.1` connection.
+    /// 2. Opens two connections by `ConnPool::connect_new` and drops them.
+    /// 3. Checks that the connections bypass the pool and are closed.
+    #[test]
+    fn ut_conn_pool_connect_new() {
+        let counter = Arc::new(Counter::default());
+        let mut config = HttpConfig {
+            version: HttpVersion::Http1,
+            ..Default::default()
+        };
+        config.http1_config.set_max_conn_num(1);
+        let pool = ConnPool::new(
+            config,
+            ReadyConnector {
+                counter: counter.clone(),
+            },
+        );
+        let uri = Uri::from_bytes(b"http://127.0.0.1:80").unwrap();
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let mut streams = Vec::new();
+        for _ in 0..2 {
+            match Box::pin(pool.connect_new(&uri, Default::default()))
+                .as_mut()
+                .poll(&mut cx)
+            {
+                Poll::Ready(Ok(stream)) => streams.push(stream),
+                _ => panic!("The connection is not opened"),
+            }
+        }
+        assert_eq!(counter.opened.load(Ordering::SeqCst), 2);
+        drop(streams);
+        assert_eq!(counter.closed.load(Ordering::SeqCst), 2);
+    }
+
+    // Opens a socket on the second poll, so that other connect attempts can
+    // run in the meantime.
+    #[cfg(all(feature = "http2", feature = "ylong_base"))]
+    struct SlowConnector {
+        counter: Arc<Counter>,
+    }
+
+    #[cfg(all(feature = "http2", feature = "ylong_base"))]
+    impl Connector for SlowConnector {
+        type Stream = MockIo;
+        type Future =
+            Pin<Box<dyn Future<Output = Result<Self::Stream, HttpClientError>> + Sync + Send>>;
+
+        fn connect(&self, _uri: &Uri, _http_version: HttpVersion) -> Self::Future {
+            let counter = self.counter.clone();
+            let mut polled = false;
+            Box::pin(async move {
+                std::future::poll_fn(|cx| {
+                    if polled {
+                        return Poll::Ready(());
+                    }
+                    polled = true;
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                })
+                .await;
+                Ok(MockIo::open(counter))
+            })
+        }
+    }
+
+    /// UT test cases for sharing an `HTTP/2` connection of `ConnPool`.
+    ///
+    /// # Brief
+    /// 1. Creates a `ConnPool` which uses `HTTP/2`.
+    /// 2. Connects to a server three times concurrently, and once again after
+    ///    that.
+    /// 3. Checks that only one connection is established.
+    #[cfg(all(feature = "http2", feature = "ylong_base"))]
+    #[test]
+    fn ut_conn_pool_share_h2_conn() {
+        use crate::util::dispatcher::Conn;
+
+        let counter = Arc::new(Counter::default());
+        let config = HttpConfig {
+            version: HttpVersion::Http2,
+            ..Default::default()
+        };
+        let pool = ConnPool::new(
+            config,
+            SlowConnector {
+                counter: counter.clone(),
+            },
+        );
+        let uri = Uri::from_bytes(b"http://127.0.0.1:80").unwrap();
+
+        ylong_runtime::block_on(async {
+            let mut futs = Vec::new();
+            for _ in 0..3 {
+                futs.push(Some(Box::pin(pool.connect_to(
+                    &uri,
+                    Default::default(),
+                    false,
+                ))));
+            }
+            let mut conns = Vec::new();
+            std::future::poll_fn(|cx| {
+                for slot in futs.iter_mut() {
+                    if let Some(fut) = slot {
+                        if let Poll::Ready(conn) = fut.as_mut().poll(cx) {
+                            conns.push(conn.unwrap().connection());
+                            *slot = None;
+                        }
+                    }
+                }
+                if conns.len() == 3 {
+                    return Poll::Ready(());
+                }
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            })
+            .await;
+            assert!(conns.iter().all(|conn| matches!(conn, Conn::Http2(_))));
+
+            let conn = pool.connect_to(&uri, Default::default(), false).await;
+            assert!(matches!(conn.unwrap().connection(), Conn::Http2(_)));
+        });
+        assert_eq!(counter.opened.load(Ordering::SeqCst), 1);
+    }
+
+    /// UT test cases for `route`.
+    ///
+    /// # Brief
+    /// 1. Gets routes of a uri with different proxies of the request.
+    /// 2. Checks if the results are correct.
+    #[test]
+    fn ut_conn_pool_route() {
+        let uri = Uri::from_bytes(b"http://127.0.0.1:80").unwrap();
+        assert_eq!(route(&uri, None), Route::Default);
+        assert_eq!(route(&uri, Some(&None)), Route::Direct);
+
+        let proxy = Proxy::http("http://proxy.example.com:8080")
+            .build()
+            .unwrap();
+        let proxy_uri = Uri::from_bytes(b"http://proxy.example.com:8080").unwrap();
+        assert_eq!(
+            route(&uri, Some(&Some(proxy))),
+            Route::Proxy(
+                proxy_uri.scheme().unwrap().clone(),
+                proxy_uri.authority().unwrap().clone()
+            )
+        );
+
+        // The proxy doesn't intercept the uri.
+        let proxy = Proxy::https("http://proxy.example.com:8080")
+            .build()
+            .unwrap();
+        assert_eq!(route(&uri, Some(&Some(proxy))), Route::Direct);
+    }
+}
@@ -13,22 +13,33 @@
 
 //! `ylong_http_client` `Request` adapter.
 
+use core::future::Future;
 use core::ops::{Deref, DerefMut};
 use core::pin::Pin;
 use core::task::{Context, Poll};
+use core::time::Duration;
 use std::io::Cursor;
+use std::net::SocketAddr;
 use std::sync::Arc;
 
 use ylong_http::body::async_impl::ReusableReader;
 use ylong_http::body::MultiPartBase;
+#[cfg(feature = "http2")]
+use ylong_http::h2::Priority;
 use ylong_http::request::uri::PercentEncoder as PerEncoder;
 use ylong_http::request::{Request as Req, RequestBuilder as ReqBuilder};
+use ylong_http::version::Version;
 
+use crate::async_impl::client::Client;
+use crate::async_impl::{Connector, Extensions, Response};
 use crate::error::{ErrorKind, HttpClientError};
 use crate::runtime::{AsyncRead, ReadBuf};
-use crate::util::interceptor::Interceptors;
+use crate::util::body_digest::DigestAlgorithm;
+use crate::util::compress::{Compressor, Encoding};
+use crate::util::interceptor::{Interceptor, Interceptors};
 use crate::util::monitor::TimeGroup;
 use crate::util::request::RequestArc;
+use crate::Proxy;
 
 /// A structure that represents an HTTP `Request`. It contains a request line,
 /// some HTTP headers and a HTTP body.
@@ -48,6 +59,26 @@ use crate::util::request::RequestArc;
 pub struct Request {
     pub(crate) inner: Req<Body>,
     pub(crate) time_group: TimeGroup,
+    pub(crate) interceptor: Option<Arc<Interceptors>>,
+    // Overrides the proxies of the client if set, `Some(None)` means a direct
+    // connection.
+    pub(crate) proxy: Option<Option<Proxy>>,
+    // Skips the verification of the certs and the hostname of the server.
+    pub(crate) unverified: bool,
+    // The address connected to instead of resolving the host of the url.
+    pub(crate) resolve_to: Option<SocketAddr>,
+    // Overrides the upload rate limit of the client if set.
+    pub(crate) upload_rate_limit: Option<u64>,
+    // Overrides the download rate limit of the client if set.
+    pub(crate) download_rate_limit: Option<u64>,
+    #[cfg(feature = "http2")]
+    pub(crate) priority: Option<Priority>,
+    pub(crate) extensions: Extensions,
+    // Whether the `Date` header was inserted by the client, which refreshes it
+    // on every redirect or retry.
+    pub(crate) date_inserted: bool,
+    // The header names in the case they were given to the builder.
+    pub(crate) header_case: Vec<String>,
 }
 
 impl Request {
@@ -71,9 +102,52 @@ impl Request {
         RequestBuilder::new()
     }
 
+    /// Gets the `Extensions` of the `Request`.
+    pub fn extensions(&self) -> &Extensions {
+        &self.extensions
+    }
+
+    /// Gets the mutable `Extensions` of the `Request`.
+    pub fn extensions_mut(&mut self) -> &mut Extensions {
+        &mut self.extensions
+    }
+
     pub(crate) fn time_group_mut(&mut self) -> &mut TimeGroup {
         &mut self.time_group
     }
+
+    // Copies the request if its body is buffered, the copy is sent from the
+    // start of the body and shares the extensions of the request.
+    pub(crate) fn try_clone(&self) -> Option<Request> {
+        let body = match self.body().inner {
+            BodyKind::Empty => Body::empty(),
+            BodyKind::Slice(ref slice) => Body::slice(slice.get_ref().clone()),
+            BodyKind::Shared(ref shared) => Body::shared(shared.get_ref().clone()),
+            _ => return None,
+        };
+        Some(Request {
+            inner: Req::from_raw_parts(self.part().clone(), body),
+            time_group: TimeGroup::default(),
+            interceptor: self.interceptor.clone(),
+            proxy: self.proxy.clone(),
+            unverified: self.unverified,
+            resolve_to: self.resolve_to,
+            upload_rate_limit: self.upload_rate_limit,
+            download_rate_limit: self.download_rate_limit,
+            #[cfg(feature = "http2")]
+            priority: self.priority.clone(),
+            extensions: self.extensions.share(),
+            date_inserted: self.date_inserted,
+            header_case: self.header_case.clone(),
+        })
+    }
+
+    // Sets `Content-Type` unless it has been set explicitly.
+    fn set_default_content_type(&mut self, value: &str) {
+        if self.headers().get("Content-Type").is_none() {
+            let _ = self.headers_mut().insert("Content-Type", value);
+        }
+    }
 }
 
 impl Deref for Request {
@@ -105,7 +179,24 @@ impl DerefMut for Request {
 ///     .body(Body::empty());
 /// ```
 #[derive(Default)]
-pub struct RequestBuilder(ReqBuilder);
+pub struct RequestBuilder {
+    inner: ReqBuilder,
+    compress: Option<Encoding>,
+    chunked: bool,
+    digest: Option<DigestAlgorithm>,
+    interceptor: Option<Arc<Interceptors>>,
+    proxy: Option<Option<Proxy>>,
+    unverified: bool,
+    resolve_to: Option<SocketAddr>,
+    upload_rate_limit: Option<u64>,
+    download_rate_limit: Option<u64>,
+    #[cfg(feature = "http2")]
+    priority: Option<(u16, u32, bool)>,
+    // Whether the body helpers leave `Content-Type` unset.
+    no_content_type: bool,
+    extensions: Extensions,
+    header_case: Vec<String>,
+}
 
 impl RequestBuilder {
     /// Creates a new, default `RequestBuilder`.
@@ -125,7 +216,23 @@ impl RequestBuilder {
     /// let builder = RequestBuilder::new();
     /// ```
     pub fn new() -> Self {
-        Self(ReqBuilder::new())
+        Self {
+            inner: ReqBuilder::new(),
+            compress: None,
+            chunked: false,
+            digest: None,
+            interceptor: None,
+            proxy: None,
+            unverified: false,
+            resolve_to: None,
+            upload_rate_limit: None,
+            download_rate_limit: None,
+            #[cfg(feature = "http2")]
+            priority: None,
+            no_content_type: false,
+            extensions: Extensions::new(),
+            header_case: Vec::new(),
+        }
     }
 
     /// Sets the `Method` of the `Request`.
@@ -137,8 +244,9 @@ impl RequestBuilder {
     ///
     /// let builder = RequestBuilder::new().method("GET");
     /// ```
-    pub fn method(self, method: &str) -> Self {
-        Self(self.0.method(method))
+    pub fn method(mut self, method: &str) -> Self {
+        self.inner = self.inner.method(method);
+        self
     }
 
     /// Sets the `Url` of the `Request`.
@@ -150,8 +258,9 @@ impl RequestBuilder {
     ///
     /// let builder = RequestBuilder::new().url("www.example.com");
     /// ```
-    pub fn url(self, url: &str) -> Self {
-        Self(self.0.url(url))
+    pub fn url(mut self, url: &str) -> Self {
+        self.inner = self.inner.url(url);
+        self
     }
 
     /// Sets the `Version` of the `Request`. Uses `Version::HTTP11` by default.
@@ -164,7 +273,7 @@ impl RequestBuilder {
     /// let builder = RequestBuilder::new().version("HTTP/1.1");
     /// ```
     pub fn version(mut self, version: &str) -> Self {
-        self.0 = self.0.version(version);
+        self.inner = self.inner.version(version);
         self
     }
 
@@ -179,13 +288,17 @@ impl RequestBuilder {
     /// let builder = RequestBuilder::new().header("Content-Type", "application/octet-stream");
     /// ```
     pub fn header(mut self, name: &str, value: &str) -> Self {
-        self.0 = self.0.header(name, value);
+        self.inner = self.inner.header(name, value);
+        self.header_case.push(name.to_string());
         self
     }
 
     /// Adds a `Header` to `Request`. Appends `HeaderValue` to the end of
     /// previous `HeaderValue` if the `HeaderName` already exists.
     ///
+    /// Unlike `header`, the previous values are kept. In `HTTP/1`, each value
+    /// is sent as a separate header line.
+    ///
     /// # Examples
     ///
     /// ```
@@ -194,7 +307,309 @@ impl RequestBuilder {
     /// let builder = RequestBuilder::new().append_header("Content-Type", "application/octet-stream");
     /// ```
     pub fn append_header(mut self, name: &str, value: &str) -> Self {
-        self.0 = self.0.append_header(name, value);
+        self.inner = self.inner.append_header(name, value);
+        self.header_case.push(name.to_string());
+        self
+    }
+
+    /// Sets the `Content-Type` header of the `Request`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::async_impl::RequestBuilder;
+    ///
+    /// let builder = RequestBuilder::new().content_type("application/octet-stream");
+    /// ```
+    pub fn content_type(self, value: &str) -> Self {
+        self.header("Content-Type", value)
+    }
+
+    /// Sets whether the body helpers, such as `body` with a multipart body
+    /// and `body_bytes`, set `Content-Type` according to the body. Uses `true`
+    /// by default.
+    ///
+    /// If `false`, no `Content-Type` is sent unless it is set explicitly. An
+    /// explicitly set `Content-Type` is never replaced in either case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::async_impl::RequestBuilder;
+    ///
+    /// let request = RequestBuilder::new()
+    ///     .method("POST")
+    ///     .auto_content_type(false)
+    ///     .body_bytes(vec![0x08, 0x96, 0x01]);
+    /// ```
+    pub fn auto_content_type(mut self, auto: bool) -> Self {
+        self.no_content_type = !auto;
+        self
+    }
+
+    /// Compresses the body of the `Request` with the given `Encoding`.
+    ///
+    /// The body is compressed while it is being sent, and `Content-Encoding`
+    /// is set. Because the compressed length is unknown, `Content-Length` is
+    /// removed and a `HTTP/1.1` request uses the chunked transfer coding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::async_impl::{Body, RequestBuilder};
+    /// use ylong_http_client::Encoding;
+    ///
+    /// let request = RequestBuilder::new()
+    ///     .method("POST")
+    ///     .compress(Encoding::Gzip)
+    ///     .body(Body::slice("HelloWorld"));
+    /// ```
+    pub fn compress(mut self, encoding: Encoding) -> Self {
+        self.compress = Some(encoding);
+        self
+    }
+
+    /// Sets whether a non-empty body of the `Request` is always sent with
+    /// the chunked transfer coding, even if its length is known.
+    ///
+    /// By default, a body whose length is known, such as `Body::slice`, is
+    /// sent with `Content-Length`. If `chunked` is `true`, `Content-Length`
+    /// is removed and a `HTTP/1.1` request sets `Transfer-Encoding: chunked`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::async_impl::{Body, RequestBuilder};
+    ///
+    /// let request = RequestBuilder::new()
+    ///     .method("POST")
+    ///     .chunked(true)
+    ///     .body(Body::slice("HelloWorld"));
+    /// ```
+    pub fn chunked(mut self, chunked: bool) -> Self {
+        self.chunked = chunked;
+        self
+    }
+
+    /// Adds a digest header of the body of the `Request`, which is computed
+    /// with the given `DigestAlgorithm`.
+    ///
+    /// `DigestAlgorithm::Md5` sets `Content-MD5`, and `DigestAlgorithm::Sha256`
+    /// sets `Digest: SHA-256=...`. The digest can only be computed for a
+    /// buffered body, such as `Body::slice` and `Body::shared`, so building
+    /// the `Request` fails with a streaming body, a multipart body, a body
+    /// produced by `body_fn` or a compressed body.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::async_impl::{Body, RequestBuilder};
+    /// use ylong_http_client::DigestAlgorithm;
+    ///
+    /// let request = RequestBuilder::new()
+    ///     .method("POST")
+    ///     .with_body_digest(DigestAlgorithm::Sha256)
+    ///     .body(Body::slice("HelloWorld"));
+    /// ```
+    pub fn with_body_digest(mut self, algorithm: DigestAlgorithm) -> Self {
+        self.digest = Some(algorithm);
+        self
+    }
+
+    /// Inserts a value into the `Extensions` of the `Request`, which replaces
+    /// the previous value of the same type.
+    ///
+    /// Extensions can be read by the `Interceptor`s through
+    /// `Request::extensions`, they are never sent to the peer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::async_impl::RequestBuilder;
+    ///
+    /// struct RequestId(u64);
+    ///
+    /// let builder = RequestBuilder::new().extension(RequestId(1));
+    /// ```
+    pub fn extension<T: Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.extensions.insert(value);
+        self
+    }
+
+    /// Adds an `Interceptor` to the `Request`.
+    ///
+    /// The interceptor only works on this request, it runs after the
+    /// interceptor of the client.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::async_impl::RequestBuilder;
+    /// use ylong_http_client::Interceptor;
+    ///
+    /// struct CaptureInterceptor;
+    ///
+    /// impl Interceptor for CaptureInterceptor {}
+    ///
+    /// let builder = RequestBuilder::new().interceptor(CaptureInterceptor);
+    /// ```
+    pub fn interceptor<T>(mut self, interceptor: T) -> Self
+    where
+        T: Interceptor + Sync + Send + 'static,
+    {
+        self.interceptor = Some(Arc::new(interceptor));
+        self
+    }
+
+    /// Sets the proxy of the `Request`, which overrides the proxies of the
+    /// client. `None` makes the `Request` connect to the server directly.
+    ///
+    /// Connections are only shared by requests which use the same proxy.
+    /// Connectors other than the default one may ignore this setting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::async_impl::RequestBuilder;
+    ///
+    /// // Bypasses the proxies of the client.
+    /// let builder = RequestBuilder::new().proxy(None);
+    /// ```
+    pub fn proxy(mut self, proxy: Option<Proxy>) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Connects the `Request` to the given address instead of resolving the
+    /// host of the url, which overrides the DNS resolver of the client.
+    ///
+    /// The host of the url is still used for `Host`, SNI and the verification
+    /// of the server. The `Request` uses its own connections, so they are
+    /// never shared with the requests to the same host which are resolved as
+    /// usual. If the `Request` goes through a proxy, the proxy resolves the
+    /// host and this setting is ignored. Connectors other than the default
+    /// one may ignore this setting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::SocketAddr;
+    ///
+    /// use ylong_http_client::async_impl::RequestBuilder;
+    ///
+    /// let addr: SocketAddr = "127.0.0.1:443".parse().unwrap();
+    /// let builder = RequestBuilder::new()
+    ///     .url("https://www.example.com")
+    ///     .resolve_to(addr);
+    /// ```
+    pub fn resolve_to(mut self, addr: SocketAddr) -> Self {
+        self.resolve_to = Some(addr);
+        self
+    }
+
+    /// Controls the verification of the server for this `Request` only,
+    /// overriding the TLS settings of the client.
+    ///
+    /// Defaults to `false` -- verify the certs and the hostname.
+    ///
+    /// The `Request` uses its own connections, so a connection made without
+    /// verification is never shared with the verified requests, and the other
+    /// way around. Connectors other than the default one may ignore this
+    /// setting. It has no effect on `HTTP/3`.
+    ///
+    /// # Warning
+    ///
+    /// When sets `true`, any certificate is trusted for the server of the
+    /// `Request`, including expired, self-signed, or ones issued for another
+    /// site. Anyone who can intercept the connection can read and modify the
+    /// `Request` and its response. Only use it for a server whose certificate
+    /// is known to be invalid, and never for requests carrying secrets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::async_impl::RequestBuilder;
+    ///
+    /// let builder = RequestBuilder::new()
+    ///     .url("https://self-signed.example.com")
+    ///     .danger_accept_invalid_certs(true);
+    /// ```
+    #[cfg(feature = "__tls")]
+    pub fn danger_accept_invalid_certs(mut self, is_invalid: bool) -> Self {
+        self.unverified = is_invalid;
+        self
+    }
+
+    /// Limits the rate of sending the body of the `Request` to
+    /// `bytes_per_sec` bytes per second on average, which overrides
+    /// `ClientBuilder::upload_rate_limit`.
+    ///
+    /// The body is paced by waiting between the writes, so the upload
+    /// intentionally takes longer than the link requires. It has no effect on
+    /// requests which are not sent over `HTTP/1.1`. The rate must be nonzero,
+    /// otherwise `RequestBuilder::body` returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::async_impl::{Body, RequestBuilder};
+    ///
+    /// let request = RequestBuilder::new()
+    ///     .method("POST")
+    ///     .upload_rate_limit(1024)
+    ///     .body(Body::slice("HelloWorld"));
+    /// ```
+    #[cfg(feature = "http1_1")]
+    pub fn upload_rate_limit(mut self, bytes_per_sec: u64) -> Self {
+        self.upload_rate_limit = Some(bytes_per_sec);
+        self
+    }
+
+    /// Limits the rate of reading the response body of the `Request` to
+    /// `bytes_per_sec` bytes per second on average, which overrides
+    /// `ClientBuilder::download_rate_limit`.
+    ///
+    /// The body is paced by waiting between the reads, so the download
+    /// intentionally takes longer than the link requires. The rate must be
+    /// nonzero, otherwise `RequestBuilder::body` returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::async_impl::{Body, RequestBuilder};
+    ///
+    /// let request = RequestBuilder::new()
+    ///     .url("http://www.example.com/large")
+    ///     .download_rate_limit(1024)
+    ///     .body(Body::empty());
+    /// ```
+    pub fn download_rate_limit(mut self, bytes_per_sec: u64) -> Self {
+        self.download_rate_limit = Some(bytes_per_sec);
+        self
+    }
+
+    /// Sets the priority of the `HTTP/2` stream which carries the `Request`.
+    ///
+    /// `weight` is in the range of 1 to 256, `depends_on` is the id of the
+    /// stream this stream depends on, 0 means no dependency. If `exclusive` is
+    /// true, this stream becomes the only dependency of `depends_on`.
+    ///
+    /// The priority is only a hint to the server, which may ignore it. It has
+    /// no effect on requests which are not sent over `HTTP/2`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::async_impl::{Body, RequestBuilder};
+    ///
+    /// let request = RequestBuilder::new()
+    ///     .version("HTTP/2.0")
+    ///     .priority(256, 0, false)
+    ///     .body(Body::empty());
+    /// ```
+    #[cfg(feature = "http2")]
+    pub fn priority(mut self, weight: u16, depends_on: u32, exclusive: bool) -> Self {
+        self.priority = Some((weight, depends_on, exclusive));
         self
     }
 
@@ -209,37 +624,390 @@ impl RequestBuilder {
     /// ```
     pub fn body(self, body: Body) -> Result<Request, HttpClientError> {
         let mut builder = self;
+        if builder.upload_rate_limit == Some(0) {
+            return err_from_msg!(Build, "Upload rate limit must be nonzero");
+        }
+        if builder.download_rate_limit == Some(0) {
+            return err_from_msg!(Build, "Download rate limit must be nonzero");
+        }
+        let compress = builder.compress.take();
+        if let Some(algorithm) = builder.digest.take() {
+            let data: &[u8] = match body.inner {
+                BodyKind::Empty if compress.is_none() => &[],
+                BodyKind::Slice(ref slice) if compress.is_none() => slice.get_ref(),
+                BodyKind::Shared(ref shared) if compress.is_none() => shared.get_ref(),
+                _ => return err_from_msg!(Build, "Body digest requires a buffered body"),
+            };
+            builder = builder.header(algorithm.header_name(), &algorithm.header_value(data));
+        }
+        #[cfg(feature = "http2")]
+        let priority = match builder.priority.take() {
+            Some((weight, depends_on, exclusive)) => {
+                Some(stream_priority(weight, depends_on, exclusive)?)
+            }
+            None => None,
+        };
+        let mut content_type = None;
         match body.inner {
-            BodyKind::Slice(ref slice) => {
+            BodyKind::Slice(ref slice) if compress.is_none() => {
                 builder = builder.header(
                     "Content-Length",
                     format!("{}", slice.get_ref().len()).as_str(),
                 );
             }
-            BodyKind::Multipart(ref multipart) => {
-                let value = format!(
-                    "multipart/form-data; boundary={}",
-                    multipart.multipart().boundary()
+            BodyKind::Shared(ref shared) if compress.is_none() => {
+                builder = builder.header(
+                    "Content-Length",
+                    format!("{}", shared.get_ref().len()).as_str(),
                 );
+            }
+            BodyKind::Multipart(ref multipart) => {
+                let boundary = multipart.multipart().boundary();
+                // A boundary with special characters must be quoted.
+                let value = if boundary
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b"'+_-.".contains(&b))
+                {
+                    format!("multipart/form-data; boundary={boundary}")
+                } else {
+                    format!("multipart/form-data; boundary=\"{boundary}\"")
+                };
 
-                builder = builder.header("Content-Type", value.as_str());
+                content_type = Some(value);
 
                 if let Some(size) = multipart.multipart().total_bytes() {
-                    builder = builder.header("Content-Length", format!("{size}").as_str());
+                    if compress.is_none() {
+                        builder = builder.header("Content-Length", format!("{size}").as_str());
+                    }
                 }
             }
             _ => {}
         }
 
-        builder
-            .0
+        let body = match compress {
+            Some(encoding) if !body.is_empty() => {
+                builder = builder.header("Content-Encoding", encoding.as_str());
+                Body::stream(CompressedBody::new(body, encoding))
+            }
+            _ => body,
+        };
+        let chunked = !body.is_empty() && (compress.is_some() || builder.chunked);
+
+        let no_content_type = builder.no_content_type;
+        let mut request = builder
+            .inner
             .body(body)
             .map(|inner| Request {
                 inner,
                 time_group: TimeGroup::default(),
+                interceptor: builder.interceptor,
+                proxy: builder.proxy,
+                unverified: builder.unverified,
+                resolve_to: builder.resolve_to,
+                upload_rate_limit: builder.upload_rate_limit,
+                download_rate_limit: builder.download_rate_limit,
+                #[cfg(feature = "http2")]
+                priority,
+                extensions: builder.extensions,
+                date_inserted: false,
+                header_case: builder.header_case,
             })
-            .map_err(|e| HttpClientError::from_error(ErrorKind::Build, e))
+            .map_err(|e| HttpClientError::from_error(ErrorKind::Build, e))?;
+        if let Some(value) = content_type.filter(|_| !no_content_type) {
+            request.set_default_content_type(value.as_str());
+        }
+        if chunked {
+            request.headers_mut().remove("Content-Length");
+            if *request.version() == Version::HTTP1_1 {
+                let _ = request.headers_mut().insert("Transfer-Encoding", "chunked");
+            }
+        }
+        Ok(request)
+    }
+
+    /// Tries to create a `Request` with the given bytes as its body.
+    ///
+    /// `Content-Length` is set to the length of `bytes`, and `Content-Type` is
+    /// set to `application/octet-stream` unless it has been set or
+    /// `auto_content_type` is `false`. The body is **reusable**, so the
+    /// `Request` can be retried and redirected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::async_impl::RequestBuilder;
+    ///
+    /// let request = RequestBuilder::new()
+    ///     .method("POST")
+    ///     .content_type("application/x-protobuf")
+    ///     .body_bytes(vec![0x08, 0x96, 0x01]);
+    /// ```
+    pub fn body_bytes(self, bytes: Vec<u8>) -> Result<Request, HttpClientError> {
+        let no_content_type = self.no_content_type;
+        let mut request = self.body(Body::slice(bytes))?;
+        if !no_content_type {
+            request.set_default_content_type("application/octet-stream");
+        }
+        Ok(request)
     }
+
+    /// Tries to create a `Request` whose body is produced by the async closure
+    /// `f`.
+    ///
+    /// `f` is not called until the body is first read, which is after the
+    /// connection is established and a `100 Continue` response (if expected)
+    /// is received, so nothing is produced for a request which fails to
+    /// connect. The length of the body is unknown beforehand, so a `HTTP/1.1`
+    /// request sets `Transfer-Encoding: chunked` unless `Content-Length` has
+    /// been set. The body is **reusable** only before it is produced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::async_impl::{Body, RequestBuilder};
+    ///
+    /// let request = RequestBuilder::new()
+    ///     .method("POST")
+    ///     .body_fn(|| async { Ok(Body::slice("HelloWorld")) });
+    /// ```
+    pub fn body_fn<F, Fut>(self, f: F) -> Result<Request, HttpClientError>
+    where
+        F: FnOnce() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = std::io::Result<Body>> + Send + Sync + 'static,
+    {
+        self.body(Body::lazy(f))
+    }
+}
+
+/// A `RequestBuilder` bound to a `Client`, which is returned by the method
+/// shortcuts of `Client`, such as `Client::get`, and sends the `Request`
+/// with `Client::request`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ylong_http_client::async_impl::{Body, Client};
+/// use ylong_http_client::HttpClientError;
+///
+/// async fn shortcut() -> Result<(), HttpClientError> {
+///     let client = Client::new();
+///     let response = client
+///         .post("http://www.example.com")
+///         .content_type("text/plain")
+///         .body(Body::slice("HelloWorld"))
+///         .send()
+///         .await?;
+///     Ok(())
+/// }
+/// ```
+pub struct ClientRequestBuilder<'a, C: Connector> {
+    client: &'a Client<C>,
+    builder: RequestBuilder,
+    body: Payload,
+}
+
+enum Payload {
+    Body(Body),
+    Bytes(Vec<u8>),
+}
+
+impl<'a, C: Connector> ClientRequestBuilder<'a, C> {
+    pub(crate) fn new(client: &'a Client<C>, method: &str, url: &str) -> Self {
+        Self {
+            client,
+            builder: RequestBuilder::new().method(method).url(url),
+            body: Payload::Body(Body::empty()),
+        }
+    }
+
+    /// Sets the `Version` of the `Request`. See `RequestBuilder::version`.
+    pub fn version(mut self, version: &str) -> Self {
+        self.builder = self.builder.version(version);
+        self
+    }
+
+    /// Adds a `Header` to `Request`. See `RequestBuilder::header`.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.builder = self.builder.header(name, value);
+        self
+    }
+
+    /// Appends a `Header` to `Request`. See `RequestBuilder::append_header`.
+    pub fn append_header(mut self, name: &str, value: &str) -> Self {
+        self.builder = self.builder.append_header(name, value);
+        self
+    }
+
+    /// Sets the `Content-Type` header of the `Request`. See
+    /// `RequestBuilder::content_type`.
+    pub fn content_type(mut self, value: &str) -> Self {
+        self.builder = self.builder.content_type(value);
+        self
+    }
+
+    /// Sets whether the body helpers set `Content-Type`. See
+    /// `RequestBuilder::auto_content_type`.
+    pub fn auto_content_type(mut self, auto: bool) -> Self {
+        self.builder = self.builder.auto_content_type(auto);
+        self
+    }
+
+    /// Compresses the body of the `Request`. See `RequestBuilder::compress`.
+    pub fn compress(mut self, encoding: Encoding) -> Self {
+        self.builder = self.builder.compress(encoding);
+        self
+    }
+
+    /// Sets whether the body is always chunked. See
+    /// `RequestBuilder::chunked`.
+    pub fn chunked(mut self, chunked: bool) -> Self {
+        self.builder = self.builder.chunked(chunked);
+        self
+    }
+
+    /// Adds a digest header of the body. See
+    /// `RequestBuilder::with_body_digest`.
+    pub fn with_body_digest(mut self, algorithm: DigestAlgorithm) -> Self {
+        self.builder = self.builder.with_body_digest(algorithm);
+        self
+    }
+
+    /// Inserts a value into the `Extensions` of the `Request`. See
+    /// `RequestBuilder::extension`.
+    pub fn extension<T: Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.builder = self.builder.extension(value);
+        self
+    }
+
+    /// Adds an `Interceptor` to the `Request`. See
+    /// `RequestBuilder::interceptor`.
+    pub fn interceptor<T>(mut self, interceptor: T) -> Self
+    where
+        T: Interceptor + Sync + Send + 'static,
+    {
+        self.builder = self.builder.interceptor(interceptor);
+        self
+    }
+
+    /// Sets the proxy of the `Request`. See `RequestBuilder::proxy`.
+    pub fn proxy(mut self, proxy: Option<Proxy>) -> Self {
+        self.builder = self.builder.proxy(proxy);
+        self
+    }
+
+    /// Connects the `Request` to the given address. See
+    /// `RequestBuilder::resolve_to`.
+    pub fn resolve_to(mut self, addr: SocketAddr) -> Self {
+        self.builder = self.builder.resolve_to(addr);
+        self
+    }
+
+    /// Controls the verification of the server for the `Request` only. See
+    /// `RequestBuilder::danger_accept_invalid_certs`.
+    #[cfg(feature = "__tls")]
+    pub fn danger_accept_invalid_certs(mut self, is_invalid: bool) -> Self {
+        self.builder = self.builder.danger_accept_invalid_certs(is_invalid);
+        self
+    }
+
+    /// Limits the upload rate of the body. See
+    /// `RequestBuilder::upload_rate_limit`.
+    #[cfg(feature = "http1_1")]
+    pub fn upload_rate_limit(mut self, bytes_per_sec: u64) -> Self {
+        self.builder = self.builder.upload_rate_limit(bytes_per_sec);
+        self
+    }
+
+    /// Limits the download rate of the response body. See
+    /// `RequestBuilder::download_rate_limit`.
+    pub fn download_rate_limit(mut self, bytes_per_sec: u64) -> Self {
+        self.builder = self.builder.download_rate_limit(bytes_per_sec);
+        self
+    }
+
+    /// Sets the priority of the `HTTP/2` stream. See
+    /// `RequestBuilder::priority`.
+    #[cfg(feature = "http2")]
+    pub fn priority(mut self, weight: u16, depends_on: u32, exclusive: bool) -> Self {
+        self.builder = self.builder.priority(weight, depends_on, exclusive);
+        self
+    }
+
+    /// Applies `f` to the inner `RequestBuilder`, so that any of its helpers
+    /// can be used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::async_impl::Client;
+    ///
+    /// let client = Client::new();
+    /// let builder = client
+    ///     .get("http://www.example.com")
+    ///     .map(|builder| builder.header("Accept", "text/html"));
+    /// ```
+    pub fn map<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(RequestBuilder) -> RequestBuilder,
+    {
+        self.builder = f(self.builder);
+        self
+    }
+
+    /// Sets the body of the `Request`. See `RequestBuilder::body`.
+    pub fn body(mut self, body: Body) -> Self {
+        self.body = Payload::Body(body);
+        self
+    }
+
+    /// Sets the given bytes as the body of the `Request`. See
+    /// `RequestBuilder::body_bytes`.
+    pub fn body_bytes(mut self, bytes: Vec<u8>) -> Self {
+        self.body = Payload::Bytes(bytes);
+        self
+    }
+
+    /// Sets a body produced by the async closure `f`. See
+    /// `RequestBuilder::body_fn`.
+    pub fn body_fn<F, Fut>(mut self, f: F) -> Self
+    where
+        F: FnOnce() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = std::io::Result<Body>> + Send + Sync + 'static,
+    {
+        self.body = Payload::Body(Body::lazy(f));
+        self
+    }
+
+    /// Builds the `Request` without sending it.
+    pub fn build(self) -> Result<Request, HttpClientError> {
+        match self.body {
+            Payload::Body(body) => self.builder.body(body),
+            Payload::Bytes(bytes) => self.builder.body_bytes(bytes),
+        }
+    }
+
+    /// Builds the `Request` and sends it with `Client::request`.
+    pub async fn send(self) -> Result<Response, HttpClientError> {
+        let client = self.client;
+        client.request(self.build()?).await
+    }
+}
+
+#[cfg(feature = "http2")]
+fn stream_priority(
+    weight: u16,
+    depends_on: u32,
+    exclusive: bool,
+) -> Result<Priority, HttpClientError> {
+    if !(1..=256).contains(&weight) {
+        return err_from_msg!(Build, "Invalid stream priority weight");
+    }
+    // A stream identifier is a 31-bit integer.
+    if depends_on > 0x7FFF_FFFF {
+        return err_from_msg!(Build, "Invalid stream dependency");
+    }
+    // The weight is transmitted as a value between 0 and 255.
+    Ok(Priority::new(exclusive, depends_on, (weight - 1) as u8))
 }
 
 /// A structure that represents body of HTTP request.
@@ -248,6 +1016,8 @@ impl RequestBuilder {
 ///
 /// - Empty: an empty body.
 /// - Slice: a body whose content comes from a memory slice.
+/// - Shared: a body whose content comes from a memory slice shared with other
+///   bodies.
 /// - Stream: a body whose content comes from a stream.
 /// - Multipart: a body whose content can transfer into a `Multipart`.
 ///
@@ -265,8 +1035,64 @@ pub struct Body {
 pub(crate) enum BodyKind {
     Empty,
     Slice(Cursor<Vec<u8>>),
+    Shared(Cursor<Arc<[u8]>>),
     Stream(Box<dyn ReusableReader + Send + Sync + Unpin>),
     Multipart(Box<dyn MultiPartBase + Send + Sync + Unpin>),
+    Lazy(Box<LazyBody>),
+}
+
+type BodyFuture = Pin<Box<dyn Future<Output = std::io::Result<Body>> + Send + Sync>>;
+
+// A body which is produced by an async closure when it is first read.
+pub(crate) struct LazyBody {
+    producer: Option<Box<dyn FnOnce() -> BodyFuture + Send + Sync>>,
+    future: Option<BodyFuture>,
+    body: Option<Body>,
+}
+
+impl LazyBody {
+    fn is_reusable(&self) -> bool {
+        match self.body {
+            Some(ref body) => body.is_reusable(),
+            None => self.producer.is_some(),
+        }
+    }
+
+    async fn reuse(&mut self) -> std::io::Result<()> {
+        match self.body {
+            Some(ref mut body) => Box::pin(body.reuse()).await,
+            // Nothing has been read before the body is produced.
+            None if self.producer.is_some() => Ok(()),
+            None => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Lazy body can not be produced again",
+            )),
+        }
+    }
+
+    fn poll_body(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<&mut Body>> {
+        if let Some(producer) = self.producer.take() {
+            self.future = Some(producer());
+        }
+        if let Some(ref mut future) = self.future {
+            match future.as_mut().poll(cx) {
+                Poll::Ready(Ok(body)) => self.body = Some(body),
+                Poll::Ready(Err(e)) => {
+                    self.future = None;
+                    return Poll::Ready(Err(e));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+            self.future = None;
+        }
+        match self.body {
+            Some(ref mut body) => Poll::Ready(Ok(body)),
+            None => Poll::Ready(Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Lazy body failed to be produced",
+            ))),
+        }
+    }
 }
 
 impl Body {
@@ -301,6 +1127,31 @@ impl Body {
         Body::new(BodyKind::Slice(Cursor::new(slice.into())))
     }
 
+    /// Creates an HTTP body that based on a shared memory slice.
+    ///
+    /// The body reads the slice without copying or consuming it, so the same
+    /// `Arc<[u8]>` can be cheaply cloned into the bodies of many requests.
+    ///
+    /// This kind of body is **reusable**.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use ylong_http_client::async_impl::Body;
+    ///
+    /// let payload: Arc<[u8]> = Arc::from("HelloWorld".as_bytes());
+    /// let first = Body::shared(payload.clone());
+    /// let second = Body::shared(payload);
+    /// ```
+    pub fn shared<T>(shared: T) -> Self
+    where
+        T: Into<Arc<[u8]>>,
+    {
+        Body::new(BodyKind::Shared(Cursor::new(shared.into())))
+    }
+
     /// Creates an HTTP body that based on an asynchronous stream.
     ///
     /// This kind of body is not **reusable**.
@@ -350,10 +1201,31 @@ impl Body {
         Self { inner }
     }
 
+    pub(crate) fn lazy<F, Fut>(f: F) -> Self
+    where
+        F: FnOnce() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = std::io::Result<Body>> + Send + Sync + 'static,
+    {
+        Body::new(BodyKind::Lazy(Box::new(LazyBody {
+            producer: Some(Box::new(move || Box::pin(f()) as BodyFuture)),
+            future: None,
+            body: None,
+        })))
+    }
+
     pub(crate) fn is_empty(&self) -> bool {
         matches!(self.inner, BodyKind::Empty)
     }
 
+    // Checks whether the body can be sent once again after `reuse`.
+    pub(crate) fn is_reusable(&self) -> bool {
+        match self.inner {
+            BodyKind::Empty | BodyKind::Slice(_) | BodyKind::Shared(_) => true,
+            BodyKind::Lazy(ref lazy) => lazy.is_reusable(),
+            _ => false,
+        }
+    }
+
     pub(crate) async fn reuse(&mut self) -> std::io::Result<()> {
         match self.inner {
             BodyKind::Empty => Ok(()),
@@ -361,8 +1233,13 @@ impl Body {
                 slice.set_position(0);
                 Ok(())
             }
+            BodyKind::Shared(ref mut shared) => {
+                shared.set_position(0);
+                Ok(())
+            }
             BodyKind::Stream(ref mut stream) => stream.reuse().await,
             BodyKind::Multipart(ref mut multipart) => multipart.reuse().await,
+            BodyKind::Lazy(ref mut lazy) => lazy.reuse().await,
         }
     }
 }
@@ -382,12 +1259,107 @@ impl AsyncRead for Body {
                 #[cfg(feature = "ylong_base")]
                 return poll_read_cursor(slice, buf);
             }
+            BodyKind::Shared(ref mut shared) => {
+                #[cfg(feature = "tokio_base")]
+                return Pin::new(shared).poll_read(cx, buf);
+                #[cfg(feature = "ylong_base")]
+                return poll_read_cursor(shared, buf);
+            }
             BodyKind::Stream(ref mut stream) => Pin::new(stream).poll_read(cx, buf),
             BodyKind::Multipart(ref mut multipart) => Pin::new(multipart).poll_read(cx, buf),
+            BodyKind::Lazy(ref mut lazy) => match lazy.poll_body(cx) {
+                Poll::Ready(Ok(body)) => Pin::new(body).poll_read(cx, buf),
+                Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+                Poll::Pending => Poll::Pending,
+            },
         }
     }
 }
 
+// A body which compresses the data of another body while it is being read.
+struct CompressedBody {
+    body: Body,
+    compressor: Compressor,
+    // The compressed bytes which have not been read.
+    output: Vec<u8>,
+    pos: usize,
+    finished: bool,
+    buf: Box<[u8]>,
+}
+
+impl CompressedBody {
+    fn new(body: Body, encoding: Encoding) -> Self {
+        Self {
+            body,
+            compressor: Compressor::new(encoding),
+            output: Vec::new(),
+            pos: 0,
+            finished: false,
+            buf: vec![0u8; 16 * 1024].into_boxed_slice(),
+        }
+    }
+}
+
+impl AsyncRead for CompressedBody {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            if this.pos < this.output.len() {
+                let size = (this.output.len() - this.pos).min(buf.remaining());
+                let data = &this.output[this.pos..this.pos + size];
+                #[cfg(feature = "tokio_base")]
+                buf.put_slice(data);
+                #[cfg(feature = "ylong_base")]
+                buf.append(data);
+                this.pos += size;
+                return Poll::Ready(Ok(()));
+            }
+            if this.finished {
+                return Poll::Ready(Ok(()));
+            }
+
+            this.output.clear();
+            this.pos = 0;
+            let mut read_buf = ReadBuf::new(&mut this.buf);
+            match Pin::new(&mut this.body).poll_read(cx, &mut read_buf) {
+                Poll::Ready(Ok(())) => {
+                    let filled = read_buf.filled();
+                    if filled.is_empty() {
+                        this.compressor.finish(&mut this.output);
+                        this.finished = true;
+                    } else {
+                        this.compressor.compress(filled, &mut this.output);
+                    }
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl ReusableReader for CompressedBody {
+    fn reuse<'a>(
+        &'a mut self,
+    ) -> Pin<Box<dyn Future<Output = std::io::Result<()>> + Send + Sync + 'a>>
+    where
+        Self: 'a,
+    {
+        Box::pin(async {
+            self.body.reuse().await?;
+            self.compressor.reset();
+            self.output.clear();
+            self.pos = 0;
+            self.finished = false;
+            Ok(())
+        })
+    }
+}
+
 /// HTTP url percent encoding implementation.
 ///
 /// # Examples
@@ -414,15 +1386,38 @@ impl PercentEncoder {
 pub(crate) struct Message {
     pub(crate) request: RequestArc,
     pub(crate) interceptor: Arc<Interceptors>,
+    // Timeout for the first byte of the response.
+    pub(crate) ttfb_timeout: Option<Duration>,
+    // Timeout for each read of the response.
+    pub(crate) read_timeout: Option<Duration>,
+    // Maximum size of each chunk of a chunked request body.
+    pub(crate) chunk_size: Option<usize>,
+    // Maximum average rate of sending the request body in bytes per second.
+    pub(crate) upload_rate_limit: Option<u64>,
+    // Whether to reject a response body delimited by the close of the
+    // connection, and the maximum size of such a body.
+    pub(crate) reject_until_close: bool,
+    pub(crate) max_until_close_size: Option<u64>,
+    // Maximum size of each response head, including the interim ones.
+    pub(crate) max_header_size: Option<usize>,
+    // Maximum time to wait for `100 Continue` before sending the body of a
+    // request which expects it, `None` to send the body at once.
+    pub(crate) expect_continue_timeout: Option<Duration>,
+    // Whether to write the header names in the case they were given.
+    pub(crate) preserve_header_case: bool,
+    // Maximum time without any frame received on an `HTTP/2` stream before
+    // the stream is reset.
+    #[cfg(feature = "http2")]
+    pub(crate) stream_idle_timeout: Option<Duration>,
 }
 
 #[cfg(feature = "ylong_base")]
-fn poll_read_cursor(
-    cursor: &mut Cursor<Vec<u8>>,
+fn poll_read_cursor<T: AsRef<[u8]>>(
+    cursor: &mut Cursor<T>,
     buf: &mut ylong_runtime::io::ReadBuf<'_>,
 ) -> Poll<std::io::Result<()>> {
     let pos = cursor.position();
-    let data = (*cursor).get_ref();
+    let data = (*cursor).get_ref().as_ref();
 
     if pos > data.len() as u64 {
         return Poll::Ready(Ok(()));
@@ -431,7 +1426,7 @@ fn poll_read_cursor(
     let start = pos as usize;
     let target = std::cmp::min(data.len() - start, buf.remaining());
     let end = start + target;
-    buf.append(&(data.as_slice())[start..end]);
+    buf.append(&data[start..end]);
     cursor.set_position(end as u64);
 
     Poll::Ready(Ok(()))
@@ -439,7 +1434,7 @@ fn poll_read_cursor(
 
 #[cfg(test)]
 mod ut_client_request {
-    use crate::async_impl::{Body, PercentEncoder, RequestBuilder};
+    use crate::async_impl::{Body, PercentEncoder, Request, RequestBuilder};
 
     /// UT test cases for `RequestBuilder::default`.
     ///
@@ -460,6 +1455,53 @@ mod ut_client_request {
         assert!(request.is_err());
     }
 
+    /// UT test cases for `RequestBuilder::extension`.
+    ///
+    /// # Brief
+    /// 1. Creates a `Request` with extensions by `RequestBuilder::extension`.
+    /// 2. Checks that the extensions are kept and are not sent as headers.
+    #[test]
+    fn ut_client_request_builder_extension() {
+        #[derive(Debug, PartialEq)]
+        struct RequestId(u64);
+
+        let mut request = RequestBuilder::new()
+            .extension(RequestId(1))
+            .extension(RequestId(2))
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(request.extensions().get::<RequestId>(), Some(&RequestId(2)));
+        assert!(request.headers().is_empty());
+
+        request.extensions_mut().insert("scope");
+        assert_eq!(request.extensions().get::<&str>(), Some(&"scope"));
+        assert!(Request::builder()
+            .body(Body::empty())
+            .unwrap()
+            .extensions()
+            .is_empty());
+    }
+
+    /// UT test cases for `Request::try_clone`.
+    ///
+    /// # Brief
+    /// 1. Creates a `Request` with a slice body and extensions.
+    /// 2. Calls `Request::try_clone`.
+    /// 3. Checks that the copy holds the extensions of the request.
+    #[test]
+    fn ut_client_request_try_clone_extensions() {
+        #[derive(Debug, PartialEq)]
+        struct RequestId(u64);
+
+        let request = RequestBuilder::new()
+            .extension(RequestId(1))
+            .body(Body::slice("body"))
+            .unwrap();
+        let copy = request.try_clone().unwrap();
+        assert_eq!(copy.extensions().get::<RequestId>(), Some(&RequestId(1)));
+        assert_eq!(request.extensions().get::<RequestId>(), Some(&RequestId(1)));
+    }
+
     /// UT test cases for `RequestBuilder::body`.
     ///
     /// # Brief
@@ -508,6 +1550,392 @@ mod ut_client_request {
         ylong_runtime::block_on(handle).unwrap();
     }
 
+    /// UT test cases for `RequestBuilder::compress`.
+    ///
+    /// # Brief
+    /// 1. Creates a `Request` by `RequestBuilder::compress`.
+    /// 2. Checks if the headers are correct.
+    /// 3. Reads the body and checks if it is a gzip stream of the origin body.
+    #[cfg(feature = "ylong_base")]
+    #[test]
+    fn ut_client_request_builder_compress() {
+        use std::pin::Pin;
+
+        use ylong_runtime::futures::poll_fn;
+        use ylong_runtime::io::ReadBuf;
+
+        use crate::runtime::AsyncRead;
+        use crate::Encoding;
+
+        let data = "HelloWorld".repeat(100);
+        let mut request = RequestBuilder::new()
+            .method("POST")
+            .header("Content-Length", "1000")
+            .compress(Encoding::Gzip)
+            .body(Body::slice(data.as_str()))
+            .unwrap();
+        let headers = request.headers();
+        assert!(headers.get("Content-Length").is_none());
+        assert_eq!(
+            headers
+                .get("Content-Encoding")
+                .unwrap()
+                .to_string()
+                .unwrap(),
+            "gzip"
+        );
+        assert_eq!(
+            headers
+                .get("Transfer-Encoding")
+                .unwrap()
+                .to_string()
+                .unwrap(),
+            "chunked"
+        );
+
+        let handle = ylong_runtime::spawn(async move {
+            let mut buf = vec![0u8; 16];
+            let mut output = vec![];
+            loop {
+                let mut read_buf = ReadBuf::new(&mut buf);
+                poll_fn(|cx| Pin::new(request.body_mut()).poll_read(cx, &mut read_buf))
+                    .await
+                    .unwrap();
+                let len = read_buf.filled().len();
+                if len == 0 {
+                    break;
+                }
+                output.extend_from_slice(&buf[..len]);
+            }
+            assert_eq!(output[..2], [0x1f, 0x8b]);
+            assert_eq!(output[output.len() - 4..], 1000u32.to_le_bytes());
+            assert!(output.len() < 100);
+        });
+        ylong_runtime::block_on(handle).unwrap();
+    }
+
+    /// UT test cases for `RequestBuilder::priority`.
+    ///
+    /// # Brief
+    /// 1. Creates `Request`s by calling `RequestBuilder::priority`.
+    /// 2. Checks if the results are correct.
+    #[cfg(feature = "http2")]
+    #[test]
+    fn ut_client_request_builder_priority() {
+        let request = RequestBuilder::new()
+            .priority(256, 3, true)
+            .body(Body::empty())
+            .unwrap();
+        let priority = request.priority.as_ref().unwrap();
+        assert_eq!(priority.get_weight(), 255);
+        assert_eq!(priority.get_stream_dependency(), 3);
+        assert!(priority.get_exclusive());
+
+        let request = RequestBuilder::new().body(Body::empty()).unwrap();
+        assert!(request.priority.is_none());
+
+        let request = RequestBuilder::new()
+            .priority(0, 0, false)
+            .body(Body::empty());
+        assert!(request.is_err());
+
+        let request = RequestBuilder::new()
+            .priority(16, 0x8000_0000, false)
+            .body(Body::empty());
+        assert!(request.is_err());
+    }
+
+    /// UT test cases for `RequestBuilder::body_bytes`.
+    ///
+    /// # Brief
+    /// 1. Creates `Request`s by calling `RequestBuilder::body_bytes`.
+    /// 2. Checks if the headers and the body are correct.
+    #[test]
+    fn ut_client_request_builder_body_bytes() {
+        use crate::async_impl::request::BodyKind;
+
+        let request = RequestBuilder::new()
+            .method("POST")
+            .body_bytes(vec![1, 2, 3])
+            .unwrap();
+        let headers = request.headers();
+        assert_eq!(
+            headers.get("Content-Length").unwrap().to_string().unwrap(),
+            "3"
+        );
+        assert_eq!(
+            headers.get("Content-Type").unwrap().to_string().unwrap(),
+            "application/octet-stream"
+        );
+        assert!(matches!(
+            request.body().inner,
+            BodyKind::Slice(ref slice) if slice.get_ref() == &[1, 2, 3]
+        ));
+
+        let request = RequestBuilder::new()
+            .method("POST")
+            .content_type("application/x-protobuf")
+            .body_bytes(Vec::new())
+            .unwrap();
+        let headers = request.headers();
+        assert_eq!(
+            headers.get("Content-Length").unwrap().to_string().unwrap(),
+            "0"
+        );
+        assert_eq!(
+            headers.get("Content-Type").unwrap().to_string().unwrap(),
+            "application/x-protobuf"
+        );
+    }
+
+    /// UT test cases for `Body::shared`.
+    ///
+    /// # Brief
+    /// 1. Creates two `Request`s whose bodies share the same slice.
+    /// 2. Checks that `Content-Length` is set and the slice is not copied.
+    /// 3. Reads a body, reuses it and reads it again.
+    /// 4. Checks that the shared slice is not consumed.
+    #[test]
+    fn ut_client_request_builder_shared_body() {
+        use std::future::Future;
+        use std::pin::Pin;
+        use std::sync::Arc;
+        use std::task::{Context, Poll};
+
+        use crate::async_impl::http_body::noop_waker;
+        use crate::async_impl::request::BodyKind;
+        use crate::runtime::{AsyncRead, ReadBuf};
+
+        fn read_all(body: &mut Body) -> Vec<u8> {
+            let waker = noop_waker();
+            let mut cx = Context::from_waker(&waker);
+            let mut buf = [0u8; 4];
+            let mut output = vec![];
+            loop {
+                let mut read_buf = ReadBuf::new(&mut buf);
+                match Pin::new(&mut *body).poll_read(&mut cx, &mut read_buf) {
+                    Poll::Ready(Ok(())) if read_buf.filled().is_empty() => return output,
+                    Poll::Ready(Ok(())) => output.extend_from_slice(read_buf.filled()),
+                    _ => panic!("The shared body is not ready"),
+                }
+            }
+        }
+
+        let payload: Arc<[u8]> = Arc::from("HelloWorld".as_bytes());
+        let mut first = RequestBuilder::new()
+            .method("POST")
+            .body(Body::shared(payload.clone()))
+            .unwrap();
+        let second = RequestBuilder::new()
+            .method("POST")
+            .body(Body::shared(payload.clone()))
+            .unwrap();
+        assert_eq!(
+            first
+                .headers()
+                .get("Content-Length")
+                .unwrap()
+                .to_string()
+                .unwrap(),
+            "10"
+        );
+        assert_eq!(Arc::strong_count(&payload), 3);
+        assert!(matches!(
+            second.body().inner,
+            BodyKind::Shared(ref shared) if Arc::ptr_eq(shared.get_ref(), &payload)
+        ));
+
+        assert_eq!(read_all(first.body_mut()), b"HelloWorld");
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut reuse = Box::pin(first.body_mut().reuse());
+        assert!(matches!(reuse.as_mut().poll(&mut cx), Poll::Ready(Ok(()))));
+        drop(reuse);
+        assert_eq!(read_all(first.body_mut()), b"HelloWorld");
+        assert_eq!(&*payload, b"HelloWorld");
+    }
+
+    /// UT test cases for `RequestBuilder::body_fn`.
+    ///
+    /// # Brief
+    /// 1. Creates a `Request` whose body is produced by an async closure.
+    /// 2. Checks that the closure is not called before the body is read.
+    /// 3. Reads the body and checks the produced data.
+    /// 4. Creates a `Request` whose closure fails.
+    /// 5. Checks that the error is returned by reading and the body can not be
+    ///    reused.
+    #[test]
+    fn ut_client_request_builder_body_fn() {
+        use std::future::Future;
+        use std::pin::Pin;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        use std::task::{Context, Poll};
+
+        use crate::async_impl::http_body::noop_waker;
+        use crate::runtime::{AsyncRead, ReadBuf};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let mut request = RequestBuilder::new()
+            .method("POST")
+            .body_fn(move || async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok(Body::stream("HelloWorld".as_bytes()))
+            })
+            .unwrap();
+        assert!(request.headers().get("Content-Length").is_none());
+        assert!(request.body().is_reusable());
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut buf = [0u8; 4];
+        let mut output = vec![];
+        loop {
+            let mut read_buf = ReadBuf::new(&mut buf);
+            match Pin::new(request.body_mut()).poll_read(&mut cx, &mut read_buf) {
+                Poll::Ready(Ok(())) if read_buf.filled().is_empty() => break,
+                Poll::Ready(Ok(())) => output.extend_from_slice(read_buf.filled()),
+                _ => panic!("The lazy body is not ready"),
+            }
+        }
+        assert_eq!(output, b"HelloWorld");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(!request.body().is_reusable());
+
+        let mut request = RequestBuilder::new()
+            .method("POST")
+            .body_fn(|| async { Err(std::io::ErrorKind::NotFound.into()) })
+            .unwrap();
+        let mut read_buf = ReadBuf::new(&mut buf);
+        assert!(matches!(
+            Pin::new(request.body_mut()).poll_read(&mut cx, &mut read_buf),
+            Poll::Ready(Err(ref e)) if e.kind() == std::io::ErrorKind::NotFound
+        ));
+        assert!(!request.body().is_reusable());
+        let mut reuse = Box::pin(request.body_mut().reuse());
+        assert!(matches!(reuse.as_mut().poll(&mut cx), Poll::Ready(Err(_))));
+    }
+
+    /// UT test cases for `RequestBuilder::with_body_digest`.
+    ///
+    /// # Brief
+    /// 1. Creates `Request`s with buffered bodies and digest algorithms.
+    /// 2. Checks that `Content-MD5` and `Digest` are set correctly.
+    /// 3. Creates `Request`s with a streaming body and a compressed body.
+    /// 4. Checks that building these `Request`s fails.
+    #[test]
+    fn ut_client_request_builder_with_body_digest() {
+        use crate::{DigestAlgorithm, Encoding, ErrorKind};
+
+        let header = |request: &Request, name: &str| {
+            request.headers().get(name).and_then(|v| v.to_string().ok())
+        };
+
+        let request = RequestBuilder::new()
+            .method("POST")
+            .with_body_digest(DigestAlgorithm::Md5)
+            .body(Body::slice("HelloWorld"))
+            .unwrap();
+        assert_eq!(
+            header(&request, "Content-MD5").as_deref(),
+            Some("aOEJ8PQMpyoV4FzCJ4b45g==")
+        );
+        assert!(header(&request, "Digest").is_none());
+
+        let request = RequestBuilder::new()
+            .method("POST")
+            .with_body_digest(DigestAlgorithm::Sha256)
+            .body(Body::shared(b"HelloWorld".to_vec()))
+            .unwrap();
+        assert_eq!(
+            header(&request, "Digest").as_deref(),
+            Some("SHA-256=hy5OUM6ZkNiwQTMMR8nd0Rvsa1A66ThqmdqFhOm7EsQ=")
+        );
+        assert_eq!(header(&request, "Content-Length").as_deref(), Some("10"));
+
+        let request = RequestBuilder::new()
+            .with_body_digest(DigestAlgorithm::Md5)
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(
+            header(&request, "Content-MD5").as_deref(),
+            Some("1B2M2Y8AsgTpgAmY7PhCfg==")
+        );
+
+        let err = RequestBuilder::new()
+            .method("POST")
+            .with_body_digest(DigestAlgorithm::Sha256)
+            .body(Body::stream("HelloWorld".as_bytes()))
+            .err()
+            .unwrap();
+        assert_eq!(err.error_kind(), ErrorKind::Build);
+
+        let request = RequestBuilder::new()
+            .method("POST")
+            .compress(Encoding::Gzip)
+            .with_body_digest(DigestAlgorithm::Md5)
+            .body(Body::slice("HelloWorld"));
+        assert!(request.is_err());
+    }
+
+    /// UT test cases for `RequestBuilder::auto_content_type`.
+    ///
+    /// # Brief
+    /// 1. Creates `Request`s with multipart and bytes bodies.
+    /// 2. Checks that an explicit `Content-Type` is not replaced.
+    /// 3. Checks that no `Content-Type` is set if `auto_content_type` is
+    ///    `false`.
+    #[test]
+    fn ut_client_request_builder_auto_content_type() {
+        use ylong_http::body::{MultiPart, Part};
+
+        let content_type = |request: &Request| {
+            request
+                .headers()
+                .get("Content-Type")
+                .map(|v| v.to_string().unwrap())
+        };
+        let multipart = || MultiPart::new().part(Part::new().name("name").body("value"));
+
+        let request = RequestBuilder::new()
+            .body(Body::multipart(multipart()))
+            .unwrap();
+        assert!(content_type(&request)
+            .unwrap()
+            .starts_with("multipart/form-data; boundary="));
+
+        let request = RequestBuilder::new()
+            .content_type("multipart/mixed; boundary=abc")
+            .body(Body::multipart(multipart()))
+            .unwrap();
+        assert_eq!(
+            content_type(&request).as_deref(),
+            Some("multipart/mixed; boundary=abc")
+        );
+
+        let request = RequestBuilder::new()
+            .auto_content_type(false)
+            .body(Body::multipart(multipart()))
+            .unwrap();
+        assert_eq!(content_type(&request), None);
+
+        let request = RequestBuilder::new()
+            .auto_content_type(false)
+            .body_bytes(vec![1, 2, 3])
+            .unwrap();
+        assert_eq!(content_type(&request), None);
+        assert!(request.headers().get("Content-Length").is_some());
+
+        let request = RequestBuilder::new()
+            .auto_content_type(false)
+            .content_type("text/plain")
+            .body_bytes(vec![1, 2, 3])
+            .unwrap();
+        assert_eq!(content_type(&request).as_deref(), Some("text/plain"));
+    }
+
     /// UT test cases for `PercentEncoder::encode`.
     ///
     /// # Brief
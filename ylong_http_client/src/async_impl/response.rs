@@ -11,20 +11,44 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use core::mem::take;
 use core::ops::{Deref, DerefMut};
+use std::net::SocketAddr;
+use std::sync::Arc;
 
 use ylong_http::body::async_impl::Body;
+use ylong_http::request::uri::Uri;
 use ylong_http::response::Response as Resp;
 
+#[cfg(feature = "sse")]
+use crate::async_impl::Events;
+#[cfg(feature = "grpc_web")]
+use crate::async_impl::GrpcWeb;
 use crate::async_impl::HttpBody;
 use crate::error::HttpClientError;
+use crate::runtime::{AsyncWrite, AsyncWriteExt};
+use crate::util::decompress::Decompressor;
+use crate::util::interceptor::ByteCounter;
 use crate::util::monitor::TimeGroup;
-use crate::ErrorKind;
+use crate::{ConnDetail, Encoding, ErrorKind, ProxyInfo};
 
 /// A structure that represents an HTTP `Response`.
 pub struct Response {
     pub(crate) inner: Resp<HttpBody>,
     pub(crate) time_group: TimeGroup,
+    pub(crate) local_addr: Option<SocketAddr>,
+    pub(crate) peer_addr: Option<SocketAddr>,
+    pub(crate) via_proxy: Option<Box<ProxyInfo>>,
+    pub(crate) bytes: Arc<ByteCounter>,
+    pub(crate) effective_url: Option<Uri>,
+    pub(crate) retry_count: usize,
+    pub(crate) redirect_count: usize,
+    pub(crate) header_bytes: Option<u64>,
+    pub(crate) decompressor: Option<Decompressor>,
+    // Decompressed bytes which are not read yet.
+    pub(crate) decoded: Vec<u8>,
+    // The maximum size of the body copied by `Response::copy_to`.
+    pub(crate) max_body_size: Option<u64>,
 }
 
 impl Response {
@@ -32,12 +56,76 @@ impl Response {
         Self {
             inner: response,
             time_group: TimeGroup::default(),
+            local_addr: None,
+            peer_addr: None,
+            via_proxy: None,
+            bytes: Arc::new(ByteCounter::default()),
+            effective_url: None,
+            retry_count: 0,
+            redirect_count: 0,
+            header_bytes: None,
+            decompressor: None,
+            decoded: Vec::new(),
+            max_body_size: None,
         }
     }
 
     /// Reads the data of the `HttpBody`.
+    ///
+    /// If the body is decompressed, the compressed body is read until the end
+    /// of its transfer framing, and an error is returned if the compressed
+    /// stream is not complete at that point.
     pub async fn data(&mut self, buf: &mut [u8]) -> Result<usize, HttpClientError> {
-        Body::data(self.inner.body_mut(), buf).await
+        let decompressor = match self.decompressor.as_mut() {
+            Some(decompressor) => decompressor,
+            None => return Body::data(self.inner.body_mut(), buf).await,
+        };
+        let mut chunk = [0u8; 4096];
+        while self.decoded.is_empty() {
+            if decompressor.has_pending() {
+                decompressor.decompress(&[], &mut self.decoded)?;
+                continue;
+            }
+            let size = Body::data(self.inner.body_mut(), &mut chunk).await?;
+            if size == 0 {
+                decompressor.finish()?;
+                return Ok(0);
+            }
+            decompressor.decompress(&chunk[..size], &mut self.decoded)?;
+        }
+        let size = buf.len().min(self.decoded.len());
+        buf[..size].copy_from_slice(&self.decoded[..size]);
+        self.decoded.drain(..size);
+        Ok(size)
+    }
+
+    /// Reads the next data of the body into `buf`, which is owned by the
+    /// caller and can be reused for every read, and returns the size read.
+    /// `0` is returned at the end of the body.
+    ///
+    /// It is the same as `Response::data`, the body is decompressed if
+    /// `auto_decompress` is set.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ylong_http_client::async_impl::Response;
+    /// use ylong_http_client::HttpClientError;
+    ///
+    /// async fn read_body(mut response: Response) -> Result<(), HttpClientError> {
+    ///     let mut buf = [0u8; 4096];
+    ///     loop {
+    ///         let size = response.read(&mut buf).await?;
+    ///         if size == 0 {
+    ///             break;
+    ///         }
+    ///         let _data = &buf[..size];
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn read(&mut self, buf: &mut [u8]) -> Result<usize, HttpClientError> {
+        self.data(buf).await
     }
 
     /// Reads all the message of the `HttpBody` and return it as a `String`.
@@ -55,6 +143,139 @@ impl Response {
         String::from_utf8(vec).map_err(|e| HttpClientError::from_error(ErrorKind::BodyDecode, e))
     }
 
+    /// Converts the `Response` into a `Lines`, which reads the body line by
+    /// line, such as a newline-delimited JSON (NDJSON) stream.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use ylong_http_client::async_impl::Response;
+    /// # use ylong_http_client::HttpClientError;
+    ///
+    /// # async fn read_lines(response: Response) -> Result<(), HttpClientError> {
+    /// let mut lines = response.lines();
+    /// while let Some(line) = lines.next_line().await? {
+    ///     // Deserializes each line as a JSON object.
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn lines(self) -> Lines {
+        Lines {
+            response: self,
+            buf: Vec::new(),
+            scanned: 0,
+            finished: false,
+            max_line_length: DEFAULT_MAX_LINE_LENGTH,
+        }
+    }
+
+    /// Converts the `Response` into an `Events`, which parses the body as a
+    /// `text/event-stream` of server-sent events.
+    #[cfg(feature = "sse")]
+    pub fn events(self) -> Events {
+        Events::new(self)
+    }
+
+    /// Reads the rest of the `HttpBody` and writes it into `writer` piece by
+    /// piece, without buffering the whole body in memory. Returns the total
+    /// number of bytes written.
+    ///
+    /// The body must not be larger than the size set by
+    /// `ClientBuilder::max_body_size`, otherwise an error is returned, early
+    /// if the `Content-Length` of the response already exceeds it.
+    pub async fn copy_to<W>(&mut self, writer: &mut W) -> Result<u64, HttpClientError>
+    where
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        self.check_body_size(self.content_length().unwrap_or(0))?;
+        let mut buf = [0u8; 16 * 1024];
+        let mut total = 0u64;
+        loop {
+            let size = self.data(&mut buf).await?;
+            if size == 0 {
+                break;
+            }
+            self.check_body_size(total + size as u64)?;
+            writer
+                .write_all(&buf[..size])
+                .await
+                .map_err(|e| HttpClientError::from_io_error(ErrorKind::BodyTransfer, e))?;
+            total += size as u64;
+        }
+        writer
+            .flush()
+            .await
+            .map_err(|e| HttpClientError::from_io_error(ErrorKind::BodyTransfer, e))?;
+        Ok(total)
+    }
+
+    /// Reads the whole `HttpBody` and decodes it as a gRPC-Web response,
+    /// whose trailer fields are carried in the last frame of the body.
+    #[cfg(feature = "grpc_web")]
+    pub async fn grpc_web(mut self) -> Result<GrpcWeb, HttpClientError> {
+        let mut buf = [0u8; 1024];
+        let mut vec = Vec::new();
+        loop {
+            let size = self.data(&mut buf).await?;
+            if size == 0 {
+                break;
+            }
+            vec.extend_from_slice(&buf[..size]);
+        }
+        let trailer = Body::trailer(self.inner.body_mut()).await?;
+        GrpcWeb::decode(self.inner.headers().clone(), &vec, trailer)
+    }
+
+    // Checks that `size` bytes of the body are within the maximum size.
+    pub(crate) fn check_body_size(&self, size: u64) -> Result<(), HttpClientError> {
+        match self.max_body_size {
+            Some(max_size) if size > max_size => {
+                err_from_msg!(BodyDecode, "Response body exceeds the maximum size")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Gets the length of the body declared by the response, without reading
+    /// the body.
+    ///
+    /// Returns `Some(0)` if the response has no body, and `None` if the
+    /// length is unknown in advance, such as a chunked body, a body
+    /// delimited by the close of the connection or a decompressed body.
+    pub fn content_length(&self) -> Option<u64> {
+        match self.decompressor {
+            Some(_) => None,
+            None => self.inner.body().content_length(),
+        }
+    }
+
+    /// Decompresses the body according to its `Content-Encoding`. The
+    /// `Content-Encoding` and `Content-Length` are removed, because they
+    /// describe the compressed body.
+    pub(crate) fn decompress_body(&mut self) {
+        if self.content_length() == Some(0) {
+            return;
+        }
+        let encoding = match self
+            .inner
+            .headers()
+            .get("Content-Encoding")
+            .and_then(|value| value.to_string().ok())
+        {
+            Some(value) => match value.trim().to_ascii_lowercase().as_str() {
+                "gzip" | "x-gzip" => Encoding::Gzip,
+                "deflate" => Encoding::Deflate,
+                _ => return,
+            },
+            None => return,
+        };
+        let headers = self.inner.headers_mut();
+        headers.remove("Content-Encoding");
+        headers.remove("Content-Length");
+        self.decompressor = Some(Decompressor::new(encoding));
+    }
+
     /// Gets the time spent on each stage of the request.
     pub fn time_group(&self) -> &TimeGroup {
         &self.time_group
@@ -63,6 +284,97 @@ impl Response {
     pub(crate) fn set_time_group(&mut self, time_group: TimeGroup) {
         self.time_group = time_group
     }
+
+    /// Gets the local socket address of the connection which the `Response`
+    /// is received from. Returns `None` if the transport has no socket
+    /// address.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr
+    }
+
+    /// Gets the peer socket address of the connection which the `Response`
+    /// is received from. Returns `None` if the transport has no socket
+    /// address.
+    pub fn peer_addr(&self) -> Option<SocketAddr> {
+        self.peer_addr
+    }
+
+    /// Gets the proxy which the request was sent through. For `https`, it is
+    /// the proxy which the tunnel was established through with `CONNECT`.
+    /// Returns `None` if the connection is direct.
+    pub fn via_proxy(&self) -> Option<&ProxyInfo> {
+        self.via_proxy.as_deref()
+    }
+
+    /// Gets the number of bytes of the request written to the connection,
+    /// including the request line, the headers and the body framing.
+    ///
+    /// Bytes are counted as they are transmitted, so a compressed body is
+    /// counted after compression. Only `HTTP/1.x` connections are counted,
+    /// `0` is returned for other versions.
+    pub fn bytes_sent(&self) -> u64 {
+        self.bytes.sent()
+    }
+
+    /// Gets the number of bytes of the response read from the connection so
+    /// far, including the status line, the headers and the body framing.
+    ///
+    /// The count grows while the body is read. Bytes are counted as they are
+    /// received, before any decoding. Only `HTTP/1.x` connections are
+    /// counted, `0` is returned for other versions.
+    pub fn bytes_received(&self) -> u64 {
+        self.bytes.received()
+    }
+
+    /// Gets the size in bytes of the response head as it is received,
+    /// including the status line, the header fields and the empty line that
+    /// ends them.
+    ///
+    /// Only `HTTP/1.x` responses are measured, `None` is returned for other
+    /// versions.
+    pub fn header_bytes(&self) -> Option<u64> {
+        self.header_bytes
+    }
+
+    /// Gets the `Uri` of the request which produced the `Response`.
+    ///
+    /// It is the final `Uri` after the redirects have been followed, in the
+    /// normalized form which is actually requested, so the scheme and the
+    /// port are always present. Returns `None` if the `Response` is not
+    /// received by a `Client`.
+    pub fn effective_url(&self) -> Option<&Uri> {
+        self.effective_url.as_ref()
+    }
+
+    /// Gets the number of times the request was retried before the
+    /// `Response` was received.
+    pub fn retry_count(&self) -> usize {
+        self.retry_count
+    }
+
+    /// Gets the number of redirects followed before the `Response` was
+    /// received.
+    pub fn redirect_count(&self) -> usize {
+        self.redirect_count
+    }
+
+    pub(crate) fn set_effective_url(&mut self, uri: Uri) {
+        self.effective_url = Some(uri);
+    }
+
+    pub(crate) fn set_header_bytes(&mut self, size: u64) {
+        self.header_bytes = Some(size);
+    }
+
+    pub(crate) fn set_byte_counter(&mut self, bytes: Arc<ByteCounter>) {
+        self.bytes = bytes;
+    }
+
+    pub(crate) fn set_conn_detail(&mut self, detail: &ConnDetail) {
+        self.local_addr = Some(detail.local());
+        self.peer_addr = Some(detail.peer());
+        self.via_proxy = detail.proxy.clone();
+    }
 }
 
 impl Deref for Response {
@@ -78,3 +390,413 @@ impl DerefMut for Response {
         &mut self.inner
     }
 }
+
+// The default maximum length of a line read by `Lines` or `Events`.
+pub(crate) const DEFAULT_MAX_LINE_LENGTH: usize = 64 * 1024;
+
+/// A reader of the body of a `Response`, which reads it line by line. Lines
+/// are read incrementally, so the body is never buffered as a whole, and a
+/// line longer than `Lines::max_line_length` is an error.
+///
+/// `Lines` is not a `Stream`, as this crate doesn't depend on `futures`.
+/// Lines are read by calling `Lines::next_line` in a loop instead.
+///
+/// Created by `Response::lines`.
+pub struct Lines {
+    response: Response,
+    buf: Vec<u8>,
+    // The bytes of `buf` already known to contain no line feed.
+    scanned: usize,
+    finished: bool,
+    max_line_length: usize,
+}
+
+impl Lines {
+    /// Sets the maximum length of a line, without its line ending. Reading a
+    /// longer line is a `BodyDecode` error. Defaults to 64 KiB.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use ylong_http_client::async_impl::Response;
+    ///
+    /// # fn lines(response: Response) {
+    /// let lines = response.lines().max_line_length(1024 * 1024);
+    /// # }
+    /// ```
+    pub fn max_line_length(mut self, len: usize) -> Self {
+        self.max_line_length = len;
+        self
+    }
+
+    /// Reads the next line of the body, without the trailing `\n` or
+    /// `\r\n`. Returns `None` once the body is read to the end.
+    ///
+    /// A line may span several chunks of the body. If the body doesn't end
+    /// with a line feed, the incomplete last line is returned as it is.
+    ///
+    /// Returns a `BodyDecode` error once a line is longer than
+    /// `Lines::max_line_length`, without buffering the rest of it.
+    pub async fn next_line(&mut self) -> Result<Option<Vec<u8>>, HttpClientError> {
+        let mut chunk = [0u8; 1024];
+        loop {
+            if let Some(pos) = self.buf[self.scanned..].iter().position(|b| *b == b'\n') {
+                let mut line = self.buf.drain(..=self.scanned + pos).collect::<Vec<_>>();
+                line.pop();
+                return self.line(line).map(Some);
+            }
+            self.scanned = self.buf.len();
+            // One more byte is allowed for the `\r` of a `\r\n`.
+            if self.buf.len() > self.max_line_length.saturating_add(1) {
+                return err_from_msg!(BodyDecode, "Line exceeds the maximum length");
+            }
+            if self.finished {
+                if self.buf.is_empty() {
+                    return Ok(None);
+                }
+                let line = take(&mut self.buf);
+                return self.line(line).map(Some);
+            }
+            let size = self.response.data(&mut chunk).await?;
+            if size == 0 {
+                self.finished = true;
+            }
+            self.buf.extend_from_slice(&chunk[..size]);
+        }
+    }
+
+    // Strips the carriage return of `line` and checks its length.
+    fn line(&mut self, mut line: Vec<u8>) -> Result<Vec<u8>, HttpClientError> {
+        self.scanned = 0;
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        if line.len() > self.max_line_length {
+            return err_from_msg!(BodyDecode, "Line exceeds the maximum length");
+        }
+        Ok(line)
+    }
+}
+
+#[cfg(test)]
+mod ut_response {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::Arc;
+    use std::task::{Context, Poll};
+
+    use ylong_http::h1::ResponseDecoder;
+    use ylong_http::response::Response as HttpResponse;
+
+    use crate::async_impl::conn::StreamData;
+    use crate::async_impl::http_body::noop_waker;
+    use crate::async_impl::{HttpBody, Lines, Response};
+    use crate::runtime::{AsyncRead, AsyncWrite, ReadBuf};
+    use crate::util::interceptor::IdleInterceptor;
+    use crate::util::normalizer::BodyLength;
+    use crate::{ErrorKind, HttpClientError};
+
+    struct BodyIo(&'static [u8]);
+
+    impl AsyncRead for BodyIo {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<std::io::Result<()>> {
+            Pin::new(&mut self.0).poll_read(cx, buf)
+        }
+    }
+
+    impl StreamData for BodyIo {
+        fn shutdown(&self) {}
+
+        fn is_stream_closable(&self) -> bool {
+            true
+        }
+    }
+
+    /// UT test cases for `Response::lines`.
+    ///
+    /// # Brief
+    /// 1. Creates a `Response` whose body has a line spanning two chunks and
+    ///    an incomplete last line.
+    /// 2. Reads the body by `Lines::next_line`.
+    /// 3. Checks if the lines are as expected.
+    #[test]
+    fn ut_response_lines() {
+        let head = "HTTP/1.1 200 OK\r\n\r\n".as_bytes();
+        let mut decoder = ResponseDecoder::new();
+        let (part, _) = decoder.decode(head).unwrap().unwrap();
+        let body = HttpBody::new(
+            Arc::new(IdleInterceptor),
+            BodyLength::UntilClose,
+            Box::new(BodyIo("c\"}\r\n\n{\"d\"".as_bytes())),
+            "{\"a\"}\n{\"b".as_bytes(),
+        )
+        .unwrap();
+        let response = Response::new(HttpResponse::from_raw_parts(part, body));
+
+        let mut lines = response.lines();
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut next_line = || match Box::pin(lines.next_line()).as_mut().poll(&mut cx) {
+            Poll::Ready(line) => line.unwrap().map(|line| String::from_utf8(line).unwrap()),
+            Poll::Pending => panic!("The body is read without waiting"),
+        };
+        assert_eq!(next_line().as_deref(), Some("{\"a\"}"));
+        assert_eq!(next_line().as_deref(), Some("{\"bc\"}"));
+        assert_eq!(next_line().as_deref(), Some(""));
+        assert_eq!(next_line().as_deref(), Some("{\"d\""));
+        assert_eq!(next_line(), None);
+        assert_eq!(next_line(), None);
+    }
+
+    /// UT test cases for `Lines::max_line_length`.
+    ///
+    /// # Brief
+    /// 1. Creates `Lines` with a maximum line length of 4.
+    /// 2. Reads a line of 4 bytes, a terminated line of 5 bytes and an
+    ///    unterminated line of 10 bytes by `Lines::next_line`.
+    /// 3. Checks if the longer lines are `BodyDecode` errors.
+    #[test]
+    fn ut_response_lines_max_length() {
+        let lines = |pre: &'static [u8], io: &'static [u8]| {
+            let head = "HTTP/1.1 200 OK\r\n\r\n".as_bytes();
+            let mut decoder = ResponseDecoder::new();
+            let (part, _) = decoder.decode(head).unwrap().unwrap();
+            let body = HttpBody::new(
+                Arc::new(IdleInterceptor),
+                BodyLength::UntilClose,
+                Box::new(BodyIo(io)),
+                pre,
+            )
+            .unwrap();
+            Response::new(HttpResponse::from_raw_parts(part, body))
+                .lines()
+                .max_line_length(4)
+        };
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut next_line =
+            |lines: &mut Lines| match Box::pin(lines.next_line()).as_mut().poll(&mut cx) {
+                Poll::Ready(line) => line,
+                Poll::Pending => panic!("The body is read without waiting"),
+            };
+
+        let mut terminated = lines(b"abcd\r\nabcde\n", b"");
+        assert_eq!(next_line(&mut terminated).unwrap(), Some(b"abcd".to_vec()));
+        let err = next_line(&mut terminated).unwrap_err();
+        assert_eq!(err.error_kind(), ErrorKind::BodyDecode);
+
+        let mut unterminated = lines(b"abc", b"defghij");
+        let err = next_line(&mut unterminated).unwrap_err();
+        assert_eq!(err.error_kind(), ErrorKind::BodyDecode);
+    }
+
+    const GZIP_HELLO: &[u8] = &[
+        0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0xcb, 0x48, 0xcd, 0xc9, 0xc9,
+        0xd7, 0x51, 0xc8, 0xc0, 0xa4, 0x14, 0x01, 0x0b, 0xd8, 0x1d, 0x85, 0x1b, 0x00, 0x00, 0x00,
+    ];
+
+    fn gzip_response(length: u64) -> Response {
+        let head = format!(
+            "HTTP/1.1 200 OK\r\nContent-Encoding: gzip\r\nContent-Length: {length}\r\n\r\n"
+        );
+        let mut decoder = ResponseDecoder::new();
+        let (part, _) = decoder.decode(head.as_bytes()).unwrap().unwrap();
+        let body = HttpBody::new(
+            Arc::new(IdleInterceptor),
+            BodyLength::Length(length),
+            Box::new(BodyIo(&[])),
+            &GZIP_HELLO[..length as usize],
+        )
+        .unwrap();
+        let mut response = Response::new(HttpResponse::from_raw_parts(part, body));
+        response.decompress_body();
+        response
+    }
+
+    fn read_body(response: &mut Response) -> Result<Vec<u8>, HttpClientError> {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut buf = [0u8; 8];
+        let mut vec = Vec::new();
+        loop {
+            let size = match Box::pin(response.data(&mut buf)).as_mut().poll(&mut cx) {
+                Poll::Ready(size) => size?,
+                Poll::Pending => panic!("The body is read without waiting"),
+            };
+            if size == 0 {
+                return Ok(vec);
+            }
+            vec.extend_from_slice(&buf[..size]);
+        }
+    }
+
+    /// UT test cases for `Response::decompress_body`.
+    ///
+    /// # Brief
+    /// 1. Creates a `Response` whose body is a `gzip` stream.
+    /// 2. Reads the body after calling `Response::decompress_body`.
+    /// 3. Creates a `Response` whose `Content-Length` ends the body before
+    ///    the end of the `gzip` stream.
+    /// 4. Checks if the test result is correct.
+    #[test]
+    fn ut_response_decompress_body() {
+        let mut response = gzip_response(GZIP_HELLO.len() as u64);
+        assert!(response.headers().get("Content-Encoding").is_none());
+        assert!(response.headers().get("Content-Length").is_none());
+        assert_eq!(response.content_length(), None);
+        assert_eq!(
+            read_body(&mut response).unwrap(),
+            b"hello, hello, hello, hello!"
+        );
+
+        let mut response = gzip_response(20);
+        let err = read_body(&mut response).unwrap_err();
+        assert_eq!(err.error_kind(), ErrorKind::BodyDecode);
+        assert!(err.is_compressed_body_truncated());
+    }
+
+    /// UT test cases for `Response::copy_to`.
+    ///
+    /// # Brief
+    /// 1. Creates a `Response` whose body is a `gzip` stream.
+    /// 2. Copies the body into a `Vec` by `Response::copy_to`.
+    /// 3. Checks that the decompressed body is copied and its size returned.
+    /// 4. Copies the body with a smaller maximum body size.
+    /// 5. Checks that a `BodyDecode` error is returned.
+    #[test]
+    fn ut_response_copy_to() {
+        struct VecWriter(Vec<u8>);
+
+        impl AsyncWrite for VecWriter {
+            fn poll_write(
+                mut self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+                buf: &[u8],
+            ) -> Poll<std::io::Result<usize>> {
+                self.0.extend_from_slice(buf);
+                Poll::Ready(Ok(buf.len()))
+            }
+
+            fn poll_flush(
+                self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+            ) -> Poll<std::io::Result<()>> {
+                Poll::Ready(Ok(()))
+            }
+
+            fn poll_shutdown(
+                self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+            ) -> Poll<std::io::Result<()>> {
+                Poll::Ready(Ok(()))
+            }
+        }
+
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut copy_to = |response: &mut Response, writer: &mut VecWriter| {
+            let mut fut = Box::pin(response.copy_to(writer));
+            match fut.as_mut().poll(&mut cx) {
+                Poll::Ready(size) => size,
+                Poll::Pending => panic!("The body is read without waiting"),
+            }
+        };
+
+        let mut response = gzip_response(GZIP_HELLO.len() as u64);
+        let mut writer = VecWriter(Vec::new());
+        assert_eq!(copy_to(&mut response, &mut writer).unwrap(), 27);
+        assert_eq!(writer.0, b"hello, hello, hello, hello!");
+
+        let mut response = gzip_response(GZIP_HELLO.len() as u64);
+        response.max_body_size = Some(26);
+        let mut writer = VecWriter(Vec::new());
+        let err = copy_to(&mut response, &mut writer).unwrap_err();
+        assert_eq!(err.error_kind(), ErrorKind::BodyDecode);
+    }
+
+    /// UT test cases for `Response::read`.
+    ///
+    /// # Brief
+    /// 1. Creates a `Response` whose body is partly received.
+    /// 2. Reads the body by `Response::read` into a small buffer repeatedly.
+    /// 3. Checks that the whole body is read and `0` is returned at the end.
+    #[test]
+    fn ut_response_read() {
+        let head = "HTTP/1.1 200 OK\r\n\r\n".as_bytes();
+        let mut decoder = ResponseDecoder::new();
+        let (part, _) = decoder.decode(head).unwrap().unwrap();
+        let body = HttpBody::new(
+            Arc::new(IdleInterceptor),
+            BodyLength::Length(11),
+            Box::new(BodyIo(" world".as_bytes())),
+            "hello".as_bytes(),
+        )
+        .unwrap();
+        let mut response = Response::new(HttpResponse::from_raw_parts(part, body));
+
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut buf = [0u8; 4];
+        let mut data = vec![];
+        loop {
+            let size = match Box::pin(response.read(&mut buf)).as_mut().poll(&mut cx) {
+                Poll::Ready(size) => size.unwrap(),
+                Poll::Pending => panic!("The body is read without waiting"),
+            };
+            if size == 0 {
+                break;
+            }
+            data.extend_from_slice(&buf[..size]);
+        }
+        assert_eq!(data, b"hello world");
+    }
+
+    /// UT test cases for `Response::via_proxy`.
+    ///
+    /// # Brief
+    /// 1. Creates a `Response` and sets a direct connection on it.
+    /// 2. Checks that `Response::via_proxy` returns `None`.
+    /// 3. Sets a connection which goes through a proxy on it.
+    /// 4. Checks that the proxy is returned.
+    #[test]
+    fn ut_response_via_proxy() {
+        use ylong_http::request::uri::Scheme;
+
+        use crate::util::proxy::ProxyInfo;
+        use crate::{ConnDetail, ConnProtocol};
+
+        let head = "HTTP/1.1 200 OK\r\n\r\n".as_bytes();
+        let mut decoder = ResponseDecoder::new();
+        let (part, _) = decoder.decode(head).unwrap().unwrap();
+        let body = HttpBody::new(
+            Arc::new(IdleInterceptor),
+            BodyLength::Empty,
+            Box::new(BodyIo(&[])),
+            &[],
+        )
+        .unwrap();
+        let mut response = Response::new(HttpResponse::from_raw_parts(part, body));
+
+        let mut detail = ConnDetail {
+            protocol: ConnProtocol::Tcp,
+            local: "127.0.0.1:50000".parse().unwrap(),
+            peer: "127.0.0.1:8080".parse().unwrap(),
+            addr: "127.0.0.1:8080".to_string(),
+            proxy: None,
+        };
+        response.set_conn_detail(&detail);
+        assert!(response.via_proxy().is_none());
+
+        detail.proxy = Some(Box::new(
+            ProxyInfo::new("http://proxy.example.com:8080").unwrap(),
+        ));
+        response.set_conn_detail(&detail);
+        let proxy = response.via_proxy().unwrap();
+        assert_eq!(proxy.scheme(), &Scheme::HTTP);
+        assert_eq!(proxy.authority().to_str(), "proxy.example.com:8080");
+    }
+}
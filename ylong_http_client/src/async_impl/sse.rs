@@ -0,0 +1,604 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Server-sent events parsing.
+//!
+//! A `text/event-stream` body is a sequence of lines ended by `CRLF`, `LF` or
+//! `CR`. Each line is a `field: value` pair or a comment starting with `:`,
+//! and an empty line dispatches the event built from the fields before it
+//! ([Server-sent events]).
+//!
+//! [Server-sent events]: https://html.spec.whatwg.org/multipage/server-sent-events.html
+
+use core::mem::take;
+use core::time::Duration;
+
+use ylong_http::response::status::StatusCode;
+
+use crate::async_impl::client::Client;
+use crate::async_impl::response::DEFAULT_MAX_LINE_LENGTH;
+use crate::async_impl::{Connector, Response};
+use crate::error::{ErrorKind, HttpClientError};
+use crate::util::request::RequestArc;
+
+const BOM: &[u8] = b"\xEF\xBB\xBF";
+const DEFAULT_RECONNECTION_TIME: Duration = Duration::from_secs(3);
+
+/// An event parsed from a `text/event-stream` body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    id: Option<String>,
+    event: String,
+    data: String,
+    retry: Option<Duration>,
+}
+
+impl Event {
+    /// Gets the last event ID of the stream when the `Event` is dispatched,
+    /// which is kept until another `id` field changes it. Returns `None` if
+    /// no ID is set.
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Gets the type of the `Event`, which is `message` unless an `event`
+    /// field sets it.
+    pub fn event(&self) -> &str {
+        &self.event
+    }
+
+    /// Gets the data of the `Event`. The values of multiple `data` fields
+    /// are joined by `\n`.
+    pub fn data(&self) -> &str {
+        &self.data
+    }
+
+    /// Gets the reconnection time set by a `retry` field of the `Event`.
+    pub fn retry(&self) -> Option<Duration> {
+        self.retry
+    }
+}
+
+/// A reader of the body of a `Response`, which parses it as server-sent
+/// events. Events are read incrementally, so the body is never buffered as
+/// a whole, and a line longer than `Events::max_line_length` is an error.
+///
+/// Created by `Response::events`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ylong_http_client::async_impl::Client;
+/// use ylong_http_client::HttpClientError;
+///
+/// async fn subscribe() -> Result<(), HttpClientError> {
+///     let client = Client::new();
+///     let response = client.get("http://127.0.0.1:8080/events").send().await?;
+///     let mut events = response.events();
+///     while let Some(event) = events.next_event().await? {
+///         println!("{}: {}", event.event(), event.data());
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct Events {
+    response: Response,
+    buf: Vec<u8>,
+    // The bytes of `buf` already known to contain no line break.
+    scanned: usize,
+    finished: bool,
+    bom_checked: bool,
+    // Whether the last line is ended by a `CR`, so a `LF` right after it is
+    // part of the line break.
+    skip_lf: bool,
+    max_line_length: usize,
+    last_event_id: String,
+    event: String,
+    data: String,
+    retry: Option<Duration>,
+    reconnection_time: Option<Duration>,
+}
+
+impl Events {
+    pub(crate) fn new(response: Response) -> Self {
+        Self {
+            response,
+            buf: Vec::new(),
+            scanned: 0,
+            finished: false,
+            bom_checked: false,
+            skip_lf: false,
+            max_line_length: DEFAULT_MAX_LINE_LENGTH,
+            last_event_id: String::new(),
+            event: String::new(),
+            data: String::new(),
+            retry: None,
+            reconnection_time: None,
+        }
+    }
+
+    /// Sets the maximum length of a line, without its line break. Reading a
+    /// longer line is a `BodyDecode` error. Defaults to 64 KiB.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use ylong_http_client::async_impl::Response;
+    ///
+    /// # fn events(response: Response) {
+    /// let events = response.events().max_line_length(1024 * 1024);
+    /// # }
+    /// ```
+    pub fn max_line_length(mut self, len: usize) -> Self {
+        self.max_line_length = len;
+        self
+    }
+
+    /// Reads the next `Event` of the body. Returns `None` once the body is
+    /// read to the end, an incomplete event at the end of the body is
+    /// discarded.
+    ///
+    /// Returns a `BodyDecode` error once a line is longer than
+    /// `Events::max_line_length`, without buffering the rest of it.
+    pub async fn next_event(&mut self) -> Result<Option<Event>, HttpClientError> {
+        while let Some(line) = self.next_line().await? {
+            if line.is_empty() {
+                if let Some(event) = self.dispatch() {
+                    return Ok(Some(event));
+                }
+                continue;
+            }
+            self.parse_line(&line);
+        }
+        Ok(None)
+    }
+
+    /// Gets the last event ID of the stream, which should be sent in the
+    /// `Last-Event-ID` header when reconnecting. Returns `None` if no ID is
+    /// set.
+    pub fn last_event_id(&self) -> Option<&str> {
+        Some(self.last_event_id.as_str()).filter(|id| !id.is_empty())
+    }
+
+    /// Gets the latest reconnection time set by a `retry` field, including
+    /// the one of an event without data.
+    pub fn reconnection_time(&self) -> Option<Duration> {
+        self.reconnection_time
+    }
+
+    // Reads the body of the `Response` of a reconnection, the last event ID
+    // and the reconnection time are kept.
+    fn reconnected(&mut self, response: Response) {
+        self.response = response;
+        self.buf.clear();
+        self.scanned = 0;
+        self.finished = false;
+        self.bom_checked = false;
+        self.skip_lf = false;
+        self.event.clear();
+        self.data.clear();
+        self.retry = None;
+    }
+
+    fn parse_line(&mut self, line: &[u8]) {
+        // Lines starting with a colon are comments.
+        if line.starts_with(b":") {
+            return;
+        }
+        let (field, value) = match line.iter().position(|b| *b == b':') {
+            Some(pos) => {
+                let value = &line[pos + 1..];
+                (&line[..pos], value.strip_prefix(b" ").unwrap_or(value))
+            }
+            None => (line, &[][..]),
+        };
+        let value = String::from_utf8_lossy(value);
+        match field {
+            b"event" => self.event = value.into_owned(),
+            b"data" => {
+                self.data.push_str(&value);
+                self.data.push('\n');
+            }
+            b"id" if !value.contains('\0') => self.last_event_id = value.into_owned(),
+            b"retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+                if let Ok(millis) = value.parse() {
+                    self.retry = Some(Duration::from_millis(millis));
+                    self.reconnection_time = self.retry;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Builds the event from the fields since the last empty line, an event
+    // without data is not dispatched.
+    fn dispatch(&mut self) -> Option<Event> {
+        let event = take(&mut self.event);
+        let retry = self.retry.take();
+        let mut data = take(&mut self.data);
+        if data.is_empty() {
+            return None;
+        }
+        data.pop();
+        Some(Event {
+            id: self.last_event_id().map(str::to_string),
+            event: if event.is_empty() {
+                "message".to_string()
+            } else {
+                event
+            },
+            data,
+            retry,
+        })
+    }
+
+    // Reads the next line without its line break, a leading `BOM` of the body
+    // is skipped.
+    async fn next_line(&mut self) -> Result<Option<Vec<u8>>, HttpClientError> {
+        loop {
+            if !self.bom_checked {
+                if self.buf.len() < BOM.len() && BOM.starts_with(&self.buf) && !self.finished {
+                    self.read().await?;
+                    continue;
+                }
+                if self.buf.starts_with(BOM) {
+                    self.buf.drain(..BOM.len());
+                }
+                self.bom_checked = true;
+            }
+            if self.skip_lf && !self.buf.is_empty() {
+                if self.buf[0] == b'\n' {
+                    self.buf.remove(0);
+                }
+                self.skip_lf = false;
+            }
+            let unscanned = &self.buf[self.scanned..];
+            if let Some(pos) = unscanned.iter().position(|b| *b == b'\r' || *b == b'\n') {
+                if self.scanned + pos > self.max_line_length {
+                    return err_from_msg!(BodyDecode, "Line exceeds the maximum length");
+                }
+                let mut line = self.buf.drain(..=self.scanned + pos).collect::<Vec<_>>();
+                self.skip_lf = line.pop() == Some(b'\r');
+                self.scanned = 0;
+                return Ok(Some(line));
+            }
+            self.scanned = self.buf.len();
+            if self.buf.len() > self.max_line_length {
+                return err_from_msg!(BodyDecode, "Line exceeds the maximum length");
+            }
+            if self.finished {
+                return Ok(None);
+            }
+            self.read().await?;
+        }
+    }
+
+    async fn read(&mut self) -> Result<(), HttpClientError> {
+        let mut chunk = [0u8; 1024];
+        let size = self.response.data(&mut chunk).await?;
+        if size == 0 {
+            self.finished = true;
+        }
+        self.buf.extend_from_slice(&chunk[..size]);
+        Ok(())
+    }
+}
+
+/// Server-sent events of a `Request`, which sends the `Request` again when
+/// the connection is lost.
+///
+/// Created by `Client::event_source`.
+pub struct EventSource<'a, C: Connector> {
+    client: &'a Client<C>,
+    request: RequestArc,
+    events: Events,
+    max_reconnects: usize,
+    // The number of reconnections since the last event.
+    reconnects: usize,
+}
+
+impl<'a, C: Connector> EventSource<'a, C> {
+    pub(crate) fn new(
+        client: &'a Client<C>,
+        request: RequestArc,
+        response: Response,
+        max_reconnects: usize,
+    ) -> Self {
+        Self {
+            client,
+            request,
+            events: Events::new(response),
+            max_reconnects,
+            reconnects: 0,
+        }
+    }
+
+    /// Reads the next `Event`, reconnecting if the connection is lost.
+    /// Returns `None` once the reconnections are exhausted after the body
+    /// ends, or if a reconnection is answered by `204 No Content`. The error
+    /// of the last attempt is returned if the reconnections are exhausted
+    /// after an error.
+    ///
+    /// A reconnection answered by a status other than `200 OK` or `204 No
+    /// Content`, or by a `Content-Type` other than `text/event-stream`, fails
+    /// the event stream at once, without reconnecting again.
+    ///
+    /// The reconnection time is waited by the clock of the `Client`.
+    pub async fn next_event(&mut self) -> Result<Option<Event>, HttpClientError> {
+        let mut error = match self.events.next_event().await {
+            Ok(Some(event)) => {
+                self.reconnects = 0;
+                return Ok(Some(event));
+            }
+            Ok(None) => None,
+            Err(e) => Some(e),
+        };
+        loop {
+            if self.reconnects >= self.max_reconnects {
+                return error.map_or(Ok(None), Err);
+            }
+            self.reconnects += 1;
+            let delay = self
+                .events
+                .reconnection_time()
+                .unwrap_or(DEFAULT_RECONNECTION_TIME);
+            self.client.clock().sleep(delay).await;
+            error = match self.reconnect().await {
+                Ok(response) => {
+                    if response.status() == StatusCode::NO_CONTENT {
+                        return Ok(None);
+                    }
+                    check_event_stream(&response)?;
+                    self.events.reconnected(response);
+                    match self.events.next_event().await {
+                        Ok(Some(event)) => {
+                            self.reconnects = 0;
+                            return Ok(Some(event));
+                        }
+                        Ok(None) => None,
+                        Err(e) => Some(e),
+                    }
+                }
+                Err(e) => Some(e),
+            };
+        }
+    }
+
+    /// Gets the last event ID received. See `Events::last_event_id`.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.events.last_event_id()
+    }
+
+    // Sends the request again with the last event ID.
+    async fn reconnect(&mut self) -> Result<Response, HttpClientError> {
+        let request = self.request.ref_mut();
+        let headers = request.headers_mut();
+        let _ = headers.remove("Last-Event-ID");
+        if let Some(id) = self.events.last_event_id() {
+            let _ = headers.insert("Last-Event-ID", id);
+        }
+        request
+            .body_mut()
+            .reuse()
+            .await
+            .map_err(|e| HttpClientError::from_io_error(ErrorKind::Request, e))?;
+        self.client.send_request_arc(self.request.clone()).await
+    }
+}
+
+// Checks that the response is an event stream, otherwise the connection fails
+// and must not be reconnected.
+pub(crate) fn check_event_stream(response: &Response) -> Result<(), HttpClientError> {
+    if response.status() != StatusCode::OK {
+        return err_from_msg!(Request, "Event stream is rejected");
+    }
+    let is_event_stream = response
+        .headers()
+        .get("Content-Type")
+        .and_then(|value| value.to_string().ok())
+        .and_then(|value| {
+            let mime = value.split(';').next()?.trim().to_string();
+            Some(mime.eq_ignore_ascii_case("text/event-stream"))
+        })
+        .unwrap_or(false);
+    if !is_event_stream {
+        return err_from_msg!(Request, "Response is not an event stream");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod ut_sse {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::Arc;
+    use std::task::{Context, Poll};
+    use std::time::Duration;
+
+    use ylong_http::h1::ResponseDecoder;
+    use ylong_http::response::Response as HttpResponse;
+
+    use crate::async_impl::conn::StreamData;
+    use crate::async_impl::http_body::noop_waker;
+    use crate::async_impl::{Event, Events, HttpBody, Response};
+    use crate::runtime::{AsyncRead, ReadBuf};
+    use crate::util::interceptor::IdleInterceptor;
+    use crate::util::normalizer::BodyLength;
+    use crate::ErrorKind;
+
+    struct BodyIo(&'static [u8]);
+
+    impl AsyncRead for BodyIo {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<std::io::Result<()>> {
+            Pin::new(&mut self.0).poll_read(cx, buf)
+        }
+    }
+
+    impl StreamData for BodyIo {
+        fn shutdown(&self) {}
+
+        fn is_stream_closable(&self) -> bool {
+            true
+        }
+    }
+
+    // Creates the events of a body, whose first chunk is `pre`.
+    fn body_events(pre: &[u8], rest: &'static [u8]) -> Events {
+        Events::new(body_response(pre, rest))
+    }
+
+    fn body_response(pre: &[u8], rest: &'static [u8]) -> Response {
+        let head = "HTTP/1.1 200 OK\r\n\r\n".as_bytes();
+        let mut decoder = ResponseDecoder::new();
+        let (part, _) = decoder.decode(head).unwrap().unwrap();
+        let body = HttpBody::new(
+            Arc::new(IdleInterceptor),
+            BodyLength::UntilClose,
+            Box::new(BodyIo(rest)),
+            pre,
+        )
+        .unwrap();
+        Response::new(HttpResponse::from_raw_parts(part, body))
+    }
+
+    fn next_event(events: &mut Events) -> Option<Event> {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        match Box::pin(events.next_event()).as_mut().poll(&mut cx) {
+            Poll::Ready(event) => event.unwrap(),
+            Poll::Pending => panic!("The body is read without waiting"),
+        }
+    }
+
+    /// UT test cases for `Events::next_event`.
+    ///
+    /// # Brief
+    /// 1. Creates `Events` of a body with comments, multi-line data, ids and
+    ///    a retry field.
+    /// 2. Reads the events by `Events::next_event`.
+    /// 3. Checks if the events are as expected.
+    #[test]
+    fn ut_sse_events() {
+        let mut events = body_events(
+            b": comment\n\ndata: first\ndata:second\nid: 1\n\n",
+            b"event: update\ndata\nretry: 3000\n\ndata: 2\n\nretry: x\nid\n\ndata: 3\n\ndata: lost",
+        );
+        let event = next_event(&mut events).unwrap();
+        assert_eq!(event.event(), "message");
+        assert_eq!(event.data(), "first\nsecond");
+        assert_eq!(event.id(), Some("1"));
+        assert_eq!(event.retry(), None);
+
+        let event = next_event(&mut events).unwrap();
+        assert_eq!(event.event(), "update");
+        assert_eq!(event.data(), "");
+        assert_eq!(event.retry(), Some(Duration::from_millis(3000)));
+        assert_eq!(
+            events.reconnection_time(),
+            Some(Duration::from_millis(3000))
+        );
+
+        let event = next_event(&mut events).unwrap();
+        assert_eq!(event.event(), "message");
+        assert_eq!(event.data(), "2");
+        assert_eq!(event.id(), Some("1"));
+        assert_eq!(event.retry(), None);
+
+        let event = next_event(&mut events).unwrap();
+        assert_eq!(event.data(), "3");
+        assert_eq!(event.id(), None);
+        assert_eq!(events.last_event_id(), None);
+
+        assert_eq!(next_event(&mut events), None);
+        assert_eq!(next_event(&mut events), None);
+    }
+
+    /// UT test cases for the line breaks of `Events`.
+    ///
+    /// # Brief
+    /// 1. Creates `Events` of a body starting with a `BOM`, whose lines are
+    ///    ended by `CRLF`, `LF` and `CR`, and a `CRLF` spans two chunks.
+    /// 2. Reads the events by `Events::next_event`.
+    /// 3. Checks if the events are as expected.
+    #[test]
+    fn ut_sse_line_breaks() {
+        let mut events = body_events(
+            b"\xEF\xBB\xBFdata: a\r\ndata: b\r",
+            b"\n\rdata: c\r\rdata: d\n\n",
+        );
+        assert_eq!(next_event(&mut events).unwrap().data(), "a\nb");
+        assert_eq!(next_event(&mut events).unwrap().data(), "c");
+        assert_eq!(next_event(&mut events).unwrap().data(), "d");
+        assert_eq!(next_event(&mut events), None);
+
+        let mut events = body_events(b"\xEF\xBB", b"\xBFdata: e\n\n");
+        assert_eq!(next_event(&mut events).unwrap().data(), "e");
+    }
+
+    /// UT test cases for reading the events of a reconnection.
+    ///
+    /// # Brief
+    /// 1. Reads the events of a body with an id, a retry field and an
+    ///    incomplete event.
+    /// 2. Reads the events of another body by `Events::reconnected`.
+    /// 3. Checks that the last event ID and the reconnection time are kept,
+    ///    and the incomplete event is discarded.
+    #[test]
+    fn ut_sse_reconnected() {
+        let mut events = body_events(b"id: 7\nretry: 500\ndata: a\n\n", b"event: lost\ndata: b");
+        assert_eq!(next_event(&mut events).unwrap().data(), "a");
+        assert_eq!(next_event(&mut events), None);
+
+        events.reconnected(body_response(b"\xEF\xBB\xBFdata: c\n\n", b""));
+        let event = next_event(&mut events).unwrap();
+        assert_eq!(event.event(), "message");
+        assert_eq!(event.data(), "c");
+        assert_eq!(event.id(), Some("7"));
+        assert_eq!(event.retry(), None);
+        assert_eq!(events.reconnection_time(), Some(Duration::from_millis(500)));
+        assert_eq!(next_event(&mut events), None);
+    }
+
+    /// UT test cases for `Events::max_line_length`.
+    ///
+    /// # Brief
+    /// 1. Creates `Events` with a maximum line length of 8.
+    /// 2. Reads a body with a line of 8 bytes, then a terminated line of 9
+    ///    bytes, and another body with an unterminated line of 20 bytes.
+    /// 3. Checks that the longer lines are `BodyDecode` errors.
+    #[test]
+    fn ut_sse_max_line_length() {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut next_event = |events: &mut Events| {
+            let mut fut = Box::pin(events.next_event());
+            match fut.as_mut().poll(&mut cx) {
+                Poll::Ready(event) => event,
+                Poll::Pending => panic!("The body is read without waiting"),
+            }
+        };
+
+        let mut events = body_events(b"data: ab\r\n\r\ndata: abc\n\n", b"").max_line_length(8);
+        assert_eq!(next_event(&mut events).unwrap().unwrap().data(), "ab");
+        let err = next_event(&mut events).unwrap_err();
+        assert_eq!(err.error_kind(), ErrorKind::BodyDecode);
+
+        let mut events = body_events(b"data: 0123", b"456789abcdef").max_line_length(8);
+        let err = next_event(&mut events).unwrap_err();
+        assert_eq!(err.error_kind(), ErrorKind::BodyDecode);
+    }
+}
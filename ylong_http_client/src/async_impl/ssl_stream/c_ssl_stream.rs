@@ -20,6 +20,7 @@ use crate::async_impl::ssl_stream::{check_io_to_poll, Wrapper};
 use crate::runtime::{AsyncRead, AsyncWrite, ReadBuf};
 use crate::util::c_openssl::error::ErrorStack;
 use crate::util::c_openssl::ssl::{self, ShutdownResult, Ssl, SslErrorCode};
+use crate::util::information::{PeerCert, TlsInfo};
 
 /// An asynchronous version of [`openssl::ssl::SslStream`].
 #[derive(Debug)]
@@ -47,10 +48,28 @@ impl<S> AsyncSslStream<S> {
         unsafe { Pin::new_unchecked(&mut self.get_unchecked_mut().0.get_mut().stream) }
     }
 
-    #[cfg(feature = "http2")]
     pub(crate) fn negotiated_alpn_protocol(&self) -> Option<&[u8]> {
         self.0.ssl().negotiated_alpn_protocol()
     }
+
+    pub(crate) fn tls_info(&self) -> TlsInfo {
+        let ssl = self.0.ssl();
+        let peer_certs = ssl
+            .peer_cert_chain()
+            .map(|chain| {
+                chain
+                    .iter()
+                    .map(|cert| PeerCert::new(cert.subject_line(), cert.issuer_line()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        TlsInfo::new(
+            ssl.version_str(),
+            ssl.cipher_name().unwrap_or_default(),
+            ssl.negotiated_alpn_protocol().map(Vec::from),
+            peer_certs,
+        )
+    }
 }
 
 impl<S> AsyncSslStream<S>
@@ -82,11 +82,7 @@ mod ut_timeout {
     #[test]
     fn ut_timeout_future() {
         let future1 = Box::pin(async {
-            let part = ResponsePart {
-                version: Version::HTTP1_1,
-                status: StatusCode::OK,
-                headers: Default::default(),
-            };
+            let part = ResponsePart::new(Version::HTTP1_1, StatusCode::OK, Default::default());
             let body = HttpBody::new(
                 Arc::new(IdleInterceptor),
                 BodyLength::Empty,
@@ -0,0 +1,48 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `TokenProvider` trait which supplies the bearer tokens of a `Client`.
+
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::error::HttpClientError;
+
+/// Futures generated by a `TokenProvider` when attempting to get a token.
+pub type TokenFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<String, HttpClientError>> + Sync + Send + 'a>>;
+
+/// `TokenProvider` supplies the bearer tokens of the requests sent by a
+/// `Client`, see `ClientBuilder::auth_provider`.
+///
+/// The provider decides whether a token is cached and when it is refreshed.
+///
+/// # Examples
+///
+/// ```
+/// use ylong_http_client::async_impl::{TokenFuture, TokenProvider};
+///
+/// struct StaticToken;
+///
+/// impl TokenProvider for StaticToken {
+///     fn token(&self, _refresh: bool) -> TokenFuture<'_> {
+///         Box::pin(async { Ok(String::from("mF_9.B5f-4.1JqM")) })
+///     }
+/// }
+/// ```
+pub trait TokenProvider: Send + Sync + 'static {
+    /// Gets the token of the next request. `refresh` is `true` if the
+    /// previous token has been rejected by a `401 Unauthorized` response, in
+    /// which case a cached token should not be returned again.
+    fn token(&self, refresh: bool) -> TokenFuture<'_>;
+}
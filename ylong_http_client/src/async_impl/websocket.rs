@@ -0,0 +1,451 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! WebSocket handshake implementation.
+//!
+//! A WebSocket is opened through the `HTTP/2` extended CONNECT method
+//! ([RFC8441]) when the connection uses `HTTP/2`, or through the `HTTP/1.1`
+//! `Upgrade` mechanism ([RFC6455]) otherwise.
+//!
+//! [RFC8441]: https://www.rfc-editor.org/rfc/rfc8441.html
+//! [RFC6455]: https://www.rfc-editor.org/rfc/rfc6455.html
+
+#[cfg(all(feature = "http1_1", not(feature = "__tls")))]
+use std::io::Read;
+#[cfg(feature = "http2")]
+use std::pin::Pin;
+use std::sync::Arc;
+#[cfg(feature = "http2")]
+use std::task::{Context, Poll};
+
+#[cfg(feature = "http2")]
+use ylong_http::body::async_impl::ReusableReader;
+use ylong_http::request::method::Method;
+use ylong_http::version::Version;
+
+use crate::async_impl::conn;
+use crate::async_impl::request::Message;
+#[cfg(feature = "http2")]
+use crate::async_impl::Response;
+use crate::async_impl::{Body, Request};
+use crate::error::{ErrorKind, HttpClientError};
+#[cfg(feature = "http2")]
+use crate::runtime::ReadBuf;
+#[cfg(feature = "http2")]
+use crate::runtime::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use crate::runtime::{AsyncRead, AsyncWrite};
+#[cfg(feature = "http1_1")]
+use crate::runtime::{AsyncReadExt, AsyncWriteExt};
+#[cfg(feature = "http1_1")]
+use crate::util::base64;
+use crate::util::dispatcher::Conn;
+use crate::util::interceptor::Interceptors;
+use crate::util::request::RequestArc;
+#[cfg(feature = "http1_1")]
+use crate::util::sha1;
+use crate::util::ConnInfo;
+
+#[cfg(feature = "http1_1")]
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+const WEBSOCKET_VERSION: &str = "13";
+
+/// A bidirectional WebSocket channel opened by [`Client::websocket`].
+///
+/// `WebSocket` transfers the raw bytes of the WebSocket connection after the
+/// opening handshake. Framing the data as described in [RFC6455] is left to
+/// the user.
+///
+/// [`Client::websocket`]: crate::async_impl::Client::websocket
+/// [RFC6455]: https://www.rfc-editor.org/rfc/rfc6455.html#section-5
+///
+/// # Examples
+///
+/// ```no_run
+/// use ylong_http_client::async_impl::{Body, Client, Request};
+/// use ylong_http_client::HttpClientError;
+///
+/// async fn websocket() -> Result<(), HttpClientError> {
+///     let client = Client::new();
+///     let request = Request::builder()
+///         .url("https://www.example.com/chat")
+///         .body(Body::empty())?;
+///     let mut websocket = client.websocket(request).await?;
+///
+///     // A masked, unfragmented text frame that contains "Hello".
+///     let frame = [
+///         0x81, 0x85, 0x37, 0xfa, 0x21, 0x3d, 0x7f, 0x9f, 0x4d, 0x51, 0x58,
+///     ];
+///     websocket.send(&frame).await?;
+///
+///     let mut buf = [0u8; 1024];
+///     let size = websocket.recv(&mut buf).await?;
+///     websocket.close().await?;
+///     Ok(())
+/// }
+/// ```
+pub struct WebSocket {
+    version: Version,
+    inner: Inner,
+}
+
+enum Inner {
+    #[cfg(feature = "http1_1")]
+    Http1 {
+        io: Box<dyn UpgradedIo>,
+        pre: Vec<u8>,
+    },
+    #[cfg(feature = "http2")]
+    Http2 {
+        sender: Option<UnboundedSender<Vec<u8>>>,
+        response: Box<Response>,
+    },
+}
+
+trait UpgradedIo: AsyncRead + AsyncWrite + Sync + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Sync + Send + Unpin> UpgradedIo for T {}
+
+impl WebSocket {
+    /// Gets the HTTP version that is used by the opening handshake.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ylong_http_client::async_impl::WebSocket;
+    ///
+    /// fn is_http2(websocket: &WebSocket) -> bool {
+    ///     websocket.version() == &ylong_http_client::Version::HTTP2
+    /// }
+    /// ```
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    /// Sends bytes to the peer.
+    pub async fn send(&mut self, data: &[u8]) -> Result<(), HttpClientError> {
+        match self.inner {
+            #[cfg(feature = "http1_1")]
+            Inner::Http1 { ref mut io, .. } => match io.write_all(data).await {
+                Ok(()) => Ok(()),
+                Err(e) => err_from_io!(BodyTransfer, e),
+            },
+            #[cfg(feature = "http2")]
+            Inner::Http2 { ref sender, .. } => match sender {
+                Some(sender) if sender.send(data.to_vec()).is_ok() => Ok(()),
+                _ => err_from_msg!(BodyTransfer, "WebSocket is closed"),
+            },
+        }
+    }
+
+    /// Receives bytes from the peer. `Ok(0)` means that the peer has closed
+    /// the channel.
+    pub async fn recv(&mut self, buf: &mut [u8]) -> Result<usize, HttpClientError> {
+        match self.inner {
+            #[cfg(feature = "http1_1")]
+            Inner::Http1 {
+                ref mut io,
+                ref mut pre,
+            } => {
+                if !pre.is_empty() {
+                    let size = std::cmp::min(pre.len(), buf.len());
+                    buf[..size].copy_from_slice(&pre[..size]);
+                    pre.drain(..size);
+                    return Ok(size);
+                }
+                match io.read(buf).await {
+                    Ok(size) => Ok(size),
+                    Err(e) => err_from_io!(BodyTransfer, e),
+                }
+            }
+            #[cfg(feature = "http2")]
+            Inner::Http2 {
+                ref mut response, ..
+            } => response.data(buf).await,
+        }
+    }
+
+    /// Closes the sending half of the channel. Bytes sent by the peer can
+    /// still be received.
+    pub async fn close(&mut self) -> Result<(), HttpClientError> {
+        match self.inner {
+            #[cfg(feature = "http1_1")]
+            Inner::Http1 { ref mut io, .. } => match io.shutdown().await {
+                Ok(()) => Ok(()),
+                Err(e) => err_from_io!(BodyTransfer, e),
+            },
+            #[cfg(feature = "http2")]
+            Inner::Http2 { ref mut sender, .. } => {
+                // The END_STREAM flag is sent once the sender is dropped.
+                sender.take();
+                Ok(())
+            }
+        }
+    }
+}
+
+pub(crate) async fn handshake<S>(
+    conn: Conn<S>,
+    request: RequestArc,
+    interceptor: Arc<Interceptors>,
+) -> Result<WebSocket, HttpClientError>
+where
+    S: AsyncRead + AsyncWrite + ConnInfo + Sync + Send + Unpin + 'static,
+{
+    match conn {
+        #[cfg(feature = "http1_1")]
+        Conn::Http1(http1) => upgrade_http1(http1, request, interceptor).await,
+        #[cfg(feature = "http2")]
+        Conn::Http2(http2) => connect_http2(http2, request, interceptor).await,
+        #[cfg(feature = "http3")]
+        Conn::Http3(_) => err_from_msg!(ConnectionUpgrade, "WebSocket over HTTP/3 is unsupported"),
+    }
+}
+
+#[cfg(feature = "http1_1")]
+async fn upgrade_http1<S>(
+    conn: crate::util::dispatcher::http1::Http1Conn<S>,
+    mut request: RequestArc,
+    interceptor: Arc<Interceptors>,
+) -> Result<WebSocket, HttpClientError>
+where
+    S: AsyncRead + AsyncWrite + ConnInfo + Sync + Send + Unpin + 'static,
+{
+    let key = generate_key()?;
+    {
+        let request = request.ref_mut();
+        *request.method_mut() = Method::GET;
+        *request.version_mut() = Version::HTTP1_1;
+        *request.body_mut() = Body::empty();
+        insert_header(request, "Upgrade", "websocket")?;
+        insert_header(request, "Connection", "Upgrade")?;
+        insert_header(request, "Sec-WebSocket-Key", key.as_str())?;
+        insert_header(request, "Sec-WebSocket-Version", WEBSOCKET_VERSION)?;
+    }
+
+    let message = Message {
+        request,
+        interceptor,
+        ttfb_timeout: None,
+        read_timeout: None,
+        chunk_size: None,
+        upload_rate_limit: None,
+        reject_until_close: false,
+        max_until_close_size: None,
+        max_header_size: None,
+        expect_continue_timeout: None,
+        preserve_header_case: false,
+        #[cfg(feature = "http2")]
+        stream_idle_timeout: None,
+    };
+    let (part, io, pre) = conn::upgrade(conn, message).await?;
+
+    let upgrade = part
+        .headers
+        .get("Upgrade")
+        .and_then(|value| value.to_string().ok());
+    if !matches!(upgrade, Some(ref value) if value.eq_ignore_ascii_case("websocket")) {
+        return err_from_msg!(ConnectionUpgrade, "Invalid Upgrade in WebSocket handshake");
+    }
+    let accept = part
+        .headers
+        .get("Sec-WebSocket-Accept")
+        .and_then(|value| value.to_string().ok());
+    if accept.as_deref() != Some(accept_key(key.as_str()).as_str()) {
+        return err_from_msg!(
+            ConnectionUpgrade,
+            "Invalid Sec-WebSocket-Accept in WebSocket handshake"
+        );
+    }
+
+    Ok(WebSocket {
+        version: Version::HTTP1_1,
+        inner: Inner::Http1 {
+            io: Box::new(io),
+            pre,
+        },
+    })
+}
+
+#[cfg(feature = "http2")]
+async fn connect_http2<S>(
+    conn: crate::util::dispatcher::http2::Http2Conn<S>,
+    mut request: RequestArc,
+    interceptor: Arc<Interceptors>,
+) -> Result<WebSocket, HttpClientError>
+where
+    S: Sync + Send + Unpin + 'static,
+{
+    let (sender, receiver) = unbounded_channel();
+    {
+        let request = request.ref_mut();
+        *request.method_mut() = Method::CONNECT;
+        *request.version_mut() = Version::HTTP2;
+        insert_header(request, "Sec-WebSocket-Version", WEBSOCKET_VERSION)?;
+        *request.body_mut() = Body::stream(ChannelReader::new(receiver));
+    }
+
+    let message = Message {
+        request,
+        interceptor,
+        ttfb_timeout: None,
+        read_timeout: None,
+        chunk_size: None,
+        upload_rate_limit: None,
+        reject_until_close: false,
+        max_until_close_size: None,
+        max_header_size: None,
+        expect_continue_timeout: None,
+        preserve_header_case: false,
+        #[cfg(feature = "http2")]
+        stream_idle_timeout: None,
+    };
+    let response = conn::extended_connect(conn, message, "websocket").await?;
+    Ok(WebSocket {
+        version: Version::HTTP2,
+        inner: Inner::Http2 {
+            sender: Some(sender),
+            response: Box::new(response),
+        },
+    })
+}
+
+fn insert_header(request: &mut Request, name: &str, value: &str) -> Result<(), HttpClientError> {
+    request
+        .headers_mut()
+        .insert(name, value)
+        .map(|_| ())
+        .map_err(|e| HttpClientError::from_error(ErrorKind::Request, e))
+}
+
+#[cfg(feature = "http1_1")]
+fn generate_key() -> Result<String, HttpClientError> {
+    let mut nonce = [0u8; 16];
+    fill_random(&mut nonce)?;
+    // All characters in base64 format are valid UTF-8 characters.
+    Ok(String::from_utf8_lossy(&base64::encode(&nonce)).into_owned())
+}
+
+#[cfg(all(feature = "http1_1", feature = "__tls"))]
+fn fill_random(buf: &mut [u8]) -> Result<(), HttpClientError> {
+    crate::util::c_openssl::rand_bytes(buf)
+        .map_err(|e| HttpClientError::from_error(ErrorKind::ConnectionUpgrade, e))
+}
+
+// Without TLS, the random bytes are read from the random source of the OS.
+#[cfg(all(feature = "http1_1", not(feature = "__tls")))]
+fn fill_random(buf: &mut [u8]) -> Result<(), HttpClientError> {
+    std::fs::File::open("/dev/urandom")
+        .and_then(|mut file| file.read_exact(buf))
+        .map_err(|e| HttpClientError::from_io_error(ErrorKind::ConnectionUpgrade, e))
+}
+
+#[cfg(feature = "http1_1")]
+fn accept_key(key: &str) -> String {
+    let digest = sha1::digest(format!("{key}{WEBSOCKET_GUID}").as_bytes());
+    String::from_utf8_lossy(&base64::encode(&digest)).into_owned()
+}
+
+// The request body of an extended CONNECT stream, fed by `WebSocket::send`.
+#[cfg(feature = "http2")]
+struct ChannelReader {
+    receiver: UnboundedReceiver<Vec<u8>>,
+    remain: Vec<u8>,
+}
+
+#[cfg(feature = "http2")]
+impl ChannelReader {
+    fn new(receiver: UnboundedReceiver<Vec<u8>>) -> Self {
+        Self {
+            receiver,
+            remain: Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "http2")]
+impl AsyncRead for ChannelReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        if this.remain.is_empty() {
+            #[cfg(feature = "tokio_base")]
+            match this.receiver.poll_recv(cx) {
+                Poll::Ready(Some(data)) => this.remain = data,
+                // All senders are dropped, so the stream is finished.
+                Poll::Ready(None) => return Poll::Ready(Ok(())),
+                Poll::Pending => return Poll::Pending,
+            }
+            #[cfg(feature = "ylong_base")]
+            match this.receiver.poll_recv(cx) {
+                Poll::Ready(Ok(data)) => this.remain = data,
+                Poll::Ready(Err(_)) => return Poll::Ready(Ok(())),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        let size = std::cmp::min(this.remain.len(), buf.remaining());
+        #[cfg(feature = "tokio_base")]
+        buf.put_slice(&this.remain[..size]);
+        #[cfg(feature = "ylong_base")]
+        buf.append(&this.remain[..size]);
+        this.remain.drain(..size);
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(feature = "http2")]
+impl ReusableReader for ChannelReader {
+    fn reuse<'a>(
+        &'a mut self,
+    ) -> Pin<Box<dyn std::future::Future<Output = std::io::Result<()>> + Send + Sync + 'a>>
+    where
+        Self: 'a,
+    {
+        Box::pin(async { Err(std::io::Error::other("WebSocket body is not reusable")) })
+    }
+}
+
+#[cfg(feature = "http1_1")]
+#[cfg(test)]
+mod ut_websocket {
+    use crate::async_impl::websocket::{accept_key, generate_key};
+
+    /// UT test cases for `accept_key`.
+    ///
+    /// # Brief
+    /// 1. Calls `accept_key` with the sample key of RFC6455.
+    /// 2. Checks if the result is the same as the sample accept value.
+    #[test]
+    fn ut_websocket_accept_key() {
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    /// UT test cases for `generate_key`.
+    ///
+    /// # Brief
+    /// 1. Calls `generate_key` twice.
+    /// 2. Checks if the keys are base64-encoded 16-byte values that differ.
+    #[test]
+    fn ut_websocket_generate_key() {
+        let first = generate_key().unwrap();
+        let second = generate_key().unwrap();
+        assert_eq!(first.len(), 24);
+        assert!(first.ends_with("=="));
+        assert_ne!(first, second);
+    }
+}
@@ -0,0 +1,163 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use super::Response;
+use crate::async_impl::{Client as AsyncClient, Request};
+use crate::error::HttpClientError;
+
+/// A blocking HTTP client which wraps an [`async_impl::Client`].
+///
+/// The `Client` owns a multi-thread runtime, which drives the `async_impl`
+/// components, such as connection tasks in the pool. The runtime is shut down
+/// when the `Client` and all the `Response`s returned by it are dropped.
+///
+/// The `Client` can be shared between threads, and each thread blocks on its
+/// own request.
+///
+/// [`async_impl::Client`]: crate::async_impl::Client
+///
+/// # Examples
+///
+/// ```no_run
+/// use ylong_http_client::async_impl::{Body, Request};
+/// use ylong_http_client::blocking::Client;
+/// use ylong_http_client::HttpClientError;
+///
+/// fn blocking_client() -> Result<(), HttpClientError> {
+///     let client = Client::new()?;
+///     let request = Request::builder()
+///         .url("http://www.example.com")
+///         .body(Body::empty())?;
+///     let response = client.request(request)?;
+///     let text = response.text()?;
+///     Ok(())
+/// }
+/// ```
+pub struct Client {
+    inner: AsyncClient,
+    runtime: Arc<Runtime>,
+}
+
+impl Client {
+    /// Creates a new, default `Client`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::blocking::Client;
+    ///
+    /// let client = Client::new();
+    /// ```
+    pub fn new() -> Result<Self, HttpClientError> {
+        Self::with_client(AsyncClient::new())
+    }
+
+    /// Creates a `Client` which wraps the given `async_impl::Client`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::async_impl::ClientBuilder;
+    /// use ylong_http_client::blocking::Client;
+    /// use ylong_http_client::HttpClientError;
+    ///
+    /// fn blocking_client() -> Result<(), HttpClientError> {
+    ///     let client = ClientBuilder::new().build()?;
+    ///     let client = Client::with_client(client)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_client(client: AsyncClient) -> Result<Self, HttpClientError> {
+        Ok(Self {
+            inner: client,
+            runtime: Arc::new(Runtime::new()?),
+        })
+    }
+
+    /// Sends HTTP `Request` and blocks the current thread until the
+    /// `Response` is received.
+    ///
+    /// # Panics
+    ///
+    /// Panics if it is called in an asynchronous context.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ylong_http_client::async_impl::{Body, Request};
+    /// use ylong_http_client::blocking::Client;
+    /// use ylong_http_client::HttpClientError;
+    ///
+    /// fn blocking_client() -> Result<(), HttpClientError> {
+    ///     let client = Client::new()?;
+    ///     let response = client.request(Request::builder().body(Body::empty())?)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn request(&self, request: Request) -> Result<Response, HttpClientError> {
+        let response = self.runtime.block_on(self.inner.request(request))?;
+        Ok(Response::new(response, Arc::clone(&self.runtime)))
+    }
+}
+
+// The dedicated runtime of the blocking `Client`.
+pub(crate) struct Runtime {
+    #[cfg(feature = "tokio_base")]
+    inner: tokio::runtime::Runtime,
+    #[cfg(feature = "ylong_base")]
+    inner: ylong_runtime::executor::Runtime,
+}
+
+impl Runtime {
+    #[cfg(feature = "tokio_base")]
+    fn new() -> Result<Self, HttpClientError> {
+        let inner = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| HttpClientError::from_io_error(crate::ErrorKind::Build, e))?;
+        Ok(Self { inner })
+    }
+
+    #[cfg(feature = "ylong_base")]
+    fn new() -> Result<Self, HttpClientError> {
+        let inner = ylong_runtime::builder::RuntimeBuilder::new_multi_thread()
+            .build()
+            .map_err(|e| HttpClientError::from_error(crate::ErrorKind::Build, e))?;
+        Ok(Self { inner })
+    }
+
+    pub(crate) fn block_on<F: core::future::Future>(&self, future: F) -> F::Output {
+        self.inner.block_on(future)
+    }
+}
+
+#[cfg(test)]
+mod ut_blocking_client {
+    use crate::blocking::{Client, Response};
+
+    /// UT test cases for `blocking::Client::new`.
+    ///
+    /// # Brief
+    /// 1. Creates a `Client` by calling `Client::new`.
+    /// 2. Checks if the `Client` and `Response` can be shared between threads.
+    #[test]
+    fn ut_blocking_client_new() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        fn assert_send<T: Send>() {}
+        assert_send_sync::<Client>();
+        assert_send::<Response>();
+        assert!(Client::new().is_ok());
+    }
+}
@@ -0,0 +1,35 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! HTTP blocking client module.
+//!
+//! This module provides a blocking wrapper of [`async_impl::Client`], which
+//! has the same features as the asynchronous client, such as HTTP/2 and
+//! connection pooling.
+//!
+//! - [`Client`]: Owns an `async_impl::Client` and a dedicated runtime, and
+//!   sends requests by blocking on the asynchronous calls.
+//!
+//! - [`Response`]: A response whose body can be read in a blocking manner.
+//!
+//! Unlike `sync_impl`, which is a separate blocking implementation, this module
+//! reuses all the components of `async_impl`. Its interfaces must not be called
+//! in an asynchronous context.
+//!
+//! [`async_impl::Client`]: crate::async_impl::Client
+
+mod client;
+mod response;
+
+pub use client::Client;
+pub use response::Response;
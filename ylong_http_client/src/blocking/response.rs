@@ -0,0 +1,121 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::ops::{Deref, DerefMut};
+use std::io::{self, Read, Write};
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use ylong_http::response::Response as Resp;
+
+use super::client::Runtime;
+use crate::async_impl::{HttpBody, Response as AsyncResponse};
+use crate::error::{ErrorKind, HttpClientError};
+use crate::util::monitor::TimeGroup;
+
+/// A structure that represents an HTTP `Response` of the blocking `Client`.
+///
+/// The body of the `Response` can be read by [`Response::data`] or by the
+/// `std::io::Read` trait, which blocks the current thread.
+pub struct Response {
+    inner: AsyncResponse,
+    runtime: Arc<Runtime>,
+}
+
+impl Response {
+    pub(crate) fn new(response: AsyncResponse, runtime: Arc<Runtime>) -> Self {
+        Self {
+            inner: response,
+            runtime,
+        }
+    }
+
+    /// Reads the data of the `HttpBody`.
+    pub fn data(&mut self, buf: &mut [u8]) -> Result<usize, HttpClientError> {
+        self.runtime.block_on(self.inner.data(buf))
+    }
+
+    /// Reads all the message of the `HttpBody` and return it as a `String`.
+    pub fn text(self) -> Result<String, HttpClientError> {
+        self.runtime.block_on(self.inner.text())
+    }
+
+    /// Reads the rest of the `HttpBody` and writes it into `writer` piece by
+    /// piece, without buffering the whole body in memory. Returns the total
+    /// number of bytes written.
+    ///
+    /// The body must not be larger than the size set by
+    /// `ClientBuilder::max_body_size` of the async `Client`, otherwise an
+    /// error is returned.
+    pub fn copy_to<W>(&mut self, writer: &mut W) -> Result<u64, HttpClientError>
+    where
+        W: Write + ?Sized,
+    {
+        let inner = &self.inner;
+        inner.check_body_size(inner.content_length().unwrap_or(0))?;
+        let mut buf = [0u8; 16 * 1024];
+        let mut total = 0u64;
+        loop {
+            let size = self.data(&mut buf)?;
+            if size == 0 {
+                break;
+            }
+            self.inner.check_body_size(total + size as u64)?;
+            writer
+                .write_all(&buf[..size])
+                .map_err(|e| HttpClientError::from_io_error(ErrorKind::BodyTransfer, e))?;
+            total += size as u64;
+        }
+        writer
+            .flush()
+            .map_err(|e| HttpClientError::from_io_error(ErrorKind::BodyTransfer, e))?;
+        Ok(total)
+    }
+
+    /// Gets the time spent on each stage of the request.
+    pub fn time_group(&self) -> &TimeGroup {
+        self.inner.time_group()
+    }
+
+    /// Gets the local socket address of the connection which the `Response`
+    /// is received from.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.inner.local_addr()
+    }
+
+    /// Gets the peer socket address of the connection which the `Response`
+    /// is received from.
+    pub fn peer_addr(&self) -> Option<SocketAddr> {
+        self.inner.peer_addr()
+    }
+}
+
+impl Read for Response {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.data(buf).map_err(io::Error::other)
+    }
+}
+
+impl Deref for Response {
+    type Target = Resp<HttpBody>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl DerefMut for Response {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
@@ -107,6 +107,9 @@ impl HttpClientError {
 
     /// Check whether the cause of the error is dns error
     ///
+    /// A name-resolution failure and a connection failure both have the
+    /// `ErrorKind::Connect` kind, this method can be used to tell them apart.
+    ///
     /// # Examples
     ///
     /// ```
@@ -115,7 +118,44 @@ impl HttpClientError {
     /// assert!(!HttpClientError::user_aborted().is_dns_error())
     /// ```
     pub fn is_dns_error(&self) -> bool {
-        matches!(self.cause, Cause::Dns(_))
+        matches!(self.cause, Cause::Dns(..))
+    }
+
+    /// Check whether the dns resolution did not finish within the dns
+    /// timeout.
+    ///
+    /// Such an error is also a dns error, but it has the `ErrorKind::Timeout`
+    /// kind.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::HttpClientError;
+    ///
+    /// assert!(!HttpClientError::user_aborted().is_dns_timeout())
+    /// ```
+    pub fn is_dns_timeout(&self) -> bool {
+        matches!(self.cause, Cause::Dns(ref err, _) if err.kind() == io::ErrorKind::TimedOut)
+    }
+
+    /// Gets the hostname that failed to be resolved if this `HttpClientError`
+    /// is a dns error.
+    ///
+    /// Returns `None` if the `HttpClientError` is not a dns error or the
+    /// hostname is unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::HttpClientError;
+    ///
+    /// assert!(HttpClientError::user_aborted().dns_host().is_none())
+    /// ```
+    pub fn dns_host(&self) -> Option<&str> {
+        match self.cause {
+            Cause::Dns(_, Some(ref host)) => Some(host),
+            _ => None,
+        }
     }
 
     /// Check whether the cause of the error is tls connection error
@@ -131,6 +171,86 @@ impl HttpClientError {
     pub fn is_tls_error(&self) -> bool {
         matches!(self.cause, Cause::Tls(_))
     }
+
+    /// Check whether the connection was closed by the peer before any byte
+    /// of the response was received.
+    ///
+    /// The request may not have been processed by the server in this case, so
+    /// idempotent requests can be safely sent again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::HttpClientError;
+    ///
+    /// assert!(!HttpClientError::user_aborted().is_closed_before_response())
+    /// ```
+    pub fn is_closed_before_response(&self) -> bool {
+        matches!(self.cause, Cause::Closed(Closed::BeforeResponse))
+    }
+
+    /// Check whether the connection was closed by the peer while the response
+    /// body was being received, which means that the body is truncated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::HttpClientError;
+    ///
+    /// assert!(!HttpClientError::user_aborted().is_body_incomplete())
+    /// ```
+    pub fn is_body_incomplete(&self) -> bool {
+        matches!(self.cause, Cause::Closed(Closed::InBody))
+    }
+
+    /// Check whether a compressed response body ended before the end of the
+    /// compressed stream, such as before the `gzip` trailer.
+    ///
+    /// The body is read as framed by the transfer, so a `Content-Length`
+    /// which is shorter than the compressed stream leads to this error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::HttpClientError;
+    ///
+    /// assert!(!HttpClientError::user_aborted().is_compressed_body_truncated())
+    /// ```
+    pub fn is_compressed_body_truncated(&self) -> bool {
+        matches!(self.cause, Cause::Truncated)
+    }
+
+    /// Check whether an `HTTP/2` stream was reset because nothing was
+    /// received on it for longer than the stream idle timeout.
+    ///
+    /// Only the stream of the request is aborted in this case, and the other
+    /// streams of the connection go on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::HttpClientError;
+    ///
+    /// assert!(!HttpClientError::user_aborted().is_stream_idle_timeout())
+    /// ```
+    pub fn is_stream_idle_timeout(&self) -> bool {
+        matches!(self.cause, Cause::StreamIdle)
+    }
+
+    /// Check whether the TLS handshake of a connection did not finish within
+    /// the TLS handshake timeout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::HttpClientError;
+    ///
+    /// assert!(!HttpClientError::user_aborted().is_tls_handshake_timeout())
+    /// ```
+    #[cfg(feature = "__tls")]
+    pub fn is_tls_handshake_timeout(&self) -> bool {
+        matches!(self.cause, Cause::TlsHandshake)
+    }
 }
 
 impl HttpClientError {
@@ -172,7 +292,68 @@ impl HttpClientError {
     pub(crate) fn from_dns_error(kind: ErrorKind, err: io::Error) -> Self {
         Self {
             kind,
-            cause: Cause::Dns(err),
+            cause: Cause::Dns(err, None),
+        }
+    }
+
+    pub(crate) fn from_closed(kind: ErrorKind, closed: Closed) -> Self {
+        Self {
+            kind,
+            cause: Cause::Closed(closed),
+        }
+    }
+
+    pub(crate) fn from_truncated(kind: ErrorKind) -> Self {
+        Self {
+            kind,
+            cause: Cause::Truncated,
+        }
+    }
+
+    pub(crate) fn from_stream_idle() -> Self {
+        Self {
+            kind: ErrorKind::Timeout,
+            cause: Cause::StreamIdle,
+        }
+    }
+
+    #[cfg(feature = "__tls")]
+    pub(crate) fn from_tls_handshake_timeout() -> Self {
+        Self {
+            kind: ErrorKind::Timeout,
+            cause: Cause::TlsHandshake,
+        }
+    }
+
+    #[cfg(feature = "http2")]
+    pub(crate) fn from_connect_protocol() -> Self {
+        Self {
+            kind: ErrorKind::ConnectionUpgrade,
+            cause: Cause::ConnectProtocol,
+        }
+    }
+
+    // Checks whether an extended CONNECT request was not sent because the
+    // server did not enable the extended CONNECT protocol.
+    #[cfg(all(feature = "http1_1", feature = "http2"))]
+    pub(crate) fn is_connect_protocol_disabled(&self) -> bool {
+        matches!(self.cause, Cause::ConnectProtocol)
+    }
+
+    pub(crate) fn from_dns_timeout(host: &str) -> Self {
+        Self {
+            kind: ErrorKind::Timeout,
+            cause: Cause::Dns(
+                io::Error::new(io::ErrorKind::TimedOut, "dns resolution timed out"),
+                Some(host.to_string()),
+            ),
+        }
+    }
+
+    pub(crate) fn from_dns_host_error(kind: ErrorKind, host: &str, err: io::Error) -> Self {
+        Self {
+            kind,
+            cause: Cause::Dns(err, Some(host.to_string())),
         }
     }
 }
@@ -272,10 +453,22 @@ impl ErrorKind {
 
 pub(crate) enum Cause {
     NoReason,
-    Dns(io::Error),
+    // The error and the hostname that failed to be resolved.
+    Dns(io::Error, Option<String>),
     #[cfg(feature = "__tls")]
     Tls(Box<dyn error::Error + Send + Sync>),
     Io(io::Error),
+    Closed(Closed),
+    // A compressed body ended before the end of the compressed stream.
+    Truncated,
+    // An HTTP/2 stream was reset after being idle for too long.
+    StreamIdle,
+    // A TLS handshake did not finish within the handshake timeout.
+    #[cfg(feature = "__tls")]
+    TlsHandshake,
+    // The HTTP/2 server did not enable the extended CONNECT protocol.
+    #[cfg(feature = "http2")]
+    ConnectProtocol,
     Msg(&'static str),
     Other(Box<dyn error::Error + Send + Sync>),
 }
@@ -284,10 +477,18 @@ impl Debug for Cause {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::NoReason => write!(f, "No reason"),
-            Self::Dns(err) => Debug::fmt(err, f),
+            Self::Dns(err, None) => Debug::fmt(err, f),
+            Self::Dns(err, Some(host)) => write!(f, "Dns {{ host: {host:?}, error: {err:?} }}"),
             #[cfg(feature = "__tls")]
             Self::Tls(err) => Debug::fmt(err, f),
             Self::Io(err) => Debug::fmt(err, f),
+            Self::Closed(closed) => write!(f, "{}", closed.as_str()),
+            Self::Truncated => write!(f, "{TRUNCATED}"),
+            Self::StreamIdle => write!(f, "{STREAM_IDLE}"),
+            #[cfg(feature = "__tls")]
+            Self::TlsHandshake => write!(f, "{TLS_HANDSHAKE}"),
+            #[cfg(feature = "http2")]
+            Self::ConnectProtocol => write!(f, "{CONNECT_PROTOCOL}"),
             Self::Msg(msg) => write!(f, "{}", msg),
             Self::Other(err) => Debug::fmt(err, f),
         }
@@ -298,16 +499,48 @@ impl Display for Cause {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::NoReason => write!(f, "No reason"),
-            Self::Dns(err) => Display::fmt(err, f),
+            Self::Dns(err, None) => Display::fmt(err, f),
+            Self::Dns(err, Some(host)) => write!(f, "failed to resolve `{host}`: {err}"),
             #[cfg(feature = "__tls")]
             Self::Tls(err) => Display::fmt(err, f),
             Self::Io(err) => Display::fmt(err, f),
+            Self::Closed(closed) => write!(f, "{}", closed.as_str()),
+            Self::Truncated => write!(f, "{TRUNCATED}"),
+            Self::StreamIdle => write!(f, "{STREAM_IDLE}"),
+            #[cfg(feature = "__tls")]
+            Self::TlsHandshake => write!(f, "{TLS_HANDSHAKE}"),
+            #[cfg(feature = "http2")]
+            Self::ConnectProtocol => write!(f, "{CONNECT_PROTOCOL}"),
             Self::Msg(msg) => write!(f, "{}", msg),
             Self::Other(err) => Display::fmt(err, f),
         }
     }
 }
 
+const TRUNCATED: &str = "Compressed body ended before the end of the stream";
+const STREAM_IDLE: &str = "Stream idle timed out";
+#[cfg(feature = "__tls")]
+const TLS_HANDSHAKE: &str = "TLS handshake timed out";
+#[cfg(feature = "http2")]
+const CONNECT_PROTOCOL: &str = "Extended CONNECT is not enabled by the server";
+
+// When the connection was closed by the peer.
+pub(crate) enum Closed {
+    // Before any byte of the response.
+    BeforeResponse,
+    // While receiving the response body.
+    InBody,
+}
+
+impl Closed {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::BeforeResponse => "Connection closed before response",
+            Self::InBody => "Connection closed before response body completed",
+        }
+    }
+}
+
 macro_rules! err_from_other {
     ($kind: ident, $err: expr) => {{
         use crate::error::{ErrorKind, HttpClientError};
@@ -336,6 +569,7 @@ macro_rules! err_from_msg {
 mod ut_util_error {
     use std::io;
 
+    use crate::error::Closed;
     use crate::{ErrorKind, HttpClientError};
 
     /// UT test cases for `ErrorKind::as_str`.
@@ -480,4 +714,122 @@ mod ut_util_error {
         let error = HttpClientError::other(io::Error::from(io::ErrorKind::BrokenPipe));
         assert_eq!(format!("{}", error), "Other Error: broken pipe");
     }
+
+    /// UT test cases for `HttpClientError::from_dns_host_error` function.
+    ///
+    /// # Brief
+    /// 1. Calls `HttpClientError::from_dns_host_error`.
+    /// 2. Checks if the results are correct.
+    #[test]
+    fn ut_err_from_dns_host_error() {
+        let error = HttpClientError::from_dns_host_error(
+            ErrorKind::Connect,
+            "example.com",
+            io::Error::from(io::ErrorKind::Interrupted),
+        );
+        assert!(error.is_dns_error());
+        assert_eq!(error.dns_host(), Some("example.com"));
+        assert_eq!(
+            format!("{}", error),
+            "Connect Error: failed to resolve `example.com`: operation interrupted"
+        );
+
+        let error = HttpClientError::from_io_error(
+            ErrorKind::Connect,
+            io::Error::from(io::ErrorKind::ConnectionRefused),
+        );
+        assert!(!error.is_dns_error());
+        assert!(error.dns_host().is_none());
+    }
+
+    /// UT test cases for `HttpClientError::from_dns_timeout` function.
+    ///
+    /// # Brief
+    /// 1. Calls `HttpClientError::from_dns_timeout`.
+    /// 2. Checks if the results are correct.
+    #[test]
+    fn ut_err_from_dns_timeout() {
+        let error = HttpClientError::from_dns_timeout("example.com");
+        assert!(error.is_dns_timeout());
+        assert!(error.is_dns_error());
+        assert_eq!(error.error_kind(), ErrorKind::Timeout);
+        assert_eq!(error.dns_host(), Some("example.com"));
+
+        let error = HttpClientError::from_dns_host_error(
+            ErrorKind::Connect,
+            "example.com",
+            io::Error::from(io::ErrorKind::Interrupted),
+        );
+        assert!(!error.is_dns_timeout());
+    }
+
+    /// UT test cases for `HttpClientError::from_closed` function.
+    ///
+    /// # Brief
+    /// 1. Calls `HttpClientError::from_closed`.
+    /// 2. Checks if the results are correct.
+    #[test]
+    fn ut_err_from_closed() {
+        let error = HttpClientError::from_closed(ErrorKind::Request, Closed::BeforeResponse);
+        assert!(error.is_closed_before_response());
+        assert!(!error.is_body_incomplete());
+        assert_eq!(
+            format!("{}", error),
+            "Request Error: Connection closed before response"
+        );
+
+        let error = HttpClientError::from_closed(ErrorKind::BodyDecode, Closed::InBody);
+        assert!(!error.is_closed_before_response());
+        assert!(error.is_body_incomplete());
+        assert_eq!(
+            format!("{}", error),
+            "Body Decode Error: Connection closed before response body completed"
+        );
+    }
+
+    /// UT test cases for `HttpClientError::from_stream_idle` function.
+    ///
+    /// # Brief
+    /// 1. Calls `HttpClientError::from_stream_idle`.
+    /// 2. Checks if the results are correct.
+    #[test]
+    fn ut_err_from_stream_idle() {
+        let error = HttpClientError::from_stream_idle();
+        assert!(error.is_stream_idle_timeout());
+        assert_eq!(error.error_kind(), ErrorKind::Timeout);
+        assert!(!HttpClientError::user_aborted().is_stream_idle_timeout());
+        assert!(format!("{}", error).ends_with("Stream idle timed out"));
+    }
+
+    /// UT test cases for `HttpClientError::from_tls_handshake_timeout`
+    /// function.
+    ///
+    /// # Brief
+    /// 1. Calls `HttpClientError::from_tls_handshake_timeout`.
+    /// 2. Checks if the results are correct.
+    #[cfg(feature = "__tls")]
+    #[test]
+    fn ut_err_from_tls_handshake_timeout() {
+        let error = HttpClientError::from_tls_handshake_timeout();
+        assert!(error.is_tls_handshake_timeout());
+        assert!(!error.is_stream_idle_timeout());
+        assert_eq!(error.error_kind(), ErrorKind::Timeout);
+        assert!(!HttpClientError::user_aborted().is_tls_handshake_timeout());
+        assert!(format!("{}", error).ends_with("TLS handshake timed out"));
+    }
+
+    /// UT test cases for `HttpClientError::from_connect_protocol` function.
+    ///
+    /// # Brief
+    /// 1. Calls `HttpClientError::from_connect_protocol`.
+    /// 2. Checks if the results are correct.
+    #[cfg(all(feature = "http1_1", feature = "http2"))]
+    #[test]
+    fn ut_err_from_connect_protocol() {
+        let error = HttpClientError::from_connect_protocol();
+        assert!(error.is_connect_protocol_disabled());
+        assert_eq!(error.error_kind(), ErrorKind::ConnectionUpgrade);
+        assert!(!HttpClientError::user_aborted().is_connect_protocol_disabled());
+        assert!(format!("{}", error).ends_with("Extended CONNECT is not enabled by the server"));
+    }
 }
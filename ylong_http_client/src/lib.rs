@@ -47,6 +47,9 @@ pub mod async_impl;
 #[cfg(all(feature = "sync", any(feature = "http1_1", feature = "http2")))]
 pub mod sync_impl;
 
+#[cfg(all(feature = "async", any(feature = "http1_1", feature = "http2")))]
+pub mod blocking;
+
 #[cfg(all(
     any(feature = "async", feature = "sync"),
     any(feature = "http1_1", feature = "http2"),
@@ -67,6 +70,15 @@ pub use util::*;
 // Runtime components import adapter.
 #[cfg(any(feature = "tokio_base", feature = "ylong_base"))]
 pub(crate) mod runtime {
+    #[cfg(all(feature = "tokio_base", feature = "async"))]
+    pub(crate) use tokio::{
+        fs::OpenOptions,
+        io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf},
+        net::TcpStream,
+        sync::{OwnedSemaphorePermit as SemaphorePermit, Semaphore},
+        task::JoinHandle,
+        time::{sleep, timeout, Sleep},
+    };
     #[cfg(all(feature = "tokio_base", any(feature = "http2", feature = "http3")))]
     pub(crate) use tokio::{
         io::{split, ReadHalf, WriteHalf},
@@ -80,16 +92,9 @@ pub(crate) mod runtime {
             Mutex as AsyncMutex, MutexGuard,
         },
     };
-    #[cfg(all(feature = "tokio_base", feature = "async"))]
-    pub(crate) use tokio::{
-        io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf},
-        net::TcpStream,
-        sync::{OwnedSemaphorePermit as SemaphorePermit, Semaphore},
-        task::JoinHandle,
-        time::{sleep, timeout, Sleep},
-    };
     #[cfg(feature = "ylong_base")]
     pub(crate) use ylong_runtime::{
+        fs::OpenOptions,
         io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf},
         net::TcpStream,
         sync::Semaphore,
@@ -11,6 +11,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "__tls")]
+use std::collections::HashMap;
+
 use ylong_http::request::uri::Uri;
 
 use super::{Body, Connector, HttpBody, HttpConnector, Request, Response};
@@ -193,6 +196,8 @@ pub struct ClientBuilder {
     /// Options and flags that is related to `TLS`.
     #[cfg(feature = "__tls")]
     tls: crate::util::TlsConfigBuilder,
+    #[cfg(feature = "__tls")]
+    host_tls: Vec<(String, crate::util::TlsConfigBuilder)>,
 }
 
 impl ClientBuilder {
@@ -213,6 +218,8 @@ impl ClientBuilder {
 
             #[cfg(feature = "__tls")]
             tls: crate::util::TlsConfig::builder(),
+            #[cfg(feature = "__tls")]
+            host_tls: Vec::new(),
         }
     }
 
@@ -309,10 +316,23 @@ impl ClientBuilder {
     /// let client = ClientBuilder::new().build();
     /// ```
     pub fn build(self) -> Result<Client<HttpConnector>, HttpClientError> {
+        #[cfg(feature = "__tls")]
+        let mut host_tls = HashMap::new();
+        #[cfg(feature = "__tls")]
+        for (host, builder) in self.host_tls {
+            host_tls.insert(host, builder.build()?);
+        }
+
         let config = ConnectorConfig {
             proxies: self.proxies,
             #[cfg(feature = "__tls")]
             tls: self.tls.build()?,
+            #[cfg(feature = "__tls")]
+            host_tls,
+            #[cfg(feature = "__tls")]
+            tls_handshake_timeout: Timeout::none(),
+            dns_timeout: None,
+            socket: Default::default(),
         };
 
         let connector = HttpConnector::new(config);
@@ -360,6 +380,31 @@ impl ClientBuilder {
         self
     }
 
+    /// Uses the TLS config built by `builder` for the connections to `host`,
+    /// instead of the TLS settings of the `ClientBuilder`.
+    ///
+    /// The config of a host replaces the global one entirely, none of the TLS
+    /// settings of the `ClientBuilder` are applied to the host. The host is
+    /// matched exactly and case-insensitively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::sync_impl::ClientBuilder;
+    /// use ylong_http_client::TlsConfigBuilder;
+    ///
+    /// let builder = ClientBuilder::new().host_tls_config(
+    ///     "internal.example.com",
+    ///     TlsConfigBuilder::new().ca_file("internal_ca.crt"),
+    /// );
+    /// ```
+    pub fn host_tls_config(mut self, host: &str, builder: crate::util::TlsConfigBuilder) -> Self {
+        let host = host.to_ascii_lowercase();
+        self.host_tls.retain(|(name, _)| *name != host);
+        self.host_tls.push((host, builder));
+        self
+    }
+
     /// Adds a custom root certificate.
     ///
     /// This can be used to connect to a server that has a self-signed.
@@ -133,3 +133,70 @@ impl<S: Read> StreamData for Http1Conn<S> {
         Self::shutdown(self)
     }
 }
+
+#[cfg(test)]
+mod ut_sync_http1 {
+    use std::io::{Cursor, Read, Write};
+    use std::sync::{Arc, Mutex};
+
+    use ylong_http::body::TextBody;
+    use ylong_http::request::Request;
+
+    use super::request;
+    use crate::util::dispatcher::http1::Http1Dispatcher;
+    use crate::util::dispatcher::Dispatcher;
+
+    struct ScriptIo {
+        response: Cursor<&'static [u8]>,
+        written: Arc<Mutex<Vec<u8>>>,
+    }
+
+    impl Read for ScriptIo {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.response.read(buf)
+        }
+    }
+
+    impl Write for ScriptIo {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.written.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// UT test cases for `request` with a `PATCH` request.
+    ///
+    /// # Brief
+    /// 1. Sends a `PATCH` request with a JSON body through a `Http1Conn`.
+    /// 2. Checks that the request line, headers and body are written.
+    /// 3. Checks that the response is read.
+    #[test]
+    fn ut_sync_http1_patch_request() {
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let io = ScriptIo {
+            response: Cursor::new(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n"),
+            written: written.clone(),
+        };
+        let dispatcher = Http1Dispatcher::new(io);
+        let conn = dispatcher.dispatch().unwrap();
+
+        let json = "{\"name\":\"ylong\"}";
+        let mut req = Request::patch("http://127.0.0.1/resource")
+            .header("Content-Type", "application/json")
+            .header("Content-Length", json.len().to_string().as_str())
+            .body(TextBody::from_bytes(json.as_bytes()))
+            .unwrap();
+        let response = request(conn, &mut req).unwrap();
+        assert_eq!(response.status().as_u16(), 204);
+
+        let written = written.lock().unwrap();
+        let written = std::str::from_utf8(&written).unwrap();
+        assert!(written.starts_with("PATCH /resource HTTP/1.1\r\n"));
+        assert!(written.contains("content-type:application/json\r\n"));
+        assert!(written.ends_with("\r\n\r\n{\"name\":\"ylong\"}"));
+    }
+}
@@ -78,9 +78,15 @@ pub mod tls_conn {
     use std::io::{Read, Write};
     use std::net::TcpStream;
 
+    use ylong_http::h1::ResponseDecoder;
+    use ylong_http::headers::Headers;
     use ylong_http::request::uri::{Scheme, Uri};
+    use ylong_http::response::status::StatusCode;
+    use ylong_http::response::ResponsePart;
+    use ylong_http::version::Version;
 
     use crate::sync_impl::{Connector, MixStream};
+    use crate::util::digest_auth;
     use crate::{ErrorKind, HttpClientError};
 
     impl Connector for super::HttpConnector {
@@ -93,6 +99,8 @@ pub mod tls_conn {
             let host = uri.host().unwrap().as_str().to_string();
             let port = uri.port().unwrap().as_u16().unwrap();
             let mut auth = None;
+            let mut credentials = None;
+            let mut headers = Headers::new();
             let mut is_proxy = false;
 
             if let Some(proxy) = self.config.proxies.match_proxy(uri) {
@@ -103,6 +111,8 @@ pub mod tls_conn {
                     .basic_auth
                     .as_ref()
                     .and_then(|v| v.to_string().ok());
+                credentials = proxy.intercept.proxy_info().credentials.clone();
+                headers = proxy.intercept.proxy_info().connect_headers.clone();
                 is_proxy = true;
             }
 
@@ -122,14 +132,14 @@ pub mod tls_conn {
                         .map_err(|e| HttpClientError::from_error(ErrorKind::Connect, e))?;
 
                     let tcp_stream = if is_proxy {
-                        tunnel(tcp_stream, host, port, auth)?
+                        tunnel(tcp_stream, host, port, auth, credentials.as_ref(), &headers)?
                     } else {
                         tcp_stream
                     };
 
                     let tls_ssl = self
                         .config
-                        .tls
+                        .tls_config(&host_name)
                         .ssl_new(&host_name)
                         .map_err(|e| HttpClientError::from_error(ErrorKind::Connect, e))?;
 
@@ -148,25 +158,92 @@ pub mod tls_conn {
         host: String,
         port: u16,
         auth: Option<String>,
+        credentials: Option<&(String, String)>,
+        headers: &Headers,
     ) -> Result<TcpStream, HttpClientError> {
-        let mut req = Vec::new();
+        let mut authorization = auth.map(|value| format!("Basic {value}"));
+        let mut challenged = false;
 
-        // `unwrap()` never failed here.
-        write!(
-            &mut req,
-            "CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n"
-        )
-        .unwrap();
+        loop {
+            let mut req = Vec::new();
 
-        if let Some(value) = auth {
-            write!(&mut req, "Proxy-Authorization: Basic {value}\r\n").unwrap();
-        }
+            // `unwrap()` never failed here.
+            write!(
+                &mut req,
+                "CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n"
+            )
+            .unwrap();
+
+            if let Some(value) = authorization.as_ref() {
+                write!(&mut req, "Proxy-Authorization: {value}\r\n").unwrap();
+            }
 
-        write!(&mut req, "\r\n").unwrap();
+            for (name, value) in headers {
+                for sub in value.iter() {
+                    req.extend_from_slice(name.as_bytes());
+                    req.extend_from_slice(b": ");
+                    req.extend_from_slice(sub);
+                    req.extend_from_slice(b"\r\n");
+                }
+            }
 
-        conn.write_all(&req)
-            .map_err(|e| HttpClientError::from_error(ErrorKind::Connect, e))?;
+            write!(&mut req, "\r\n").unwrap();
+
+            conn.write_all(&req)
+                .map_err(|e| HttpClientError::from_error(ErrorKind::Connect, e))?;
+
+            let (part, body, reusable) = read_connect_response(&mut conn)?;
+            if part.status.is_successful() {
+                return Ok(conn);
+            }
+
+            // Answers a `Digest` challenge once, on the same connection.
+            let challenge = part
+                .headers
+                .get("Proxy-Authenticate")
+                .and_then(|value| digest_auth::find_challenge(value.iter().map(Vec::as_slice)));
+            match challenge {
+                Some(challenge)
+                    if part.status == StatusCode::PROXY_AUTHENTICATION_REQUIRED && !challenged =>
+                {
+                    let (username, password) = credentials.ok_or(HttpClientError::from_str(
+                        ErrorKind::Connect,
+                        "no credentials for the proxy digest challenge",
+                    ))?;
+                    let value = digest_auth::authorization(
+                        challenge,
+                        (username, password),
+                        ("CONNECT", &format!("{host}:{port}")),
+                        1,
+                    )
+                    .map_err(|e| HttpClientError::from_str(ErrorKind::Connect, e))?;
+                    if !reusable {
+                        return Err(HttpClientError::from_str(
+                            ErrorKind::Connect,
+                            "proxy closes the connection of the digest challenge",
+                        ));
+                    }
+                    authorization = Some(value);
+                    challenged = true;
+                }
+                _ => {
+                    let mut msg = format!("proxy rejected tunnel with status {}", part.status);
+                    if !body.is_empty() {
+                        msg.push_str(": ");
+                        msg.push_str(&String::from_utf8_lossy(&body));
+                    }
+                    return Err(HttpClientError::from_error(ErrorKind::Connect, msg));
+                }
+            }
+        }
+    }
 
+    // Reads the response of the proxy to `CONNECT`, with the body of a non-2xx
+    // response. Also returns whether the connection can be used for another
+    // `CONNECT` request.
+    fn read_connect_response(
+        conn: &mut TcpStream,
+    ) -> Result<(ResponsePart, Vec<u8>, bool), HttpClientError> {
         let mut buf = [0; 8192];
         let mut pos = 0;
 
@@ -174,37 +251,56 @@ pub mod tls_conn {
             let n = conn
                 .read(&mut buf[pos..])
                 .map_err(|e| HttpClientError::from_error(ErrorKind::Connect, e))?;
-
-            if n == 0 {
-                return Err(HttpClientError::from_str(
-                    ErrorKind::Connect,
-                    "Error receiving from proxy",
-                ));
-            }
-
             pos += n;
-            let resp = &buf[..pos];
-            if resp.starts_with(b"HTTP/1.1 200") {
-                if resp.ends_with(b"\r\n\r\n") {
-                    return Ok(conn);
+            // No more bytes of the response can be received.
+            let end = n == 0 || pos == buf.len();
+
+            let (part, body) = match ResponseDecoder::new().decode(&buf[..pos]) {
+                Ok(Some(decoded)) => decoded,
+                Ok(None) if n == 0 => {
+                    return Err(HttpClientError::from_str(
+                        ErrorKind::Connect,
+                        "Error receiving from proxy",
+                    ))
                 }
-                if pos == buf.len() {
+                Ok(None) if end => {
                     return Err(HttpClientError::from_str(
                         ErrorKind::Connect,
                         "proxy headers too long for tunnel",
-                    ));
+                    ))
                 }
-            } else if resp.starts_with(b"HTTP/1.1 407") {
-                return Err(HttpClientError::from_str(
-                    ErrorKind::Connect,
-                    "proxy authentication required",
-                ));
-            } else {
-                return Err(HttpClientError::from_str(
-                    ErrorKind::Connect,
-                    "unsuccessful tunnel",
-                ));
+                Ok(None) => continue,
+                Err(_) => {
+                    return Err(HttpClientError::from_str(
+                        ErrorKind::Connect,
+                        "unsuccessful tunnel",
+                    ))
+                }
+            };
+            if part.status.is_successful() {
+                return Ok((part, Vec::new(), false));
             }
+
+            // Keeps the body of the refusal, such as an authentication
+            // challenge, for the error.
+            let length = part
+                .headers
+                .get("Content-Length")
+                .and_then(|v| v.to_string().ok())
+                .and_then(|v| v.trim().parse::<usize>().ok());
+            let (body, complete) = match length {
+                Some(len) if body.len() < len && !end => continue,
+                Some(len) => (body[..len.min(body.len())].to_vec(), body.len() == len),
+                None => (body.to_vec(), false),
+            };
+            let close = part
+                .headers
+                .get("Connection")
+                .and_then(|v| v.to_string().ok())
+                .map(|v| v.eq_ignore_ascii_case("close"))
+                .unwrap_or(false);
+            let reusable = complete && !close && part.version == Version::HTTP1_1;
+            return Ok((part, body, reusable));
         }
     }
 }
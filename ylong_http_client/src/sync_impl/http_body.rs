@@ -11,7 +11,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::io::{Cursor, Read};
+use std::io::{Cursor, Read, Write};
 
 use ylong_http::body::{ChunkBodyDecoder, ChunkState, TextBodyDecoder};
 use ylong_http::headers::Headers;
@@ -117,6 +117,81 @@ impl Body for HttpBody {
     }
 }
 
+impl HttpBody {
+    /// Gets a `Read` of the body, which reads the decoded data into the buffer
+    /// of the caller and returns `0` at the end of the body.
+    ///
+    /// `HttpBody` itself does not implement `Read`, since every `Read` is a
+    /// `Body` whose error is `std::io::Error`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::io::Read;
+    ///
+    /// use ylong_http_client::sync_impl::{Client, EmptyBody, Request};
+    ///
+    /// let mut client = Client::new();
+    /// let mut response = client.request(Request::new(EmptyBody)).unwrap();
+    /// let mut text = String::new();
+    /// let _ = response.body_mut().reader().read_to_string(&mut text);
+    /// ```
+    pub fn reader(&mut self) -> HttpBodyReader<'_> {
+        HttpBodyReader { body: self }
+    }
+
+    /// Reads the rest of the body and writes it into `writer` piece by piece,
+    /// without buffering the whole body in memory. Returns the total number
+    /// of bytes written.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    ///
+    /// use ylong_http_client::sync_impl::{Client, EmptyBody, Request};
+    ///
+    /// let mut client = Client::new();
+    /// let mut response = client.request(Request::new(EmptyBody)).unwrap();
+    /// let mut file = File::create("./body").unwrap();
+    /// let size = response.body_mut().copy_to(&mut file).unwrap();
+    /// ```
+    pub fn copy_to<W>(&mut self, writer: &mut W) -> Result<u64, HttpClientError>
+    where
+        W: Write + ?Sized,
+    {
+        let mut buf = [0u8; 16 * 1024];
+        let mut total = 0u64;
+        loop {
+            let size = self.data(&mut buf)?;
+            if size == 0 {
+                break;
+            }
+            writer
+                .write_all(&buf[..size])
+                .map_err(|e| HttpClientError::from_io_error(ErrorKind::BodyTransfer, e))?;
+            total += size as u64;
+        }
+        writer
+            .flush()
+            .map_err(|e| HttpClientError::from_io_error(ErrorKind::BodyTransfer, e))?;
+        Ok(total)
+    }
+}
+
+/// A `Read` of a `HttpBody`, which is created by `HttpBody::reader`.
+pub struct HttpBodyReader<'a> {
+    body: &'a mut HttpBody,
+}
+
+impl Read for HttpBodyReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.body
+            .data(buf)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+    }
+}
+
 impl Text {
     fn data(&mut self, buf: &mut [u8]) -> Result<usize, HttpClientError> {
         if buf.is_empty() {
@@ -320,8 +395,23 @@ impl Chunk {
 
 #[cfg(test)]
 mod ut_syn_http_body {
+    use std::io::Read;
+
+    use crate::sync_impl::conn::StreamData;
     use crate::sync_impl::{Body, HttpBody};
 
+    struct ChunkIo(&'static [u8]);
+
+    impl Read for ChunkIo {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.0.read(buf)
+        }
+    }
+
+    impl StreamData for ChunkIo {
+        fn shutdown(&self) {}
+    }
+
     /// UT test cases for `HttpBody::empty`.
     ///
     /// # Brief
@@ -336,4 +426,48 @@ mod ut_syn_http_body {
         assert!(data.is_ok());
         assert_eq!(data.unwrap(), 0);
     }
+
+    /// UT test cases for `HttpBody::reader`.
+    ///
+    /// # Brief
+    /// 1. Creates a chunked `HttpBody` whose data is partly received.
+    /// 2. Reads it by `HttpBodyReader` into a small buffer repeatedly.
+    /// 3. Checks that the decoded data is read and `0` is returned at the end.
+    #[test]
+    fn ut_http_body_read() {
+        let mut body = HttpBody::chunk(
+            b"5\r\nhello\r\n",
+            Box::new(ChunkIo(b"6\r\n world\r\n0\r\n\r\n")),
+            false,
+        );
+        let mut buf = [0u8; 4];
+        let mut data = vec![];
+        loop {
+            let size = body.reader().read(&mut buf).unwrap();
+            if size == 0 {
+                break;
+            }
+            data.extend_from_slice(&buf[..size]);
+        }
+        assert_eq!(data, b"hello world");
+        assert_eq!(body.reader().read(&mut buf).unwrap(), 0);
+    }
+
+    /// UT test cases for `HttpBody::copy_to`.
+    ///
+    /// # Brief
+    /// 1. Creates a chunked `HttpBody` whose data is partly received.
+    /// 2. Copies it into a `Vec` by `HttpBody::copy_to`.
+    /// 3. Checks that the decoded data is copied and its size is returned.
+    #[test]
+    fn ut_http_body_copy_to() {
+        let mut body = HttpBody::chunk(
+            b"5\r\nhello\r\n",
+            Box::new(ChunkIo(b"6\r\n world\r\n0\r\n\r\n")),
+            false,
+        );
+        let mut data = vec![];
+        assert_eq!(body.copy_to(&mut data).unwrap(), 11);
+        assert_eq!(data, b"hello world");
+    }
 }
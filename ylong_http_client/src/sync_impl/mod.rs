@@ -35,7 +35,7 @@ mod reader;
 pub use client::{Client, ClientBuilder};
 pub use connector::Connector;
 pub(crate) use connector::HttpConnector;
-pub use http_body::HttpBody;
+pub use http_body::{HttpBody, HttpBodyReader};
 pub use reader::{BodyProcessError, BodyProcessor, BodyReader, DefaultBodyProcessor};
 pub use ylong_http::body::sync_impl::Body;
 pub use ylong_http::body::{EmptyBody, TextBody};
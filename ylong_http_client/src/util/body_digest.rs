@@ -0,0 +1,103 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Request body digest headers.
+//!
+//! A digest of a buffered request body is sent in [`Content-MD5`] or in
+//! [`Digest`], so that the server can check the integrity of the body.
+//!
+//! [`Content-MD5`]: https://www.rfc-editor.org/rfc/rfc1864.html
+//! [`Digest`]: https://www.rfc-editor.org/rfc/rfc3230.html
+
+use crate::util::{base64, md5, sha256};
+
+/// Digest algorithms which can be used to add a digest header of a request
+/// body.
+///
+/// # Examples
+///
+/// ```
+/// use ylong_http_client::DigestAlgorithm;
+///
+/// assert_eq!(DigestAlgorithm::Md5.header_name(), "Content-MD5");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestAlgorithm {
+    /// `MD5`, which is sent in `Content-MD5`.
+    Md5,
+    /// `SHA-256`, which is sent in `Digest`.
+    Sha256,
+}
+
+impl DigestAlgorithm {
+    /// Gets the name of the header which carries a digest of this
+    /// `DigestAlgorithm`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::DigestAlgorithm;
+    ///
+    /// assert_eq!(DigestAlgorithm::Sha256.header_name(), "Digest");
+    /// ```
+    pub fn header_name(&self) -> &'static str {
+        match self {
+            Self::Md5 => "Content-MD5",
+            Self::Sha256 => "Digest",
+        }
+    }
+
+    // Computes the header value of the digest of `data`.
+    pub(crate) fn header_value(&self, data: &[u8]) -> String {
+        let encoded = match self {
+            Self::Md5 => base64::encode(&md5::digest(data)),
+            Self::Sha256 => base64::encode(&sha256::digest(data)),
+        };
+        // Base64 output is always ASCII.
+        let encoded = String::from_utf8(encoded).unwrap_or_default();
+        match self {
+            Self::Md5 => encoded,
+            Self::Sha256 => format!("SHA-256={encoded}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod ut_body_digest {
+    use crate::util::body_digest::DigestAlgorithm;
+
+    /// UT test cases for `DigestAlgorithm::header_value`.
+    ///
+    /// # Brief
+    /// 1. Computes the header values of known bodies.
+    /// 2. Checks if the results are correct.
+    #[test]
+    fn ut_digest_algorithm_header_value() {
+        assert_eq!(
+            DigestAlgorithm::Md5.header_value(b"HelloWorld"),
+            "aOEJ8PQMpyoV4FzCJ4b45g=="
+        );
+        assert_eq!(
+            DigestAlgorithm::Md5.header_value(b""),
+            "1B2M2Y8AsgTpgAmY7PhCfg=="
+        );
+        assert_eq!(
+            DigestAlgorithm::Sha256.header_value(b"HelloWorld"),
+            "SHA-256=hy5OUM6ZkNiwQTMMR8nd0Rvsa1A66ThqmdqFhOm7EsQ="
+        );
+        assert_eq!(
+            DigestAlgorithm::Sha256.header_value(b""),
+            "SHA-256=47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="
+        );
+    }
+}
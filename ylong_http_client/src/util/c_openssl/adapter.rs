@@ -18,12 +18,12 @@ use std::sync::Arc;
 use crate::error::{ErrorKind, HttpClientError};
 use crate::util::c_openssl::error::ErrorStack;
 use crate::util::c_openssl::ssl::{
-    Ssl, SslContext, SslContextBuilder, SslFiletype, SslMethod, SslVersion,
+    Ssl, SslContext, SslContextBuilder, SslFiletype, SslMethod, SslVersion, SSL_VERIFY_NONE,
 };
 use crate::util::c_openssl::verify::PubKeyPins;
 use crate::util::c_openssl::x509::{X509Store, X509};
 use crate::util::config::tls::DefaultCertVerifier;
-use crate::util::AlpnProtocolList;
+use crate::util::{AlpnProtocol, AlpnProtocolList};
 
 /// `TlsContextBuilder` implementation based on `SSL_CTX`.
 ///
@@ -286,7 +286,7 @@ impl TlsConfigBuilder {
         if is_invalid {
             self.inner = self.inner.and_then(|mut builder| {
                 {
-                    builder.set_verify(crate::util::c_openssl::ssl::SSL_VERIFY_NONE);
+                    builder.set_verify(SSL_VERIFY_NONE);
                     Ok(())
                 }
                 .map(|_| builder)
@@ -389,6 +389,8 @@ impl TlsConfigBuilder {
             cert_verifier: self.cert_verifier,
             use_sni: self.use_sni,
             verify_hostname: self.verify_hostname,
+            verify_certs: true,
+            http1_only: false,
             pins: self.pins,
         })
     }
@@ -417,6 +419,11 @@ pub struct TlsConfig {
     cert_verifier: Option<Arc<DefaultCertVerifier>>,
     use_sni: bool,
     verify_hostname: bool,
+    // Whether the certs are verified as set in the context.
+    verify_certs: bool,
+    // Whether only `HTTP/1.1` is offered by ALPN, whatever is set in the
+    // context.
+    http1_only: bool,
     pins: Option<PubKeyPins>,
 }
 
@@ -444,13 +451,38 @@ impl TlsConfig {
             ssl.set_host_name_in_sni(domain)?;
         }
 
+        // Overrides the verify mode of the context for unverified requests.
+        if !self.verify_certs {
+            ssl.set_verify(SSL_VERIFY_NONE);
+        }
+
         // Hostname verification in certificate verification.
         if self.verify_hostname {
             ssl.set_verify_hostname(domain)?;
         }
+
+        // Overrides the ALPN protocols of the context for `HTTP/1.1` connections.
+        if self.http1_only {
+            ssl.set_alpn_protos(AlpnProtocol::HTTP11.wire_format_bytes())?;
+        }
         Ok(TlsSsl(ssl))
     }
 
+    /// Creates a `TlsConfig` like this one, but which verifies neither the
+    /// certs nor the hostname of the server.
+    pub(crate) fn unverified(mut self) -> Self {
+        self.verify_certs = false;
+        self.verify_hostname = false;
+        self
+    }
+
+    /// Creates a `TlsConfig` like this one, but which only offers `HTTP/1.1`
+    /// by ALPN, so that the server can't select another protocol.
+    pub(crate) fn http1_only(mut self) -> Self {
+        self.http1_only = true;
+        self
+    }
+
     pub(crate) fn pinning_host_match(&self, domain: &str) -> Option<String> {
         match &self.pins {
             None => None,
@@ -872,6 +904,51 @@ mod ut_openssl_adapter {
         println!("{}", String::from_utf8_lossy(&res));
     }
 
+    /// UT test cases for `TlsConfig::unverified`.
+    ///
+    /// # Brief
+    /// 1. Creates a `TlsConfig` which verifies the server.
+    /// 2. Calls `TlsConfig::unverified`.
+    /// 3. Checks that neither the certs nor the hostname are verified, and
+    ///    that `TlsSsl` can still be created.
+    #[test]
+    fn ut_tls_config_unverified() {
+        let config = TlsConfigBuilder::new()
+            .build()
+            .expect("TlsConfig build error.");
+        assert!(config.verify_certs);
+        assert!(config.verify_hostname);
+
+        let config = config.unverified();
+        assert!(!config.verify_certs);
+        assert!(!config.verify_hostname);
+        assert!(config.ssl_new("example.com").is_ok());
+    }
+
+    /// UT test cases for `TlsConfig::http1_only`.
+    ///
+    /// # Brief
+    /// 1. Creates a `TlsConfig` which offers `h2` and `http/1.1` by ALPN.
+    /// 2. Calls `TlsConfig::http1_only`.
+    /// 3. Checks that only `http/1.1` is offered, and that `TlsSsl` can still
+    ///    be created.
+    #[test]
+    fn ut_tls_config_http1_only() {
+        let config = TlsConfigBuilder::new()
+            .alpn_proto_list(
+                AlpnProtocolList::new()
+                    .extend(AlpnProtocol::H2)
+                    .extend(AlpnProtocol::HTTP11),
+            )
+            .build()
+            .expect("TlsConfig build error.");
+        assert!(!config.http1_only);
+
+        let config = config.http1_only();
+        assert!(config.http1_only);
+        assert!(config.ssl_new("example.com").is_ok());
+    }
+
     /// UT test cases for `Cert::from_pem`.
     ///
     /// # Brief
@@ -275,10 +275,12 @@ pub(crate) struct VerifyError {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum VerifyKind {
     PubKeyPinning,
+    CertVerifier,
 }
 
 pub(crate) enum Reason {
     Msg(&'static str),
+    Custom(String),
 }
 
 impl VerifyError {
@@ -288,12 +290,20 @@ impl VerifyError {
             cause: Reason::Msg(msg),
         }
     }
+
+    pub(crate) fn from_reason(kind: VerifyKind, reason: String) -> Self {
+        Self {
+            kind,
+            cause: Reason::Custom(reason),
+        }
+    }
 }
 
 impl VerifyKind {
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::PubKeyPinning => "Public Key Pinning Error",
+            Self::CertVerifier => "Certificate Verification Error",
         }
     }
 }
@@ -319,6 +329,7 @@ impl fmt::Debug for Reason {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Msg(msg) => write!(f, "{}", msg),
+            Self::Custom(reason) => write!(f, "{}", reason),
         }
     }
 }
@@ -327,6 +338,7 @@ impl fmt::Display for Reason {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Msg(msg) => write!(f, "{}", msg),
+            Self::Custom(reason) => write!(f, "{}", reason),
         }
     }
 }
@@ -364,6 +376,24 @@ mod ut_c_openssl_error {
         );
         assert_eq!(format!("{error}"), "Public Key Pinning Error: error");
     }
+
+    /// UT test cases for `VerifyError::from_reason` function.
+    ///
+    /// # Brief
+    /// 1. Calls `VerifyError::from_reason` with a custom reason.
+    /// 2. Checks if the results are correct.
+    #[test]
+    fn ut_verify_err_from_reason() {
+        let error = VerifyError::from_reason(VerifyKind::CertVerifier, String::from("Expired"));
+        assert_eq!(
+            format!("{:?}", error),
+            "VerifyError { ErrorKind: CertVerifier, Cause: Expired }"
+        );
+        assert_eq!(
+            format!("{error}"),
+            "Certificate Verification Error: Expired"
+        );
+    }
 }
 
 #[cfg(test)]
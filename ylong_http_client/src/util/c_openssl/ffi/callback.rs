@@ -11,18 +11,38 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cell::RefCell;
 use std::ffi::{c_int, c_void};
 
 use crate::util::c_openssl::ffi::x509::X509_STORE_CTX;
 use crate::util::c_openssl::foreign::ForeignRef;
 use crate::util::c_openssl::x509::X509StoreContextRef;
 use crate::util::config::tls::DefaultCertVerifier;
-use crate::{CertVerifier, ServerCerts};
+use crate::{CertVerifier, ServerCerts, VerifyResult};
+
+thread_local! {
+    // The reason the custom verifier rejected the certs. The callback runs
+    // inside `SSL_connect`, so the reason is taken on the same thread when the
+    // handshake fails.
+    static REJECT_REASON: RefCell<Option<String>> = const { RefCell::new(None) };
+}
 
 pub(crate) extern "C" fn cert_verify(ctx: *mut X509_STORE_CTX, arg: *mut c_void) -> c_int {
     unsafe {
         let verifier = &*(arg as *const DefaultCertVerifier);
         let ctx = X509StoreContextRef::from_ptr(ctx);
-        verifier.verify(&ServerCerts::new(ctx)) as c_int
+        match verifier.verify_result(&ServerCerts::new(ctx)) {
+            VerifyResult::Accept => 1,
+            VerifyResult::Reject(reason) => {
+                REJECT_REASON.with(|r| *r.borrow_mut() = Some(reason));
+                0
+            }
+        }
     }
 }
+
+// Takes the reason the custom verifier rejected the certs in the last
+// handshake on this thread.
+pub(crate) fn take_reject_reason() -> Option<String> {
+    REJECT_REASON.with(|r| r.borrow_mut().take())
+}
@@ -22,11 +22,14 @@ pub mod ssl; // todo
 pub(crate) mod stack;
 pub(crate) mod x509;
 
-use libc::c_int;
+use libc::{c_int, c_uchar};
 pub(crate) enum OPENSSL_INIT_SETTINGS {}
 
 extern "C" {
     /// Calls this function will explicitly initialise BOTH libcrypto and
     /// libssl.
     pub(crate) fn OPENSSL_init_ssl(opts: u64, settings: *const OPENSSL_INIT_SETTINGS) -> c_int;
+
+    /// Puts num cryptographically strong pseudo-random bytes into buf.
+    pub(crate) fn RAND_bytes(buf: *mut c_uchar, num: c_int) -> c_int;
 }
@@ -14,7 +14,7 @@
 use libc::{c_char, c_int, c_long, c_uchar, c_uint, c_void};
 
 use super::bio::BIO;
-use super::x509::{C_X509, X509_STORE, X509_STORE_CTX, X509_VERIFY_PARAM};
+use super::x509::{C_X509, STACK_X509, X509_STORE, X509_STORE_CTX, X509_VERIFY_PARAM};
 
 /// This is the global context structure which is created by a server or client
 /// once per program life-time and which holds mainly default values for the
@@ -82,8 +82,11 @@ extern "C" {
         len: c_uint,
     ) -> c_int;
 
+    /// Client sets the list of protocols available to be negotiated on ssl,
+    /// which overrides the list of the SSL_CTX it is created from.
+    pub(crate) fn SSL_set_alpn_protos(ssl: *mut SSL, data: *const c_uchar, len: c_uint) -> c_int;
+
     /// returns the selected protocol. It is not NUL-terminated.
-    #[cfg(feature = "http2")]
     pub(crate) fn SSL_get0_alpn_selected(
         ssl: *const SSL,
         data: *mut *const c_uchar,
@@ -141,6 +144,15 @@ extern "C" {
     pub(crate) fn SSL_CTX_set1_sigalgs_list(ctx: *mut SSL_CTX, parg: *mut c_void) -> c_int;
 }
 
+/// The cipher suite negotiated on a connection.
+pub(crate) enum SSL_CIPHER {}
+
+// for `SSL_CIPHER`
+extern "C" {
+    /// Returns the name of the cipher, such as "TLS_AES_128_GCM_SHA256".
+    pub(crate) fn SSL_CIPHER_get_name(cipher: *const SSL_CIPHER) -> *const c_char;
+}
+
 /// This is the main SSL/TLS structure which is created by a server or client
 /// per established connection. This actually is the core structure in the SSL
 /// API. At run-time the application usually deals with this structure which has
@@ -177,6 +189,18 @@ extern "C" {
     #[cfg(any(feature = "c_openssl_1_1", feature = "c_boringssl"))]
     pub(crate) fn SSL_get_peer_certificate(ssl: *const SSL) -> *mut C_X509;
 
+    /// Returns the certificate chain sent by the peer, the returned pointer
+    /// must not be freed by the calling application.
+    pub(crate) fn SSL_get_peer_cert_chain(ssl: *const SSL) -> *mut STACK_X509;
+
+    /// Returns the name of the protocol used for the connection, such as
+    /// "TLSv1.3".
+    pub(crate) fn SSL_get_version(ssl: *const SSL) -> *const c_char;
+
+    /// Returns the cipher actually used, or `Null` if no session has been
+    /// established.
+    pub(crate) fn SSL_get_current_cipher(ssl: *const SSL) -> *const SSL_CIPHER;
+
     pub(crate) fn SSL_set_bio(ssl: *mut SSL, rbio: *mut BIO, wbio: *mut BIO);
 
     pub(crate) fn SSL_get_rbio(ssl: *const SSL) -> *mut BIO;
@@ -197,6 +221,14 @@ extern "C" {
     /// application.
     pub(crate) fn SSL_get0_param(ssl: *mut SSL) -> *mut X509_VERIFY_PARAM;
 
+    /// Sets the verification flags for ssl to be mode, which overrides the
+    /// flags of the SSL_CTX it is created from.
+    pub(crate) fn SSL_set_verify(
+        ssl: *mut SSL,
+        mode: c_int,
+        verify_callback: Option<extern "C" fn(c_int, *mut X509_STORE_CTX) -> c_int>,
+    );
+
     #[cfg(feature = "c_boringssl")]
     pub(crate) fn SSL_set_tlsext_host_name(ssl: *mut SSL, name: *mut c_void) -> c_int;
 }
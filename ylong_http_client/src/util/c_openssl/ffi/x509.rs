@@ -11,7 +11,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use libc::{c_char, c_int, c_long, c_uchar, c_uint, size_t};
+use libc::{c_char, c_int, c_long, c_uchar, c_uint, c_void, size_t};
 
 pub(crate) enum EVP_PKEY {}
 
@@ -28,6 +28,21 @@ extern "C" {
 
     pub(crate) fn EVP_sha256() -> *mut EVP_MD;
 
+    pub(crate) fn EVP_sha1() -> *mut EVP_MD;
+
+    pub(crate) fn EVP_md5() -> *mut EVP_MD;
+
+    /// Hashes count bytes of data at d into md in one call, and writes the
+    /// size of the digest to size. The impl is the ENGINE to use, or NULL.
+    pub(crate) fn EVP_Digest(
+        data: *const c_void,
+        count: size_t,
+        md: *mut c_uchar,
+        size: *mut c_uint,
+        md_type: *const EVP_MD,
+        engine: *mut c_void,
+    ) -> c_int;
+
     pub(crate) fn EVP_DigestInit(ctx: *mut EVP_MD_CTX, md: *mut EVP_MD) -> c_int;
 
     pub(crate) fn EVP_MD_CTX_free(ctx: *mut EVP_MD_CTX);
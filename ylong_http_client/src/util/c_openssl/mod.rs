@@ -32,15 +32,17 @@ pub mod adapter;
 pub(crate) mod verify;
 
 use core::ptr;
+use std::ffi::c_void;
 use std::sync::Once;
 
 pub use adapter::{Cert, Certificate, TlsConfig, TlsConfigBuilder, TlsFileType, TlsVersion};
 use error::ErrorStack;
-use libc::c_int;
+use libc::{c_int, c_uint};
 pub use verify::{PubKeyPins, PubKeyPinsBuilder};
 
 pub(crate) use crate::util::c_openssl::ffi::callback::*;
-use crate::util::c_openssl::ffi::OPENSSL_init_ssl;
+use crate::util::c_openssl::ffi::x509::{EVP_Digest, EVP_MD};
+use crate::util::c_openssl::ffi::{OPENSSL_init_ssl, RAND_bytes};
 
 /// Automatic loading of the libssl error strings. This option is a default
 /// option.
@@ -64,6 +66,37 @@ pub(crate) fn check_ret(r: c_int) -> Result<c_int, ErrorStack> {
     }
 }
 
+/// Fills `buf` with cryptographically strong random bytes.
+pub(crate) fn rand_bytes(buf: &mut [u8]) -> Result<(), ErrorStack> {
+    assert!(buf.len() <= c_int::MAX as usize);
+    check_ret(unsafe { RAND_bytes(buf.as_mut_ptr(), buf.len() as c_int) }).map(|_| ())
+}
+
+/// Computes the digest of `input` by the message digest that `md` returns,
+/// such as `EVP_sha256`.
+///
+/// `EVP_Digest` only fails if no memory can be allocated, which aborts the
+/// program anyway, or if `N` is not the size of the digest.
+pub(crate) fn evp_digest<const N: usize>(
+    md: unsafe extern "C" fn() -> *mut EVP_MD,
+    input: &[u8],
+) -> [u8; N] {
+    let mut output = [0u8; N];
+    let mut size: c_uint = 0;
+    let ret = unsafe {
+        EVP_Digest(
+            input.as_ptr() as *const c_void,
+            input.len(),
+            output.as_mut_ptr(),
+            &mut size,
+            md(),
+            ptr::null_mut(),
+        )
+    };
+    assert!(ret == 1 && size as usize == N, "EVP_Digest failed");
+    output
+}
+
 /// Calls this function will explicitly initialise BOTH libcrypto and libssl.
 pub(crate) fn ssl_init() {
     static SSL_INIT: Once = Once::new();
@@ -79,6 +79,8 @@ impl fmt::Display for SslError {
             SslErrorCode::SSL => {
                 if let Some(InternalError::Ssl(e)) = &self.internal {
                     write!(f, "ErrorStack: {e}")
+                } else if let Some(InternalError::User(e)) = &self.internal {
+                    write!(f, "{e}")
                 } else {
                     write!(f, "SslCode: [{}]", self.code)
                 }
@@ -302,6 +304,18 @@ mod ut_ssl_error {
         };
         assert_eq!(format!("{}", ssl_error), "SslCode: [1]".to_string());
 
+        let ssl_error = SslError {
+            code: SslErrorCode::SSL,
+            internal: Some(InternalError::User(VerifyError::from_msg(
+                PubKeyPinning,
+                "error",
+            ))),
+        };
+        assert_eq!(
+            format!("{}", ssl_error),
+            "Public Key Pinning Error: error".to_string()
+        );
+
         let error_stack = ErrorStack::get();
         let ssl_error = SslError {
             code: SslErrorCode::SSL,
@@ -26,12 +26,14 @@ use super::{SslContext, SslErrorCode};
 use crate::c_openssl::check_ret;
 use crate::c_openssl::ffi::bio::BIO;
 use crate::c_openssl::ffi::ssl::{
-    SSL_get0_param, SSL_get_error, SSL_get_rbio, SSL_get_verify_result, SSL_read,
-    SSL_state_string_long, SSL_write,
+    SSL_CIPHER_get_name, SSL_get0_param, SSL_get_current_cipher, SSL_get_error,
+    SSL_get_peer_cert_chain, SSL_get_rbio, SSL_get_verify_result, SSL_get_version, SSL_read,
+    SSL_set_verify, SSL_state_string_long, SSL_write,
 };
 use crate::c_openssl::foreign::ForeignRef;
+use crate::c_openssl::stack::StackRef;
 use crate::c_openssl::x509::{
-    X509VerifyParamRef, X509VerifyResult, X509_CHECK_FLAG_NO_PARTIAL_WILDCARDS,
+    X509VerifyParamRef, X509VerifyResult, X509, X509_CHECK_FLAG_NO_PARTIAL_WILDCARDS,
 };
 use crate::util::c_openssl::check_ptr;
 use crate::util::c_openssl::error::ErrorStack;
@@ -104,6 +106,36 @@ impl SslRef {
         str::from_utf8(status.to_bytes()).unwrap_or_default()
     }
 
+    pub(crate) fn version_str(&self) -> &'static str {
+        let version = unsafe {
+            let ptr = SSL_get_version(self.as_ptr());
+            ffi::CStr::from_ptr(ptr as *const _)
+        };
+        str::from_utf8(version.to_bytes()).unwrap_or_default()
+    }
+
+    pub(crate) fn cipher_name(&self) -> Option<&'static str> {
+        let name = unsafe {
+            let cipher = SSL_get_current_cipher(self.as_ptr());
+            if cipher.is_null() {
+                return None;
+            }
+            ffi::CStr::from_ptr(SSL_CIPHER_get_name(cipher) as *const _)
+        };
+        str::from_utf8(name.to_bytes()).ok()
+    }
+
+    pub(crate) fn peer_cert_chain(&self) -> Option<&StackRef<X509>> {
+        unsafe {
+            let ptr = SSL_get_peer_cert_chain(self.as_ptr());
+            if ptr.is_null() {
+                None
+            } else {
+                Some(StackRef::from_ptr(ptr))
+            }
+        }
+    }
+
     pub(crate) fn verify_result(&self) -> X509VerifyResult {
         unsafe { X509VerifyResult::from_raw(SSL_get_verify_result(self.as_ptr()) as c_int) }
     }
@@ -137,6 +169,24 @@ impl SslRef {
         unsafe { X509VerifyParamRef::from_ptr_mut(SSL_get0_param(self.as_ptr())) }
     }
 
+    pub(crate) fn set_verify(&mut self, mode: c_int) {
+        unsafe { SSL_set_verify(self.as_ptr(), mode, None) };
+    }
+
+    pub(crate) fn set_alpn_protos(&mut self, protocols: &[u8]) -> Result<(), ErrorStack> {
+        use libc::c_uint;
+
+        use crate::util::c_openssl::ffi::ssl::SSL_set_alpn_protos;
+
+        assert!(protocols.len() <= c_uint::MAX as usize);
+        match unsafe {
+            SSL_set_alpn_protos(self.as_ptr(), protocols.as_ptr(), protocols.len() as c_uint)
+        } {
+            0 => Ok(()),
+            _ => Err(ErrorStack::get()),
+        }
+    }
+
     pub(crate) fn set_verify_hostname(&mut self, host_name: &str) -> Result<(), ErrorStack> {
         let param = self.param_mut();
         param.set_hostflags(X509_CHECK_FLAG_NO_PARTIAL_WILDCARDS);
@@ -146,7 +196,6 @@ impl SslRef {
         }
     }
 
-    #[cfg(feature = "http2")]
     pub(crate) fn negotiated_alpn_protocol(&self) -> Option<&[u8]> {
         use std::ffi::c_uchar;
         use std::ptr::null;
@@ -27,8 +27,9 @@ use crate::c_openssl::ffi::ssl::{SSL_connect, SSL_set_bio, SSL_shutdown};
 use crate::c_openssl::foreign::Foreign;
 use crate::util::base64::encode;
 use crate::util::c_openssl::bio::BioMethod;
-use crate::util::c_openssl::error::VerifyError;
 use crate::util::c_openssl::error::VerifyKind::PubKeyPinning;
+use crate::util::c_openssl::error::{VerifyError, VerifyKind};
+use crate::util::c_openssl::ffi::callback::take_reject_reason;
 use crate::util::c_openssl::ffi::ssl::SSL;
 use crate::util::c_openssl::ffi::x509::{i2d_X509_PUBKEY, X509_free, X509_get_X509_PUBKEY, C_X509};
 use crate::util::c_openssl::verify::sha256_digest;
@@ -157,6 +158,8 @@ impl<S: Read + Write> SslStream<S> {
     }
 
     pub(crate) fn connect(&mut self) -> Result<(), SslError> {
+        // Clears the reason left by a handshake that didn't report its failure.
+        take_reject_reason();
         let ret = unsafe { SSL_connect(self.ssl.as_ptr()) };
         if ret > 0 {
             match &self.pinned_pubkey {
@@ -166,6 +169,14 @@ impl<S: Read + Write> SslStream<S> {
                 }
             }
             Ok(())
+        } else if let Some(reason) = take_reject_reason() {
+            Err(SslError {
+                code: SslErrorCode::SSL,
+                internal: Some(InternalError::User(VerifyError::from_reason(
+                    VerifyKind::CertVerifier,
+                    reason,
+                ))),
+            })
         } else {
             Err(self.get_error(ret))
         }
@@ -104,6 +104,13 @@ impl<T: Stackof> StackRef<T> {
         unsafe { OPENSSL_sk_num(self.as_stack()) as usize }
     }
 
+    pub(crate) fn iter(&self) -> StackRefIter<'_, T> {
+        StackRefIter {
+            stack: self,
+            index: 0..self.len() as c_int,
+        }
+    }
+
     pub(crate) fn pop(&mut self) -> Option<T> {
         unsafe {
             let ptr = OPENSSL_sk_pop(self.as_stack());
@@ -123,6 +123,14 @@ impl X509Ref {
         })?))
     }
 
+    pub(crate) fn subject_line(&self) -> String {
+        unsafe { name_line(X509_get_subject_name(self.as_ptr() as *const _)) }
+    }
+
+    pub(crate) fn issuer_line(&self) -> String {
+        unsafe { name_line(X509_get_issuer_name(self.as_ptr() as *const _)) }
+    }
+
     pub(crate) fn get_cert(&self) -> Result<EvpPkey, ErrorStack> {
         Ok(EvpPkey(check_ptr(unsafe {
             X509_get_pubkey(self.as_ptr() as *mut _)
@@ -134,6 +142,19 @@ impl X509Ref {
     }
 }
 
+// Formats a name owned by a cert in the one line form, such as
+// "/C=US/O=Example/CN=example.com".
+unsafe fn name_line(name: *mut X509_NAME) -> String {
+    if name.is_null() {
+        return String::new();
+    }
+    let mut buf = [0u8; 256];
+    let _ = X509_NAME_oneline(name, buf.as_mut_ptr() as *mut _, buf.len() as c_int);
+    ffi::CStr::from_ptr(buf.as_ptr() as *const _)
+        .to_string_lossy()
+        .into_owned()
+}
+
 impl X509Name {
     pub(crate) fn get_x509_name_info(&self, buf: &mut [u8], size: c_int) -> String {
         unsafe {
@@ -0,0 +1,88 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Time source of the client.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use crate::runtime::sleep;
+
+/// The future returned by `Clock::sleep`.
+pub type SleepFuture = Pin<Box<dyn Future<Output = ()> + Send + Sync>>;
+
+/// Provides the waits which the client schedules between its attempts, so
+/// that they can be driven without waiting in real time. These are the retry
+/// backoff, the connect backoff, the delay of hedged requests and the
+/// reconnection time of event streams.
+///
+/// Timeouts, the rate limits and the delays inside a `Connector` always use
+/// the timer of the runtime.
+///
+/// # Examples
+///
+/// ```
+/// use std::future::ready;
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// use ylong_http_client::async_impl::ClientBuilder;
+/// use ylong_http_client::{Clock, SleepFuture};
+///
+/// // A clock whose waits complete at once.
+/// struct Immediate;
+///
+/// impl Clock for Immediate {
+///     fn sleep(&self, _duration: Duration) -> SleepFuture {
+///         Box::pin(ready(()))
+///     }
+/// }
+///
+/// let builder = ClientBuilder::new().clock(Arc::new(Immediate));
+/// ```
+pub trait Clock: Send + Sync {
+    /// Returns a future which completes after `duration`.
+    fn sleep(&self, duration: Duration) -> SleepFuture;
+}
+
+/// The `Clock` of the runtime, which is used by default.
+pub(crate) struct RuntimeClock;
+
+impl Clock for RuntimeClock {
+    fn sleep(&self, duration: Duration) -> SleepFuture {
+        Box::pin(sleep(duration))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod ut_clock {
+    use std::future::ready;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use super::{Clock, SleepFuture};
+
+    /// A `Clock` whose waits complete at once and are recorded instead.
+    #[derive(Default)]
+    pub(crate) struct ManualClock {
+        pub(crate) sleeps: Mutex<Vec<Duration>>,
+    }
+
+    impl Clock for ManualClock {
+        fn sleep(&self, duration: Duration) -> SleepFuture {
+            self.sleeps.lock().unwrap().push(duration);
+            Box::pin(ready(()))
+        }
+    }
+}
@@ -0,0 +1,518 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Request body compression simple implementation.
+//!
+//! The body is compressed by a streaming [`DEFLATE`] encoder, which uses LZ77
+//! matching with the fixed Huffman codes, and then wrapped in the [`GZIP`] or
+//! [`ZLIB`] format.
+//!
+//! OpenSSL has no `DEFLATE` encoder and the crate depends on no compression
+//! library, so the encoder is implemented here. It only needs to produce a
+//! valid stream, and its output is checked to be decompressed by zlib.
+//!
+//! [`DEFLATE`]: https://www.rfc-editor.org/rfc/rfc1951.html
+//! [`GZIP`]: https://www.rfc-editor.org/rfc/rfc1952.html
+//! [`ZLIB`]: https://www.rfc-editor.org/rfc/rfc1950.html
+
+pub(crate) const WINDOW_SIZE: usize = 32768;
+const WINDOW_MASK: usize = WINDOW_SIZE - 1;
+const HASH_SIZE: usize = 1 << 15;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+const MAX_CHAIN: usize = 64;
+const END_OF_BLOCK: u16 = 256;
+
+const GZIP_HEADER: [u8; 10] = [0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff];
+const ZLIB_HEADER: [u8; 2] = [0x78, 0x01];
+
+pub(crate) const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+pub(crate) const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+pub(crate) const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+pub(crate) const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+static CRC32_TABLE: [u32; 256] = crc32_table();
+
+/// Content codings which can be used to compress a request body.
+///
+/// # Examples
+///
+/// ```
+/// use ylong_http_client::Encoding;
+///
+/// assert_eq!(Encoding::Gzip.as_str(), "gzip");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// The `gzip` content coding.
+    Gzip,
+    /// The `deflate` content coding, which is the `zlib` format.
+    Deflate,
+}
+
+impl Encoding {
+    /// Gets the value of `Content-Encoding` of this `Encoding`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::Encoding;
+    ///
+    /// assert_eq!(Encoding::Deflate.as_str(), "deflate");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Deflate => "deflate",
+        }
+    }
+}
+
+/// A streaming compressor which produces the body of a `Content-Encoding`.
+pub(crate) struct Compressor {
+    encoding: Encoding,
+    deflater: Deflater,
+    checksum: Checksum,
+    header: bool,
+}
+
+impl Compressor {
+    pub(crate) fn new(encoding: Encoding) -> Self {
+        Self {
+            encoding,
+            deflater: Deflater::new(),
+            checksum: Checksum::new(encoding),
+            header: false,
+        }
+    }
+
+    /// Compresses `input` and appends the produced bytes to `output`.
+    pub(crate) fn compress(&mut self, input: &[u8], output: &mut Vec<u8>) {
+        self.write_header(output);
+        self.checksum.update(input);
+        self.deflater.deflate(input, output);
+    }
+
+    /// Finishes the stream and appends the remaining bytes to `output`.
+    pub(crate) fn finish(&mut self, output: &mut Vec<u8>) {
+        self.write_header(output);
+        self.deflater.finish(output);
+        match self.checksum {
+            Checksum::Crc32 { crc, size } => {
+                output.extend_from_slice(&crc.to_le_bytes());
+                output.extend_from_slice(&size.to_le_bytes());
+            }
+            Checksum::Adler32 { a, b } => {
+                output.extend_from_slice(&((b << 16) | a).to_be_bytes());
+            }
+        }
+    }
+
+    /// Resets the compressor so that a new stream can be produced.
+    pub(crate) fn reset(&mut self) {
+        *self = Self::new(self.encoding);
+    }
+
+    fn write_header(&mut self, output: &mut Vec<u8>) {
+        if !self.header {
+            self.header = true;
+            match self.encoding {
+                Encoding::Gzip => output.extend_from_slice(&GZIP_HEADER),
+                Encoding::Deflate => output.extend_from_slice(&ZLIB_HEADER),
+            }
+        }
+    }
+}
+
+pub(crate) enum Checksum {
+    Crc32 { crc: u32, size: u32 },
+    Adler32 { a: u32, b: u32 },
+}
+
+impl Checksum {
+    pub(crate) fn new(encoding: Encoding) -> Self {
+        match encoding {
+            Encoding::Gzip => Self::Crc32 { crc: 0, size: 0 },
+            Encoding::Deflate => Self::Adler32 { a: 1, b: 0 },
+        }
+    }
+
+    pub(crate) fn update(&mut self, input: &[u8]) {
+        match self {
+            Self::Crc32 { crc, size } => {
+                let mut value = !*crc;
+                for byte in input {
+                    value = CRC32_TABLE[((value ^ *byte as u32) & 0xff) as usize] ^ (value >> 8);
+                }
+                *crc = !value;
+                *size = size.wrapping_add(input.len() as u32);
+            }
+            Self::Adler32 { a, b } => {
+                for chunk in input.chunks(5552) {
+                    for byte in chunk {
+                        *a += *byte as u32;
+                        *b += *a;
+                    }
+                    *a %= 65521;
+                    *b %= 65521;
+                }
+            }
+        }
+    }
+}
+
+// DEFLATE encoder which emits a fixed Huffman block for each input.
+struct Deflater {
+    // The history bytes followed by the bytes being compressed.
+    window: Vec<u8>,
+    // The absolute position of `window[0]`.
+    offset: usize,
+    // The latest absolute position + 1 of each hash value.
+    head: Vec<usize>,
+    // The previous absolute position + 1 with the same hash value.
+    prev: Vec<usize>,
+    bits: u64,
+    bits_len: u32,
+}
+
+impl Deflater {
+    fn new() -> Self {
+        Self {
+            window: Vec::new(),
+            offset: 0,
+            head: vec![0; HASH_SIZE],
+            prev: vec![0; WINDOW_SIZE],
+            bits: 0,
+            bits_len: 0,
+        }
+    }
+
+    fn deflate(&mut self, input: &[u8], output: &mut Vec<u8>) {
+        if input.is_empty() {
+            return;
+        }
+        // BFINAL = 0, BTYPE = 01.
+        self.write_bits(0b010, 3, output);
+
+        let mut pos = self.window.len();
+        self.window.extend_from_slice(input);
+        while pos < self.window.len() {
+            let (len, dist) = self.longest_match(pos);
+            if len >= MIN_MATCH {
+                self.write_match(len, dist, output);
+                for i in pos..pos + len {
+                    self.insert(i);
+                }
+                pos += len;
+            } else {
+                self.write_literal(self.window[pos] as u16, output);
+                self.insert(pos);
+                pos += 1;
+            }
+        }
+        self.write_literal(END_OF_BLOCK, output);
+
+        if self.window.len() > 2 * WINDOW_SIZE {
+            let drained = self.window.len() - WINDOW_SIZE;
+            self.window.drain(..drained);
+            self.offset += drained;
+        }
+    }
+
+    fn finish(&mut self, output: &mut Vec<u8>) {
+        // BFINAL = 1, BTYPE = 01, followed by an empty block.
+        self.write_bits(0b011, 3, output);
+        self.write_literal(END_OF_BLOCK, output);
+        if self.bits_len > 0 {
+            output.push(self.bits as u8);
+            self.bits = 0;
+            self.bits_len = 0;
+        }
+    }
+
+    fn hash(&self, pos: usize) -> Option<usize> {
+        let bytes = self.window.get(pos..pos + MIN_MATCH)?;
+        Some(
+            (((bytes[0] as usize) << 10) ^ ((bytes[1] as usize) << 5) ^ bytes[2] as usize)
+                & (HASH_SIZE - 1),
+        )
+    }
+
+    fn insert(&mut self, pos: usize) {
+        if let Some(hash) = self.hash(pos) {
+            let abs = self.offset + pos;
+            self.prev[abs & WINDOW_MASK] = self.head[hash];
+            self.head[hash] = abs + 1;
+        }
+    }
+
+    fn longest_match(&self, pos: usize) -> (usize, usize) {
+        let hash = match self.hash(pos) {
+            Some(hash) => hash,
+            None => return (0, 0),
+        };
+        let abs = self.offset + pos;
+        let max = (self.window.len() - pos).min(MAX_MATCH);
+        let (mut best_len, mut best_dist) = (0, 0);
+        let mut next = self.head[hash];
+        for _ in 0..MAX_CHAIN {
+            if next == 0 {
+                break;
+            }
+            let candidate = next - 1;
+            if candidate < self.offset || abs - candidate > WINDOW_SIZE {
+                break;
+            }
+            let start = candidate - self.offset;
+            let len = self.window[start..]
+                .iter()
+                .zip(&self.window[pos..pos + max])
+                .take_while(|(a, b)| a == b)
+                .count();
+            if len > best_len {
+                best_len = len;
+                best_dist = abs - candidate;
+                if len == max {
+                    break;
+                }
+            }
+            next = self.prev[candidate & WINDOW_MASK];
+            if next == 0 || next > candidate {
+                break;
+            }
+        }
+        (best_len, best_dist)
+    }
+
+    fn write_literal(&mut self, symbol: u16, output: &mut Vec<u8>) {
+        let (code, len) = match symbol {
+            0..=143 => (0x30 + symbol, 8),
+            144..=255 => (0x190 + symbol - 144, 9),
+            256..=279 => (symbol - 256, 7),
+            _ => (0xc0 + symbol - 280, 8),
+        };
+        self.write_code(code, len, output);
+    }
+
+    fn write_match(&mut self, len: usize, dist: usize, output: &mut Vec<u8>) {
+        let index = LENGTH_BASE
+            .iter()
+            .rposition(|base| *base as usize <= len)
+            .unwrap_or(0);
+        self.write_literal(257 + index as u16, output);
+        self.write_bits(
+            (len - LENGTH_BASE[index] as usize) as u64,
+            LENGTH_EXTRA[index] as u32,
+            output,
+        );
+
+        let index = DISTANCE_BASE
+            .iter()
+            .rposition(|base| *base as usize <= dist)
+            .unwrap_or(0);
+        self.write_code(index as u16, 5, output);
+        self.write_bits(
+            (dist - DISTANCE_BASE[index] as usize) as u64,
+            DISTANCE_EXTRA[index] as u32,
+            output,
+        );
+    }
+
+    // Huffman codes are packed starting with the most significant bit.
+    fn write_code(&mut self, code: u16, len: u32, output: &mut Vec<u8>) {
+        let reversed = code.reverse_bits() >> (16 - len);
+        self.write_bits(reversed as u64, len, output);
+    }
+
+    fn write_bits(&mut self, value: u64, len: u32, output: &mut Vec<u8>) {
+        self.bits |= value << self.bits_len;
+        self.bits_len += len;
+        while self.bits_len >= 8 {
+            output.push(self.bits as u8);
+            self.bits >>= 8;
+            self.bits_len -= 8;
+        }
+    }
+}
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut value = i as u32;
+        let mut j = 0;
+        while j < 8 {
+            value = if value & 1 == 1 {
+                0xedb88320 ^ (value >> 1)
+            } else {
+                value >> 1
+            };
+            j += 1;
+        }
+        table[i] = value;
+        i += 1;
+    }
+    table
+}
+
+#[cfg(test)]
+mod ut_util_compress {
+    use crate::util::compress::{
+        Compressor, Encoding, DISTANCE_BASE, DISTANCE_EXTRA, LENGTH_BASE, LENGTH_EXTRA,
+    };
+
+    // Inflates a DEFLATE stream which only has fixed Huffman blocks.
+    fn inflate(input: &[u8]) -> Vec<u8> {
+        let mut bit = 0;
+        let mut read = |len: usize| {
+            let mut value = 0;
+            for i in 0..len {
+                value |= (((input[bit / 8] >> (bit % 8)) & 1) as usize) << i;
+                bit += 1;
+            }
+            value
+        };
+        let mut output = Vec::new();
+        loop {
+            let last = read(1);
+            assert_eq!(read(2), 1);
+            loop {
+                let mut code = 0;
+                let mut len = 0;
+                let symbol = loop {
+                    code = (code << 1) | read(1);
+                    len += 1;
+                    match (len, code) {
+                        (7, 0..=0x17) => break code + 256,
+                        (8, 0x30..=0xbf) => break code - 0x30,
+                        (8, 0xc0..=0xc7) => break code - 0xc0 + 280,
+                        (9, 0x190..=0x1ff) => break code - 0x190 + 144,
+                        _ => assert!(len < 9),
+                    }
+                };
+                match symbol {
+                    0..=255 => output.push(symbol as u8),
+                    256 => break,
+                    _ => {
+                        let index = symbol - 257;
+                        let len = LENGTH_BASE[index] as usize + read(LENGTH_EXTRA[index] as usize);
+                        let mut index = 0;
+                        for _ in 0..5 {
+                            index = (index << 1) | read(1);
+                        }
+                        let dist =
+                            DISTANCE_BASE[index] as usize + read(DISTANCE_EXTRA[index] as usize);
+                        for _ in 0..len {
+                            output.push(output[output.len() - dist]);
+                        }
+                    }
+                }
+            }
+            if last == 1 {
+                return output;
+            }
+        }
+    }
+
+    fn compress(encoding: Encoding, chunks: &[&[u8]]) -> Vec<u8> {
+        let mut compressor = Compressor::new(encoding);
+        let mut output = Vec::new();
+        for chunk in chunks {
+            compressor.compress(chunk, &mut output);
+        }
+        compressor.finish(&mut output);
+        output
+    }
+
+    /// UT test cases for `Compressor` with `Encoding::Gzip`.
+    ///
+    /// # Brief
+    /// 1. Compresses some bytes in chunks by calling `Compressor::compress`.
+    /// 2. Checks if the gzip header and trailer are correct.
+    /// 3. Checks if the inflated bytes are the same as the origin bytes.
+    #[test]
+    fn ut_compressor_gzip() {
+        let data = b"hello hello hello world, hello hello hello world!".repeat(100);
+        let output = compress(Encoding::Gzip, &[&data[..1000], &data[1000..]]);
+        assert!(output.len() < data.len() / 10);
+        assert_eq!(output[..3], [0x1f, 0x8b, 0x08]);
+        let (body, trailer) = output[10..].split_at(output.len() - 18);
+        assert_eq!(inflate(body), data);
+        assert_eq!(
+            trailer[4..],
+            (data.len() as u32).to_le_bytes(),
+            "ISIZE mismatch"
+        );
+
+        let output = compress(Encoding::Gzip, &[b"abc"]);
+        assert_eq!(
+            output[output.len() - 8..output.len() - 4],
+            0x352441c2u32.to_le_bytes()
+        );
+    }
+
+    /// UT test cases for `Compressor` with `Encoding::Deflate`.
+    ///
+    /// # Brief
+    /// 1. Compresses some bytes by calling `Compressor::compress`.
+    /// 2. Checks if the zlib header and trailer are correct.
+    /// 3. Checks if the inflated bytes are the same as the origin bytes.
+    #[test]
+    fn ut_compressor_deflate() {
+        let output = compress(Encoding::Deflate, &[b"Wikipedia"]);
+        assert_eq!(output[..2], [0x78, 0x01]);
+        assert_eq!(output[output.len() - 4..], 0x11e60398u32.to_be_bytes());
+        assert_eq!(inflate(&output[2..output.len() - 4]), b"Wikipedia");
+
+        let output = compress(Encoding::Deflate, &[]);
+        assert_eq!(inflate(&output[2..output.len() - 4]), b"");
+    }
+
+    /// UT test cases for `Compressor` with the vectors checked by zlib.
+    ///
+    /// # Brief
+    /// 1. Compresses a corpus in chunks by calling `Compressor::compress`.
+    /// 2. Checks if the gzip and zlib streams are the same as the vectors,
+    ///    which zlib decompresses to the corpus.
+    /// 3. Checks if the inflated bytes are the same as the corpus.
+    #[test]
+    fn ut_compressor_zlib_vectors() {
+        let data = include_bytes!("../../tests/file/decompress/corpus.bin");
+        let chunks = data.chunks(1000).collect::<Vec<_>>();
+
+        let output = compress(Encoding::Gzip, &chunks);
+        assert_eq!(
+            output,
+            include_bytes!("../../tests/file/compress/corpus.gz")
+        );
+        assert_eq!(inflate(&output[10..output.len() - 8]), data);
+
+        let output = compress(Encoding::Deflate, &chunks);
+        assert_eq!(
+            output,
+            include_bytes!("../../tests/file/compress/corpus.zz")
+        );
+        assert_eq!(inflate(&output[2..output.len() - 4]), data);
+    }
+}
@@ -13,14 +13,65 @@
 
 //! Client configure module.
 
+#[cfg(any(feature = "auth", feature = "async"))]
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "async")]
+use crate::util::clock::{Clock, RuntimeClock};
+#[cfg(feature = "auth")]
+use crate::util::digest_auth::DigestAuth;
 use crate::util::{Redirect, Retry, Timeout};
 
+// The default maximum size of a single chunk of a chunked response body.
+const DEFAULT_MAX_CHUNK_SIZE: usize = 64 * 1024 * 1024;
+// The default time to wait for `100 Continue` before sending a request body.
+const DEFAULT_EXPECT_CONTINUE_TIMEOUT: Duration = Duration::from_secs(1);
+// The default maximum length of a request URI or a redirect location.
+#[cfg(feature = "async")]
+const DEFAULT_MAX_URI_LENGTH: usize = 8 * 1024;
+
 /// Options and flags which can be used to configure a client.
 pub(crate) struct ClientConfig {
     pub(crate) redirect: Redirect,
     pub(crate) retry: Retry,
+    pub(crate) connect_retries: usize,
     pub(crate) connect_timeout: Timeout,
     pub(crate) request_timeout: Timeout,
+    pub(crate) total_timeout: Timeout,
+    pub(crate) ttfb_timeout: Timeout,
+    pub(crate) read_timeout: Timeout,
+    #[cfg(feature = "http2")]
+    pub(crate) stream_idle_timeout: Timeout,
+    pub(crate) default_accept: Option<String>,
+    pub(crate) drain_on_drop: usize,
+    pub(crate) max_chunk_size: usize,
+    pub(crate) request_chunk_size: Option<usize>,
+    pub(crate) upload_rate_limit: Option<u64>,
+    pub(crate) download_rate_limit: Option<u64>,
+    pub(crate) allow_body_on_get: bool,
+    pub(crate) dns_cache_ttl: Option<Duration>,
+    pub(crate) dns_timeout: Option<Duration>,
+    pub(crate) te_trailers: bool,
+    pub(crate) send_date_header: bool,
+    pub(crate) auto_decompress: bool,
+    pub(crate) reject_until_close: bool,
+    pub(crate) max_until_close_size: Option<u64>,
+    pub(crate) max_header_size: Option<usize>,
+    pub(crate) preserve_header_case: bool,
+    pub(crate) max_body_size: Option<u64>,
+    pub(crate) max_concurrent_requests: Option<usize>,
+    pub(crate) expect_continue_timeout: Duration,
+    #[cfg(feature = "async")]
+    pub(crate) clock: Arc<dyn Clock>,
+    // The delay before each backup request and the maximum number of copies
+    // of an idempotent request in flight.
+    #[cfg(feature = "async")]
+    pub(crate) hedge: Option<(Duration, usize)>,
+    #[cfg(feature = "async")]
+    pub(crate) max_uri_length: usize,
+    #[cfg(feature = "auth")]
+    pub(crate) digest_auth: Option<Arc<DigestAuth>>,
 }
 
 impl ClientConfig {
@@ -29,8 +80,41 @@ impl ClientConfig {
         Self {
             redirect: Redirect::no_limit(),
             retry: Retry::none(),
+            connect_retries: 0,
             connect_timeout: Timeout::none(),
             request_timeout: Timeout::none(),
+            total_timeout: Timeout::none(),
+            ttfb_timeout: Timeout::none(),
+            read_timeout: Timeout::none(),
+            #[cfg(feature = "http2")]
+            stream_idle_timeout: Timeout::none(),
+            default_accept: Some(String::from("*/*")),
+            drain_on_drop: 0,
+            max_chunk_size: DEFAULT_MAX_CHUNK_SIZE,
+            request_chunk_size: None,
+            upload_rate_limit: None,
+            download_rate_limit: None,
+            allow_body_on_get: true,
+            dns_cache_ttl: None,
+            dns_timeout: None,
+            te_trailers: false,
+            send_date_header: false,
+            auto_decompress: false,
+            reject_until_close: false,
+            max_until_close_size: None,
+            max_header_size: None,
+            preserve_header_case: false,
+            max_body_size: None,
+            max_concurrent_requests: None,
+            expect_continue_timeout: DEFAULT_EXPECT_CONTINUE_TIMEOUT,
+            #[cfg(feature = "async")]
+            clock: Arc::new(RuntimeClock),
+            #[cfg(feature = "async")]
+            hedge: None,
+            #[cfg(feature = "async")]
+            max_uri_length: DEFAULT_MAX_URI_LENGTH,
+            #[cfg(feature = "auth")]
+            digest_auth: None,
         }
     }
 }
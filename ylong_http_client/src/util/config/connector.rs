@@ -13,8 +13,13 @@
 
 //! Connector configure module.
 
+#[cfg(feature = "__tls")]
+use std::collections::HashMap;
+use std::time::Duration;
+
 #[cfg(all(target_os = "linux", feature = "ylong_base", feature = "__tls"))]
 use super::FchownConfig;
+use super::SocketConfig;
 use crate::util::proxy::Proxies;
 use crate::Timeout;
 
@@ -22,12 +27,36 @@ use crate::Timeout;
 pub(crate) struct ConnectorConfig {
     pub(crate) proxies: Proxies,
     pub(crate) timeout: Timeout,
+    // Timeout of every dns resolution.
+    pub(crate) dns_timeout: Option<Duration>,
+    pub(crate) socket: SocketConfig,
 
     #[cfg(all(target_os = "linux", feature = "ylong_base", feature = "__tls"))]
     pub(crate) fchown: Option<FchownConfig>,
 
     #[cfg(feature = "__tls")]
     pub(crate) tls: crate::util::TlsConfig,
+
+    // Timeout of the TLS handshake, which is separate from `timeout`.
+    #[cfg(feature = "__tls")]
+    pub(crate) tls_handshake_timeout: Timeout,
+
+    // TLS configs which replace `tls` for the hosts, keyed by lowercase host.
+    #[cfg(feature = "__tls")]
+    pub(crate) host_tls: HashMap<String, crate::util::TlsConfig>,
+}
+
+impl ConnectorConfig {
+    /// Gets the TLS config of connections to `host`.
+    #[cfg(feature = "__tls")]
+    pub(crate) fn tls_config(&self, host: &str) -> &crate::util::TlsConfig {
+        if self.host_tls.is_empty() {
+            return &self.tls;
+        }
+        self.host_tls
+            .get(&host.to_ascii_lowercase())
+            .unwrap_or(&self.tls)
+    }
 }
 
 #[cfg(test)]
@@ -47,4 +76,26 @@ mod ut_connector_config {
         let uri = Uri::from_bytes(b"http://127.0.0.1").unwrap();
         assert!(config.proxies.match_proxy(&uri).is_none())
     }
+
+    /// UT test cases for `ConnectorConfig::tls_config`.
+    ///
+    /// # Brief
+    /// 1. Creates a `ConnectorConfig` with a TLS config of a host.
+    /// 2. Checks that the host, in any case, uses its own TLS config and
+    ///    other hosts use the global one.
+    #[cfg(feature = "__tls")]
+    #[test]
+    fn ut_connector_config_host_tls() {
+        let mut config = ConnectorConfig::default();
+        config.host_tls.insert(
+            String::from("internal.example.com"),
+            crate::TlsConfig::builder().build().unwrap(),
+        );
+        let host = config.host_tls.get("internal.example.com").unwrap();
+        assert!(std::ptr::eq(
+            config.tls_config("Internal.Example.com"),
+            host
+        ));
+        assert!(std::ptr::eq(config.tls_config("example.com"), &config.tls));
+    }
 }
@@ -28,6 +28,10 @@ pub(crate) struct HttpConfig {
 
     #[cfg(feature = "http3")]
     pub(crate) http3_config: http3::H3Config,
+
+    /// The maximum number of connect attempts in flight, `None` means no
+    /// limit.
+    pub(crate) max_concurrent_connects: Option<usize>,
 }
 
 impl HttpConfig {
@@ -44,6 +48,8 @@ impl HttpConfig {
 
             #[cfg(feature = "http3")]
             http3_config: http3::H3Config::new(),
+
+            max_concurrent_connects: None,
         }
     }
 }
@@ -55,7 +61,7 @@ impl Default for HttpConfig {
 }
 
 /// `HTTP` version to use.
-#[derive(PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub enum HttpVersion {
     /// Enforces `HTTP/1.1` or `HTTP/1.0` requests.
     Http1,
@@ -99,6 +105,9 @@ pub(crate) mod http1 {
     #[derive(Clone)]
     pub(crate) struct H1Config {
         max_conn_num: usize,
+        // The maximum number of requests in flight on a connection, pipelining
+        // is disabled if it is not greater than 1.
+        pipelining: usize,
     }
 
     impl H1Config {
@@ -109,12 +118,21 @@ pub(crate) mod http1 {
         pub(crate) fn max_conn_num(&self) -> usize {
             self.max_conn_num
         }
+
+        pub(crate) fn set_pipelining(&mut self, depth: usize) {
+            self.pipelining = depth
+        }
+
+        pub(crate) fn pipelining(&self) -> usize {
+            self.pipelining
+        }
     }
 
     impl Default for H1Config {
         fn default() -> Self {
             Self {
                 max_conn_num: DEFAULT_MAX_CONN_NUM,
+                pipelining: 0,
             }
         }
     }
@@ -122,6 +140,13 @@ pub(crate) mod http1 {
 
 #[cfg(feature = "http2")]
 pub(crate) mod http2 {
+    use std::sync::Arc;
+
+    use ylong_http::h2::Setting;
+
+    use crate::error::{ErrorKind, HttpClientError};
+    use crate::util::h2::H2Observer;
+
     const DEFAULT_MAX_FRAME_SIZE: u32 = 16 * 1024;
     const DEFAULT_HEADER_TABLE_SIZE: u32 = 4096;
     const DEFAULT_MAX_HEADER_LIST_SIZE: u32 = 16 * 1024;
@@ -143,6 +168,10 @@ pub(crate) mod http2 {
         enable_push: bool,
         allowed_cache_frame_size: usize,
         use_huffman: bool,
+        raw_settings: Vec<(u16, u32)>,
+        custom_settings: Vec<Setting>,
+        observer: Option<Arc<dyn H2Observer>>,
+        max_send_buffer_size: Option<usize>,
     }
 
     impl H2Config {
@@ -182,6 +211,65 @@ pub(crate) mod http2 {
             self.use_huffman = use_huffman;
         }
 
+        /// Sets the SETTINGS_ENABLE_PUSH.
+        pub(crate) fn set_enable_push(&mut self, enable: bool) {
+            self.enable_push = enable;
+        }
+
+        /// Sets the observer of the frames of the connection.
+        pub(crate) fn set_observer(&mut self, observer: Arc<dyn H2Observer>) {
+            self.observer = Some(observer);
+        }
+
+        pub(crate) fn observer(&self) -> Option<&Arc<dyn H2Observer>> {
+            self.observer.as_ref()
+        }
+
+        /// Sets the max size of the request body data of a stream waiting to
+        /// be written.
+        pub(crate) fn set_max_send_buffer_size(&mut self, size: usize) {
+            self.max_send_buffer_size = Some(size);
+        }
+
+        pub(crate) fn max_send_buffer_size(&self) -> Option<usize> {
+            self.max_send_buffer_size
+        }
+
+        /// Adds a raw SETTINGS parameter, which is validated by
+        /// `merge_custom_settings`.
+        pub(crate) fn add_raw_setting(&mut self, id: u16, value: u32) {
+            self.raw_settings.push((id, value));
+        }
+
+        /// Validates the raw SETTINGS parameters and merges them into this
+        /// config. A raw parameter overrides the value set by the typed
+        /// setter of the same SETTINGS identifier, and a later raw parameter
+        /// overrides an earlier one with the same identifier.
+        pub(crate) fn merge_custom_settings(&mut self) -> Result<(), HttpClientError> {
+            for (id, value) in core::mem::take(&mut self.raw_settings) {
+                let setting = Setting::from_raw(id, value).map_err(|_| {
+                    HttpClientError::from_str(ErrorKind::Build, "Invalid HTTP/2 SETTINGS parameter")
+                })?;
+                match setting {
+                    Setting::HeaderTableSize(size) => self.header_table_size = size,
+                    Setting::EnablePush(enable) => self.enable_push = enable,
+                    Setting::InitialWindowSize(size) => self.init_stream_window_size = size,
+                    Setting::MaxFrameSize(size) => self.max_frame_size = size,
+                    Setting::MaxHeaderListSize(size) => self.max_header_list_size = size,
+                    _ => {}
+                }
+                match self
+                    .custom_settings
+                    .iter_mut()
+                    .find(|s| s.setting_identifier() == id)
+                {
+                    Some(existing) => *existing = setting,
+                    None => self.custom_settings.push(setting),
+                }
+            }
+            Ok(())
+        }
+
         /// Gets the SETTINGS_MAX_FRAME_SIZE.
         pub(crate) fn max_frame_size(&self) -> u32 {
             self.max_frame_size
@@ -216,6 +304,12 @@ pub(crate) mod http2 {
         pub(crate) fn use_huffman_coding(&self) -> bool {
             self.use_huffman
         }
+
+        /// Gets the merged custom SETTINGS parameters in the order they were
+        /// added.
+        pub(crate) fn custom_settings(&self) -> &[Setting] {
+            &self.custom_settings
+        }
     }
 
     impl Default for H2Config {
@@ -229,6 +323,10 @@ pub(crate) mod http2 {
                 enable_push: false,
                 allowed_cache_frame_size: 5,
                 use_huffman: true,
+                raw_settings: Vec::new(),
+                custom_settings: Vec::new(),
+                observer: None,
+                max_send_buffer_size: None,
             }
         }
     }
@@ -23,15 +23,19 @@ pub(crate) use http::http2::H2Config;
 #[cfg(feature = "http3")]
 pub(crate) use http::http3::H3Config;
 pub(crate) use http::{HttpConfig, HttpVersion};
+#[cfg(feature = "async")]
+pub(crate) use settings::is_idempotent;
 pub use settings::{Proxy, ProxyBuilder, Redirect, Retry, SpeedLimit, Timeout};
 #[cfg(feature = "__tls")]
 pub(crate) mod tls;
 #[cfg(feature = "__tls")]
 pub(crate) use tls::{AlpnProtocol, AlpnProtocolList};
 #[cfg(feature = "__tls")]
-pub use tls::{CertVerifier, ServerCerts};
+pub use tls::{CertVerifier, ServerCerts, VerifyResult};
 #[cfg(feature = "tls_rust_ssl")]
 pub use tls::{Certificate, PrivateKey, TlsConfig, TlsConfigBuilder, TlsFileType, TlsVersion};
+mod socket;
+pub(crate) use socket::SocketConfig;
 #[cfg(all(target_os = "linux", feature = "ylong_base", feature = "__tls"))]
 mod fchown;
 #[cfg(all(target_os = "linux", feature = "ylong_base", feature = "__tls"))]
@@ -14,6 +14,9 @@
 use core::cmp;
 use core::time::Duration;
 
+use ylong_http::request::method::Method;
+use ylong_http::response::status::StatusCode;
+
 use crate::error::HttpClientError;
 use crate::util::{proxy, redirect};
 
@@ -76,6 +79,40 @@ impl Redirect {
         Self(redirect::Redirect::none())
     }
 
+    /// Sets whether exceeding the maximum number of redirects returns an
+    /// error. Defaults to `true`. If `false`, the last redirect response is
+    /// returned instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ylong_http_client::Redirect;
+    ///
+    /// let redirect = Redirect::limited(10).error_on_limit(false);
+    /// ```
+    pub fn error_on_limit(mut self, error: bool) -> Self {
+        self.0.set_error_on_limit(error);
+        self
+    }
+
+    /// Sets whether a redirect which has to resend a request body that is
+    /// not held in memory, such as a `Body::stream` or a `Body::multipart`,
+    /// returns an error instead of being followed. Defaults to `false`, in
+    /// which case such a body is rewound by its `ReusableReader::reuse`
+    /// before the redirected request is sent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ylong_http_client::Redirect;
+    ///
+    /// let redirect = Redirect::limited(10).strict_body(true);
+    /// ```
+    pub fn strict_body(mut self, strict: bool) -> Self {
+        self.0.set_strict_body(strict);
+        self
+    }
+
     pub(crate) fn inner(&self) -> &redirect::Redirect {
         &self.0
     }
@@ -98,7 +135,12 @@ impl Redirect {
 /// let custom = Retry::new(2).unwrap();
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct Retry(Option<usize>);
+pub struct Retry {
+    times: Option<usize>,
+    retry_status: bool,
+    non_idempotent: bool,
+    backoff: Option<Duration>,
+}
 
 impl Retry {
     const MAX_RETRIES: usize = 3;
@@ -118,7 +160,7 @@ impl Retry {
         if times >= Self::MAX_RETRIES {
             return err_from_msg!(Build, "Invalid params");
         }
-        Ok(Self(Some(times)))
+        Ok(Self::with_times(Some(times)))
     }
 
     /// Creates a `Retry` that indicates never retry.
@@ -131,7 +173,7 @@ impl Retry {
     /// let retry = Retry::none();
     /// ```
     pub fn none() -> Self {
-        Self(None)
+        Self::with_times(None)
     }
 
     /// Creates a `Retry` with a max retry times.
@@ -146,7 +188,57 @@ impl Retry {
     /// let retry = Retry::max();
     /// ```
     pub fn max() -> Self {
-        Self(Some(Self::MAX_RETRIES))
+        Self::with_times(Some(Self::MAX_RETRIES))
+    }
+
+    /// Sets whether to retry the request when the response status is
+    /// `408 Request Timeout` or `425 Too Early`.
+    ///
+    /// Only requests with idempotent methods are retried on these status
+    /// codes, unless [`Retry::non_idempotent`] is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::Retry;
+    ///
+    /// let retry = Retry::max().retry_status(true);
+    /// ```
+    pub fn retry_status(mut self, enable: bool) -> Self {
+        self.retry_status = enable;
+        self
+    }
+
+    /// Sets whether requests with non-idempotent methods, such as `POST`, are
+    /// retried on a retryable response status.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::Retry;
+    ///
+    /// let retry = Retry::max().retry_status(true).non_idempotent(true);
+    /// ```
+    pub fn non_idempotent(mut self, enable: bool) -> Self {
+        self.non_idempotent = enable;
+        self
+    }
+
+    /// Sets the time to wait before each retry. The request is retried
+    /// immediately by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use ylong_http_client::Retry;
+    ///
+    /// let retry = Retry::max().backoff(Duration::from_millis(100));
+    /// ```
+    pub fn backoff(mut self, delay: Duration) -> Self {
+        self.backoff = Some(delay);
+        self
     }
 
     /// Get the retry times, returns None if not set.
@@ -159,8 +251,45 @@ impl Retry {
     /// assert!(Retry::default().times().is_none());
     /// ```
     pub fn times(&self) -> Option<usize> {
-        self.0
+        self.times
+    }
+
+    pub(crate) fn backoff_delay(&self) -> Option<Duration> {
+        self.backoff
+    }
+
+    // Checks whether a response with the given status should be retried.
+    pub(crate) fn is_retryable_status(&self, method: &Method, status: StatusCode) -> bool {
+        if !self.retry_status {
+            return false;
+        }
+        if !self.non_idempotent && !is_idempotent(method) {
+            return false;
+        }
+        // 425 Too Early
+        status == StatusCode::REQUEST_TIMEOUT || status.as_u16() == 425
     }
+
+    fn with_times(times: Option<usize>) -> Self {
+        Self {
+            times,
+            retry_status: false,
+            non_idempotent: false,
+            backoff: None,
+        }
+    }
+}
+
+pub(crate) fn is_idempotent(method: &Method) -> bool {
+    [
+        Method::GET,
+        Method::HEAD,
+        Method::PUT,
+        Method::DELETE,
+        Method::OPTIONS,
+        Method::TRACE,
+    ]
+    .contains(method)
 }
 
 impl Default for Retry {
@@ -394,6 +523,10 @@ impl Proxy {
     pub(crate) fn inner(self) -> proxy::Proxy {
         self.0
     }
+
+    pub(crate) fn inner_ref(&self) -> &proxy::Proxy {
+        &self.0
+    }
 }
 
 /// A builder that constructs a `Proxy`.
@@ -448,6 +581,49 @@ impl ProxyBuilder {
         self
     }
 
+    /// Sets username and password which answer a `Digest` challenge of the
+    /// proxy. The CONNECT request of a tunnel is sent without credentials,
+    /// and sent once again with the digest response if the proxy responds
+    /// `407` with a `Proxy-Authenticate: Digest` challenge. The `MD5` and
+    /// `SHA-256` algorithms are supported.
+    ///
+    /// The credentials of `basic_auth` are also used to answer such a
+    /// challenge.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::Proxy;
+    ///
+    /// let builder = Proxy::https("http://example.com").digest_auth("username", "password");
+    /// ```
+    pub fn digest_auth(mut self, username: &str, password: &str) -> Self {
+        self.inner = self.inner.map(|mut proxy| {
+            proxy.digest_auth(username, password);
+            proxy
+        });
+        self
+    }
+
+    /// Adds a header to the CONNECT request which is sent to the proxy to
+    /// create a tunnel for HTTPS requests. The header is not sent with the
+    /// tunneled requests.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::Proxy;
+    ///
+    /// let builder = Proxy::https("http://example.com").connect_header("X-Proxy-Token", "token");
+    /// ```
+    pub fn connect_header(mut self, name: &str, value: &str) -> Self {
+        self.inner = self.inner.and_then(|mut proxy| {
+            proxy.connect_header(name, value)?;
+            Ok(proxy)
+        });
+        self
+    }
+
     /// Constructs a `Proxy`.
     ///
     /// # Examples
@@ -466,7 +642,9 @@ impl ProxyBuilder {
 mod ut_settings {
     use std::time::Duration;
 
+    use ylong_http::request::method::Method;
     use ylong_http::request::uri::Uri;
+    use ylong_http::response::status::StatusCode;
 
     use crate::{Proxy, Redirect, Retry, SpeedLimit, Timeout};
 
@@ -485,6 +663,30 @@ mod ut_settings {
         assert!(retry.is_err());
     }
 
+    /// UT test cases for `Retry::is_retryable_status`.
+    ///
+    /// # Brief
+    /// 1. Creates `Retry`s by calling `Retry::retry_status` and
+    ///    `Retry::non_idempotent`.
+    /// 2. Checks if the results are correct.
+    #[test]
+    fn ut_retry_is_retryable_status() {
+        let timeout = StatusCode::REQUEST_TIMEOUT;
+        let too_early = StatusCode::from_u16(425).unwrap();
+
+        let retry = Retry::max();
+        assert!(!retry.is_retryable_status(&Method::GET, timeout));
+
+        let retry = Retry::max().retry_status(true);
+        assert!(retry.is_retryable_status(&Method::GET, timeout));
+        assert!(retry.is_retryable_status(&Method::PUT, too_early));
+        assert!(!retry.is_retryable_status(&Method::GET, StatusCode::OK));
+        assert!(!retry.is_retryable_status(&Method::POST, timeout));
+
+        let retry = retry.non_idempotent(true);
+        assert!(retry.is_retryable_status(&Method::POST, too_early));
+    }
+
     /// UT test cases for `Redirect::default`.
     ///
     /// # Brief
@@ -500,6 +702,105 @@ mod ut_settings {
         assert_eq!(redirect.clone(), redirect_10)
     }
 
+    /// UT test cases for `Redirect::strict_body`.
+    ///
+    /// # Brief
+    /// 1. Creates `Redirect`s with and without `strict_body`.
+    /// 2. Checks if the results are as expected.
+    #[test]
+    fn ut_redirect_strict_body() {
+        assert!(!Redirect::default().inner().strict_body());
+        assert!(Redirect::limited(3).strict_body(true).inner().strict_body());
+        assert_ne!(Redirect::default(), Redirect::default().strict_body(true));
+    }
+
+    /// UT test cases for `Redirect::error_on_limit`.
+    ///
+    /// # Brief
+    /// 1. Creates a default `Redirect` and follows 11 redirects.
+    /// 2. Checks that the 11th redirect returns an error.
+    /// 3. Disables `error_on_limit` and checks that the 11th redirect stops.
+    #[test]
+    fn ut_redirect_error_on_limit() {
+        use ylong_http::headers::Headers;
+        use ylong_http::request::Request;
+        use ylong_http::response::{Response, ResponsePart};
+        use ylong_http::version::Version;
+
+        use crate::util::redirect::{RedirectInfo, Trigger};
+
+        let redirect_to = |hop: usize| {
+            let mut headers = Headers::new();
+            let location = format!("http://example.com/{hop}");
+            headers.insert("Location", location.as_str()).unwrap();
+            Response::from_raw_parts(
+                ResponsePart::new(Version::HTTP1_1, StatusCode::FOUND, headers),
+                (),
+            )
+        };
+
+        let follow = |redirect: Redirect| {
+            let mut request = Request::builder()
+                .url("http://example.com/")
+                .body(())
+                .unwrap();
+            let mut info = RedirectInfo::new();
+            for hop in 0..11 {
+                let response = redirect_to(hop);
+                match redirect
+                    .inner()
+                    .redirect(&mut request, &response, &mut info)
+                {
+                    Ok(Trigger::NextLink) => {}
+                    result => return result,
+                }
+            }
+            Ok(Trigger::NextLink)
+        };
+
+        assert!(follow(Redirect::default()).is_err());
+        let trigger = follow(Redirect::default().error_on_limit(false));
+        assert!(matches!(trigger, Ok(Trigger::Stop)));
+    }
+
+    /// UT test cases for redirect loop detection.
+    ///
+    /// # Brief
+    /// 1. Creates a default `Redirect`.
+    /// 2. Follows a redirect chain A -> B -> A.
+    /// 3. Checks that the loop is detected before the redirect limit.
+    #[test]
+    fn ut_redirect_loop() {
+        use ylong_http::headers::Headers;
+        use ylong_http::request::Request;
+        use ylong_http::response::{Response, ResponsePart};
+        use ylong_http::version::Version;
+
+        use crate::util::redirect::{RedirectInfo, Trigger};
+
+        let redirect_to = |location: &str| {
+            let mut headers = Headers::new();
+            headers.insert("Location", location).unwrap();
+            Response::from_raw_parts(
+                ResponsePart::new(Version::HTTP1_1, StatusCode::FOUND, headers),
+                (),
+            )
+        };
+
+        let redirect = Redirect::default();
+        let mut request = Request::builder()
+            .url("http://example.com/a")
+            .body(())
+            .unwrap();
+        let mut info = RedirectInfo::new();
+        let to_b = redirect_to("http://example.com/b");
+        let trigger = redirect.inner().redirect(&mut request, &to_b, &mut info);
+        assert!(matches!(trigger, Ok(Trigger::NextLink)));
+        let to_a = redirect_to("http://example.com/a");
+        let trigger = redirect.inner().redirect(&mut request, &to_a, &mut info);
+        assert!(trigger.is_err());
+    }
+
     /// UT test cases for `Retry::clone`.
     ///
     /// # Brief
@@ -613,4 +914,32 @@ mod ut_settings {
         let uri = Uri::from_bytes(b"https://127.0.0.1:3456").unwrap();
         assert!(proxy.inner().is_intercepted(&uri));
     }
+
+    /// UT test cases for `ProxyBuilder::connect_header`.
+    ///
+    /// # Brief
+    /// 1. Creates a `Proxy` with CONNECT headers by calling
+    ///    `ProxyBuilder::connect_header`.
+    /// 2. Creates a `Proxy` with an invalid CONNECT header.
+    /// 3. Checks if the results are correct.
+    #[test]
+    fn ut_proxy_connect_header() {
+        let proxy = Proxy::https("http://127.0.0.1:6789")
+            .connect_header("X-Proxy-Token", "token")
+            .build()
+            .unwrap()
+            .inner();
+        let headers = &proxy.intercept.proxy_info().connect_headers;
+        assert_eq!(
+            headers
+                .get("x-proxy-token")
+                .and_then(|v| v.to_string().ok()),
+            Some(String::from("token"))
+        );
+
+        let proxy = Proxy::https("http://127.0.0.1:6789")
+            .connect_header("X Proxy\r\n", "token")
+            .build();
+        assert!(proxy.is_err());
+    }
 }
@@ -0,0 +1,32 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Socket options configure module.
+
+use std::net::SocketAddr;
+
+/// Options set on outgoing tcp sockets before they connect.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub(crate) struct SocketConfig {
+    pub(crate) reuse_addr: bool,
+    pub(crate) reuse_port: bool,
+    // The local address, including the port, bound before connecting.
+    pub(crate) local_addr: Option<SocketAddr>,
+}
+
+impl SocketConfig {
+    // Checks if no option is set, so that the socket can be connected directly.
+    pub(crate) fn is_empty(&self) -> bool {
+        !self.reuse_addr && !self.reuse_port && self.local_addr.is_none()
+    }
+}
@@ -16,4 +16,4 @@ pub(crate) use alpn::{AlpnProtocol, AlpnProtocolList};
 
 mod verifier;
 pub(crate) use verifier::DefaultCertVerifier;
-pub use verifier::{CertVerifier, ServerCerts};
+pub use verifier::{CertVerifier, ServerCerts, VerifyResult};
@@ -18,6 +18,67 @@ pub use self::openssl::ServerCerts;
 pub trait CertVerifier {
     /// provided to users custom certs adapter
     fn verify(&self, certs: &ServerCerts) -> bool;
+
+    /// Verifies the certs like `verify`, but also gives the reason when the
+    /// certs are rejected. The reason is carried by the `HttpClientError` of
+    /// the failed connection.
+    ///
+    /// The default implementation adapts the result of `verify`, with a
+    /// generic reason. The client only calls this method, so a verifier which
+    /// implements it may implement `verify` by checking its result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::{CertVerifier, ServerCerts, VerifyResult};
+    ///
+    /// struct Verifier;
+    ///
+    /// impl CertVerifier for Verifier {
+    ///     fn verify(&self, certs: &ServerCerts) -> bool {
+    ///         self.verify_result(certs).is_accepted()
+    ///     }
+    ///
+    ///     fn verify_result(&self, certs: &ServerCerts) -> VerifyResult {
+    ///         match certs.version() {
+    ///             Ok(2) => VerifyResult::Accept,
+    ///             _ => VerifyResult::Reject(String::from("Not a X509v3 certificate")),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    fn verify_result(&self, certs: &ServerCerts) -> VerifyResult {
+        if self.verify(certs) {
+            VerifyResult::Accept
+        } else {
+            VerifyResult::Reject(String::from("Rejected by the custom verifier"))
+        }
+    }
+}
+
+/// The result of verifying the certs of the server by a `CertVerifier`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VerifyResult {
+    /// The certs are trusted.
+    Accept,
+    /// The certs are not trusted, for the given reason.
+    Reject(String),
+}
+
+impl VerifyResult {
+    /// Checks whether the certs are trusted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::VerifyResult;
+    ///
+    /// assert!(VerifyResult::Accept.is_accepted());
+    /// assert!(!VerifyResult::Reject(String::from("Expired")).is_accepted());
+    /// ```
+    pub fn is_accepted(&self) -> bool {
+        matches!(self, Self::Accept)
+    }
 }
 
 /// default cert verifier
@@ -37,4 +98,8 @@ impl CertVerifier for DefaultCertVerifier {
     fn verify(&self, certs: &ServerCerts) -> bool {
         self.inner.verify(certs)
     }
+
+    fn verify_result(&self, certs: &ServerCerts) -> VerifyResult {
+        self.inner.verify_result(certs)
+    }
 }
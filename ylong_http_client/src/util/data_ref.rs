@@ -14,19 +14,23 @@
 //! defines `BodyDataRef`.
 
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use crate::runtime::{AsyncRead, ReadBuf};
+use crate::util::interceptor::Interceptors;
 use crate::util::request::RequestArc;
 
 pub(crate) struct BodyDataRef {
     body: Option<RequestArc>,
+    interceptor: Arc<Interceptors>,
 }
 
 impl BodyDataRef {
-    pub(crate) fn new(request: RequestArc) -> Self {
+    pub(crate) fn new(request: RequestArc, interceptor: Arc<Interceptors>) -> Self {
         Self {
             body: Some(request),
+            interceptor,
         }
     }
 
@@ -49,7 +53,13 @@ impl BodyDataRef {
         let data = Pin::new(data);
         match data.poll_read(cx, &mut read_buf) {
             Poll::Ready(Err(_)) => Poll::Ready(None),
-            Poll::Ready(Ok(_)) => Poll::Ready(Some(read_buf.filled().len())),
+            Poll::Ready(Ok(_)) => {
+                let filled = read_buf.filled();
+                match self.interceptor.intercept_request_body(filled) {
+                    Ok(()) => Poll::Ready(Some(filled.len())),
+                    Err(_) => Poll::Ready(None),
+                }
+            }
             Poll::Pending => Poll::Pending,
         }
     }
@@ -0,0 +1,747 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Response body decompression simple implementation.
+//!
+//! The body is decompressed by a streaming [`DEFLATE`] decoder, which supports
+//! stored, fixed Huffman and dynamic Huffman blocks, after the [`GZIP`] or
+//! [`ZLIB`] header is removed. A raw `DEFLATE` stream is also accepted as
+//! `deflate`, because some servers send one.
+//!
+//! [`DEFLATE`]: https://www.rfc-editor.org/rfc/rfc1951.html
+//! [`GZIP`]: https://www.rfc-editor.org/rfc/rfc1952.html
+//! [`ZLIB`]: https://www.rfc-editor.org/rfc/rfc1950.html
+
+use crate::error::{ErrorKind, HttpClientError};
+use crate::util::compress::{
+    Checksum, Encoding, DISTANCE_BASE, DISTANCE_EXTRA, LENGTH_BASE, LENGTH_EXTRA, WINDOW_SIZE,
+};
+
+const MAX_BITS: usize = 15;
+const END_OF_BLOCK: u16 = 256;
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+// The most bytes decompressed by one call, which bounds the memory used by a
+// highly compressed body.
+const MAX_OUTPUT: usize = 64 * 1024;
+
+const GZIP_FHCRC: usize = 0x02;
+const GZIP_FEXTRA: usize = 0x04;
+const GZIP_FNAME: usize = 0x08;
+const GZIP_FCOMMENT: usize = 0x10;
+
+/// A streaming decompressor which decodes the body of a `Content-Encoding`.
+pub(crate) struct Decompressor {
+    encoding: Encoding,
+    state: State,
+    reader: BitReader,
+    // Whether the `deflate` body has no zlib wrapper.
+    raw: bool,
+    // Whether the current block is the last one.
+    last: bool,
+    // Huffman codes of the current block.
+    literals: Huffman,
+    distances: Huffman,
+    // The decompressed bytes kept for back references.
+    window: Vec<u8>,
+    checksum: Checksum,
+    // Whether the buffered input is left because of the output limit.
+    pending: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    Header,
+    BlockHeader,
+    Stored(usize),
+    Codes,
+    Trailer,
+    Done,
+}
+
+enum Error {
+    // More input is needed to finish the step.
+    Incomplete,
+    Invalid(&'static str),
+}
+
+impl Decompressor {
+    pub(crate) fn new(encoding: Encoding) -> Self {
+        Self {
+            encoding,
+            state: State::Header,
+            reader: BitReader::default(),
+            raw: false,
+            last: false,
+            literals: Huffman::default(),
+            distances: Huffman::default(),
+            window: Vec::new(),
+            checksum: Checksum::new(encoding),
+            pending: false,
+        }
+    }
+
+    /// Decompresses `input` and appends the produced bytes to `output`.
+    ///
+    /// The input may end anywhere in the stream, the rest of the stream is
+    /// decompressed by the following calls. At most `MAX_OUTPUT` bytes, plus
+    /// the output of one step, are produced by a call, and the rest of the
+    /// input is kept until `decompress` is called again, see `has_pending`.
+    pub(crate) fn decompress(
+        &mut self,
+        input: &[u8],
+        output: &mut Vec<u8>,
+    ) -> Result<(), HttpClientError> {
+        self.reader.input.extend_from_slice(input);
+        self.pending = false;
+        let limit = output.len() + MAX_OUTPUT;
+        let result = loop {
+            if output.len() >= limit {
+                self.pending = true;
+                break Ok(());
+            }
+            // Each step either finishes or is undone to wait for more input.
+            let mark = self.reader.bit;
+            let start = output.len();
+            match self.step(output) {
+                Ok(()) if self.state == State::Done => {
+                    // Bytes after the end of the stream are ignored.
+                    self.reader.input.clear();
+                    self.reader.bit = 0;
+                    break Ok(());
+                }
+                Ok(()) => self.checksum.update(&output[start..]),
+                Err(Error::Incomplete) => {
+                    self.reader.bit = mark;
+                    break Ok(());
+                }
+                Err(Error::Invalid(msg)) => {
+                    break Err(HttpClientError::from_str(ErrorKind::BodyDecode, msg))
+                }
+            }
+        };
+        self.reader.consume();
+        result
+    }
+
+    /// Checks whether the buffered input is not decompressed yet because of
+    /// the output limit, `decompress` should be called with no more input
+    /// until it returns `false`.
+    pub(crate) fn has_pending(&self) -> bool {
+        self.pending
+    }
+
+    /// Checks that the whole stream is decompressed when the body ends. An
+    /// empty body is accepted as well.
+    pub(crate) fn finish(&self) -> Result<(), HttpClientError> {
+        match self.state {
+            State::Done => Ok(()),
+            State::Header if self.reader.input.is_empty() => Ok(()),
+            _ => Err(HttpClientError::from_truncated(ErrorKind::BodyDecode)),
+        }
+    }
+
+    fn step(&mut self, output: &mut Vec<u8>) -> Result<(), Error> {
+        match self.state {
+            State::Header => self.header(),
+            State::BlockHeader => self.block_header(),
+            State::Stored(len) => self.stored(len, output),
+            State::Codes => self.codes(output),
+            State::Trailer => self.trailer(),
+            State::Done => Ok(()),
+        }
+    }
+
+    fn header(&mut self) -> Result<(), Error> {
+        match self.encoding {
+            Encoding::Gzip => {
+                let id = [self.reader.byte()?, self.reader.byte()?];
+                if id != [0x1f, 0x8b] || self.reader.byte()? != 8 {
+                    return Err(Error::Invalid("Invalid gzip header"));
+                }
+                let flags = self.reader.bits(8)?;
+                // Skips MTIME, XFL and OS.
+                self.reader.skip(6)?;
+                if flags & GZIP_FEXTRA != 0 {
+                    let len = self.reader.bits(16)?;
+                    self.reader.skip(len)?;
+                }
+                if flags & GZIP_FNAME != 0 {
+                    while self.reader.byte()? != 0 {}
+                }
+                if flags & GZIP_FCOMMENT != 0 {
+                    while self.reader.byte()? != 0 {}
+                }
+                if flags & GZIP_FHCRC != 0 {
+                    self.reader.skip(2)?;
+                }
+            }
+            Encoding::Deflate => {
+                let cmf = self.reader.byte()? as u16;
+                let flg = self.reader.byte()? as u16;
+                // The check bits make the header a multiple of 31.
+                let check = ((cmf << 8) | flg) % 31;
+                if cmf & 0x0f == 8 && cmf >> 4 <= 7 && check == 0 {
+                    if flg & 0x20 != 0 {
+                        return Err(Error::Invalid("Preset dictionary is not supported"));
+                    }
+                } else {
+                    // Not a zlib header, the stream starts with a block.
+                    self.raw = true;
+                    self.reader.bit = 0;
+                }
+            }
+        }
+        self.state = State::BlockHeader;
+        Ok(())
+    }
+
+    fn block_header(&mut self) -> Result<(), Error> {
+        let last = self.reader.bits(1)? == 1;
+        match self.reader.bits(2)? {
+            0 => {
+                self.reader.align();
+                let len = self.reader.bits(16)?;
+                let nlen = self.reader.bits(16)?;
+                if len != !nlen & 0xffff {
+                    return Err(Error::Invalid("Invalid stored block length"));
+                }
+                self.state = State::Stored(len);
+            }
+            1 => {
+                let mut lengths = [0u8; 288];
+                lengths[..144].fill(8);
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                lengths[280..].fill(8);
+                self.literals = Huffman::new(&lengths)?;
+                // Distance codes 30 and 31 are part of the code but invalid.
+                self.distances = Huffman::new(&[5; 32])?;
+                self.state = State::Codes;
+            }
+            2 => {
+                self.dynamic_codes()?;
+                self.state = State::Codes;
+            }
+            _ => return Err(Error::Invalid("Invalid block type")),
+        }
+        self.last = last;
+        Ok(())
+    }
+
+    fn dynamic_codes(&mut self) -> Result<(), Error> {
+        let nlen = self.reader.bits(5)? + 257;
+        let ndist = self.reader.bits(5)? + 1;
+        let ncode = self.reader.bits(4)? + 4;
+        if nlen > 286 || ndist > 30 {
+            return Err(Error::Invalid("Too many length or distance codes"));
+        }
+
+        let mut lengths = [0u8; 19];
+        for index in CODE_LENGTH_ORDER.iter().take(ncode) {
+            lengths[*index] = self.reader.bits(3)? as u8;
+        }
+        let code = Huffman::new(&lengths)?;
+
+        let mut lengths = vec![0u8; nlen + ndist];
+        let mut index = 0;
+        while index < lengths.len() {
+            let symbol = code.decode(&mut self.reader)?;
+            let (len, repeat) = match symbol {
+                0..=15 => (symbol as u8, 1),
+                16 if index == 0 => return Err(Error::Invalid("No previous length to repeat")),
+                16 => (lengths[index - 1], 3 + self.reader.bits(2)?),
+                17 => (0, 3 + self.reader.bits(3)?),
+                _ => (0, 11 + self.reader.bits(7)?),
+            };
+            if index + repeat > lengths.len() {
+                return Err(Error::Invalid("Too many code lengths"));
+            }
+            lengths[index..index + repeat].fill(len);
+            index += repeat;
+        }
+        if lengths[END_OF_BLOCK as usize] == 0 {
+            return Err(Error::Invalid("No end-of-block code"));
+        }
+        self.literals = Huffman::new(&lengths[..nlen])?;
+        self.distances = Huffman::new(&lengths[nlen..])?;
+        Ok(())
+    }
+
+    fn stored(&mut self, len: usize, output: &mut Vec<u8>) -> Result<(), Error> {
+        if len == 0 {
+            self.end_block();
+            return Ok(());
+        }
+        let bytes = self.reader.bytes(len)?;
+        let read = bytes.len();
+        output.extend_from_slice(bytes);
+        self.window.extend_from_slice(bytes);
+        self.trim_window();
+        self.state = State::Stored(len - read);
+        Ok(())
+    }
+
+    fn codes(&mut self, output: &mut Vec<u8>) -> Result<(), Error> {
+        let symbol = self.literals.decode(&mut self.reader)?;
+        match symbol {
+            0..=255 => {
+                output.push(symbol as u8);
+                self.window.push(symbol as u8);
+            }
+            END_OF_BLOCK => self.end_block(),
+            257..=285 => {
+                let index = symbol as usize - 257;
+                let len =
+                    LENGTH_BASE[index] as usize + self.reader.bits(LENGTH_EXTRA[index] as usize)?;
+                let index = self.distances.decode(&mut self.reader)? as usize;
+                if index >= DISTANCE_BASE.len() {
+                    return Err(Error::Invalid("Invalid distance code"));
+                }
+                let dist = DISTANCE_BASE[index] as usize
+                    + self.reader.bits(DISTANCE_EXTRA[index] as usize)?;
+                if dist > self.window.len() {
+                    return Err(Error::Invalid("Invalid distance too far back"));
+                }
+                for _ in 0..len {
+                    let byte = self.window[self.window.len() - dist];
+                    output.push(byte);
+                    self.window.push(byte);
+                }
+            }
+            _ => return Err(Error::Invalid("Invalid literal or length code")),
+        }
+        self.trim_window();
+        Ok(())
+    }
+
+    fn trailer(&mut self) -> Result<(), Error> {
+        self.reader.align();
+        match self.checksum {
+            _ if self.raw => {}
+            Checksum::Crc32 { crc, size } => {
+                if self.reader.bits(32)? as u32 != crc {
+                    return Err(Error::Invalid("Gzip CRC32 mismatch"));
+                }
+                if self.reader.bits(32)? as u32 != size {
+                    return Err(Error::Invalid("Gzip size mismatch"));
+                }
+            }
+            Checksum::Adler32 { a, b } => {
+                let adler = (0..4).try_fold(0u32, |value, _| {
+                    Ok((value << 8) | self.reader.byte()? as u32)
+                })?;
+                if adler != (b << 16) | a {
+                    return Err(Error::Invalid("Zlib Adler32 mismatch"));
+                }
+            }
+        }
+        self.state = State::Done;
+        Ok(())
+    }
+
+    fn end_block(&mut self) {
+        self.state = if self.last {
+            State::Trailer
+        } else {
+            State::BlockHeader
+        };
+    }
+
+    fn trim_window(&mut self) {
+        if self.window.len() > 2 * WINDOW_SIZE {
+            self.window.drain(..self.window.len() - WINDOW_SIZE);
+        }
+    }
+}
+
+// Reads the input from the least significant bit of each byte.
+#[derive(Default)]
+struct BitReader {
+    input: Vec<u8>,
+    // The position of the next bit in `input`.
+    bit: usize,
+}
+
+impl BitReader {
+    fn bits(&mut self, len: usize) -> Result<usize, Error> {
+        if self.bit + len > self.input.len() * 8 {
+            return Err(Error::Incomplete);
+        }
+        let mut value = 0;
+        for i in 0..len {
+            value |= (((self.input[self.bit / 8] >> (self.bit % 8)) & 1) as usize) << i;
+            self.bit += 1;
+        }
+        Ok(value)
+    }
+
+    fn byte(&mut self) -> Result<u8, Error> {
+        self.bits(8).map(|value| value as u8)
+    }
+
+    fn skip(&mut self, len: usize) -> Result<(), Error> {
+        if self.bit + len * 8 > self.input.len() * 8 {
+            return Err(Error::Incomplete);
+        }
+        self.bit += len * 8;
+        Ok(())
+    }
+
+    // Reads at most `len` bytes from a byte boundary, at least one byte.
+    fn bytes(&mut self, len: usize) -> Result<&[u8], Error> {
+        let start = self.bit / 8;
+        let end = self.input.len().min(start + len);
+        if start == end {
+            return Err(Error::Incomplete);
+        }
+        self.bit = end * 8;
+        Ok(&self.input[start..end])
+    }
+
+    fn align(&mut self) {
+        self.bit = (self.bit + 7) & !7;
+    }
+
+    // Drops the bytes which are read.
+    fn consume(&mut self) {
+        let bytes = self.bit / 8;
+        self.input.drain(..bytes);
+        self.bit -= bytes * 8;
+    }
+}
+
+// A canonical Huffman code, decoded bit by bit.
+#[derive(Default)]
+struct Huffman {
+    // The number of codes of each length.
+    counts: [u16; MAX_BITS + 1],
+    // The symbols ordered by their codes.
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Result<Self, Error> {
+        let mut counts = [0u16; MAX_BITS + 1];
+        for len in lengths {
+            counts[*len as usize] += 1;
+        }
+        counts[0] = 0;
+
+        let mut left = 1i32;
+        for count in &counts[1..] {
+            left = (left << 1) - *count as i32;
+            if left < 0 {
+                return Err(Error::Invalid("Over-subscribed Huffman code"));
+            }
+        }
+        // An incomplete code is only allowed for a single code of one bit,
+        // or no code at all, as zlib does.
+        let max = counts.iter().rposition(|count| *count != 0).unwrap_or(0);
+        if left > 0 && max > 1 {
+            return Err(Error::Invalid("Incomplete Huffman code"));
+        }
+
+        let mut offsets = [0u16; MAX_BITS + 1];
+        for len in 1..MAX_BITS {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+        let mut symbols = vec![0u16; lengths.len()];
+        for (symbol, len) in lengths.iter().enumerate() {
+            if *len != 0 {
+                symbols[offsets[*len as usize] as usize] = symbol as u16;
+                offsets[*len as usize] += 1;
+            }
+        }
+        Ok(Self { counts, symbols })
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Result<u16, Error> {
+        let (mut code, mut first, mut index) = (0usize, 0usize, 0usize);
+        for count in &self.counts[1..] {
+            code |= reader.bits(1)?;
+            let count = *count as usize;
+            if code < first + count {
+                return Ok(self.symbols[index + code - first]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(Error::Invalid("Invalid Huffman code"))
+    }
+}
+
+#[cfg(test)]
+mod ut_util_decompress {
+    use crate::util::compress::{Compressor, Encoding};
+    use crate::util::decompress::{Decompressor, MAX_OUTPUT};
+    use crate::{ErrorKind, HttpClientError};
+
+    const DATA: &[u8] = b"The quick brown fox jumps over the lazy dog. \
+        The quick brown fox jumps over the lazy dog. \
+        The quick brown fox jumps over the lazy dog. \
+        ylong_http_client decompression test.\n";
+
+    // `DATA` compressed by gzip with a dynamic Huffman block and the file
+    // name "a.txt".
+    const GZIP_DYNAMIC: [u8; 104] = [
+        0x1f, 0x8b, 0x08, 0x08, 0x00, 0x00, 0x00, 0x00, 0x02, 0xff, 0x61, 0x2e, 0x74, 0x78, 0x74,
+        0x00, 0xb5, 0xca, 0xc9, 0x15, 0x80, 0x20, 0x0c, 0x05, 0xc0, 0xbb, 0x55, 0xfc, 0x0a, 0xac,
+        0xc6, 0x3b, 0x4f, 0x21, 0x22, 0x0a, 0x09, 0x42, 0x70, 0xab, 0x5e, 0x9b, 0xf0, 0x3c, 0x33,
+        0x2c, 0x84, 0xbd, 0x05, 0xbb, 0x61, 0x2a, 0x72, 0x32, 0x66, 0xb9, 0xb0, 0xb6, 0x94, 0x2b,
+        0xe4, 0xa0, 0x02, 0xfd, 0x38, 0x8e, 0xcf, 0x0d, 0x27, 0xbe, 0xc7, 0xf0, 0x5b, 0xbe, 0xa3,
+        0xb0, 0x37, 0x8b, 0x6a, 0x36, 0x36, 0x06, 0x62, 0x85, 0x23, 0x2b, 0x29, 0x17, 0xaa, 0x35,
+        0x08, 0x43, 0xa9, 0x6a, 0xdf, 0xbd, 0x0b, 0x4e, 0xd1, 0xe6, 0xad, 0x00, 0x00, 0x00,
+    ];
+
+    // "stored" compressed by zlib in a stored block.
+    const ZLIB_STORED: [u8; 17] = [
+        0x78, 0x01, 0x01, 0x06, 0x00, 0xf9, 0xff, 0x73, 0x74, 0x6f, 0x72, 0x65, 0x64, 0x09, 0x3c,
+        0x02, 0x92,
+    ];
+
+    fn decompress(
+        encoding: Encoding,
+        input: &[u8],
+        chunk: usize,
+    ) -> Result<Vec<u8>, HttpClientError> {
+        let mut decompressor = Decompressor::new(encoding);
+        let mut output = Vec::new();
+        for chunk in input.chunks(chunk) {
+            decompressor.decompress(chunk, &mut output)?;
+            while decompressor.has_pending() {
+                decompressor.decompress(&[], &mut output)?;
+            }
+        }
+        decompressor.finish()?;
+        Ok(output)
+    }
+
+    fn compress(encoding: Encoding, data: &[u8]) -> Vec<u8> {
+        let mut compressor = Compressor::new(encoding);
+        let mut output = Vec::new();
+        compressor.compress(data, &mut output);
+        compressor.finish(&mut output);
+        output
+    }
+
+    /// UT test cases for `Decompressor` with `Encoding::Gzip`.
+    ///
+    /// # Brief
+    /// 1. Decompresses gzip streams in chunks of different sizes.
+    /// 2. Checks if the decompressed bytes are the same as the origin bytes.
+    #[test]
+    fn ut_decompressor_gzip() {
+        for chunk in [1, 7, GZIP_DYNAMIC.len()] {
+            assert_eq!(
+                decompress(Encoding::Gzip, &GZIP_DYNAMIC, chunk).unwrap(),
+                DATA
+            );
+        }
+
+        let data = b"hello hello hello world, hello hello hello world!".repeat(1000);
+        let output = compress(Encoding::Gzip, &data);
+        assert_eq!(decompress(Encoding::Gzip, &output, 1000).unwrap(), data);
+    }
+
+    /// UT test cases for `Decompressor` with `Encoding::Deflate`.
+    ///
+    /// # Brief
+    /// 1. Decompresses zlib streams and a raw DEFLATE stream.
+    /// 2. Checks if the decompressed bytes are the same as the origin bytes.
+    #[test]
+    fn ut_decompressor_deflate() {
+        assert_eq!(
+            decompress(Encoding::Deflate, &ZLIB_STORED, 3).unwrap(),
+            b"stored"
+        );
+
+        let data = b"Wikipedia Wikipedia".repeat(100);
+        let output = compress(Encoding::Deflate, &data);
+        assert_eq!(decompress(Encoding::Deflate, &output, 5).unwrap(), data);
+
+        let raw = &output[2..output.len() - 4];
+        assert_eq!(decompress(Encoding::Deflate, raw, 5).unwrap(), data);
+    }
+
+    /// UT test cases for `Decompressor` with truncated or corrupted streams.
+    ///
+    /// # Brief
+    /// 1. Decompresses gzip streams which end early or have a wrong CRC32.
+    /// 2. Checks that a truncated stream is reported as truncated.
+    /// 3. Checks that a corrupted stream is reported as a decode error.
+    #[test]
+    fn ut_decompressor_truncated() {
+        for len in [5, 20, GZIP_DYNAMIC.len() - 8, GZIP_DYNAMIC.len() - 1] {
+            let err = decompress(Encoding::Gzip, &GZIP_DYNAMIC[..len], 10).unwrap_err();
+            assert!(err.is_compressed_body_truncated());
+            assert_eq!(err.error_kind(), ErrorKind::BodyDecode);
+        }
+        assert_eq!(decompress(Encoding::Gzip, &[], 1).unwrap(), b"");
+
+        let mut corrupted = GZIP_DYNAMIC;
+        corrupted[GZIP_DYNAMIC.len() - 8] ^= 1;
+        let err = decompress(Encoding::Gzip, &corrupted, 10).unwrap_err();
+        assert!(!err.is_compressed_body_truncated());
+        assert_eq!(err.error_kind(), ErrorKind::BodyDecode);
+
+        let err = decompress(Encoding::Gzip, b"not gzip", 10).unwrap_err();
+        assert_eq!(err.error_kind(), ErrorKind::BodyDecode);
+    }
+
+    /// UT test cases for `Decompressor` with streams produced by zlib.
+    ///
+    /// # Brief
+    /// 1. Decompresses gzip, zlib and raw DEFLATE streams of the same data,
+    ///    which are produced by zlib with different levels, strategies,
+    ///    flushes and gzip header flags, in chunks of different sizes.
+    /// 2. Checks if the decompressed bytes are the same as the origin bytes.
+    #[test]
+    fn ut_decompressor_zlib_corpus() {
+        let data = include_bytes!("../../tests/file/decompress/corpus.bin");
+        let corpus: [(Encoding, &[u8]); 8] = [
+            (
+                Encoding::Gzip,
+                include_bytes!("../../tests/file/decompress/corpus.gz"),
+            ),
+            (
+                Encoding::Gzip,
+                include_bytes!("../../tests/file/decompress/corpus_fast.gz"),
+            ),
+            (
+                Encoding::Gzip,
+                include_bytes!("../../tests/file/decompress/corpus_flags.gz"),
+            ),
+            (
+                Encoding::Deflate,
+                include_bytes!("../../tests/file/decompress/corpus.zz"),
+            ),
+            (
+                Encoding::Deflate,
+                include_bytes!("../../tests/file/decompress/corpus_stored.zz"),
+            ),
+            (
+                Encoding::Deflate,
+                include_bytes!("../../tests/file/decompress/corpus_fixed.zz"),
+            ),
+            (
+                Encoding::Deflate,
+                include_bytes!("../../tests/file/decompress/corpus_flush.zz"),
+            ),
+            (
+                Encoding::Deflate,
+                include_bytes!("../../tests/file/decompress/corpus.deflate"),
+            ),
+        ];
+        for (encoding, input) in corpus {
+            for chunk in [1, 13, 4096, input.len()] {
+                assert_eq!(decompress(encoding, input, chunk).unwrap(), data);
+            }
+        }
+    }
+
+    /// UT test cases for `Decompressor` with malformed streams.
+    ///
+    /// # Brief
+    /// 1. Decompresses raw DEFLATE streams which zlib rejects, with an
+    ///    over-subscribed or incomplete Huffman code, no end-of-block code, a
+    ///    distance past the window, an invalid distance code, an invalid block
+    ///    type or invalid stored block lengths.
+    /// 2. Decompresses zlib and gzip streams with a truncated or wrong trailer.
+    /// 3. Checks that each stream is reported as a decode error, and only the
+    ///    truncated ones as truncated.
+    #[test]
+    fn ut_decompressor_malformed() {
+        let malformed: [&[u8]; 7] = [
+            // Over-subscribed code length code.
+            &[0x05, 0x00, 0x92, 0x04],
+            // Incomplete code length code.
+            &[0x05, 0x00, 0x04, 0x00, 0x00],
+            // Dynamic block without an end-of-block code.
+            &[
+                0x05, 0xe0, 0x01, 0x24, 0x00, 0x00, 0x00, 0x00, 0x00, 0xfc, 0xff, 0xff, 0xff, 0xff,
+                0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x0b,
+            ],
+            // A literal followed by a match of distance 2.
+            &[0x4b, 0x04, 0x42, 0x00],
+            // A literal followed by the distance code 30.
+            &[0x4b, 0x04, 0x3e, 0x00],
+            // The reserved block type.
+            &[0x07, 0x00],
+            // A stored block whose NLEN is not the complement of LEN.
+            &[0x01, 0x05, 0x00, 0xfa, 0xfe, 0x68, 0x65, 0x6c, 0x6c, 0x6f],
+        ];
+        for input in malformed {
+            let err = decompress(Encoding::Deflate, input, 1).unwrap_err();
+            assert!(!err.is_compressed_body_truncated());
+            assert_eq!(err.error_kind(), ErrorKind::BodyDecode);
+        }
+
+        let zlib = include_bytes!("../../tests/file/decompress/corpus.zz");
+        let gzip = include_bytes!("../../tests/file/decompress/corpus.gz");
+        for (encoding, input) in [(Encoding::Deflate, &zlib[..]), (Encoding::Gzip, &gzip[..])] {
+            for cut in 1..4 {
+                let truncated = &input[..input.len() - cut];
+                let err = decompress(encoding, truncated, 4096).unwrap_err();
+                assert!(err.is_compressed_body_truncated());
+                assert_eq!(err.error_kind(), ErrorKind::BodyDecode);
+            }
+        }
+
+        let mut wrong_size = gzip.to_vec();
+        let len = wrong_size.len();
+        wrong_size[len - 1] ^= 1;
+        let err = decompress(Encoding::Gzip, &wrong_size, 4096).unwrap_err();
+        assert!(!err.is_compressed_body_truncated());
+        assert_eq!(err.error_kind(), ErrorKind::BodyDecode);
+
+        let mut wrong_adler = zlib.to_vec();
+        let len = wrong_adler.len();
+        wrong_adler[len - 1] ^= 1;
+        let err = decompress(Encoding::Deflate, &wrong_adler, 4096).unwrap_err();
+        assert!(!err.is_compressed_body_truncated());
+        assert_eq!(err.error_kind(), ErrorKind::BodyDecode);
+    }
+
+    /// UT test cases for the output limit of `Decompressor::decompress`.
+    ///
+    /// # Brief
+    /// 1. Compresses 1 MiB of zeros, and decompresses them in one call.
+    /// 2. Checks that each call produces at most `MAX_OUTPUT` bytes plus one
+    ///    match, until no input is pending.
+    /// 3. Checks if the decompressed bytes are the same as the origin bytes.
+    #[test]
+    fn ut_decompressor_output_limit() {
+        let data = vec![0u8; 1024 * 1024];
+        let input = compress(Encoding::Gzip, &data);
+        let mut decompressor = Decompressor::new(Encoding::Gzip);
+        let mut output = Vec::new();
+        decompressor.decompress(&input, &mut output).unwrap();
+        assert!(decompressor.has_pending());
+        assert!(output.len() <= MAX_OUTPUT + 258);
+        let mut calls = 1;
+        while decompressor.has_pending() {
+            let start = output.len();
+            decompressor.decompress(&[], &mut output).unwrap();
+            assert!(output.len() - start <= MAX_OUTPUT + 258);
+            calls += 1;
+        }
+        decompressor.finish().unwrap();
+        assert_eq!(output, data);
+        assert!(calls >= data.len() / (MAX_OUTPUT + 258));
+    }
+}
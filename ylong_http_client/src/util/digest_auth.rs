@@ -0,0 +1,376 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! HTTP Digest Access Authentication, [`RFC7616`].
+//!
+//! Answers the `Proxy-Authenticate` challenge of a proxy to a `CONNECT`
+//! request and, with the `auth` feature, the `WWW-Authenticate` challenge of
+//! an origin server. The `MD5` and `SHA-256` algorithms and the `auth` quality
+//! of protection are supported.
+//!
+//! [`RFC7616`]: https://www.rfc-editor.org/rfc/rfc7616.html
+
+use std::collections::hash_map::RandomState;
+#[cfg(feature = "auth")]
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+#[cfg(feature = "auth")]
+use std::sync::Mutex;
+
+use crate::util::{md5, sha256};
+
+/// Finds the `Digest` challenge among the values of a `WWW-Authenticate` or
+/// `Proxy-Authenticate` header, and returns its parameters.
+pub(crate) fn find_challenge<'a, I>(values: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a [u8]>,
+{
+    values.into_iter().find_map(|value| {
+        let value = std::str::from_utf8(value).ok()?.trim();
+        let (scheme, params) = value.split_once(' ').unwrap_or((value, ""));
+        scheme
+            .eq_ignore_ascii_case("Digest")
+            .then_some(params.trim())
+    })
+}
+
+/// Checks whether the challenge rejects a previous request only because its
+/// nonce is stale, so the same credentials can be sent again.
+#[cfg(feature = "auth")]
+pub(crate) fn is_stale(challenge: &str) -> bool {
+    parse_params(challenge)
+        .iter()
+        .any(|(key, value)| key.eq_ignore_ascii_case("stale") && value.eq_ignore_ascii_case("true"))
+}
+
+/// Computes the `Digest` credentials which answer the challenge for the
+/// `nc`-th request with its nonce.
+pub(crate) fn authorization(
+    challenge: &str,
+    credentials: (&str, &str),
+    request: (&str, &str),
+    nc: u32,
+) -> Result<String, &'static str> {
+    authorization_with_cnonce(challenge, credentials, request, nc, &generate_cnonce())
+}
+
+fn authorization_with_cnonce(
+    challenge: &str,
+    (username, password): (&str, &str),
+    (method, uri): (&str, &str),
+    nc: u32,
+    cnonce: &str,
+) -> Result<String, &'static str> {
+    let params = parse_params(challenge);
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    };
+
+    let realm = param("realm").ok_or("Digest challenge without realm")?;
+    let nonce = param("nonce").ok_or("Digest challenge without nonce")?;
+    let algorithm = param("algorithm").unwrap_or("MD5");
+    let (md, session) = match algorithm.to_ascii_uppercase().as_str() {
+        "MD5" => (Algorithm::Md5, false),
+        "MD5-SESS" => (Algorithm::Md5, true),
+        "SHA-256" => (Algorithm::Sha256, false),
+        "SHA-256-SESS" => (Algorithm::Sha256, true),
+        _ => return Err("Unsupported digest algorithm"),
+    };
+    let qop = match param("qop") {
+        Some(qop)
+            if qop
+                .split(',')
+                .any(|v| v.trim().eq_ignore_ascii_case("auth")) =>
+        {
+            Some("auth")
+        }
+        Some(_) => return Err("Unsupported digest qop"),
+        None => None,
+    };
+    let nc = format!("{nc:08x}");
+
+    let mut ha1 = md.hex(format!("{username}:{realm}:{password}").as_bytes());
+    if session {
+        ha1 = md.hex(format!("{ha1}:{nonce}:{cnonce}").as_bytes());
+    }
+    let ha2 = md.hex(format!("{method}:{uri}").as_bytes());
+    let response = match qop {
+        Some(qop) => md.hex(format!("{ha1}:{nonce}:{nc}:{cnonce}:{qop}:{ha2}").as_bytes()),
+        None => md.hex(format!("{ha1}:{nonce}:{ha2}").as_bytes()),
+    };
+
+    let mut value = format!(
+        "Digest username=\"{}\", realm=\"{}\", nonce=\"{}\", uri=\"{uri}\", \
+         algorithm={algorithm}, response=\"{response}\"",
+        quote(username),
+        quote(realm),
+        quote(nonce),
+    );
+    if let Some(qop) = qop {
+        value.push_str(&format!(", qop={qop}, nc={nc}, cnonce=\"{cnonce}\""));
+    }
+    if let Some(opaque) = param("opaque") {
+        value.push_str(&format!(", opaque=\"{}\"", quote(opaque)));
+    }
+    Ok(value)
+}
+
+/// Credentials of a client which answer the `Digest` challenges of origin
+/// servers.
+///
+/// The latest challenge of each origin is kept, so the following requests to
+/// the origin are authorized without another round trip, counting the uses of
+/// its nonce.
+#[cfg(feature = "auth")]
+pub(crate) struct DigestAuth {
+    username: String,
+    password: String,
+    sessions: Mutex<HashMap<String, DigestSession>>,
+}
+
+#[cfg(feature = "auth")]
+struct DigestSession {
+    challenge: String,
+    nc: u32,
+}
+
+#[cfg(feature = "auth")]
+impl DigestAuth {
+    pub(crate) fn new(username: &str, password: &str) -> Self {
+        Self {
+            username: username.to_string(),
+            password: password.to_string(),
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Keeps the challenge of the origin, and resets the count of its nonce.
+    pub(crate) fn update(&self, origin: &str, challenge: &str) {
+        let mut sessions = self.sessions.lock().unwrap();
+        sessions.insert(
+            origin.to_string(),
+            DigestSession {
+                challenge: challenge.to_string(),
+                nc: 0,
+            },
+        );
+    }
+
+    /// Computes the credentials of a request to the origin, if the origin has
+    /// sent a challenge.
+    pub(crate) fn authorization(
+        &self,
+        origin: &str,
+        method: &str,
+        uri: &str,
+    ) -> Option<Result<String, &'static str>> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let session = sessions.get_mut(origin)?;
+        session.nc = session.nc.wrapping_add(1);
+        Some(authorization(
+            &session.challenge,
+            (&self.username, &self.password),
+            (method, uri),
+            session.nc,
+        ))
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Algorithm {
+    Md5,
+    Sha256,
+}
+
+impl Algorithm {
+    fn hex(self, data: &[u8]) -> String {
+        let digest = match self {
+            Self::Md5 => md5::digest(data).to_vec(),
+            Self::Sha256 => sha256::digest(data).to_vec(),
+        };
+        digest.iter().map(|b| format!("{b:02x}")).collect()
+    }
+}
+
+// Splits `key=value, key="quoted, value"` parameters of a challenge.
+fn parse_params(s: &str) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+    let mut rest = s;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c == ',' || c.is_ascii_whitespace());
+        let Some((key, tail)) = rest.split_once('=') else {
+            return params;
+        };
+        let key = key.trim().to_string();
+        let tail = tail.trim_start();
+
+        let mut value = String::new();
+        if let Some(quoted) = tail.strip_prefix('"') {
+            let mut chars = quoted.char_indices();
+            let mut end = quoted.len();
+            while let Some((i, c)) = chars.next() {
+                match c {
+                    '\\' => {
+                        if let Some((_, escaped)) = chars.next() {
+                            value.push(escaped);
+                        }
+                    }
+                    '"' => {
+                        end = i + 1;
+                        break;
+                    }
+                    c => value.push(c),
+                }
+            }
+            rest = &quoted[end..];
+        } else {
+            let end = tail.find(',').unwrap_or(tail.len());
+            value.push_str(tail[..end].trim());
+            rest = &tail[end..];
+        }
+        params.push((key, value));
+    }
+}
+
+fn quote(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn generate_cnonce() -> String {
+    let mut nonce = String::new();
+    for i in 0..2 {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_usize(i);
+        nonce.push_str(&format!("{:016x}", hasher.finish()));
+    }
+    nonce
+}
+
+#[cfg(test)]
+mod ut_digest_auth {
+    use crate::util::digest_auth::{
+        authorization, authorization_with_cnonce, find_challenge, parse_params,
+    };
+    #[cfg(feature = "auth")]
+    use crate::util::digest_auth::{is_stale, DigestAuth};
+
+    const CHALLENGE: &str = "realm=\"http-auth@example.org\", qop=\"auth, auth-int\", \
+         algorithm=ALG, nonce=\"7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v\", \
+         opaque=\"FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS\"";
+    const CNONCE: &str = "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ";
+
+    /// UT test cases for `authorization`.
+    ///
+    /// # Brief
+    /// 1. Answers the `MD5` and `SHA-256` challenges of the example of
+    ///    RFC7616.
+    /// 2. Checks if the responses are as expected.
+    /// 3. Answers challenges with an unsupported algorithm, an unsupported
+    ///    qop and without nonce.
+    /// 4. Checks if the errors are as expected.
+    #[test]
+    fn ut_digest_auth_authorization() {
+        let credentials = ("Mufasa", "Circle of Life");
+        let request = ("GET", "/dir/index.html");
+
+        let md5 = CHALLENGE.replace("ALG", "MD5");
+        let value = authorization_with_cnonce(&md5, credentials, request, 1, CNONCE).unwrap();
+        assert!(value.starts_with("Digest username=\"Mufasa\", realm=\"http-auth@example.org\""));
+        assert!(value.contains("response=\"8ca523f5e9506fed4657c9700eebdbec\""));
+        assert!(value.contains(", qop=auth, nc=00000001, cnonce=\"f2/wE4q74E6zIJEtWaHKaf5wv"));
+        assert!(value.ends_with(", opaque=\"FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS\""));
+
+        let sha256 = CHALLENGE.replace("ALG", "SHA-256");
+        let value = authorization_with_cnonce(&sha256, credentials, request, 1, CNONCE).unwrap();
+        assert!(value.contains(
+            "response=\"753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1\""
+        ));
+
+        let sha512 = CHALLENGE.replace("ALG", "SHA-512-256");
+        assert_eq!(
+            authorization(&sha512, credentials, request, 1),
+            Err("Unsupported digest algorithm")
+        );
+        assert_eq!(
+            authorization(
+                "realm=\"a\", nonce=\"b\", qop=auth-int",
+                credentials,
+                request,
+                1
+            ),
+            Err("Unsupported digest qop")
+        );
+        assert_eq!(
+            authorization("realm=\"a\"", credentials, request, 1),
+            Err("Digest challenge without nonce")
+        );
+    }
+
+    /// UT test cases for `DigestAuth`.
+    ///
+    /// # Brief
+    /// 1. Computes credentials for an origin without a challenge.
+    /// 2. Keeps a challenge of the origin and computes credentials twice.
+    /// 3. Checks that the nonce count increases and is reset by a new
+    ///    challenge.
+    #[cfg(feature = "auth")]
+    #[test]
+    fn ut_digest_auth_nonce_count() {
+        let auth = DigestAuth::new("Mufasa", "Circle of Life");
+        let origin = "http://example.org";
+        assert!(auth.authorization(origin, "GET", "/").is_none());
+
+        let challenge = CHALLENGE.replace("ALG", "MD5");
+        auth.update(origin, &challenge);
+        let first = auth.authorization(origin, "GET", "/").unwrap().unwrap();
+        assert!(first.contains(", nc=00000001, "));
+        let second = auth.authorization(origin, "GET", "/a").unwrap().unwrap();
+        assert!(second.contains(", nc=00000002, "));
+        assert!(second.contains("uri=\"/a\""));
+        assert!(auth
+            .authorization("http://example.com", "GET", "/")
+            .is_none());
+
+        auth.update(origin, &challenge);
+        let third = auth.authorization(origin, "GET", "/").unwrap().unwrap();
+        assert!(third.contains(", nc=00000001, "));
+        assert!(!is_stale(&challenge));
+        assert!(is_stale("realm=\"a\", nonce=\"b\", stale=TRUE"));
+    }
+
+    /// UT test cases for `find_challenge` and `parse_params`.
+    ///
+    /// # Brief
+    /// 1. Finds the `Digest` challenge among `Proxy-Authenticate` values.
+    /// 2. Parses quoted and unquoted parameters.
+    /// 3. Checks if the results are as expected.
+    #[test]
+    fn ut_digest_auth_parse_challenge() {
+        let values: [&[u8]; 2] = [b"Basic realm=\"proxy\"", b"digest realm=\"a\", nonce=b"];
+        assert_eq!(find_challenge(values), Some("realm=\"a\", nonce=b"));
+        assert_eq!(find_challenge([b"Basic realm=\"proxy\"".as_slice()]), None);
+
+        let params = parse_params("realm=\"a, \\\"b\\\"\" ,nonce=c,  qop=\"auth\"");
+        assert_eq!(
+            params,
+            vec![
+                ("realm".to_string(), "a, \"b\"".to_string()),
+                ("nonce".to_string(), "c".to_string()),
+                ("qop".to_string(), "auth".to_string()),
+            ]
+        );
+    }
+}
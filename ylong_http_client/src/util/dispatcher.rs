@@ -11,6 +11,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "async")]
+use std::sync::Arc;
+
+#[cfg(any(feature = "http2", feature = "http3"))]
+use ylong_http::version::Version;
+
+#[cfg(feature = "async")]
+use crate::runtime::Semaphore;
+#[cfg(all(feature = "async", feature = "tokio_base"))]
+use crate::runtime::SemaphorePermit;
 use crate::util::ConnInfo;
 use crate::{ConnDetail, TimeGroup};
 
@@ -103,6 +113,21 @@ impl<S: ConnInfo> Conn<S> {
     }
 }
 
+impl<S> Conn<S> {
+    // Gets the `HTTP` version negotiated on the connection.
+    #[cfg(any(feature = "http2", feature = "http3"))]
+    pub(crate) fn version(&self) -> Version {
+        match self {
+            #[cfg(feature = "http1_1")]
+            Conn::Http1(_) => Version::HTTP1_1,
+            #[cfg(feature = "http2")]
+            Conn::Http2(_) => Version::HTTP2,
+            #[cfg(feature = "http3")]
+            Conn::Http3(_) => Version::HTTP3,
+        }
+    }
+}
+
 pub(crate) struct TimeInfoConn<S> {
     conn: Conn<S>,
     time_group: TimeGroup,
@@ -126,21 +151,106 @@ impl<S> TimeInfoConn<S> {
     }
 }
 
+#[cfg(feature = "async")]
+pub(crate) struct WrappedSemaphore {
+    sem: Arc<Semaphore>,
+}
+
+#[cfg(feature = "async")]
+impl WrappedSemaphore {
+    pub(crate) fn new(permits: usize) -> Self {
+        Self {
+            #[cfg(feature = "tokio_base")]
+            sem: Arc::new(tokio::sync::Semaphore::new(permits)),
+            #[cfg(feature = "ylong_base")]
+            sem: Arc::new(ylong_runtime::sync::Semaphore::new(permits).unwrap()),
+        }
+    }
+
+    pub(crate) async fn acquire(&self) -> WrappedSemPermit {
+        #[cfg(feature = "ylong_base")]
+        {
+            let semaphore = self.sem.clone();
+            let _permit = semaphore.acquire().await.unwrap();
+            WrappedSemPermit { sem: semaphore }
+        }
+
+        #[cfg(feature = "tokio_base")]
+        {
+            let permit = self.sem.clone().acquire_owned().await.unwrap();
+            WrappedSemPermit { permit }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl Clone for WrappedSemaphore {
+    fn clone(&self) -> Self {
+        Self {
+            sem: self.sem.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+pub(crate) struct WrappedSemPermit {
+    #[cfg(feature = "ylong_base")]
+    pub(crate) sem: Arc<Semaphore>,
+    #[cfg(feature = "tokio_base")]
+    #[allow(dead_code)]
+    pub(crate) permit: SemaphorePermit,
+}
+
+#[cfg(all(feature = "async", feature = "ylong_base"))]
+impl Drop for WrappedSemPermit {
+    fn drop(&mut self) {
+        self.sem.release();
+    }
+}
+
 #[cfg(feature = "http1_1")]
 pub(crate) mod http1 {
     use std::cell::UnsafeCell;
+    use std::collections::VecDeque;
     use std::sync::atomic::{AtomicBool, Ordering};
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll, Waker};
 
-    use super::{ConnDispatcher, Dispatcher};
-    use crate::runtime::Semaphore;
-    #[cfg(feature = "tokio_base")]
-    use crate::runtime::SemaphorePermit;
+    #[cfg(feature = "async")]
+    use super::WrappedSemPermit;
+    use super::{Conn, ConnDispatcher, Dispatcher};
+
+    // The sync client doesn't limit the requests in flight, so its handles
+    // never hold a permit.
+    #[cfg(not(feature = "async"))]
+    pub(crate) enum WrappedSemPermit {}
 
     impl<S> ConnDispatcher<S> {
+        #[cfg(any(feature = "sync", test))]
         pub(crate) fn http1(io: S) -> Self {
             Self::Http1(Http1Dispatcher::new(io))
         }
+
+        pub(crate) fn http1_with_pipeline(io: S, depth: usize) -> Self {
+            Self::Http1(Http1Dispatcher::with_pipeline(io, depth))
+        }
+
+        // Checks if the connection can be dispatched right now.
+        pub(crate) fn is_idle(&self) -> bool {
+            match self {
+                Self::Http1(h1) => !h1.is_shutdown() && !h1.inner.occupied.load(Ordering::Acquire),
+                #[cfg(any(feature = "http2", feature = "http3"))]
+                _ => false,
+            }
+        }
+
+        pub(crate) fn pipeline(&self) -> Option<Conn<S>> {
+            match self {
+                Self::Http1(h1) => h1.pipeline().map(Conn::Http1),
+                #[cfg(any(feature = "http2", feature = "http3"))]
+                _ => None,
+            }
+        }
     }
 
     /// HTTP1-based connection manager, which can dispatch connections to other
@@ -157,20 +267,93 @@ pub(crate) mod http1 {
         pub(crate) occupied: AtomicBool,
         // `shutdown` indicates that the connection need to be shut down.
         pub(crate) shutdown: AtomicBool,
+        // `pipeline` holds the requests sent behind the one in flight, it is
+        // `None` if pipelining is disabled.
+        pub(crate) pipeline: Option<Mutex<Pipeline>>,
     }
 
     unsafe impl<S> Sync for Inner<S> {}
 
+    /// The requests pipelined on a connection. The handle which reads the
+    /// responses hands the connection over to the pipelined requests in the
+    /// order they are sent.
+    pub(crate) struct Pipeline {
+        depth: usize,
+        // `open` indicates that a request can be pipelined behind the last one
+        // sent, which must be fully written and idempotent.
+        open: bool,
+        // Encoded pipelined requests which have not been written yet.
+        pending: Vec<u8>,
+        // The waker of the handle which reads the responses.
+        reader: Option<Waker>,
+        waiting: VecDeque<Arc<Mutex<Turn>>>,
+    }
+
+    enum Turn {
+        Waiting(Option<Waker>),
+        Ready(Option<WrappedSemPermit>),
+        Failed,
+    }
+
+    impl Pipeline {
+        // Fails all the pipelined requests, whose responses will never come.
+        fn close(&mut self) {
+            self.open = false;
+            self.pending.clear();
+            for turn in self.waiting.drain(..) {
+                let mut turn = turn.lock().unwrap();
+                if let Turn::Waiting(Some(waker)) = std::mem::replace(&mut *turn, Turn::Failed) {
+                    waker.wake();
+                }
+            }
+        }
+    }
+
     impl<S> Http1Dispatcher<S> {
+        #[cfg(any(feature = "sync", test))]
         pub(crate) fn new(io: S) -> Self {
+            Self::with_pipeline(io, 0)
+        }
+
+        pub(crate) fn with_pipeline(io: S, depth: usize) -> Self {
+            let pipeline = (depth > 1).then(|| {
+                Mutex::new(Pipeline {
+                    depth,
+                    open: false,
+                    pending: Vec::new(),
+                    reader: None,
+                    waiting: VecDeque::new(),
+                })
+            });
             Self {
                 inner: Arc::new(Inner {
                     io: UnsafeCell::new(io),
                     occupied: AtomicBool::new(false),
                     shutdown: AtomicBool::new(false),
+                    pipeline,
                 }),
             }
         }
+
+        /// Gets a handle to send a request behind the requests in flight, if
+        /// the connection accepts one more pipelined request. The handle gets
+        /// the connection once the responses before it have been read.
+        pub(crate) fn pipeline(&self) -> Option<Http1Conn<S>> {
+            let mut pipeline = self.inner.pipeline.as_ref()?.lock().unwrap();
+            if !pipeline.open || self.is_shutdown() || pipeline.waiting.len() + 1 >= pipeline.depth
+            {
+                return None;
+            }
+            // Closed until the request of the handle is sent.
+            pipeline.open = false;
+            let turn = Arc::new(Mutex::new(Turn::Waiting(None)));
+            pipeline.waiting.push_back(turn.clone());
+            Some(Http1Conn {
+                sem: None,
+                inner: self.inner.clone(),
+                turn: Some(turn),
+            })
+        }
     }
 
     impl<S> Dispatcher for Http1Dispatcher<S> {
@@ -197,13 +380,21 @@ pub(crate) mod http1 {
     pub(crate) struct Http1Conn<S> {
         pub(crate) sem: Option<WrappedSemPermit>,
         pub(crate) inner: Arc<Inner<S>>,
+        // `turn` is `Some` while a pipelined request waits for the responses
+        // before it.
+        turn: Option<Arc<Mutex<Turn>>>,
     }
 
     impl<S> Http1Conn<S> {
         pub(crate) fn from_inner(inner: Arc<Inner<S>>) -> Self {
-            Self { sem: None, inner }
+            Self {
+                sem: None,
+                inner,
+                turn: None,
+            }
         }
 
+        #[cfg(feature = "async")]
         pub(crate) fn occupy_sem(&mut self, sem: WrappedSemPermit) {
             self.sem = Some(sem);
         }
@@ -217,64 +408,117 @@ pub(crate) mod http1 {
         pub(crate) fn shutdown(&self) {
             self.inner.shutdown.store(true, Ordering::Release);
         }
-    }
 
-    impl<S> Drop for Http1Conn<S> {
-        fn drop(&mut self) {
-            self.inner.occupied.store(false, Ordering::Release)
+        /// Checks if the handle sends a pipelined request, which is buffered
+        /// until the handle reading the responses writes it.
+        pub(crate) fn is_pipelined(&self) -> bool {
+            self.turn.is_some()
         }
-    }
 
-    pub(crate) struct WrappedSemaphore {
-        sem: Arc<Semaphore>,
-    }
-
-    impl WrappedSemaphore {
-        pub(crate) fn new(permits: usize) -> Self {
-            Self {
-                #[cfg(feature = "tokio_base")]
-                sem: Arc::new(tokio::sync::Semaphore::new(permits)),
-                #[cfg(feature = "ylong_base")]
-                sem: Arc::new(ylong_runtime::sync::Semaphore::new(permits).unwrap()),
+        /// Allows or forbids pipelining requests behind the one sent on this
+        /// handle.
+        pub(crate) fn set_pipelinable(&self, pipelinable: bool) {
+            if let Some(pipeline) = self.inner.pipeline.as_ref() {
+                let mut pipeline = pipeline.lock().unwrap();
+                pipeline.open = pipelinable && !self.inner.shutdown.load(Ordering::Acquire);
             }
         }
 
-        pub(crate) async fn acquire(&self) -> WrappedSemPermit {
-            #[cfg(feature = "ylong_base")]
-            {
-                let semaphore = self.sem.clone();
-                let _permit = semaphore.acquire().await.unwrap();
-                WrappedSemPermit { sem: semaphore }
+        /// Buffers `buf` as part of the pipelined request and wakes up the
+        /// handle reading the responses to write it.
+        pub(crate) fn buffer_pipelined(&self, buf: &[u8]) {
+            if let Some(pipeline) = self.inner.pipeline.as_ref() {
+                let mut pipeline = pipeline.lock().unwrap();
+                pipeline.pending.extend_from_slice(buf);
+                if let Some(waker) = pipeline.reader.take() {
+                    waker.wake();
+                }
             }
+        }
 
-            #[cfg(feature = "tokio_base")]
-            {
-                let permit = self.sem.clone().acquire_owned().await.unwrap();
-                WrappedSemPermit { permit }
-            }
+        /// Waits for the responses before the pipelined request to be read.
+        /// Returns `false` if the connection is closed before that.
+        pub(crate) fn poll_turn(&mut self, cx: &mut Context<'_>) -> Poll<bool> {
+            let sem = match self.turn {
+                None => return Poll::Ready(true),
+                Some(ref turn) => match &mut *turn.lock().unwrap() {
+                    Turn::Waiting(waker) => {
+                        *waker = Some(cx.waker().clone());
+                        return Poll::Pending;
+                    }
+                    Turn::Ready(sem) => sem.take(),
+                    Turn::Failed => return Poll::Ready(false),
+                },
+            };
+            self.turn = None;
+            self.sem = sem;
+            Poll::Ready(true)
         }
-    }
 
-    impl Clone for WrappedSemaphore {
-        fn clone(&self) -> Self {
-            Self {
-                sem: self.sem.clone(),
+        /// Writes the buffered pipelined requests, and registers the handle
+        /// to be woken up once more requests are buffered.
+        #[cfg(feature = "async")]
+        pub(crate) fn poll_write_pipelined(
+            &mut self,
+            cx: &mut Context<'_>,
+        ) -> Poll<std::io::Result<()>>
+        where
+            S: crate::runtime::AsyncWrite + Unpin,
+        {
+            let inner = &*self.inner;
+            let mut pipeline = match inner.pipeline.as_ref() {
+                Some(pipeline) => pipeline.lock().unwrap(),
+                None => return Poll::Ready(Ok(())),
+            };
+            pipeline.reader = Some(cx.waker().clone());
+            // SAFETY: The handle reading the responses is the only one which
+            // does I/O on the connection.
+            let io = unsafe { &mut *inner.io.get() };
+            while !pipeline.pending.is_empty() {
+                match std::pin::Pin::new(&mut *io).poll_write(cx, &pipeline.pending) {
+                    Poll::Ready(Ok(0)) => {
+                        return Poll::Ready(Err(std::io::ErrorKind::WriteZero.into()))
+                    }
+                    Poll::Ready(Ok(written)) => {
+                        pipeline.pending.drain(..written);
+                    }
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                    Poll::Pending => return Poll::Pending,
+                }
             }
+            Poll::Ready(Ok(()))
         }
     }
 
-    pub(crate) struct WrappedSemPermit {
-        #[cfg(feature = "ylong_base")]
-        pub(crate) sem: Arc<Semaphore>,
-        #[cfg(feature = "tokio_base")]
-        #[allow(dead_code)]
-        pub(crate) permit: SemaphorePermit,
-    }
-
-    #[cfg(feature = "ylong_base")]
-    impl Drop for WrappedSemPermit {
+    impl<S> Drop for Http1Conn<S> {
         fn drop(&mut self) {
-            self.sem.release();
+            if let Some(pipeline) = self.inner.pipeline.as_ref() {
+                let mut pipeline = pipeline.lock().unwrap();
+                if let Some(turn) = self.turn.take() {
+                    // The response of the dropped request would be read as the
+                    // response of the next one.
+                    self.shutdown();
+                    match &mut *turn.lock().unwrap() {
+                        Turn::Ready(sem) => self.sem = sem.take(),
+                        _ => return,
+                    }
+                }
+                if !self.inner.shutdown.load(Ordering::Acquire) {
+                    if let Some(next) = pipeline.waiting.pop_front() {
+                        let mut turn = next.lock().unwrap();
+                        let sem = self.sem.take();
+                        if let Turn::Waiting(Some(waker)) =
+                            std::mem::replace(&mut *turn, Turn::Ready(sem))
+                        {
+                            waker.wake();
+                        }
+                        // The connection stays occupied by the next request.
+                        return;
+                    }
+                }
+                pipeline.close();
+            }
+            self.inner.occupied.store(false, Ordering::Release)
         }
     }
 }
@@ -302,7 +546,7 @@ pub(crate) mod http2 {
     use crate::util::config::H2Config;
     use crate::util::dispatcher::{ConnDispatcher, Dispatcher};
     use crate::util::h2::{
-        ConnManager, FlowControl, H2StreamState, RecvData, RequestWrapper, SendData,
+        ConnManager, FlowControl, H2StreamState, RecvData, RequestWrapper, SendBuffer, SendData,
         StreamEndState, Streams,
     };
     use crate::ErrorKind::Request;
@@ -334,6 +578,8 @@ pub(crate) mod http2 {
         Io(std::io::ErrorKind),
         ChannelClosed,
         Disconnect,
+        // The server did not enable the extended CONNECT protocol.
+        ConnectProtocol,
     }
 
     // HTTP2-based connection manager, which can dispatch connections to other
@@ -342,6 +588,8 @@ pub(crate) mod http2 {
         pub(crate) detail: ConnDetail,
         pub(crate) allowed_cache: usize,
         pub(crate) sender: UnboundedSender<ReqMessage>,
+        // Asks the manager to reset the streams whose responses are dropped.
+        pub(crate) reset: UnboundedSender<()>,
         pub(crate) io_shutdown: Arc<AtomicBool>,
         pub(crate) io_goaway: Arc<AtomicBool>,
         pub(crate) handles: Vec<crate::runtime::JoinHandle<()>>,
@@ -353,6 +601,7 @@ pub(crate) mod http2 {
         // Sends frame to StreamController
         pub(crate) sender: UnboundedSender<ReqMessage>,
         pub(crate) receiver: RespReceiver,
+        pub(crate) reset: UnboundedSender<()>,
         pub(crate) io_shutdown: Arc<AtomicBool>,
         pub(crate) detail: ConnDetail,
         pub(crate) _mark: PhantomData<S>,
@@ -372,6 +621,13 @@ pub(crate) mod http2 {
         pub(crate) go_away_error_code: Option<u32>,
         // The last GO_AWAY frame sent by the client.
         pub(crate) go_away_sync: GoAwaySync,
+        // Whether the peer allows extended CONNECT, unknown before its first SETTINGS frame.
+        pub(crate) connect_protocol: Option<bool>,
+        // Extended CONNECT requests waiting for the first SETTINGS frame of the peer.
+        pub(crate) pending_connect: Vec<ReqMessage>,
+        // Whether the SETTINGS_ENABLE_PUSH sent to the peer is 1, in which case
+        // pushes are refused instead of being connection errors.
+        pub(crate) enable_push: bool,
     }
 
     #[derive(Default)]
@@ -413,14 +669,22 @@ pub(crate) mod http2 {
             let mut flow = FlowControl::new(DEFAULT_WINDOW_SIZE, DEFAULT_WINDOW_SIZE);
             flow.setup_recv_window(config.conn_window_size());
 
-            let streams = Streams::new(config.stream_window_size(), DEFAULT_WINDOW_SIZE, flow);
+            let mut streams = Streams::new(config.stream_window_size(), DEFAULT_WINDOW_SIZE, flow);
+            let send_buffer = config
+                .max_send_buffer_size()
+                .map(|size| Arc::new(SendBuffer::new(size)));
+            if let Some(ref send_buffer) = send_buffer {
+                streams.set_send_buffer(send_buffer.clone());
+            }
             let shutdown_flag = Arc::new(AtomicBool::new(false));
             let goaway_flag = Arc::new(AtomicBool::new(false));
             let mut controller =
                 StreamController::new(streams, shutdown_flag.clone(), goaway_flag.clone());
+            controller.enable_push = config.enable_push();
 
             let (input_tx, input_rx) = unbounded_channel();
             let (req_tx, req_rx) = unbounded_channel();
+            let (reset_tx, reset_rx) = unbounded_channel();
 
             let settings = create_initial_settings(&config);
 
@@ -435,11 +699,11 @@ pub(crate) mod http2 {
                     .is_ok()
             {
                 Self::launch(
-                    config.allowed_cache_frame_size(),
-                    config.use_huffman_coding(),
+                    &config,
                     controller,
+                    send_buffer,
                     (input_tx, input_rx),
-                    req_rx,
+                    (req_rx, reset_rx),
                     &mut handles,
                     io,
                 );
@@ -448,6 +712,7 @@ pub(crate) mod http2 {
                 detail,
                 allowed_cache: config.allowed_cache_frame_size(),
                 sender: req_tx,
+                reset: reset_tx,
                 io_shutdown: shutdown_flag,
                 io_goaway: goaway_flag,
                 handles,
@@ -456,15 +721,19 @@ pub(crate) mod http2 {
         }
 
         fn launch(
-            allow_num: usize,
-            use_huffman: bool,
+            config: &H2Config,
             controller: StreamController,
+            send_buffer: Option<Arc<SendBuffer>>,
             input_channel: (UnboundedSender<Frame>, UnboundedReceiver<Frame>),
-            req_rx: UnboundedReceiver<ReqMessage>,
+            req_channel: (UnboundedReceiver<ReqMessage>, UnboundedReceiver<()>),
             handles: &mut Vec<crate::runtime::JoinHandle<()>>,
             io: S,
         ) {
-            let (resp_tx, resp_rx) = bounded_channel(allow_num);
+            let (req_rx, reset_rx) = req_channel;
+            let (resp_tx, resp_rx) = bounded_channel(config.allowed_cache_frame_size());
+            let use_huffman = config.use_huffman_coding();
+            let send_observer = config.observer().cloned();
+            let recv_observer = config.observer().cloned();
             let (read, write) = crate::runtime::split(io);
             let settings_sync = Arc::new(Mutex::new(SettingsSync::default()));
             let send_settings_sync = settings_sync.clone();
@@ -473,7 +742,9 @@ pub(crate) mod http2 {
                 if async_send_preface(&mut writer).await.is_ok() {
                     let encoder = FrameEncoder::new(DEFAULT_MAX_FRAME_SIZE, use_huffman);
                     let mut send =
-                        SendData::new(encoder, send_settings_sync, writer, input_channel.1);
+                        SendData::new(encoder, send_settings_sync, writer, input_channel.1)
+                            .observer(send_observer)
+                            .send_buffer(send_buffer);
                     let _ = Pin::new(&mut send).await;
                 }
             });
@@ -482,14 +753,21 @@ pub(crate) mod http2 {
             let recv_settings_sync = settings_sync.clone();
             let recv = crate::runtime::spawn(async move {
                 let decoder = FrameDecoder::new();
-                let mut recv = RecvData::new(decoder, recv_settings_sync, read, resp_tx);
+                let mut recv = RecvData::new(decoder, recv_settings_sync, read, resp_tx)
+                    .observer(recv_observer);
                 let _ = Pin::new(&mut recv).await;
             });
             handles.push(recv);
 
             let manager = crate::runtime::spawn(async move {
-                let mut conn_manager =
-                    ConnManager::new(settings_sync, input_channel.0, resp_rx, req_rx, controller);
+                let mut conn_manager = ConnManager::new(
+                    settings_sync,
+                    input_channel.0,
+                    resp_rx,
+                    req_rx,
+                    reset_rx,
+                    controller,
+                );
                 let _ = Pin::new(&mut conn_manager).await;
             });
             handles.push(manager);
@@ -504,7 +782,7 @@ pub(crate) mod http2 {
             let handle = Http2Conn::new(
                 self.allowed_cache,
                 self.io_shutdown.clone(),
-                sender,
+                (sender, self.reset.clone()),
                 self.detail.clone(),
             );
             Some(handle)
@@ -534,13 +812,15 @@ pub(crate) mod http2 {
         pub(crate) fn new(
             allow_cached_num: usize,
             io_shutdown: Arc<AtomicBool>,
-            sender: UnboundedSender<ReqMessage>,
+            senders: (UnboundedSender<ReqMessage>, UnboundedSender<()>),
             detail: ConnDetail,
         ) -> Self {
+            let (sender, reset) = senders;
             Self {
                 allow_cached_frames: allow_cached_num,
                 sender,
                 receiver: RespReceiver::default(),
+                reset,
                 io_shutdown,
                 detail,
                 _mark: PhantomData,
@@ -562,6 +842,15 @@ pub(crate) mod http2 {
                     HttpClientError::from_str(ErrorKind::Request, "Request Sender Closed !")
                 })
         }
+
+        /// Aborts the stream of this handle with `RST_STREAM`. The other
+        /// streams of the connection are not affected.
+        pub(crate) fn reset_stream(mut self) {
+            // The receiver is closed first, so the manager finds the stream
+            // when it is notified.
+            self.receiver = RespReceiver::default();
+            let _ = self.reset.send(());
+        }
     }
 
     impl StreamController {
@@ -578,6 +867,9 @@ pub(crate) mod http2 {
                 streams,
                 go_away_error_code: None,
                 go_away_sync: GoAwaySync::default(),
+                connect_protocol: None,
+                pending_connect: Vec::new(),
+                enable_push: false,
             }
         }
 
@@ -633,6 +925,29 @@ pub(crate) mod http2 {
             }
         }
 
+        // Resets the streams whose response receivers are closed, except the
+        // ones whose `HEADERS` are not sent yet.
+        pub(crate) fn reset_closed_streams(&mut self, input_tx: &UnboundedSender<Frame>) {
+            let closed: Vec<StreamId> = self
+                .senders
+                .iter()
+                .filter(|(_, sender)| sender.is_closed())
+                .map(|(id, _)| *id)
+                .collect();
+            for id in closed {
+                if let Some(H2StreamState::Idle) = self.streams.stream_state(id) {
+                    continue;
+                }
+                self.senders.remove(&id);
+                self.curr_message.remove(&id);
+                if let StreamEndState::OK = self.streams.send_local_reset(id) {
+                    let reset = RstStream::new(ErrorCode::Cancel.into_code());
+                    let frame = Frame::new(id, FrameFlags::empty(), Payload::RstStream(reset));
+                    let _ = input_tx.send(frame);
+                }
+            }
+        }
+
         pub(crate) fn poll_blocked_message(
             &mut self,
             cx: &mut Context<'_>,
@@ -760,7 +1075,7 @@ pub(crate) mod http2 {
     }
 
     pub(crate) fn create_initial_settings(config: &H2Config) -> Frame {
-        let settings = SettingsBuilder::new()
+        let computed = SettingsBuilder::new()
             .max_header_list_size(config.max_header_list_size())
             .max_frame_size(config.max_frame_size())
             .header_table_size(config.header_table_size())
@@ -768,7 +1083,27 @@ pub(crate) mod http2 {
             .initial_window_size(config.stream_window_size())
             .build();
 
-        Frame::new(0, FrameFlags::new(0), Payload::Settings(settings))
+        // Custom settings are sent first in the order given by the user, and
+        // the computed settings they do not override follow.
+        let custom = config.custom_settings();
+        let mut settings = custom.to_vec();
+        settings.extend(
+            computed
+                .get_settings()
+                .iter()
+                .filter(|setting| {
+                    !custom
+                        .iter()
+                        .any(|s| s.setting_identifier() == setting.setting_identifier())
+                })
+                .cloned(),
+        );
+
+        Frame::new(
+            0,
+            FrameFlags::new(0),
+            Payload::Settings(Settings::new(settings)),
+        )
     }
 
     impl From<std::io::Error> for DispatchErrorKind {
@@ -795,6 +1130,7 @@ pub(crate) mod http2 {
             DispatchErrorKind::Disconnect => {
                 HttpClientError::from_str(Request, "remote peer closed.")
             }
+            DispatchErrorKind::ConnectProtocol => HttpClientError::from_connect_protocol(),
         }
     }
 }
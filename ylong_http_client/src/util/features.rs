@@ -0,0 +1,206 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Features compiled into the client.
+
+use core::fmt::{Display, Formatter};
+
+/// The cargo features compiled into this build of the client, which can be
+/// checked at runtime.
+///
+/// # Examples
+///
+/// ```
+/// let features = ylong_http_client::features();
+/// if !features.gzip() {
+///     println!("gzip is not supported by this build: {features}");
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Features {
+    sync: bool,
+    async_client: bool,
+    http1_1: bool,
+    http2: bool,
+    http3: bool,
+    tls: Option<&'static str>,
+    runtime: Option<&'static str>,
+    gzip: bool,
+    websocket: bool,
+    grpc_web: bool,
+    auth: bool,
+    sse: bool,
+}
+
+/// Gets the `Features` compiled into this build of the client.
+///
+/// # Examples
+///
+/// ```
+/// let features = ylong_http_client::features();
+/// println!("{features}");
+/// ```
+pub fn features() -> Features {
+    let tls = if cfg!(feature = "c_openssl_3_0") {
+        Some("openssl-3.0")
+    } else if cfg!(feature = "c_openssl_1_1") {
+        Some("openssl-1.1")
+    } else if cfg!(feature = "c_boringssl") {
+        Some("boringssl")
+    } else {
+        None
+    };
+    let runtime = if cfg!(feature = "ylong_base") {
+        Some("ylong")
+    } else if cfg!(feature = "tokio_base") {
+        Some("tokio")
+    } else {
+        None
+    };
+    Features {
+        sync: cfg!(feature = "sync"),
+        async_client: cfg!(feature = "async"),
+        http1_1: cfg!(feature = "http1_1"),
+        http2: cfg!(feature = "http2"),
+        http3: cfg!(feature = "http3"),
+        tls,
+        runtime,
+        gzip: cfg!(feature = "async"),
+        websocket: cfg!(feature = "async"),
+        grpc_web: cfg!(feature = "grpc_web"),
+        auth: cfg!(feature = "auth"),
+        sse: cfg!(feature = "sse"),
+    }
+}
+
+impl Features {
+    /// Checks if the sync client is compiled, see `sync_impl`.
+    pub fn sync(&self) -> bool {
+        self.sync
+    }
+
+    /// Checks if the async client is compiled, see `async_impl`.
+    pub fn async_client(&self) -> bool {
+        self.async_client
+    }
+
+    /// Checks if `HTTP/1.1` is supported.
+    pub fn http1_1(&self) -> bool {
+        self.http1_1
+    }
+
+    /// Checks if `HTTP/2` is supported.
+    pub fn http2(&self) -> bool {
+        self.http2
+    }
+
+    /// Checks if `HTTP/3` is supported.
+    pub fn http3(&self) -> bool {
+        self.http3
+    }
+
+    /// Gets the TLS backend, such as `openssl-3.0`, `openssl-1.1` or
+    /// `boringssl`. `None` means `HTTPS` is not supported.
+    pub fn tls(&self) -> Option<&'static str> {
+        self.tls
+    }
+
+    /// Gets the async runtime, `ylong` or `tokio`.
+    pub fn runtime(&self) -> Option<&'static str> {
+        self.runtime
+    }
+
+    /// Checks if the `gzip` and `deflate` content codings are supported, which
+    /// compress request bodies and decompress response bodies. Only the async
+    /// client supports them.
+    pub fn gzip(&self) -> bool {
+        self.gzip
+    }
+
+    /// Checks if WebSocket is supported.
+    pub fn websocket(&self) -> bool {
+        self.websocket
+    }
+
+    /// Checks if gRPC-Web is supported.
+    pub fn grpc_web(&self) -> bool {
+        self.grpc_web
+    }
+
+    /// Checks if HTTP Digest authentication of origin servers is supported.
+    pub fn auth(&self) -> bool {
+        self.auth
+    }
+
+    /// Checks if server-sent events parsing is supported.
+    pub fn sse(&self) -> bool {
+        self.sse
+    }
+}
+
+impl Display for Features {
+    // Lists the enabled features, like `async, http1_1, tls=openssl-3.0`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let flags = [
+            ("sync", self.sync),
+            ("async", self.async_client),
+            ("http1_1", self.http1_1),
+            ("http2", self.http2),
+            ("http3", self.http3),
+            ("gzip", self.gzip),
+            ("websocket", self.websocket),
+            ("grpc_web", self.grpc_web),
+            ("auth", self.auth),
+            ("sse", self.sse),
+        ];
+        let mut list = flags
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| name.to_string())
+            .collect::<Vec<_>>();
+        if let Some(tls) = self.tls {
+            list.push(format!("tls={tls}"));
+        }
+        if let Some(runtime) = self.runtime {
+            list.push(format!("runtime={runtime}"));
+        }
+        write!(f, "{}", list.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod ut_features {
+    use crate::util::features::features;
+
+    /// UT test cases for `features`.
+    ///
+    /// # Brief
+    /// 1. Gets the `Features` of this build.
+    /// 2. Checks that they match the enabled cargo features.
+    /// 3. Checks that `Display` lists the enabled features.
+    #[test]
+    fn ut_features() {
+        let features = features();
+        assert_eq!(features.sync(), cfg!(feature = "sync"));
+        assert_eq!(features.async_client(), cfg!(feature = "async"));
+        assert_eq!(features.http1_1(), cfg!(feature = "http1_1"));
+        assert_eq!(features.http2(), cfg!(feature = "http2"));
+        assert_eq!(features.tls().is_some(), cfg!(feature = "__tls"));
+        assert_eq!(features.gzip(), cfg!(feature = "async"));
+        assert_eq!(features.sse(), cfg!(feature = "sse"));
+
+        let display = features.to_string();
+        assert_eq!(display.contains("http1_1"), cfg!(feature = "http1_1"));
+        assert_eq!(display.contains("tls="), cfg!(feature = "__tls"));
+    }
+}
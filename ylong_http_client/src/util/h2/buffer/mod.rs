@@ -18,10 +18,13 @@
 //! -[`FlowControl`] is used to Provides connection-level Flow-Control
 //! capability. -[`RecvWindow`] is the structure of the client response
 //! receiving window. -[`SendWindow`] is the structure of the client request
-//! sending window.
+//! sending window. -[`SendBuffer`] bounds the request body data of each
+//! stream waiting to be written.
 
+mod send_buffer;
 mod settings;
 mod window;
 
+pub(crate) use send_buffer::SendBuffer;
 pub(crate) use settings::FlowControl;
 pub(crate) use window::{RecvWindow, SendWindow};
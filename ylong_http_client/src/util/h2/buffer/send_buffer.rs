@@ -0,0 +1,108 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! http2 send buffer definition.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::task::{Context, Waker};
+
+use ylong_http::h2::StreamId;
+
+/// Bounds the request body data of each stream which is read from the body
+/// but not written to the io yet.
+pub(crate) struct SendBuffer {
+    max_size: usize,
+    inner: Mutex<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    // The size of the queued DATA frames of each stream.
+    queued: HashMap<StreamId, usize>,
+    // Wakes the manager when a stream waiting for buffer space can go on.
+    waker: Option<Waker>,
+}
+
+impl SendBuffer {
+    pub(crate) fn new(max_size: usize) -> Self {
+        Self {
+            max_size: max_size.max(1),
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    /// Gets the buffer space left for the stream. If there is none, the task
+    /// is woken when the queued data of the stream is written.
+    pub(crate) fn vacant(&self, cx: &mut Context<'_>, id: StreamId) -> usize {
+        let mut inner = self.inner.lock().unwrap();
+        let queued = inner.queued.get(&id).copied().unwrap_or(0);
+        let vacant = self.max_size.saturating_sub(queued);
+        if vacant == 0 {
+            inner.waker = Some(cx.waker().clone());
+        }
+        vacant
+    }
+
+    /// Records a DATA frame of the stream queued for writing.
+    pub(crate) fn push(&self, id: StreamId, size: usize) {
+        let mut inner = self.inner.lock().unwrap();
+        *inner.queued.entry(id).or_insert(0) += size;
+    }
+
+    /// Releases the space of a DATA frame of the stream which is written.
+    pub(crate) fn release(&self, id: StreamId, size: usize) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(queued) = inner.queued.get_mut(&id) {
+            *queued = queued.saturating_sub(size);
+            if *queued == 0 {
+                inner.queued.remove(&id);
+            }
+        }
+        if let Some(waker) = inner.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(test)]
+mod ut_send_buffer {
+    use std::task::Context;
+
+    use crate::async_impl::noop_waker;
+    use crate::util::h2::buffer::SendBuffer;
+
+    /// UT test cases for `SendBuffer`.
+    ///
+    /// # Brief
+    /// 1. Creates a `SendBuffer` and queues DATA frames of two streams.
+    /// 2. Releases the queued frames.
+    /// 3. Checks if the vacant space of each stream is correct.
+    #[test]
+    fn ut_send_buffer_vacant() {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let buffer = SendBuffer::new(100);
+        assert_eq!(buffer.vacant(&mut cx, 1), 100);
+        buffer.push(1, 60);
+        buffer.push(1, 40);
+        buffer.push(3, 10);
+        assert_eq!(buffer.vacant(&mut cx, 1), 0);
+        assert_eq!(buffer.vacant(&mut cx, 3), 90);
+        buffer.release(1, 60);
+        assert_eq!(buffer.vacant(&mut cx, 1), 60);
+        buffer.release(1, 40);
+        assert_eq!(buffer.vacant(&mut cx, 1), 100);
+        assert_eq!(SendBuffer::new(0).vacant(&mut cx, 1), 1);
+    }
+}
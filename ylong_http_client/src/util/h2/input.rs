@@ -18,10 +18,13 @@ use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
 
-use ylong_http::h2::{ErrorCode, Frame, FrameEncoder, H2Error, Payload, Setting, Settings};
+use ylong_http::h2::{
+    ErrorCode, Frame, FrameEncoder, H2Error, Payload, Setting, Settings, StreamId,
+};
 
 use crate::runtime::{AsyncWrite, UnboundedReceiver, WriteHalf};
 use crate::util::dispatcher::http2::{DispatchErrorKind, SettingsState, SettingsSync};
+use crate::util::h2::{observe, H2Direction, H2Observer, SendBuffer};
 
 pub(crate) struct SendData<S> {
     encoder: FrameEncoder,
@@ -30,6 +33,10 @@ pub(crate) struct SendData<S> {
     req_rx: UnboundedReceiver<Frame>,
     state: InputState,
     buf: WriteBuf,
+    observer: Option<Arc<dyn H2Observer>>,
+    send_buffer: Option<Arc<SendBuffer>>,
+    // The stream and size of the DATA frame being written.
+    writing_data: Option<(StreamId, usize)>,
 }
 
 enum InputState {
@@ -83,6 +90,10 @@ impl<S: AsyncWrite + Unpin + Sync + Send + 'static> Future for SendData<S> {
                         Poll::Pending => return Poll::Pending,
                     };
 
+                    observe(sender.observer.as_ref(), H2Direction::Sent, &frame);
+                    if let Payload::Data(data) = frame.payload() {
+                        sender.writing_data = Some((frame.stream_id(), data.data().len()));
+                    }
                     let state = sender.update_settings(&frame);
 
                     if let SettingState::Local(setting) = &state {
@@ -107,6 +118,11 @@ impl<S: AsyncWrite + Unpin + Sync + Send + 'static> Future for SendData<S> {
                         Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
                         Poll::Pending => return Poll::Pending,
                     };
+                    if let Some((id, size)) = sender.writing_data.take() {
+                        if let Some(ref send_buffer) = sender.send_buffer {
+                            send_buffer.release(id, size);
+                        }
+                    }
                     sender.state = InputState::RecvFrame;
                 }
             }
@@ -128,9 +144,22 @@ impl<S: AsyncWrite + Unpin + Sync + Send + 'static> SendData<S> {
             req_rx,
             state: InputState::RecvFrame,
             buf: WriteBuf::new(),
+            observer: None,
+            send_buffer: None,
+            writing_data: None,
         }
     }
 
+    pub(crate) fn observer(mut self, observer: Option<Arc<dyn H2Observer>>) -> Self {
+        self.observer = observer;
+        self
+    }
+
+    pub(crate) fn send_buffer(mut self, send_buffer: Option<Arc<SendBuffer>>) -> Self {
+        self.send_buffer = send_buffer;
+        self
+    }
+
     // io write interface
     fn poll_writer_frame(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), DispatchErrorKind>> {
         if !self.buf.empty {
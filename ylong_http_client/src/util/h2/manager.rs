@@ -14,12 +14,14 @@
 //! Streams manage coroutine.
 
 use std::future::Future;
+use std::mem::take;
 use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
 
 use ylong_http::h2::{
-    ErrorCode, Frame, FrameFlags, Goaway, H2Error, Payload, Ping, RstStream, Setting, StreamId,
+    ErrorCode, Frame, FrameFlags, Goaway, H2Error, Payload, Ping, RstStream, Setting, Settings,
+    StreamId,
 };
 
 use crate::runtime::{BoundedReceiver, UnboundedReceiver, UnboundedSender};
@@ -47,6 +49,8 @@ pub(crate) struct ConnManager {
     resp_rx: BoundedReceiver<OutputMessage>,
     // channel receiver between manager and stream coroutine.
     req_rx: UnboundedReceiver<ReqMessage>,
+    // channel receiver of the notifications to reset the dropped streams.
+    reset_rx: UnboundedReceiver<()>,
     controller: StreamController,
 }
 
@@ -125,6 +129,7 @@ impl ConnManager {
         input_tx: UnboundedSender<Frame>,
         resp_rx: BoundedReceiver<OutputMessage>,
         req_rx: UnboundedReceiver<ReqMessage>,
+        reset_rx: UnboundedReceiver<()>,
         controller: StreamController,
     ) -> Self {
         Self {
@@ -134,6 +139,7 @@ impl ConnManager {
             input_tx,
             resp_rx,
             req_rx,
+            reset_rx,
             controller,
         }
     }
@@ -147,11 +153,27 @@ impl ConnManager {
             self.poll_deal_with_go_away(error_code)?;
             return Poll::Pending;
         }
+        self.poll_recv_reset(cx);
         self.poll_recv_request(cx)?;
         self.poll_input_request(cx)?;
         Poll::Pending
     }
 
+    fn poll_recv_reset(&mut self, cx: &mut Context<'_>) {
+        let mut notified = false;
+        #[cfg(feature = "tokio_base")]
+        while let Poll::Ready(Some(())) = self.reset_rx.poll_recv(cx) {
+            notified = true;
+        }
+        #[cfg(feature = "ylong_base")]
+        while let Poll::Ready(Ok(())) = self.reset_rx.poll_recv(cx) {
+            notified = true;
+        }
+        if notified {
+            self.controller.reset_closed_streams(&self.input_tx);
+        }
+    }
+
     fn poll_recv_request(&mut self, cx: &mut Context<'_>) -> Result<(), DispatchErrorKind> {
         loop {
             #[cfg(feature = "tokio_base")]
@@ -166,30 +188,95 @@ impl ConnManager {
                 Poll::Ready(Err(_e)) => return Err(DispatchErrorKind::ChannelClosed),
                 Poll::Pending => break,
             };
-            let id = match self.controller.streams.generate_id() {
-                Ok(id) => id,
-                Err(e) => {
-                    let _ = message.sender.try_send(RespMessage::OutputExit(e));
-                    break;
+            if is_extended_connect(&message) {
+                match self.controller.connect_protocol {
+                    // The peer's SETTINGS has not arrived yet.
+                    None => {
+                        self.controller.pending_connect.push(message);
+                        continue;
+                    }
+                    Some(false) => {
+                        reject_extended_connect(message);
+                        continue;
+                    }
+                    Some(true) => {}
                 }
-            };
-            let headers = Frame::new(id, message.request.flag, message.request.payload);
-            if self.controller.streams.reach_max_concurrency()
-                || !self.controller.streams.is_pending_concurrency_empty()
-            {
-                self.controller.streams.push_pending_concurrency(id)
-            } else {
-                self.controller.streams.increase_current_concurrency();
-                self.controller.streams.push_back_pending_send(id)
             }
-            self.controller.senders.insert(id, message.sender);
-            self.controller
-                .streams
-                .insert(id, headers, message.request.data);
+            if !self.insert_request(message) {
+                break;
+            }
         }
         Ok(())
     }
 
+    fn insert_request(&mut self, message: ReqMessage) -> bool {
+        let id = match self.controller.streams.generate_id() {
+            Ok(id) => id,
+            Err(e) => {
+                let _ = message.sender.try_send(RespMessage::OutputExit(e));
+                return false;
+            }
+        };
+        let headers = Frame::new(id, message.request.flag, message.request.payload);
+        if self.controller.streams.reach_max_concurrency()
+            || !self.controller.streams.is_pending_concurrency_empty()
+        {
+            self.controller.streams.push_pending_concurrency(id)
+        } else {
+            self.controller.streams.increase_current_concurrency();
+            self.controller.streams.push_back_pending_send(id)
+        }
+        self.controller.senders.insert(id, message.sender);
+        self.controller
+            .streams
+            .insert(id, headers, message.request.data);
+        if let Some(priority) = message.request.priority {
+            let frame = Frame::new(id, FrameFlags::empty(), Payload::Priority(priority));
+            self.controller.streams.set_priority(id, frame);
+        }
+        true
+    }
+
+    // Extended CONNECT requests can only be sent after the peer's
+    // SETTINGS_ENABLE_CONNECT_PROTOCOL is known.
+    fn apply_connect_protocol(&mut self, settings: &Settings) {
+        let enabled = settings
+            .get_settings()
+            .iter()
+            .find_map(|setting| match setting {
+                Setting::EnableConnectProtocol(enable) => Some(*enable),
+                _ => None,
+            });
+        match enabled {
+            // A peer that has enabled it must not disable it later.
+            Some(enable) if self.controller.connect_protocol != Some(true) => {
+                self.controller.connect_protocol = Some(enable)
+            }
+            None if self.controller.connect_protocol.is_none() => {
+                self.controller.connect_protocol = Some(false)
+            }
+            _ => {}
+        }
+
+        let enabled = self.controller.connect_protocol == Some(true);
+        let mut messages = take(&mut self.controller.pending_connect).into_iter();
+        for message in messages.by_ref() {
+            if !enabled {
+                reject_extended_connect(message);
+            } else if !self.insert_request(message) {
+                break;
+            }
+        }
+        // The stream ids are used up, so the rest can't be sent either.
+        for message in messages {
+            let _ = message
+                .sender
+                .try_send(RespMessage::OutputExit(DispatchErrorKind::H2(
+                    H2Error::ConnectionError(ErrorCode::ProtocolError),
+                )));
+        }
+    }
+
     fn poll_input_request(&mut self, cx: &mut Context<'_>) -> Result<(), DispatchErrorKind> {
         self.controller.streams.try_consume_pending_concurrency();
         let size = self.controller.streams.pending_stream_num();
@@ -218,6 +305,9 @@ impl ConnManager {
             Some(header) => {
                 let is_end_stream = header.flags().is_end_stream();
                 self.poll_send_frame(header)?;
+                if let Some(priority) = self.controller.streams.priority(id) {
+                    self.poll_send_frame(priority)?;
+                }
                 // Prevent sending empty data frames
                 if is_end_stream {
                     return Ok(());
@@ -287,11 +377,13 @@ impl ConnManager {
             Payload::Ping(_ping) => {
                 self.recv_ping_frame(frame)?;
             }
-            Payload::PushPromise(_) => {
-                // TODO The current settings_enable_push setting is fixed to false.
-                return Poll::Ready(Err(
-                    H2Error::ConnectionError(ErrorCode::ProtocolError).into()
-                ));
+            Payload::PushPromise(push) => {
+                if !self.controller.enable_push {
+                    return Poll::Ready(Err(
+                        H2Error::ConnectionError(ErrorCode::ProtocolError).into()
+                    ));
+                }
+                self.refuse_push(push.promised_stream_id())?;
             }
             Payload::Goaway(_go_away) => {
                 return self.recv_go_away_frame(cx, frame).map_err(Into::into);
@@ -347,6 +439,7 @@ impl ConnManager {
                         .apply_send_initial_window_size(*size)?;
                 }
             }
+            self.apply_connect_protocol(settings);
 
             // The reason for copying the payload is to pass information to the io input to
             // set the frame encoder, and the input will empty the
@@ -490,6 +583,19 @@ impl ConnManager {
         }
     }
 
+    // Server push is never accepted, the promised stream is reset at once.
+    fn refuse_push(&mut self, id: StreamId) -> Result<(), DispatchErrorKind> {
+        self.controller.streams.refuse_push(id)?;
+        let reset = RstStream::new(ErrorCode::RefusedStream.into_code());
+        self.input_tx
+            .send(Frame::new(
+                id,
+                FrameFlags::empty(),
+                Payload::RstStream(reset),
+            ))
+            .map_err(|_e| DispatchErrorKind::ChannelClosed)
+    }
+
     fn recv_window_frame(&mut self, frame: Frame) -> Result<(), DispatchErrorKind> {
         let windows = if let Payload::WindowUpdate(windows) = frame.payload() {
             windows
@@ -752,3 +858,16 @@ impl ConnManager {
         Ok(())
     }
 }
+
+fn is_extended_connect(message: &ReqMessage) -> bool {
+    match message.request.payload {
+        Payload::Headers(ref headers) => headers.parts().0.protocol().is_some(),
+        _ => false,
+    }
+}
+
+fn reject_extended_connect(message: ReqMessage) {
+    let _ = message
+        .sender
+        .try_send(RespMessage::OutputExit(DispatchErrorKind::ConnectProtocol));
+}
@@ -20,21 +20,25 @@
 //! -[`Streams`] is used to manage the state of individual streams.
 //! -[`ConnManager`] is used to coordinate the Request sending and Response
 //! receiving of multiple streams.
+//! -[`H2Observer`] is used to observe the frames sent and received.
 
 mod buffer;
 mod input;
 mod manager;
+mod observer;
 mod output;
 mod streams;
 
 #[cfg(feature = "ylong_base")]
 mod io;
 
-pub(crate) use buffer::FlowControl;
+pub(crate) use buffer::{FlowControl, SendBuffer};
 pub(crate) use input::SendData;
 #[cfg(feature = "ylong_base")]
 pub(crate) use io::{split, Reader, Writer};
 pub(crate) use manager::ConnManager;
+pub(crate) use observer::observe;
+pub use observer::{H2Direction, H2Event, H2Observer};
 pub(crate) use output::RecvData;
 pub(crate) use streams::{H2StreamState, RequestWrapper, StreamEndState, Streams};
 
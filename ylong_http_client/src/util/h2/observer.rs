@@ -0,0 +1,185 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Observer of the frames of HTTP2 connections.
+
+use std::sync::Arc;
+
+use ylong_http::h2::{Frame, Payload, StreamId};
+
+/// The direction of a frame observed by an [`H2Observer`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum H2Direction {
+    /// The frame is sent by the client.
+    Sent,
+    /// The frame is received from the server.
+    Received,
+}
+
+/// A frame of an HTTP2 connection observed by an [`H2Observer`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum H2Event {
+    /// A `HEADERS` frame.
+    Headers {
+        /// The id of the stream.
+        stream_id: StreamId,
+        /// Whether the frame ends the stream.
+        end_stream: bool,
+    },
+    /// A `DATA` frame.
+    Data {
+        /// The id of the stream.
+        stream_id: StreamId,
+        /// The size of the data.
+        size: usize,
+        /// Whether the frame ends the stream.
+        end_stream: bool,
+    },
+    /// A `WINDOW_UPDATE` frame.
+    WindowUpdate {
+        /// The id of the stream, `0` for the connection.
+        stream_id: StreamId,
+        /// The window size increment.
+        increment: u32,
+    },
+    /// A `RST_STREAM` frame.
+    RstStream {
+        /// The id of the stream.
+        stream_id: StreamId,
+        /// The error code.
+        error_code: u32,
+    },
+    /// A `GOAWAY` frame.
+    GoAway {
+        /// The id of the last stream processed by the sender.
+        last_stream_id: StreamId,
+        /// The error code.
+        error_code: u32,
+    },
+}
+
+impl H2Event {
+    // Gets the event of the frame, if the frame is observed.
+    pub(crate) fn from_frame(frame: &Frame) -> Option<Self> {
+        let stream_id = frame.stream_id();
+        let end_stream = frame.flags().is_end_stream();
+        let event = match frame.payload() {
+            Payload::Headers(_) => Self::Headers {
+                stream_id,
+                end_stream,
+            },
+            Payload::Data(data) => Self::Data {
+                stream_id,
+                size: data.size(),
+                end_stream,
+            },
+            Payload::WindowUpdate(update) => Self::WindowUpdate {
+                stream_id,
+                increment: update.get_increment(),
+            },
+            Payload::RstStream(reset) => Self::RstStream {
+                stream_id,
+                error_code: reset.error_code(),
+            },
+            Payload::Goaway(goaway) => Self::GoAway {
+                last_stream_id: goaway.get_last_stream_id(),
+                error_code: goaway.get_error_code(),
+            },
+            _ => return None,
+        };
+        Some(event)
+    }
+}
+
+/// `H2Observer` receives the `HEADERS`, `DATA`, `WINDOW_UPDATE`,
+/// `RST_STREAM` and `GOAWAY` frames sent and received on the HTTP2
+/// connections of a `Client`, for diagnostics.
+///
+/// The observer is called by the tasks driving the connections, so it should
+/// return quickly.
+///
+/// # Examples
+///
+/// ```
+/// use ylong_http_client::{H2Direction, H2Event, H2Observer};
+///
+/// struct Logger;
+///
+/// impl H2Observer for Logger {
+///     fn on_event(&self, direction: H2Direction, event: &H2Event) {
+///         println!("{direction:?} {event:?}");
+///     }
+/// }
+/// ```
+pub trait H2Observer: Send + Sync + 'static {
+    /// Called once for each observed frame.
+    fn on_event(&self, direction: H2Direction, event: &H2Event);
+}
+
+// Passes the frame to the observer if there is one.
+pub(crate) fn observe(
+    observer: Option<&Arc<dyn H2Observer>>,
+    direction: H2Direction,
+    frame: &Frame,
+) {
+    if let Some(observer) = observer {
+        if let Some(event) = H2Event::from_frame(frame) {
+            observer.on_event(direction, &event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod ut_h2_observer {
+    use ylong_http::h2::{Data, Frame, FrameFlags, Payload, Ping, WindowUpdate};
+
+    use crate::util::h2::{H2Direction, H2Event};
+
+    /// UT test cases for `H2Event::from_frame`.
+    ///
+    /// # Brief
+    /// 1. Creates frames of different types.
+    /// 2. Converts them to `H2Event`s.
+    /// 3. Checks if the results are as expected.
+    #[test]
+    fn ut_h2_event_from_frame() {
+        let data = Frame::new(
+            3,
+            FrameFlags::new(0x1),
+            Payload::Data(Data::new(vec![0; 10])),
+        );
+        assert_eq!(
+            H2Event::from_frame(&data),
+            Some(H2Event::Data {
+                stream_id: 3,
+                size: 10,
+                end_stream: true,
+            })
+        );
+        let update = Frame::new(
+            0,
+            FrameFlags::new(0),
+            Payload::WindowUpdate(WindowUpdate::new(1024)),
+        );
+        assert_eq!(
+            H2Event::from_frame(&update),
+            Some(H2Event::WindowUpdate {
+                stream_id: 0,
+                increment: 1024,
+            })
+        );
+        let ping = Frame::new(0, FrameFlags::new(0), Payload::Ping(Ping::new([0; 8])));
+        assert_eq!(H2Event::from_frame(&ping), None);
+        assert_ne!(H2Direction::Sent, H2Direction::Received);
+    }
+}
@@ -26,6 +26,7 @@ use crate::runtime::{AsyncRead, BoundedSender, ReadBuf, ReadHalf, SendError};
 use crate::util::dispatcher::http2::{
     DispatchErrorKind, OutputMessage, SettingsState, SettingsSync,
 };
+use crate::util::h2::{observe, H2Direction, H2Observer};
 
 pub(crate) type OutputSendFut =
     Pin<Box<dyn Future<Output = Result<(), SendError<OutputMessage>>> + Send + Sync>>;
@@ -46,6 +47,7 @@ pub(crate) struct RecvData<S> {
     resp_tx: BoundedSender<OutputMessage>,
     curr_message: Option<OutputSendFut>,
     pending_iter: Option<FramesIntoIter>,
+    observer: Option<Arc<dyn H2Observer>>,
 }
 
 impl<S: AsyncRead + Unpin + Sync + Send + 'static> Future for RecvData<S> {
@@ -73,9 +75,15 @@ impl<S: AsyncRead + Unpin + Sync + Send + 'static> RecvData<S> {
             resp_tx,
             curr_message: None,
             pending_iter: None,
+            observer: None,
         }
     }
 
+    pub(crate) fn observer(mut self, observer: Option<Arc<dyn H2Observer>>) -> Self {
+        self.observer = observer;
+        self
+    }
+
     fn poll_read_frame(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), DispatchErrorKind>> {
         let mut buf = [0u8; 1024];
         loop {
@@ -171,6 +179,7 @@ impl<S: AsyncRead + Unpin + Sync + Send + 'static> RecvData<S> {
         while let Some(kind) = iter.next() {
             match kind {
                 FrameKind::Complete(frame) => {
+                    observe(self.observer.as_ref(), H2Direction::Received, &frame);
                     // TODO Whether to continue processing the remaining frames after connection
                     // error occurs in the Settings frame.
                     let message = if let Err(e) = self.update_settings(&frame) {
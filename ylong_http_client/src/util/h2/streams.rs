@@ -15,14 +15,15 @@
 
 use std::cmp::{min, Ordering};
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
-use ylong_http::h2::{Data, ErrorCode, Frame, FrameFlags, H2Error, Payload, StreamId};
+use ylong_http::h2::{Data, ErrorCode, Frame, FrameFlags, H2Error, Payload, Priority, StreamId};
 
 use crate::runtime::UnboundedSender;
 use crate::util::data_ref::BodyDataRef;
 use crate::util::dispatcher::http2::DispatchErrorKind;
-use crate::util::h2::buffer::{FlowControl, RecvWindow, SendWindow};
+use crate::util::h2::buffer::{FlowControl, RecvWindow, SendBuffer, SendWindow};
 
 pub(crate) const INITIAL_MAX_SEND_STREAM_ID: StreamId = u32::MAX >> 1;
 pub(crate) const INITIAL_MAX_RECV_STREAM_ID: StreamId = u32::MAX >> 1;
@@ -123,12 +124,14 @@ pub(crate) struct Stream {
     pub(crate) send_window: SendWindow,
     pub(crate) state: H2StreamState,
     pub(crate) header: Option<Frame>,
+    pub(crate) priority: Option<Frame>,
     pub(crate) data: BodyDataRef,
 }
 
 pub(crate) struct RequestWrapper {
     pub(crate) flag: FrameFlags,
     pub(crate) payload: Payload,
+    pub(crate) priority: Option<Priority>,
     pub(crate) data: BodyDataRef,
 }
 
@@ -137,7 +140,8 @@ pub(crate) struct Streams {
     pub(crate) max_send_id: StreamId,
     // Records the send goaway last_stream_id.
     pub(crate) max_recv_id: StreamId,
-    // Currently the client doesn't support push promise, so this value is always 0.
+    // The latest stream promised by the peer. The client refuses every push, so
+    // the frames received on such streams are ignored.
     pub(crate) latest_remote_id: StreamId,
     pub(crate) stream_recv_window_size: u32,
     pub(crate) stream_send_window_size: u32,
@@ -151,6 +155,8 @@ pub(crate) struct Streams {
     window_updating_streams: VecDeque<StreamId>,
     pub(crate) stream_map: HashMap<StreamId, Stream>,
     pub(crate) next_stream_id: StreamId,
+    // Bounds the body data of each stream waiting to be written.
+    send_buffer: Option<Arc<SendBuffer>>,
 }
 
 macro_rules! change_stream_state {
@@ -205,9 +211,14 @@ impl Streams {
             window_updating_streams: VecDeque::new(),
             stream_map: HashMap::new(),
             next_stream_id: 1,
+            send_buffer: None,
         }
     }
 
+    pub(crate) fn set_send_buffer(&mut self, send_buffer: Arc<SendBuffer>) {
+        self.send_buffer = Some(send_buffer);
+    }
+
     pub(crate) fn decrease_current_concurrency(&mut self) {
         self.current_concurrent_streams -= 1;
     }
@@ -414,6 +425,18 @@ impl Streams {
         }
     }
 
+    pub(crate) fn set_priority(&mut self, id: StreamId, priority: Frame) {
+        if let Some(stream) = self.stream_map.get_mut(&id) {
+            stream.priority = Some(priority);
+        }
+    }
+
+    pub(crate) fn priority(&mut self, id: StreamId) -> Option<Frame> {
+        self.stream_map
+            .get_mut(&id)
+            .and_then(|stream| stream.priority.take())
+    }
+
     pub(crate) fn poll_read_body(
         &mut self,
         cx: &mut Context<'_>,
@@ -440,7 +463,15 @@ impl Streams {
                         return Ok(DataReadState::Pending);
                     }
 
-                    let available = min(stream_send_vacant, conn_send_vacant);
+                    let mut available = min(stream_send_vacant, conn_send_vacant);
+                    if let Some(ref send_buffer) = self.send_buffer {
+                        let buffer_vacant = send_buffer.vacant(cx, id);
+                        if buffer_vacant == 0 {
+                            self.push_back_pending_send(id);
+                            return Ok(DataReadState::Pending);
+                        }
+                        available = min(available, buffer_vacant);
+                    }
                     let len = min(available, DEFAULT_MAX_FRAME_SIZE);
 
                     let mut buf = [0u8; DEFAULT_MAX_FRAME_SIZE];
@@ -466,6 +497,9 @@ impl Streams {
                 if size > 0 {
                     stream.send_window.send_data(size as u32);
                     self.flow_control.send_data(size as u32);
+                    if let Some(ref send_buffer) = self.send_buffer {
+                        send_buffer.push(id, size);
+                    }
                     let data_vec = Vec::from(&buf[..size]);
                     let flag = FrameFlags::new(0);
 
@@ -638,8 +672,22 @@ impl Streams {
         FrameRecvState::OK
     }
 
+    // Refuses the stream promised by a PUSH_PROMISE frame, which must be newer
+    // than the streams promised before.
+    pub(crate) fn refuse_push(&mut self, id: StreamId) -> Result<(), H2Error> {
+        if id & 1 == 1 || id <= self.latest_remote_id {
+            return Err(H2Error::ConnectionError(ErrorCode::ProtocolError));
+        }
+        self.latest_remote_id = id;
+        Ok(())
+    }
+
+    fn is_refused_push(&self, id: StreamId) -> bool {
+        id != 0 && id & 1 == 0 && id <= self.latest_remote_id
+    }
+
     pub(crate) fn recv_remote_reset(&mut self, id: StreamId) -> StreamEndState {
-        if id > self.max_recv_id {
+        if id > self.max_recv_id || self.is_refused_push(id) {
             return StreamEndState::Ignore;
         }
         return match self.stream_map.get_mut(&id) {
@@ -658,7 +706,7 @@ impl Streams {
     }
 
     pub(crate) fn recv_headers(&mut self, id: StreamId, eos: bool) -> FrameRecvState {
-        if id > self.max_recv_id {
+        if id > self.max_recv_id || self.is_refused_push(id) {
             return FrameRecvState::Ignore;
         }
 
@@ -698,7 +746,7 @@ impl Streams {
     }
 
     pub(crate) fn recv_data(&mut self, id: StreamId, eos: bool) -> FrameRecvState {
-        if id > self.max_recv_id {
+        if id > self.max_recv_id || self.is_refused_push(id) {
             return FrameRecvState::Ignore;
         }
         match self.stream_map.get_mut(&id) {
@@ -754,6 +802,7 @@ impl Stream {
             send_window,
             state: H2StreamState::Idle,
             header: Some(headers),
+            priority: None,
             data,
         }
     }
@@ -806,8 +855,11 @@ mod ut_h2streamstate {
 #[cfg(test)]
 mod ut_streams {
     use super::*;
+    use std::sync::Arc;
+
     use crate::async_impl::{Body, Request};
     use crate::request::RequestArc;
+    use crate::util::interceptor::IdleInterceptor;
 
     fn stream_new(state: H2StreamState) -> Stream {
         Stream {
@@ -815,9 +867,11 @@ mod ut_streams {
             recv_window: RecvWindow::new(100),
             state,
             header: None,
-            data: BodyDataRef::new(RequestArc::new(
-                Request::builder().body(Body::empty()).unwrap(),
-            )),
+            priority: None,
+            data: BodyDataRef::new(
+                RequestArc::new(Request::builder().body(Body::empty()).unwrap()),
+                Arc::new(IdleInterceptor),
+            ),
         }
     }
 
@@ -992,6 +1046,28 @@ mod ut_streams {
         assert_eq!(streams.send_local_reset(1), StreamEndState::OK);
     }
 
+    /// UT test case for `Streams::refuse_push`.
+    ///
+    /// # Brief
+    /// 1. Refuses pushes on several promised streams.
+    /// 2. Asserts that invalid or reused promised ids are errors.
+    /// 3. Asserts that frames on refused streams are ignored.
+    #[test]
+    fn ut_streams_refuse_push() {
+        let mut streams = Streams::new(1000, 1000, FlowControl::new(1000, 1000));
+        assert!(streams.refuse_push(2).is_ok());
+        assert!(streams.refuse_push(2).is_err());
+        assert!(streams.refuse_push(3).is_err());
+        assert!(streams.refuse_push(4).is_ok());
+        assert_eq!(streams.recv_headers(2, false), FrameRecvState::Ignore);
+        assert_eq!(streams.recv_data(4, true), FrameRecvState::Ignore);
+        assert_eq!(streams.recv_remote_reset(4), StreamEndState::Ignore);
+        assert_eq!(
+            streams.recv_headers(6, false),
+            FrameRecvState::Err(H2Error::ConnectionError(ErrorCode::ProtocolError))
+        );
+    }
+
     /// UT test case for `Streams::send_headers_frame`.
     ///
     /// # Brief
@@ -1047,6 +1123,55 @@ mod ut_streams {
         );
     }
 
+    /// UT test for `Streams::poll_read_body` with a `SendBuffer`.
+    ///
+    /// # Brief
+    /// 1. Reads the body of a stream whose send buffer is smaller than its
+    ///    send window.
+    /// 2. Asserts that the read waits until the queued data is released.
+    #[test]
+    fn ut_streams_poll_read_body_send_buffer() {
+        let waker = crate::async_impl::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let send_buffer = Arc::new(SendBuffer::new(30));
+        let mut streams = Streams::new(100, 100, FlowControl::new(100, 100));
+        streams.set_send_buffer(send_buffer.clone());
+        let mut stream = stream_new(H2StreamState::Open {
+            send: ActiveState::WaitData,
+            recv: ActiveState::WaitHeaders,
+        });
+        stream.data = BodyDataRef::new(
+            RequestArc::new(
+                Request::builder()
+                    .body(Body::slice(vec![b'a'; 50]))
+                    .unwrap(),
+            ),
+            Arc::new(IdleInterceptor),
+        );
+        streams.stream_map.insert(1, stream);
+
+        match streams.poll_read_body(&mut cx, 1).unwrap() {
+            DataReadState::Ready(frame) => match frame.payload() {
+                Payload::Data(data) => assert_eq!(data.data().len(), 30),
+                _ => panic!("Unexpected payload type"),
+            },
+            _ => panic!("The body is not read"),
+        }
+        assert!(matches!(
+            streams.poll_read_body(&mut cx, 1).unwrap(),
+            DataReadState::Pending
+        ));
+        assert_eq!(streams.next_pending_stream(), Some(1));
+        send_buffer.release(1, 30);
+        match streams.poll_read_body(&mut cx, 1).unwrap() {
+            DataReadState::Ready(frame) => match frame.payload() {
+                Payload::Data(data) => assert_eq!(data.data().len(), 20),
+                _ => panic!("Unexpected payload type"),
+            },
+            _ => panic!("The body is not read"),
+        }
+    }
+
     /// UT test for `Streams::recv_remote_reset`.
     ///
     /// # Brief
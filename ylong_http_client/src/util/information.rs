@@ -12,9 +12,12 @@
 // limitations under the License.
 
 use std::net::SocketAddr;
+#[cfg(feature = "async")]
+use std::time::Duration;
 
 #[cfg(feature = "http3")]
 use crate::async_impl::QuicConn;
+use crate::util::proxy::ProxyInfo;
 use crate::{ConnProtocol, TimeGroup};
 
 /// `ConnDetail` trait, which is used to obtain information about the current
@@ -42,6 +45,8 @@ pub struct ConnDetail {
     pub(crate) peer: SocketAddr,
     /// peer domain information.
     pub(crate) addr: String,
+    /// proxy which the connection goes through.
+    pub(crate) proxy: Option<Box<ProxyInfo>>,
 }
 
 impl ConnDetail {
@@ -85,12 +90,130 @@ impl NegotiateInfo {
     }
 }
 
+/// The TLS session negotiated on a connection.
+#[cfg(feature = "__tls")]
+#[derive(Clone, Debug, Default)]
+pub struct TlsInfo {
+    version: String,
+    cipher: String,
+    alpn: Option<Vec<u8>>,
+    peer_certs: Vec<PeerCert>,
+}
+
+#[cfg(feature = "__tls")]
+impl TlsInfo {
+    pub(crate) fn new(
+        version: &str,
+        cipher: &str,
+        alpn: Option<Vec<u8>>,
+        peer_certs: Vec<PeerCert>,
+    ) -> Self {
+        Self {
+            version: version.to_string(),
+            cipher: cipher.to_string(),
+            alpn,
+            peer_certs,
+        }
+    }
+
+    /// Gets the TLS protocol version, such as "TLSv1.3".
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Gets the name of the cipher suite, such as "TLS_AES_128_GCM_SHA256".
+    pub fn cipher(&self) -> &str {
+        &self.cipher
+    }
+
+    /// Gets the protocol selected by ALPN, such as `b"h2"`.
+    pub fn alpn(&self) -> Option<&[u8]> {
+        self.alpn.as_deref()
+    }
+
+    /// Gets the certificate chain sent by the server, the leaf certificate
+    /// comes first.
+    pub fn peer_certs(&self) -> &[PeerCert] {
+        &self.peer_certs
+    }
+}
+
+/// Summary of a certificate sent by the server.
+#[cfg(feature = "__tls")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PeerCert {
+    subject: String,
+    issuer: String,
+}
+
+#[cfg(feature = "__tls")]
+impl PeerCert {
+    pub(crate) fn new(subject: String, issuer: String) -> Self {
+        Self { subject, issuer }
+    }
+
+    /// Gets the subject name of the certificate, such as
+    /// "/C=US/O=Example/CN=example.com".
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    /// Gets the issuer name of the certificate.
+    pub fn issuer(&self) -> &str {
+        &self.issuer
+    }
+}
+
+/// The result of `Client::inspect`, which describes a connection established
+/// to a server without sending any request.
+#[cfg(feature = "async")]
+#[derive(Clone)]
+pub struct ConnectionReport {
+    data: ConnData,
+}
+
+#[cfg(feature = "async")]
+impl ConnectionReport {
+    pub(crate) fn new(data: ConnData) -> Self {
+        Self { data }
+    }
+
+    /// Gets the addresses of the connection.
+    pub fn detail(&self) -> &ConnDetail {
+        &self.data.detail
+    }
+
+    /// Whether the connection goes through a proxy.
+    pub fn is_proxy(&self) -> bool {
+        self.data.proxy
+    }
+
+    /// Gets the TLS session of the connection, `None` for `http`.
+    #[cfg(feature = "__tls")]
+    pub fn tls(&self) -> Option<&TlsInfo> {
+        self.data.tls.as_ref()
+    }
+
+    /// Gets the time spent on each phase of establishing the connection.
+    pub fn time_group(&self) -> &TimeGroup {
+        &self.data.time_group
+    }
+
+    /// Gets the round trip time to the peer, which is measured by the TCP
+    /// handshake. The peer is the proxy if the connection goes through one.
+    pub fn rtt(&self) -> Option<Duration> {
+        self.data.time_group.tcp_duration()
+    }
+}
+
 /// Transport layer connection establishment information data.
 #[derive(Clone)]
 pub struct ConnData {
     detail: ConnDetail,
     #[cfg(feature = "http2")]
     negotiate: NegotiateInfo,
+    #[cfg(feature = "__tls")]
+    tls: Option<TlsInfo>,
     proxy: bool,
     time_group: TimeGroup,
 }
@@ -124,6 +247,8 @@ impl ConnData {
 pub struct ConnDataBuilder {
     #[cfg(feature = "http2")]
     negotiate: NegotiateInfo,
+    #[cfg(feature = "__tls")]
+    tls: Option<TlsInfo>,
     proxy: bool,
     time_group: TimeGroup,
 }
@@ -136,6 +261,13 @@ impl ConnDataBuilder {
         self
     }
 
+    /// Sets the TLS session negotiated on the connection.
+    #[cfg(feature = "__tls")]
+    pub fn tls(mut self, tls: TlsInfo) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
     /// Sets whether the peer is a proxy.
     pub fn proxy(mut self, proxy: bool) -> Self {
         self.proxy = proxy;
@@ -154,6 +286,8 @@ impl ConnDataBuilder {
             detail,
             #[cfg(feature = "http2")]
             negotiate: self.negotiate,
+            #[cfg(feature = "__tls")]
+            tls: self.tls,
             proxy: self.proxy,
             time_group: self.time_group,
         }
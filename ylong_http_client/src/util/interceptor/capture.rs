@@ -0,0 +1,150 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Mutex};
+
+use crate::async_impl::Request;
+use crate::util::interceptor::Interceptor;
+use crate::HttpClientError;
+
+/// An `Interceptor` that captures the request body and the response body.
+///
+/// The bodies are captured as they are before encoding and after decoding,
+/// so a chunked body is captured without its chunk framing. At most `limit`
+/// bytes of each body are kept, the rest is discarded. The captured bodies
+/// are cleared when a new request is sent, so after a redirect only the
+/// last exchange is kept.
+///
+/// The response body is captured while it is being read, so it is complete
+/// only after the body has been read to the end.
+///
+/// # Examples
+///
+/// ```
+/// use ylong_http_client::async_impl::{Body, RequestBuilder};
+/// use ylong_http_client::BodyCapture;
+///
+/// let capture = BodyCapture::new(64 * 1024);
+/// let request = RequestBuilder::new()
+///     .url("http://www.example.com")
+///     .interceptor(capture.clone())
+///     .body(Body::slice("HelloWorld"));
+/// // Sends the request and reads the response body, then:
+/// let request_body = capture.request_body();
+/// let response_body = capture.response_body();
+/// ```
+#[derive(Clone)]
+pub struct BodyCapture {
+    inner: Arc<CaptureInner>,
+}
+
+struct CaptureInner {
+    limit: usize,
+    request: Mutex<Vec<u8>>,
+    response: Mutex<Vec<u8>>,
+}
+
+impl BodyCapture {
+    /// Creates a `BodyCapture` which keeps at most `limit` bytes of each body.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::BodyCapture;
+    ///
+    /// let capture = BodyCapture::new(1024);
+    /// ```
+    pub fn new(limit: usize) -> Self {
+        Self {
+            inner: Arc::new(CaptureInner {
+                limit,
+                request: Mutex::new(Vec::new()),
+                response: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Gets the captured request body.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::BodyCapture;
+    ///
+    /// let capture = BodyCapture::new(1024);
+    /// assert!(capture.request_body().is_empty());
+    /// ```
+    pub fn request_body(&self) -> Vec<u8> {
+        self.inner.request.lock().unwrap().clone()
+    }
+
+    /// Gets the captured response body.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ylong_http_client::BodyCapture;
+    ///
+    /// let capture = BodyCapture::new(1024);
+    /// assert!(capture.response_body().is_empty());
+    /// ```
+    pub fn response_body(&self) -> Vec<u8> {
+        self.inner.response.lock().unwrap().clone()
+    }
+
+    fn append(&self, body: &Mutex<Vec<u8>>, bytes: &[u8]) {
+        let mut body = body.lock().unwrap();
+        let size = self.inner.limit.saturating_sub(body.len()).min(bytes.len());
+        body.extend_from_slice(&bytes[..size]);
+    }
+}
+
+impl Interceptor for BodyCapture {
+    fn intercept_request(&self, _request: &Request) -> Result<(), HttpClientError> {
+        self.inner.request.lock().unwrap().clear();
+        self.inner.response.lock().unwrap().clear();
+        Ok(())
+    }
+
+    fn intercept_request_body(&self, bytes: &[u8]) -> Result<(), HttpClientError> {
+        self.append(&self.inner.request, bytes);
+        Ok(())
+    }
+
+    fn intercept_response_body(&self, bytes: &[u8]) -> Result<(), HttpClientError> {
+        self.append(&self.inner.response, bytes);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod ut_capture {
+    use crate::util::interceptor::{BodyCapture, Interceptor};
+
+    /// UT test cases for `BodyCapture`.
+    ///
+    /// # Brief
+    /// 1. Creates a `BodyCapture` with a limit.
+    /// 2. Calls `intercept_request_body` and `intercept_response_body`.
+    /// 3. Checks if the bodies are captured up to the limit.
+    #[test]
+    fn ut_body_capture() {
+        let capture = BodyCapture::new(8);
+        let interceptor = capture.clone();
+        interceptor.intercept_request_body(b"Hello").unwrap();
+        interceptor.intercept_request_body(b"World").unwrap();
+        interceptor.intercept_response_body(b"Hi").unwrap();
+        assert_eq!(capture.request_body(), b"HelloWor");
+        assert_eq!(capture.response_body(), b"Hi");
+    }
+}
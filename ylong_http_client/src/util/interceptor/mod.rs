@@ -13,6 +13,15 @@
 
 //! Http network interceptor.
 
+#[cfg(feature = "async")]
+mod capture;
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+#[cfg(feature = "async")]
+pub use capture::BodyCapture;
+
 use ylong_http::response::Response as HttpResp;
 
 use crate::async_impl::{HttpBody, Request, Response};
@@ -54,6 +63,25 @@ pub trait Interceptor {
         Ok(())
     }
 
+    /// Intercepts the raw bytes of an `HTTP/1` response status line and
+    /// headers, as they are received from the transport.
+    ///
+    /// If the response head fails to decode, the bytes received so far are
+    /// intercepted before the error is returned.
+    fn intercept_response_head(&self, _bytes: &[u8]) -> Result<(), HttpClientError> {
+        Ok(())
+    }
+
+    /// Intercepts the request body before it is encoded for the transport.
+    fn intercept_request_body(&self, _bytes: &[u8]) -> Result<(), HttpClientError> {
+        Ok(())
+    }
+
+    /// Intercepts the response body after it is decoded from the transport.
+    fn intercept_response_body(&self, _bytes: &[u8]) -> Result<(), HttpClientError> {
+        Ok(())
+    }
+
     /// Intercepts the Request that is eventually transmitted to the peer end.
     fn intercept_request(&self, _request: &Request) -> Result<(), HttpClientError> {
         Ok(())
@@ -87,3 +115,170 @@ pub trait Interceptor {
 pub(crate) struct IdleInterceptor;
 
 impl Interceptor for IdleInterceptor {}
+
+/// An Interceptor that runs two interceptors one after another.
+pub(crate) struct ChainInterceptor {
+    first: Arc<Interceptors>,
+    second: Arc<Interceptors>,
+}
+
+impl ChainInterceptor {
+    pub(crate) fn new(first: Arc<Interceptors>, second: Arc<Interceptors>) -> Self {
+        Self { first, second }
+    }
+}
+
+impl Interceptor for ChainInterceptor {
+    fn intercept_connection(&self, info: ConnDetail) -> Result<(), HttpClientError> {
+        self.first.intercept_connection(info.clone())?;
+        self.second.intercept_connection(info)
+    }
+
+    fn intercept_input(&self, bytes: &[u8]) -> Result<(), HttpClientError> {
+        self.first.intercept_input(bytes)?;
+        self.second.intercept_input(bytes)
+    }
+
+    fn intercept_output(&self, bytes: &[u8]) -> Result<(), HttpClientError> {
+        self.first.intercept_output(bytes)?;
+        self.second.intercept_output(bytes)
+    }
+
+    fn intercept_response_head(&self, bytes: &[u8]) -> Result<(), HttpClientError> {
+        self.first.intercept_response_head(bytes)?;
+        self.second.intercept_response_head(bytes)
+    }
+
+    fn intercept_request_body(&self, bytes: &[u8]) -> Result<(), HttpClientError> {
+        self.first.intercept_request_body(bytes)?;
+        self.second.intercept_request_body(bytes)
+    }
+
+    fn intercept_response_body(&self, bytes: &[u8]) -> Result<(), HttpClientError> {
+        self.first.intercept_response_body(bytes)?;
+        self.second.intercept_response_body(bytes)
+    }
+
+    fn intercept_request(&self, request: &Request) -> Result<(), HttpClientError> {
+        self.first.intercept_request(request)?;
+        self.second.intercept_request(request)
+    }
+
+    fn intercept_response(&self, response: &Response) -> Result<(), HttpClientError> {
+        self.first.intercept_response(response)?;
+        self.second.intercept_response(response)
+    }
+
+    fn intercept_retry(&self, error: &HttpClientError) -> Result<(), HttpClientError> {
+        self.first.intercept_retry(error)?;
+        self.second.intercept_retry(error)
+    }
+
+    fn intercept_redirect_request(&self, request: &Request) -> Result<(), HttpClientError> {
+        self.first.intercept_redirect_request(request)?;
+        self.second.intercept_redirect_request(request)
+    }
+
+    fn intercept_redirect_response(
+        &self,
+        response: &HttpResp<HttpBody>,
+    ) -> Result<(), HttpClientError> {
+        self.first.intercept_redirect_response(response)?;
+        self.second.intercept_redirect_response(response)
+    }
+}
+
+/// An Interceptor that counts the bytes written to and read from the transport
+/// layer io.
+#[derive(Default)]
+pub(crate) struct ByteCounter {
+    sent: AtomicU64,
+    received: AtomicU64,
+}
+
+impl ByteCounter {
+    pub(crate) fn sent(&self) -> u64 {
+        self.sent.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn received(&self) -> u64 {
+        self.received.load(Ordering::Relaxed)
+    }
+}
+
+impl Interceptor for ByteCounter {
+    fn intercept_input(&self, bytes: &[u8]) -> Result<(), HttpClientError> {
+        self.sent.fetch_add(bytes.len() as u64, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn intercept_output(&self, bytes: &[u8]) -> Result<(), HttpClientError> {
+        self.received
+            .fetch_add(bytes.len() as u64, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod ut_interceptor {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use crate::util::interceptor::{ChainInterceptor, Interceptor};
+    use crate::{ErrorKind, HttpClientError};
+
+    struct CountInterceptor {
+        count: Arc<AtomicUsize>,
+        reject: bool,
+    }
+
+    impl Interceptor for CountInterceptor {
+        fn intercept_output(&self, _bytes: &[u8]) -> Result<(), HttpClientError> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            if self.reject {
+                return Err(HttpClientError::from_str(ErrorKind::Other, "rejected"));
+            }
+            Ok(())
+        }
+    }
+
+    /// UT test cases for `ChainInterceptor`.
+    ///
+    /// # Brief
+    /// 1. Creates a `ChainInterceptor` with two interceptors.
+    /// 2. Calls `ChainInterceptor::intercept_output`.
+    /// 3. Checks if both interceptors run, and the second one is skipped
+    ///    when the first one fails.
+    #[test]
+    fn ut_chain_interceptor() {
+        let first = Arc::new(AtomicUsize::new(0));
+        let second = Arc::new(AtomicUsize::new(0));
+        let chain = ChainInterceptor::new(
+            Arc::new(CountInterceptor {
+                count: first.clone(),
+                reject: false,
+            }),
+            Arc::new(CountInterceptor {
+                count: second.clone(),
+                reject: false,
+            }),
+        );
+        assert!(chain.intercept_output(b"bytes").is_ok());
+        assert_eq!(first.load(Ordering::SeqCst), 1);
+        assert_eq!(second.load(Ordering::SeqCst), 1);
+
+        let chain = ChainInterceptor::new(
+            Arc::new(CountInterceptor {
+                count: first.clone(),
+                reject: true,
+            }),
+            Arc::new(CountInterceptor {
+                count: second.clone(),
+                reject: false,
+            }),
+        );
+        assert!(chain.intercept_output(b"bytes").is_err());
+        assert_eq!(first.load(Ordering::SeqCst), 2);
+        assert_eq!(second.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! MD5 digest.
+//!
+//! Used to compute the `MD5` responses of HTTP Digest authentication, which
+//! the server may still require, and the `Content-MD5` of a request body.
+//!
+//! The digest of OpenSSL is used when TLS is compiled. Otherwise the simple
+//! implementation below is used, so that HTTP Digest authentication works
+//! without OpenSSL. It is checked against the RFC1321 test vectors and
+//! against OpenSSL.
+
+#[cfg(any(not(feature = "__tls"), test))]
+const SHIFTS: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9,
+    14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10, 15,
+    21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+#[cfg(feature = "__tls")]
+pub(crate) fn digest(input: &[u8]) -> [u8; 16] {
+    use crate::util::c_openssl::ffi::x509::EVP_md5;
+
+    crate::util::c_openssl::evp_digest(EVP_md5, input)
+}
+
+#[cfg(not(feature = "__tls"))]
+pub(crate) fn digest(input: &[u8]) -> [u8; 16] {
+    fallback(input)
+}
+
+#[cfg(any(not(feature = "__tls"), test))]
+fn fallback(input: &[u8]) -> [u8; 16] {
+    let mut state: [u32; 4] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476];
+
+    let bit_len = (input.len() as u64).wrapping_mul(8);
+    let mut message = input.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&bit_len.to_le_bytes());
+
+    for block in message.chunks(64) {
+        let mut words = [0u32; 16];
+        for (i, word) in block.chunks(4).enumerate() {
+            words[i] = u32::from_le_bytes([word[0], word[1], word[2], word[3]]);
+        }
+
+        let [mut a, mut b, mut c, mut d] = state;
+        for (i, shift) in SHIFTS.iter().enumerate() {
+            let (f, g) = match i {
+                0..=15 => ((b & c) | (!b & d), i),
+                16..=31 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+                32..=47 => (b ^ c ^ d, (3 * i + 5) % 16),
+                _ => (c ^ (b | !d), (7 * i) % 16),
+            };
+            // The constants are the integer parts of abs(sin(i + 1)) * 2^32.
+            let k = ((i as f64 + 1.0).sin().abs() * 4294967296.0) as u32;
+            let temp = d;
+            d = c;
+            c = b;
+            b = b.wrapping_add(
+                a.wrapping_add(f)
+                    .wrapping_add(k)
+                    .wrapping_add(words[g])
+                    .rotate_left(*shift),
+            );
+            a = temp;
+        }
+
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+    }
+
+    let mut output = [0u8; 16];
+    for (chunk, word) in output.chunks_mut(4).zip(state.iter()) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    output
+}
+
+#[cfg(test)]
+mod ut_util_md5 {
+    use crate::util::md5::{digest, fallback};
+
+    fn hex(bytes: [u8; 16]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    /// UT test cases for `md5::digest`.
+    ///
+    /// # Brief
+    /// 1. Calls `digest` and the fallback implementation with inputs of
+    ///    different lengths.
+    /// 2. Checks if the results are the same as the RFC1321 test vectors.
+    #[test]
+    fn ut_util_md5_digest() {
+        let million = vec![b'a'; 1_000_000];
+        let vectors: [(&str, &[u8]); 5] = [
+            ("d41d8cd98f00b204e9800998ecf8427e", b""),
+            ("900150983cd24fb0d6963f7d28e17f72", b"abc"),
+            (
+                "c3fcd3d76192e4007dfb496cca67e13b",
+                b"abcdefghijklmnopqrstuvwxyz",
+            ),
+            (
+                "57edf4a22be3c955ac49da2e2107b67a",
+                b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+            ),
+            ("7707d6ae4e027c70eea2a935c2296f21", &million),
+        ];
+        for (expected, input) in vectors {
+            assert_eq!(hex(digest(input)), expected);
+            assert_eq!(hex(fallback(input)), expected);
+        }
+    }
+
+    /// UT test cases for the fallback implementation of `md5::digest`.
+    ///
+    /// # Brief
+    /// 1. Calls `digest` of OpenSSL and the fallback implementation with
+    ///    inputs of all lengths around the padding boundaries of a block.
+    /// 2. Checks if the results are the same.
+    #[cfg(feature = "__tls")]
+    #[test]
+    fn ut_util_md5_fallback() {
+        let input = (0..=255u8).cycle().take(300).collect::<Vec<u8>>();
+        for len in 0..input.len() {
+            assert_eq!(digest(&input[..len]), fallback(&input[..len]));
+        }
+    }
+}
@@ -20,14 +20,31 @@
 //! -[`HttpVersion`] is used to provide Http Version.
 
 pub(crate) mod base64;
+#[cfg(feature = "async")]
+pub(crate) mod body_digest;
+#[cfg(feature = "async")]
+pub(crate) mod clock;
+#[cfg(feature = "async")]
+pub(crate) mod compress;
 pub(crate) mod config;
+#[cfg(feature = "async")]
+pub(crate) mod decompress;
+#[cfg(any(feature = "__tls", feature = "auth"))]
+pub(crate) mod digest_auth;
+pub(crate) mod features;
 pub(crate) mod normalizer;
+#[cfg(feature = "async")]
+pub(crate) mod pacer;
 pub(crate) mod pool;
 pub(crate) mod proxy;
 pub(crate) mod redirect;
 
 #[cfg(feature = "async")]
 pub(crate) mod request;
+#[cfg(all(feature = "async", feature = "http1_1"))]
+pub(crate) mod sha1;
+#[cfg(any(feature = "__tls", feature = "async"))]
+pub(crate) mod sha256;
 
 #[cfg(feature = "__tls")]
 pub(crate) mod c_openssl;
@@ -45,22 +62,40 @@ pub(crate) mod h2;
 pub(crate) mod h3;
 pub(crate) mod information;
 pub(crate) mod interceptor;
+#[cfg(any(feature = "__tls", feature = "async"))]
+pub(crate) mod md5;
 pub(crate) mod monitor;
-#[cfg(all(test, feature = "ylong_base"))]
+#[cfg(test)]
 pub(crate) mod test_utils;
 
+#[cfg(feature = "async")]
+pub use body_digest::DigestAlgorithm;
+#[cfg(feature = "async")]
+pub use clock::{Clock, SleepFuture};
 #[cfg(feature = "__tls")]
 pub use c_openssl::{
     Cert, Certificate, PubKeyPins, PubKeyPinsBuilder, TlsConfig, TlsConfigBuilder, TlsFileType,
     TlsVersion,
 };
+#[cfg(feature = "async")]
+pub use compress::Encoding;
 #[cfg(feature = "__tls")]
 pub(crate) use config::{AlpnProtocol, AlpnProtocolList};
 #[cfg(feature = "__tls")]
-pub use config::{CertVerifier, ServerCerts};
+pub use config::{CertVerifier, ServerCerts, VerifyResult};
 pub use config::{Proxy, ProxyBuilder, Redirect, Retry, SpeedLimit, Timeout};
+pub use features::{features, Features};
 #[cfg(all(feature = "async", feature = "ylong_base", feature = "http2"))]
 pub(crate) use h2::{split, Reader, Writer};
+#[cfg(feature = "http2")]
+pub use h2::{H2Direction, H2Event, H2Observer};
+#[cfg(feature = "async")]
+pub use information::ConnectionReport;
 pub use information::{ConnData, ConnDataBuilder, ConnDetail, ConnInfo, NegotiateInfo};
+#[cfg(feature = "__tls")]
+pub use information::{PeerCert, TlsInfo};
+#[cfg(feature = "async")]
+pub use interceptor::BodyCapture;
 pub use interceptor::{ConnProtocol, Interceptor};
 pub use monitor::TimeGroup;
+pub use proxy::ProxyInfo;
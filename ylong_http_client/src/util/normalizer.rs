@@ -11,6 +11,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::borrow::Cow;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(any(feature = "http2", feature = "http3"))]
+use ylong_http::error::HttpError;
+#[cfg(any(feature = "http2", feature = "http3"))]
+use ylong_http::headers::Headers;
 use ylong_http::request::method::Method;
 use ylong_http::request::uri::{Scheme, Uri};
 use ylong_http::request::Request;
@@ -22,11 +29,47 @@ use crate::error::{ErrorKind, HttpClientError};
 
 pub(crate) struct RequestFormatter<'a, T> {
     part: &'a mut Request<T>,
+    // The `Accept` value inserted when the request has no `Accept` header.
+    accept: Option<&'a str>,
+    // Whether to insert `TE: trailers` when the request has no `TE` header.
+    te_trailers: bool,
+    // Whether to insert the current `Date` when the request has no `Date`
+    // header.
+    date: bool,
+    // Whether to insert the decompressible codings when the request has no
+    // `Accept-Encoding` header.
+    accept_encoding: bool,
 }
 
 impl<'a, T> RequestFormatter<'a, T> {
     pub(crate) fn new(part: &'a mut Request<T>) -> Self {
-        Self { part }
+        Self {
+            part,
+            accept: Some("*/*"),
+            te_trailers: false,
+            date: false,
+            accept_encoding: false,
+        }
+    }
+
+    pub(crate) fn default_accept(mut self, accept: Option<&'a str>) -> Self {
+        self.accept = accept;
+        self
+    }
+
+    pub(crate) fn te_trailers(mut self, te_trailers: bool) -> Self {
+        self.te_trailers = te_trailers;
+        self
+    }
+
+    pub(crate) fn date(mut self, date: bool) -> Self {
+        self.date = date;
+        self
+    }
+
+    pub(crate) fn accept_encoding(mut self, accept_encoding: bool) -> Self {
+        self.accept_encoding = accept_encoding;
+        self
     }
 
     pub(crate) fn format(&mut self) -> Result<(), HttpClientError> {
@@ -42,8 +85,26 @@ impl<'a, T> RequestFormatter<'a, T> {
 
         let host_value = format_host_value(self.part.uri())?;
 
-        if self.part.headers_mut().get("Accept").is_none() {
-            let _ = self.part.headers_mut().insert("Accept", "*/*");
+        if let Some(accept) = self.accept {
+            if self.part.headers_mut().get("Accept").is_none() {
+                let _ = self.part.headers_mut().insert("Accept", accept);
+            }
+        }
+
+        if self.te_trailers && self.part.headers_mut().get("TE").is_none() {
+            let _ = self.part.headers_mut().insert("TE", "trailers");
+        }
+
+        if self.accept_encoding && self.part.headers_mut().get("Accept-Encoding").is_none() {
+            let _ = self
+                .part
+                .headers_mut()
+                .insert("Accept-Encoding", "gzip, deflate");
+        }
+
+        if self.date && self.part.headers_mut().get("Date").is_none() {
+            let date = http_date(SystemTime::now());
+            let _ = self.part.headers_mut().insert("Date", date.as_str());
         }
 
         let _ = self
@@ -53,6 +114,92 @@ impl<'a, T> RequestFormatter<'a, T> {
 
         Ok(())
     }
+
+    // Formats the request for the version of the connection it is sent on.
+    // The headers only meaningful to `HTTP/1` are removed on `HTTP/2` and
+    // `HTTP/3` connections.
+    #[cfg(any(feature = "http2", feature = "http3"))]
+    pub(crate) fn format_for(&mut self, version: &Version) -> Result<(), HttpClientError> {
+        if *version == Version::HTTP2 || *version == Version::HTTP3 {
+            remove_connection_specific_headers(self.part.headers_mut())
+                .map_err(|e| HttpClientError::from_error(ErrorKind::Request, e))?;
+        }
+        Ok(())
+    }
+}
+
+// Illegal headers validation in http2 and http3.
+// [`Connection-Specific Headers`] implementation.
+//
+// [`Connection-Specific Headers`]: https://www.rfc-editor.org/rfc/rfc9113.html#name-connection-specific-header-
+#[cfg(any(feature = "http2", feature = "http3"))]
+pub(crate) fn remove_connection_specific_headers(headers: &mut Headers) -> Result<(), HttpError> {
+    const CONNECTION_SPECIFIC_HEADERS: &[&str; 5] = &[
+        "connection",
+        "keep-alive",
+        "proxy-connection",
+        "upgrade",
+        "transfer-encoding",
+    ];
+    // The headers listed in `Connection` are connection-specific as well. `TE`
+    // is checked below, since `TE: trailers` is still allowed.
+    if let Some(connection) = headers.get("connection") {
+        let options = connection.to_string()?;
+        for option in options.split(',').map(str::trim) {
+            if !option.is_empty() && !option.eq_ignore_ascii_case("te") {
+                headers.remove(option);
+            }
+        }
+    }
+    for specific_header in CONNECTION_SPECIFIC_HEADERS.iter() {
+        headers.remove(*specific_header);
+    }
+
+    if let Some(te_ref) = headers.get("te") {
+        let te = te_ref.to_string()?;
+        if te.as_str() != "trailers" {
+            headers.remove("te");
+        }
+    }
+    Ok(())
+}
+
+// Formats the time as an `IMF-fixdate`, such as `Sun, 06 Nov 1994 08:49:37
+// GMT`.
+pub(crate) fn http_date(time: SystemTime) -> String {
+    const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    let (days, secs) = (secs / 86400, secs % 86400);
+
+    // Converts the days since 1970-01-01 to a civil date, counting years from
+    // March so that the leap day is the last day of a year.
+    let days_from_0000 = days + 719468;
+    let era = days_from_0000 / 146097;
+    let day_of_era = days_from_0000 % 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = (month_from_march + 2) % 12;
+    let year = era * 400 + year_of_era + u64::from(month < 2);
+
+    format!(
+        "{}, {:02} {} {} {:02}:{:02}:{:02} GMT",
+        WEEKDAYS[(days % 7) as usize],
+        day,
+        MONTHS[month as usize],
+        year,
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
 }
 
 pub(crate) struct UriFormatter;
@@ -123,16 +270,7 @@ impl<'a> BodyLengthParser<'a> {
     }
 
     pub(crate) fn parse(&self) -> Result<BodyLength, HttpClientError> {
-        if self.part.status.is_informational()
-            || self.part.status == StatusCode::NO_CONTENT
-            || self.part.status == StatusCode::NOT_MODIFIED
-        {
-            return Ok(BodyLength::Empty);
-        }
-
-        if (self.req_method == &Method::CONNECT && self.part.status.is_successful())
-            || self.req_method == &Method::HEAD
-        {
+        if self.is_bodiless() {
             return Ok(BodyLength::Empty);
         }
 
@@ -175,6 +313,26 @@ impl<'a> BodyLengthParser<'a> {
 
         Ok(BodyLength::UntilClose)
     }
+
+    // Checks whether the response carries both `Transfer-Encoding` and
+    // `Content-Length`. Such a response may be used for request smuggling, so
+    // the connection must be closed after it. Responses without a body, such
+    // as responses to `HEAD`, never read those headers for framing.
+    #[cfg(feature = "http1_1")]
+    pub(crate) fn is_framing_ambiguous(&self) -> bool {
+        !self.is_bodiless()
+            && self.part.headers.get("Transfer-Encoding").is_some()
+            && self.part.headers.get("Content-Length").is_some()
+    }
+
+    // Checks whether the response never has a body regardless of its headers.
+    fn is_bodiless(&self) -> bool {
+        self.part.status.is_informational()
+            || self.part.status == StatusCode::NO_CONTENT
+            || self.part.status == StatusCode::NOT_MODIFIED
+            || (self.req_method == &Method::CONNECT && self.part.status.is_successful())
+            || self.req_method == &Method::HEAD
+    }
 }
 #[derive(PartialEq, Debug)]
 pub(crate) enum BodyLength {
@@ -182,6 +340,8 @@ pub(crate) enum BodyLength {
     Chunk,
     Length(u64),
     Empty,
+    // The body is delimited by the close of the connection, so the connection
+    // can't be reused after it.
     UntilClose,
 }
 
@@ -193,12 +353,12 @@ pub(crate) fn format_host_value(uri: &Uri) -> Result<String, HttpClientError> {
                 .map_err(|e| HttpClientError::from_error(ErrorKind::Request, e))?
                 == uri.scheme().unwrap_or(&Scheme::HTTP).default_port()
             {
-                host.to_string()
+                strip_zone_id(host.as_str()).to_string()
             } else {
-                uri.authority().unwrap().to_str()
+                format!("{}:{}", strip_zone_id(host.as_str()), port.as_str())
             }
         }
-        (Some(host), None) => host.to_string(),
+        (Some(host), None) => strip_zone_id(host.as_str()).to_string(),
         (None, _) => {
             return err_from_msg!(Request, "Request Uri lack host");
         }
@@ -206,6 +366,15 @@ pub(crate) fn format_host_value(uri: &Uri) -> Result<String, HttpClientError> {
     Ok(host_value)
 }
 
+// Removes the zone id from an IPv6 literal like `[fe80::1%eth0]`, since it is
+// only meaningful to the local host.
+fn strip_zone_id(host: &str) -> Cow<'_, str> {
+    match (host.starts_with('['), host.find('%')) {
+        (true, Some(idx)) => Cow::Owned(format!("{}]", &host[..idx])),
+        _ => Cow::Borrowed(host),
+    }
+}
+
 #[cfg(test)]
 mod ut_normalizer {
     use ylong_http::h1::ResponseDecoder;
@@ -215,7 +384,7 @@ mod ut_normalizer {
 
     use crate::normalizer::UriFormatter;
     use crate::util::normalizer::{
-        format_host_value, BodyLength, BodyLengthParser, RequestFormatter,
+        format_host_value, http_date, BodyLength, BodyLengthParser, RequestFormatter,
     };
 
     /// UT test cases for `UriFormatter::format`.
@@ -261,6 +430,160 @@ mod ut_normalizer {
         assert_eq!(res.to_string().unwrap().as_bytes(), b"example1.com");
     }
 
+    /// UT test cases for `RequestFormatter::format_for`.
+    ///
+    /// # Brief
+    /// 1. Creates a request with connection-specific headers, including a
+    ///    header listed in `Connection`.
+    /// 2. Calls `RequestFormatter::format_for` with `HTTP/1.1` and `HTTP/2`.
+    /// 3. Checks if the headers are only removed for `HTTP/2`, and that
+    ///    `TE: trailers` is kept.
+    #[cfg(feature = "http2")]
+    #[test]
+    fn ut_request_format_for_version() {
+        use ylong_http::version::Version;
+
+        let mut request = Request::new("");
+        let headers = request.headers_mut();
+        headers.insert("Connection", "keep-alive").unwrap();
+        headers.insert("Transfer-Encoding", "chunked").unwrap();
+        headers.insert("TE", "trailers").unwrap();
+        headers.insert("X-Hop", "1").unwrap();
+        headers.append("Connection", "X-Hop, TE").unwrap();
+
+        let mut formatter = RequestFormatter::new(&mut request);
+        formatter.format_for(&Version::HTTP1_1).unwrap();
+        assert!(request.headers().get("Connection").is_some());
+        assert!(request.headers().get("Transfer-Encoding").is_some());
+        assert!(request.headers().get("X-Hop").is_some());
+
+        let mut formatter = RequestFormatter::new(&mut request);
+        formatter.format_for(&Version::HTTP2).unwrap();
+        assert!(request.headers().get("Connection").is_none());
+        assert!(request.headers().get("Transfer-Encoding").is_none());
+        assert!(request.headers().get("X-Hop").is_none());
+        assert!(request.headers().get("TE").is_some());
+    }
+
+    /// UT test cases for `RequestFormatter::default_accept`.
+    ///
+    /// # Brief
+    /// 1. Creates a `RequestFormatter` with different default `Accept` values.
+    /// 2. Calls `RequestFormatter::format`.
+    /// 3. Checks if the request's `Accept` header is correct.
+    #[test]
+    fn ut_request_format_default_accept() {
+        let accept = |request: &Request<&str>| {
+            request
+                .headers()
+                .get("Accept")
+                .map(|v| v.to_string().unwrap())
+        };
+
+        let mut request = Request::new("");
+        *request.uri_mut() = Uri::from_bytes(b"http://example1.com").unwrap();
+        RequestFormatter::new(&mut request).format().unwrap();
+        assert_eq!(accept(&request), Some(String::from("*/*")));
+
+        let mut request = Request::new("");
+        *request.uri_mut() = Uri::from_bytes(b"http://example1.com").unwrap();
+        RequestFormatter::new(&mut request)
+            .default_accept(Some("application/json"))
+            .format()
+            .unwrap();
+        assert_eq!(accept(&request), Some(String::from("application/json")));
+
+        let mut request = Request::new("");
+        *request.uri_mut() = Uri::from_bytes(b"http://example1.com").unwrap();
+        RequestFormatter::new(&mut request)
+            .default_accept(None)
+            .format()
+            .unwrap();
+        assert_eq!(accept(&request), None);
+
+        let mut request = Request::new("");
+        *request.uri_mut() = Uri::from_bytes(b"http://example1.com").unwrap();
+        request.headers_mut().insert("Accept", "text/html").unwrap();
+        RequestFormatter::new(&mut request)
+            .default_accept(Some("application/json"))
+            .format()
+            .unwrap();
+        assert_eq!(accept(&request), Some(String::from("text/html")));
+    }
+
+    /// UT test cases for `RequestFormatter::date`.
+    ///
+    /// # Brief
+    /// 1. Formats requests with and without a `Date` header.
+    /// 2. Formats times as `IMF-fixdate`s.
+    /// 3. Checks that only a missing `Date` header is inserted.
+    #[test]
+    fn ut_request_format_date() {
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let mut request = Request::new("");
+        *request.uri_mut() = Uri::from_bytes(b"http://example1.com").unwrap();
+        RequestFormatter::new(&mut request).format().unwrap();
+        assert!(request.headers().get("Date").is_none());
+        RequestFormatter::new(&mut request)
+            .date(true)
+            .format()
+            .unwrap();
+        let date = request.headers().get("Date").unwrap().to_string().unwrap();
+        assert!(date.ends_with(" GMT") && date.len() == 29);
+
+        let mut request = Request::new("");
+        *request.uri_mut() = Uri::from_bytes(b"http://example1.com").unwrap();
+        let fixed = "Sun, 06 Nov 1994 08:49:37 GMT";
+        request.headers_mut().insert("Date", fixed).unwrap();
+        RequestFormatter::new(&mut request)
+            .date(true)
+            .format()
+            .unwrap();
+        let date = request.headers().get("Date").unwrap().to_string().unwrap();
+        assert_eq!(date, fixed);
+
+        let at = |secs| http_date(UNIX_EPOCH + Duration::from_secs(secs));
+        assert_eq!(at(0), "Thu, 01 Jan 1970 00:00:00 GMT");
+        assert_eq!(at(784111777), fixed);
+        assert_eq!(at(951782400), "Tue, 29 Feb 2000 00:00:00 GMT");
+        assert_eq!(at(4102444799), "Thu, 31 Dec 2099 23:59:59 GMT");
+    }
+
+    /// UT test cases for `RequestFormatter::te_trailers`.
+    ///
+    /// # Brief
+    /// 1. Creates a `RequestFormatter` with `te_trailers` set.
+    /// 2. Calls `RequestFormatter::format`.
+    /// 3. Checks if the request's `TE` header is correct.
+    #[test]
+    fn ut_request_format_te_trailers() {
+        let te =
+            |request: &Request<&str>| request.headers().get("TE").map(|v| v.to_string().unwrap());
+
+        let mut request = Request::new("");
+        *request.uri_mut() = Uri::from_bytes(b"http://example1.com").unwrap();
+        RequestFormatter::new(&mut request).format().unwrap();
+        assert_eq!(te(&request), None);
+
+        let mut request = Request::new("");
+        *request.uri_mut() = Uri::from_bytes(b"http://example1.com").unwrap();
+        RequestFormatter::new(&mut request)
+            .te_trailers(true)
+            .format()
+            .unwrap();
+        assert_eq!(te(&request), Some(String::from("trailers")));
+
+        let mut request = Request::new("");
+        *request.uri_mut() = Uri::from_bytes(b"http://example1.com").unwrap();
+        request.headers_mut().insert("TE", "gzip").unwrap();
+        RequestFormatter::new(&mut request)
+            .te_trailers(true)
+            .format()
+            .unwrap();
+        assert_eq!(te(&request), Some(String::from("gzip")));
+    }
+
     /// UT test cases for `BodyLengthParser::parse`.
     ///
     /// # Brief
@@ -310,6 +633,38 @@ mod ut_normalizer {
             .is_err());
     }
 
+    /// UT test cases for `BodyLengthParser::is_framing_ambiguous`.
+    ///
+    /// # Brief
+    /// 1. Creates `BodyLengthParser`s for `GET` and `HEAD` with a response
+    ///    which contains both `Transfer-Encoding` and `Content-Length`.
+    /// 2. Creates a `BodyLengthParser` with a response which contains only
+    ///    `Transfer-Encoding`.
+    /// 3. Checks if the results are correct.
+    #[test]
+    fn ut_body_length_parser_framing_ambiguous() {
+        let response_str =
+            "HTTP/1.1 200 \r\nTransfer-Encoding: chunked\r\nContent-Length: 20\r\n\r\n".as_bytes();
+        let mut decoder = ResponseDecoder::new();
+        let result = decoder.decode(response_str).unwrap().unwrap();
+        let method = Method::GET;
+        let body_len_parser = BodyLengthParser::new(&method, &result.0);
+        assert_eq!(body_len_parser.parse().unwrap(), BodyLength::Chunk);
+        assert!(body_len_parser.is_framing_ambiguous());
+
+        let method = Method::HEAD;
+        let body_len_parser = BodyLengthParser::new(&method, &result.0);
+        assert_eq!(body_len_parser.parse().unwrap(), BodyLength::Empty);
+        assert!(!body_len_parser.is_framing_ambiguous());
+
+        let response_str = "HTTP/1.1 200 \r\nTransfer-Encoding: chunked\r\n\r\n".as_bytes();
+        let mut decoder = ResponseDecoder::new();
+        let result = decoder.decode(response_str).unwrap().unwrap();
+        let method = Method::GET;
+        let body_len_parser = BodyLengthParser::new(&method, &result.0);
+        assert!(!body_len_parser.is_framing_ambiguous());
+    }
+
     /// UT test cases for function `format_host_value`.
     ///
     /// # Brief
@@ -333,4 +688,25 @@ mod ut_normalizer {
         let uri = Uri::from_bytes(b"www.example.com").expect("Uri parse failed");
         assert_eq!(format_host_value(&uri).unwrap(), "www.example.com");
     }
+
+    /// UT test cases for `format_host_value` with IPv6 literals.
+    ///
+    /// # Brief
+    /// 1. Creates `Uri`s with bracketed IPv6 hosts, with and without a port
+    ///    and a zone id.
+    /// 2. Calls `format_host_value`.
+    /// 3. Checks that brackets are kept and the zone id is stripped.
+    #[test]
+    fn ut_format_host_value_ipv6() {
+        let uri = Uri::from_bytes(b"http://[fe80::1]").expect("Uri parse failed");
+        assert_eq!(format_host_value(&uri).unwrap(), "[fe80::1]");
+        let uri = Uri::from_bytes(b"http://[fe80::1]:8080/").expect("Uri parse failed");
+        assert_eq!(format_host_value(&uri).unwrap(), "[fe80::1]:8080");
+        let uri = Uri::from_bytes(b"http://[fe80::1%eth0]:8080/").expect("Uri parse failed");
+        assert_eq!(format_host_value(&uri).unwrap(), "[fe80::1]:8080");
+        let uri = Uri::from_bytes(b"http://[fe80::1%25eth0]:80/").expect("Uri parse failed");
+        assert_eq!(format_host_value(&uri).unwrap(), "[fe80::1]");
+        let uri = Uri::from_bytes(b"http://[fe80::1%eth0]").expect("Uri parse failed");
+        assert_eq!(format_host_value(&uri).unwrap(), "[fe80::1]");
+    }
 }
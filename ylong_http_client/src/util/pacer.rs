@@ -0,0 +1,72 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rate limit of the transfer of a body.
+
+use std::time::{Duration, Instant};
+
+/// Paces the transfer of a body to at most `rate` bytes per second on average
+/// since the pacer is created.
+pub(crate) struct RatePacer {
+    rate: u64,
+    start: Instant,
+    transferred: u64,
+}
+
+impl RatePacer {
+    pub(crate) fn new(rate: u64) -> Self {
+        Self {
+            rate,
+            start: Instant::now(),
+            transferred: 0,
+        }
+    }
+
+    /// Records `size` transferred bytes and returns how long to wait before
+    /// the next transfer.
+    pub(crate) fn delay(&mut self, size: usize) -> Option<Duration> {
+        self.transferred += size as u64;
+        let expected = Duration::from_secs_f64(self.transferred as f64 / self.rate as f64);
+        expected
+            .checked_sub(self.start.elapsed())
+            .filter(|delay| !delay.is_zero())
+    }
+}
+
+#[cfg(test)]
+mod ut_pacer {
+    use std::time::Duration;
+
+    use crate::util::pacer::RatePacer;
+
+    /// UT test cases for `RatePacer::delay`.
+    ///
+    /// # Brief
+    /// 1. Creates a `RatePacer` with a rate of 1000 bytes per second.
+    /// 2. Records transferred bytes.
+    /// 3. Checks that the delays keep the average rate under the limit.
+    #[test]
+    fn ut_rate_pacer_delay() {
+        let mut pacer = RatePacer::new(1000);
+        let delay = pacer.delay(500).unwrap();
+        assert!(delay > Duration::from_millis(400) && delay <= Duration::from_millis(500));
+        let delay = pacer.delay(1500).unwrap();
+        assert!(delay > Duration::from_millis(1900) && delay <= Duration::from_secs(2));
+        assert!(pacer.delay(0).is_some());
+
+        pacer.start -= Duration::from_secs(3);
+        assert!(pacer.delay(0).is_none());
+        let delay = pacer.delay(2000).unwrap();
+        assert!(delay > Duration::from_millis(900) && delay <= Duration::from_secs(1));
+    }
+}
@@ -16,10 +16,13 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::hash::Hash;
+use std::net::SocketAddr;
 use std::sync::{Arc, Mutex};
 
 use ylong_http::request::uri::{Authority, Scheme};
 
+use crate::util::config::HttpVersion;
+
 pub(crate) struct Pool<K, V> {
     pool: Arc<Mutex<HashMap<K, V>>>,
 }
@@ -46,19 +49,68 @@ impl<K: Eq + Hash, V: Clone> Pool<K, V> {
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
-pub(crate) struct PoolKey(Scheme, Authority);
+// The fourth field is whether the connections skip the verification of the
+// server, which must not be shared with verified requests. The fifth field is
+// the version requested, connections of different versions are never shared.
+// The last field is the address the connections are pinned to instead of the
+// resolved ones.
+pub(crate) struct PoolKey(
+    Scheme,
+    Authority,
+    Route,
+    bool,
+    HttpVersion,
+    Option<SocketAddr>,
+);
 
 impl PoolKey {
     pub(crate) fn new(scheme: Scheme, authority: Authority) -> Self {
-        Self(scheme, authority)
+        Self(
+            scheme,
+            authority,
+            Route::Default,
+            false,
+            HttpVersion::Negotiate,
+            None,
+        )
+    }
+
+    pub(crate) fn route(mut self, route: Route) -> Self {
+        self.2 = route;
+        self
+    }
+
+    pub(crate) fn unverified(mut self, unverified: bool) -> Self {
+        self.3 = unverified;
+        self
+    }
+
+    pub(crate) fn version(mut self, version: HttpVersion) -> Self {
+        self.4 = version;
+        self
     }
+
+    pub(crate) fn resolve_to(mut self, addr: Option<SocketAddr>) -> Self {
+        self.5 = addr;
+        self
+    }
+}
+
+/// The way connections of a `PoolKey` reach the server.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub(crate) enum Route {
+    // Through the proxies of the client.
+    Default,
+    Direct,
+    Proxy(Scheme, Authority),
 }
 
 #[cfg(test)]
 mod ut_pool {
     use ylong_http::request::uri::Uri;
 
-    use crate::pool::{Pool, PoolKey};
+    use crate::pool::{Pool, PoolKey, Route};
+    use crate::util::config::HttpVersion;
 
     /// UT test cases for `Pool::get`.
     ///
@@ -79,4 +131,111 @@ mod ut_pool {
         let res = pool.get(key, consume_and_return_data, 6);
         assert_eq!(res, "Data info".to_string());
     }
+
+    /// UT test cases for `PoolKey::route`.
+    ///
+    /// # Brief
+    /// 1. Creates `PoolKey`s with the same uri but different routes.
+    /// 2. Gets connections from a `pool` by these keys.
+    /// 3. Checks that keys with different routes get different connections.
+    #[test]
+    fn ut_pool_key_route() {
+        let uri = Uri::from_bytes(b"http://example1.com:80/foo?a=1").unwrap();
+        let proxy = Uri::from_bytes(b"http://proxy.com:8080").unwrap();
+        let key = PoolKey::new(
+            uri.scheme().unwrap().clone(),
+            uri.authority().unwrap().clone(),
+        );
+        let direct = key.clone().route(Route::Direct);
+        let proxied = key.clone().route(Route::Proxy(
+            proxy.scheme().unwrap().clone(),
+            proxy.authority().unwrap().clone(),
+        ));
+        assert_ne!(key, direct);
+        assert_ne!(key, proxied);
+        assert_ne!(direct, proxied);
+
+        let pool = Pool::new();
+        assert_eq!(pool.get(key.clone(), |_| "default", 6), "default");
+        assert_eq!(pool.get(direct, |_| "direct", 6), "direct");
+        assert_eq!(pool.get(proxied, |_| "proxied", 6), "proxied");
+        assert_eq!(pool.get(key, |_| "other", 6), "default");
+    }
+
+    /// UT test cases for `PoolKey::unverified`.
+    ///
+    /// # Brief
+    /// 1. Creates `PoolKey`s with the same uri, one of which is unverified.
+    /// 2. Gets connections from a `pool` by these keys.
+    /// 3. Checks that the unverified key gets different connections.
+    #[test]
+    fn ut_pool_key_unverified() {
+        let uri = Uri::from_bytes(b"https://example1.com:443/foo").unwrap();
+        let key = PoolKey::new(
+            uri.scheme().unwrap().clone(),
+            uri.authority().unwrap().clone(),
+        );
+        let unverified = key.clone().unverified(true);
+        assert_ne!(key, unverified);
+        assert_eq!(key, key.clone().unverified(false));
+
+        let pool = Pool::new();
+        assert_eq!(pool.get(key, |_| "verified", 6), "verified");
+        assert_eq!(pool.get(unverified, |_| "unverified", 6), "unverified");
+    }
+
+    /// UT test cases for `PoolKey::version`.
+    ///
+    /// # Brief
+    /// 1. Creates `PoolKey`s with the same uri but different versions.
+    /// 2. Gets connections from a `pool` by these keys.
+    /// 3. Checks that keys with different versions get different connections.
+    #[test]
+    fn ut_pool_key_version() {
+        let uri = Uri::from_bytes(b"https://example1.com:443/foo").unwrap();
+        let key = PoolKey::new(
+            uri.scheme().unwrap().clone(),
+            uri.authority().unwrap().clone(),
+        );
+        let http1 = key.clone().version(HttpVersion::Http1);
+        assert_ne!(key, http1);
+        assert_eq!(key, key.clone().version(HttpVersion::Negotiate));
+
+        let pool = Pool::new();
+        assert_eq!(pool.get(key, |_| "negotiate", 6), "negotiate");
+        assert_eq!(pool.get(http1.clone(), |_| "http1", 6), "http1");
+        assert_eq!(pool.get(http1, |_| "other", 6), "http1");
+    }
+
+    /// UT test cases for `PoolKey::resolve_to`.
+    ///
+    /// # Brief
+    /// 1. Creates `PoolKey`s with the same uri, some of which are pinned to
+    ///    addresses.
+    /// 2. Gets connections from a `pool` by these keys.
+    /// 3. Checks that keys pinned to different addresses get different
+    ///    connections.
+    #[test]
+    fn ut_pool_key_resolve_to() {
+        let uri = Uri::from_bytes(b"https://example1.com:443/foo").unwrap();
+        let key = PoolKey::new(
+            uri.scheme().unwrap().clone(),
+            uri.authority().unwrap().clone(),
+        );
+        let pinned = key
+            .clone()
+            .resolve_to(Some("127.0.0.1:443".parse().unwrap()));
+        let other = key
+            .clone()
+            .resolve_to(Some("127.0.0.2:443".parse().unwrap()));
+        assert_ne!(key, pinned);
+        assert_ne!(pinned, other);
+        assert_eq!(key, key.clone().resolve_to(None));
+
+        let pool = Pool::new();
+        assert_eq!(pool.get(key, |_| "resolved", 6), "resolved");
+        assert_eq!(pool.get(pinned.clone(), |_| "pinned", 6), "pinned");
+        assert_eq!(pool.get(other, |_| "other", 6), "other");
+        assert_eq!(pool.get(pinned, |_| "again", 6), "pinned");
+    }
 }
@@ -16,7 +16,7 @@
 use core::convert::TryFrom;
 use std::net::IpAddr;
 
-use ylong_http::headers::HeaderValue;
+use ylong_http::headers::{HeaderValue, Headers};
 use ylong_http::request::uri::{Authority, Scheme, Uri};
 
 use crate::error::HttpClientError;
@@ -85,6 +85,29 @@ impl Proxy {
             Intercept::Http(info) => info.basic_auth = Some(auth),
             Intercept::Https(info) => info.basic_auth = Some(auth),
         }
+        self.digest_auth(username, password);
+    }
+
+    pub(crate) fn digest_auth(&mut self, username: &str, password: &str) {
+        let credentials = Some((username.to_string(), password.to_string()));
+        match &mut self.intercept {
+            Intercept::All(info) => info.credentials = credentials,
+            Intercept::Http(info) => info.credentials = credentials,
+            Intercept::Https(info) => info.credentials = credentials,
+        }
+    }
+
+    pub(crate) fn connect_header(
+        &mut self,
+        name: &str,
+        value: &str,
+    ) -> Result<(), HttpClientError> {
+        match &mut self.intercept {
+            Intercept::All(info) => info.connect_headers.append(name, value),
+            Intercept::Http(info) => info.connect_headers.append(name, value),
+            Intercept::Https(info) => info.connect_headers.append(name, value),
+        }
+        .map_err(|e| HttpClientError::from_error(crate::ErrorKind::Build, e))
     }
 
     pub(crate) fn no_proxy(&mut self, no_proxy: &str) {
@@ -145,10 +168,14 @@ impl Intercept {
 
 /// ProxyInfo which contains authentication, scheme and host.
 #[derive(Clone)]
-pub(crate) struct ProxyInfo {
+pub struct ProxyInfo {
     pub(crate) scheme: Scheme,
     pub(crate) authority: Authority,
     pub(crate) basic_auth: Option<HeaderValue>,
+    // Username and password which answer a `Digest` challenge of the proxy.
+    pub(crate) credentials: Option<(String, String)>,
+    // Headers sent with the CONNECT request of a tunnel.
+    pub(crate) connect_headers: Headers,
 }
 
 impl ProxyInfo {
@@ -165,16 +192,20 @@ impl ProxyInfo {
         // `scheme` and `authority` must have values after formatting.
         Ok(Self {
             basic_auth: None,
+            credentials: None,
+            connect_headers: Headers::new(),
             scheme: scheme.unwrap(),
             authority: authority.unwrap(),
         })
     }
 
-    pub(crate) fn authority(&self) -> &Authority {
+    /// Gets the host and port of the proxy.
+    pub fn authority(&self) -> &Authority {
         &self.authority
     }
 
-    pub(crate) fn scheme(&self) -> &Scheme {
+    /// Gets the scheme used to connect to the proxy.
+    pub fn scheme(&self) -> &Scheme {
         &self.scheme
     }
 }
@@ -11,6 +11,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashSet;
+
 use ylong_http::request::method::Method;
 use ylong_http::request::uri::Uri;
 use ylong_http::request::Request;
@@ -22,21 +24,40 @@ use crate::error::{ErrorKind, HttpClientError};
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub(crate) struct Redirect {
     strategy: Strategy,
+    error_on_limit: bool,
+    // Fails a redirect which has to resend a body that is not reusable.
+    strict_body: bool,
 }
 
 impl Redirect {
     pub(crate) fn limited(times: usize) -> Self {
         Self {
             strategy: Strategy::LimitTimes(times),
+            error_on_limit: true,
+            strict_body: false,
         }
     }
 
     pub(crate) fn none() -> Self {
         Self {
             strategy: Strategy::NoRedirect,
+            error_on_limit: true,
+            strict_body: false,
         }
     }
 
+    pub(crate) fn set_error_on_limit(&mut self, error: bool) {
+        self.error_on_limit = error;
+    }
+
+    pub(crate) fn set_strict_body(&mut self, strict: bool) {
+        self.strict_body = strict;
+    }
+
+    pub(crate) fn strict_body(&self) -> bool {
+        self.strict_body
+    }
+
     // todo: check h3?
     pub(crate) fn redirect<A, B>(
         &self,
@@ -44,6 +65,7 @@ impl Redirect {
         response: &Response<B>,
         info: &mut RedirectInfo,
     ) -> Result<Trigger, HttpClientError> {
+        info.visit(request.method(), request.uri());
         match response.status() {
             StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND | StatusCode::SEE_OTHER => {
                 for header_name in UPDATED_HEADERS {
@@ -81,8 +103,21 @@ impl Redirect {
             location = Uri::from_raw_parts(scheme, authority, path, query);
         }
 
-        let trigger = self.strategy.trigger(info)?;
+        let trigger = match self.strategy.trigger(info) {
+            Err(_) if !self.error_on_limit => {
+                // Hands the last redirect response back to the caller.
+                let _ = info.previous.pop();
+                Trigger::Stop
+            }
+            result => result?,
+        };
         if let Trigger::NextLink = trigger {
+            if info.is_visited(request.method(), &location) {
+                return Err(HttpClientError::from_str(
+                    ErrorKind::Redirect,
+                    "Redirect loop detected",
+                ));
+            }
             if let Some(previous) = info.previous.last() {
                 if location.authority() != previous.authority() {
                     for header_name in SENSITIVE_HEADERS {
@@ -105,14 +140,27 @@ impl Default for Redirect {
 
 pub(crate) struct RedirectInfo {
     previous: Vec<Uri>,
+    // Method and uri of every request sent in the redirect chain.
+    visited: HashSet<(String, String)>,
 }
 
 impl RedirectInfo {
     pub(crate) fn new() -> Self {
         Self {
             previous: Vec::new(),
+            visited: HashSet::new(),
         }
     }
+
+    fn visit(&mut self, method: &Method, uri: &Uri) {
+        self.visited
+            .insert((method.as_str().to_string(), uri.to_string()));
+    }
+
+    fn is_visited(&self, method: &Method, uri: &Uri) -> bool {
+        self.visited
+            .contains(&(method.as_str().to_string(), uri.to_string()))
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
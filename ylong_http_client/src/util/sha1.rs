@@ -0,0 +1,146 @@
+// Copyright (c) 2023 Huawei Device Co., Ltd.
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! SHA-1 digest.
+//!
+//! Only used to check the `Sec-WebSocket-Accept` field of a WebSocket
+//! handshake, which is not a security-sensitive usage.
+//!
+//! The digest of OpenSSL is used when TLS is compiled. Otherwise the simple
+//! implementation below is used, so that a plain `HTTP/1.1` client doesn't
+//! need OpenSSL. It is checked against the FIPS 180-2 test vectors and
+//! against OpenSSL.
+
+#[cfg(feature = "__tls")]
+pub(crate) fn digest(input: &[u8]) -> [u8; 20] {
+    use crate::util::c_openssl::ffi::x509::EVP_sha1;
+
+    crate::util::c_openssl::evp_digest(EVP_sha1, input)
+}
+
+#[cfg(not(feature = "__tls"))]
+pub(crate) fn digest(input: &[u8]) -> [u8; 20] {
+    fallback(input)
+}
+
+#[cfg(any(not(feature = "__tls"), test))]
+fn fallback(input: &[u8]) -> [u8; 20] {
+    let mut state: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+    let bit_len = (input.len() as u64).wrapping_mul(8);
+    let mut message = input.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&bit_len.to_be_bytes());
+
+    for block in message.chunks(64) {
+        let mut words = [0u32; 80];
+        for (i, word) in block.chunks(4).enumerate() {
+            words[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            words[i] = (words[i - 3] ^ words[i - 8] ^ words[i - 14] ^ words[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = state;
+        for (i, word) in words.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(*word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+        state[4] = state[4].wrapping_add(e);
+    }
+
+    let mut output = [0u8; 20];
+    for (chunk, word) in output.chunks_mut(4).zip(state.iter()) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+    output
+}
+
+#[cfg(test)]
+mod ut_util_sha1 {
+    use crate::util::sha1::{digest, fallback};
+
+    fn hex(bytes: [u8; 20]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    /// UT test cases for `sha1::digest`.
+    ///
+    /// # Brief
+    /// 1. Calls `digest` and the fallback implementation with inputs of
+    ///    different lengths.
+    /// 2. Checks if the results are the same as the FIPS 180-2 test vectors.
+    #[test]
+    fn ut_util_sha1_digest() {
+        let million = vec![b'a'; 1_000_000];
+        let vectors: [(&str, &[u8]); 5] = [
+            ("da39a3ee5e6b4b0d3255bfef95601890afd80709", b""),
+            ("a9993e364706816aba3e25717850c26c9cd0d89d", b"abc"),
+            (
+                "84983e441c3bd26ebaae4aa1f95129e5e54670f1",
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+            ),
+            (
+                "a49b2446a02c645bf419f995b67091253a04a259",
+                concat!(
+                    "abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmno",
+                    "ijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu"
+                )
+                .as_bytes(),
+            ),
+            ("34aa973cd4c4daa4f61eeb2bdbad27316534016f", &million),
+        ];
+        for (expected, input) in vectors {
+            assert_eq!(hex(digest(input)), expected);
+            assert_eq!(hex(fallback(input)), expected);
+        }
+    }
+
+    /// UT test cases for the fallback implementation of `sha1::digest`.
+    ///
+    /// # Brief
+    /// 1. Calls `digest` of OpenSSL and the fallback implementation with
+    ///    inputs of all lengths around the padding boundaries of a block.
+    /// 2. Checks if the results are the same.
+    #[cfg(feature = "__tls")]
+    #[test]
+    fn ut_util_sha1_fallback() {
+        let input = (0..=255u8).cycle().take(300).collect::<Vec<u8>>();
+        for len in 0..input.len() {
+            assert_eq!(digest(&input[..len]), fallback(&input[..len]));
+        }
+    }
+}
@@ -11,10 +11,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Construct the http server using TcpStream.
+//! Construct the http server using TcpStream, or mock the connections of a
+//! client in memory.
 
+#[cfg(feature = "ylong_base")]
 use std::sync::mpsc::Receiver;
 
+#[cfg(feature = "async")]
+pub(crate) use mock::MockConnector;
+
+#[cfg(feature = "ylong_base")]
 pub(crate) struct TcpHandle {
     pub addr: String,
 
@@ -22,6 +28,7 @@ pub(crate) struct TcpHandle {
     pub server_shutdown: Receiver<()>,
 }
 
+#[cfg(feature = "ylong_base")]
 pub(crate) fn format_header_str(key: &str, value: &str) -> String {
     format!("{}:{}\r\n", key.to_ascii_lowercase(), value)
 }
@@ -126,3 +133,136 @@ macro_rules! start_tcp_server {
                 $handle_vec.push(handle);
         }};
     }
+
+#[cfg(feature = "async")]
+mod mock {
+    use std::collections::VecDeque;
+    use std::future::{ready, Ready};
+    use std::io;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll, Waker};
+
+    use ylong_http::request::uri::Uri;
+
+    use crate::async_impl::Connector;
+    use crate::runtime::{AsyncRead, AsyncWrite, ReadBuf};
+    use crate::util::config::HttpVersion;
+    use crate::util::interceptor::ConnProtocol;
+    use crate::util::ConnInfo;
+    use crate::{ConnData, ConnDetail, HttpClientError};
+
+    /// A `Connector` of `MockStream`s, which answer each request head written
+    /// into them with the next of the given responses.
+    #[derive(Clone, Default)]
+    pub(crate) struct MockConnector {
+        pub(crate) connects: Arc<AtomicUsize>,
+        responses: Arc<Mutex<VecDeque<&'static [u8]>>>,
+    }
+
+    impl MockConnector {
+        pub(crate) fn new(responses: Vec<&'static [u8]>) -> Self {
+            Self {
+                connects: Arc::new(AtomicUsize::new(0)),
+                responses: Arc::new(Mutex::new(responses.into())),
+            }
+        }
+    }
+
+    impl Connector for MockConnector {
+        type Stream = MockStream;
+        type Future = Ready<Result<MockStream, HttpClientError>>;
+
+        fn connect(&self, _uri: &Uri, _http_version: HttpVersion) -> Self::Future {
+            self.connects.fetch_add(1, Ordering::SeqCst);
+            ready(Ok(MockStream {
+                responses: self.responses.clone(),
+                written: Vec::new(),
+                unread: VecDeque::new(),
+                waker: None,
+            }))
+        }
+    }
+
+    /// A connection of `MockConnector`. Once the response is read, reading
+    /// waits until another request is written, which can be used to stall a
+    /// body.
+    pub(crate) struct MockStream {
+        responses: Arc<Mutex<VecDeque<&'static [u8]>>>,
+        written: Vec<u8>,
+        unread: VecDeque<u8>,
+        waker: Option<Waker>,
+    }
+
+    impl ConnInfo for MockStream {
+        fn is_proxy(&self) -> bool {
+            false
+        }
+
+        fn conn_data(&self) -> ConnData {
+            let detail = ConnDetail {
+                protocol: ConnProtocol::Tcp,
+                local: "127.0.0.1:0".parse().unwrap(),
+                peer: "127.0.0.1:80".parse().unwrap(),
+                addr: String::new(),
+                proxy: None,
+            };
+            ConnData::builder().build(detail)
+        }
+
+        #[cfg(feature = "http3")]
+        fn quic_conn(&mut self) -> Option<crate::async_impl::quic::QuicConn> {
+            None
+        }
+    }
+
+    impl AsyncRead for MockStream {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            let this = self.get_mut();
+            if this.unread.is_empty() {
+                this.waker = Some(cx.waker().clone());
+                return Poll::Pending;
+            }
+            let size = buf.remaining().min(this.unread.len());
+            let data = this.unread.drain(..size).collect::<Vec<_>>();
+            buf.put_slice(&data);
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    impl AsyncWrite for MockStream {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            let this = self.get_mut();
+            this.written.extend_from_slice(buf);
+            // Request bodies are not expected, so the request ends with its
+            // head.
+            if let Some(pos) = this.written.windows(4).position(|w| w == b"\r\n\r\n") {
+                this.written.drain(..pos + 4);
+                if let Some(response) = this.responses.lock().unwrap().pop_front() {
+                    this.unread.extend(response);
+                    if let Some(waker) = this.waker.take() {
+                        waker.wake();
+                    }
+                }
+            }
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+}